@@ -1,4 +1,5 @@
 use crate::auth::AuthResult;
+use crate::config::Config;
 use anyhow::Result;
 use librespot::connect::{ConnectConfig, Spirc};
 use librespot::core::config::SessionConfig;
@@ -10,13 +11,38 @@ use librespot::playback::{audio_backend, mixer};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
-use tracing;
 
-const DEVICE_NAME: &str = "Spotify-RS";
+/// Default name of the local Spotify Connect device
+pub const DEFAULT_DEVICE_NAME: &str = "Spotify-RS";
 
 /// Default volume as a percentage (0-100)
 pub const DEFAULT_VOLUME_PERCENT: u8 = 70;
 
+/// Local device settings taken from the config file
+#[derive(Clone, Debug)]
+pub struct AudioSettings {
+    pub device_name: String,
+    pub bitrate: Bitrate,
+    pub initial_volume: u8,
+    pub normalisation: bool,
+}
+
+impl AudioSettings {
+    pub fn from_config(config: &Config) -> Self {
+        let bitrate = match config.audio.bitrate {
+            96 => Bitrate::Bitrate96,
+            160 => Bitrate::Bitrate160,
+            _ => Bitrate::Bitrate320,
+        };
+        Self {
+            device_name: config.device.name.clone(),
+            bitrate,
+            initial_volume: config.audio.initial_volume,
+            normalisation: config.audio.normalisation,
+        }
+    }
+}
+
 /// Convert percentage (0-100) to librespot volume (0-65535)
 pub fn percent_to_librespot_volume(percent: u8) -> u16 {
     ((percent as f32 / 100.0) * 65535.0) as u16
@@ -36,33 +62,36 @@ pub struct AudioPlayer {
 pub struct AudioBackend {
     inner: Mutex<Option<AudioPlayer>>,
     auth: AuthResult,
+    settings: AudioSettings,
 }
 
 impl AudioPlayer {
     /// Create a new audio player
     /// - silent: don't print initialization messages (for TUI mode)
     /// - activate: whether to activate the device immediately
-    async fn new_internal(auth: AuthResult, silent: bool, activate: bool) -> Result<Self> {
+    async fn new_internal(auth: AuthResult, settings: &AudioSettings, silent: bool, activate: bool) -> Result<Self> {
+        let device_name = settings.device_name.as_str();
         if !silent {
             tracing::info!("Initializing audio backend...");
         }
-        tracing::debug!(device_name = DEVICE_NAME, "Creating audio player");
+        tracing::debug!(device_name, "Creating audio player");
 
         let session_config = SessionConfig {
-            device_id: Self::get_device_id(),
+            device_id: Self::get_device_id(device_name),
             ..Default::default()
         };
 
         let player_config = PlayerConfig {
-            bitrate: Bitrate::Bitrate320,
+            bitrate: settings.bitrate,
+            normalisation: settings.normalisation,
             position_update_interval: Some(Duration::from_millis(500)),
             ..Default::default()
         };
         let audio_format = AudioFormat::default();
 
-        let initial_volume = percent_to_librespot_volume(DEFAULT_VOLUME_PERCENT);
+        let initial_volume = percent_to_librespot_volume(settings.initial_volume);
         let connect_config = ConnectConfig {
-            name: DEVICE_NAME.to_string(),
+            name: device_name.to_string(),
             initial_volume,
             ..Default::default()
         };
@@ -104,9 +133,9 @@ impl AudioPlayer {
         });
 
         if !silent {
-            tracing::info!(device_name = DEVICE_NAME, "Audio backend ready");
+            tracing::info!(device_name, "Audio backend ready");
         }
-        tracing::debug!(device_id = %Self::get_device_id(), is_active, "Audio player created");
+        tracing::debug!(device_id = %Self::get_device_id(device_name), is_active, "Audio player created");
 
         Ok(Self {
             player,
@@ -121,7 +150,7 @@ impl AudioPlayer {
         if !self.is_active {
             self.spirc.activate()?;
             self.is_active = true;
-            tracing::info!(device_name = DEVICE_NAME, "Audio device activated for Spotify Connect");
+            tracing::info!("Audio device activated for Spotify Connect");
         }
         Ok(())
     }
@@ -130,15 +159,11 @@ impl AudioPlayer {
         self.player.get_player_event_channel()
     }
 
-    fn get_device_id() -> String {
+    fn get_device_id(device_name: &str) -> String {
         let hostname = hostname::get()
             .map(|h| h.to_string_lossy().to_string())
             .unwrap_or_else(|_| "unknown".to_string());
-        format!("{}-{}", AudioPlayer::get_device_name(), hostname)
-    }
-
-    pub fn get_device_name() -> &'static str {
-        DEVICE_NAME
+        format!("{}-{}", device_name, hostname)
     }
 }
 
@@ -146,12 +171,13 @@ impl AudioBackend {
     /// Create a new AudioBackend and initialize the player (but don't activate it)
     /// This creates the librespot session but doesn't make it the active device
     /// Call `activate()` later to make it available for playback
-    pub async fn new(auth: AuthResult) -> Result<Self> {
+    pub async fn new(auth: AuthResult, settings: AudioSettings) -> Result<Self> {
         // Create the player but don't activate it yet (silent mode - TUI may be active)
-        let player = AudioPlayer::new_internal(auth.clone(), true, false).await?;
+        let player = AudioPlayer::new_internal(auth.clone(), &settings, true, false).await?;
         Ok(Self {
             inner: Mutex::new(Some(player)),
             auth,
+            settings,
         })
     }
 
@@ -160,8 +186,8 @@ impl AudioBackend {
         guard.as_ref().map(|p| p.get_player_event_channel())
     }
 
-    pub fn get_device_name() -> &'static str {
-        AudioPlayer::get_device_name()
+    pub fn device_name(&self) -> &str {
+        &self.settings.device_name
     }
 
     pub async fn is_active(&self) -> bool {
@@ -198,7 +224,7 @@ impl AudioBackend {
         tokio::time::sleep(Duration::from_millis(500)).await;
 
         // Create a new player and activate it (restart is for recovery, so activate)
-        let new_player = AudioPlayer::new_internal(self.auth.clone(), true, true).await?;
+        let new_player = AudioPlayer::new_internal(self.auth.clone(), &self.settings, true, true).await?;
         let event_channel = new_player.get_player_event_channel();
 
         {
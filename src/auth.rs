@@ -1,16 +1,18 @@
 use std::fs;
+use std::path::Path;
 use chrono::Utc;
 use anyhow::Result;
 use std::collections::HashSet;
-use tracing;
 
-use rspotify::Token;
+use rspotify::{prelude::*, AuthCodePkceSpotify, Token};
 use librespot::core::{authentication::Credentials, cache::Cache};
 
+use crate::endpoints::Endpoints;
+
 const SPOTIFY_CLIENT_ID: &str = "492e1e45ea814fa3ac555fe1576aaf5b";
 const SPOTIFY_REDIRECT_URI: &str = "http://127.0.0.1:8898/login";
 pub const SCOPES: &str =
-    "streaming user-read-playback-state user-modify-playback-state user-read-currently-playing playlist-read-private playlist-read-collaborative playlist-modify-private playlist-modify-public user-read-playback-position user-top-read user-read-recently-played user-library-modify user-library-read";
+    "streaming user-read-playback-state user-modify-playback-state user-read-currently-playing playlist-read-private playlist-read-collaborative playlist-modify-private playlist-modify-public user-read-playback-position user-top-read user-read-recently-played user-library-modify user-library-read user-follow-read user-follow-modify";
 
 const RESPONSE: &str = r#"
 <!doctype html>
@@ -19,9 +21,10 @@ const RESPONSE: &str = r#"
 <body><h1>Authentication Successful!</h1><script>window.close();</script></body>
 </html>
 "#;
-const CACHE: &str = ".cache";
-const CACHE_FILES: &str = ".cache/files";
-const REFRESH_TOKEN_FILE: &str = ".cache/refresh_token";
+/// Audio file cache, relative to the cache directory
+const CACHE_FILES_DIR: &str = "files";
+/// Stored refresh token, relative to the cache directory
+const REFRESH_TOKEN_FILE: &str = "refresh_token";
 
 #[derive(Clone)]
 pub struct AuthResult {
@@ -31,8 +34,11 @@ pub struct AuthResult {
     pub refresh_token: String,
 }
 
-async fn perform_browser_auth() -> Result<(Credentials, String, String)> {
+async fn perform_browser_auth(endpoints: &Endpoints, cache_dir: &Path) -> Result<(Credentials, String, String)> {
     tracing::info!("Starting browser-based OAuth flow");
+    if !endpoints.uses_default_accounts() {
+        tracing::warn!("Browser login always uses accounts.spotify.com; the custom accounts endpoint only applies to token refresh");
+    }
     let client = librespot_oauth::OAuthClientBuilder::new(
         SPOTIFY_CLIENT_ID,
         SPOTIFY_REDIRECT_URI,
@@ -50,7 +56,7 @@ async fn perform_browser_auth() -> Result<(Credentials, String, String)> {
 
     let refresh_token = token.refresh_token.clone();
 
-    let _ = fs::write(REFRESH_TOKEN_FILE, &refresh_token);
+    let _ = fs::write(cache_dir.join(REFRESH_TOKEN_FILE), &refresh_token);
     tracing::debug!("Saved refresh token to disk");
 
     let credentials = Credentials::with_access_token(token.access_token.clone());
@@ -58,38 +64,78 @@ async fn perform_browser_auth() -> Result<(Credentials, String, String)> {
     Ok((credentials, token.access_token, refresh_token))
 }
 
-pub async fn perform_oauth_flow() -> Result<AuthResult> {
-    let cache = Cache::new(Some(CACHE), Some(CACHE), Some(CACHE_FILES), None)?;
+/// Exchange a refresh token for a new access token at the configured accounts service.
+/// Returns `(access_token, refresh_token, expires_at)`.
+async fn request_token_refresh(
+    refresh_token: &str,
+    endpoints: &Endpoints,
+) -> Result<(String, String, chrono::DateTime<Utc>)> {
+    let client = AuthCodePkceSpotify::with_config(
+        rspotify::Credentials::new_pkce(SPOTIFY_CLIENT_ID),
+        rspotify::OAuth {
+            redirect_uri: SPOTIFY_REDIRECT_URI.to_string(),
+            scopes: SCOPES.split_whitespace().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        },
+        endpoints.rspotify_config(),
+    );
+    *client
+        .token
+        .lock()
+        .await
+        .map_err(|_| anyhow::anyhow!("Token lock is poisoned"))? = Some(Token {
+        refresh_token: Some(refresh_token.to_string()),
+        ..Default::default()
+    });
+
+    let token = client
+        .refetch_token()
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Accounts service returned no token"))?;
 
-    let stored_refresh_token = fs::read_to_string(REFRESH_TOKEN_FILE).ok();
+    // Spotify may keep the old refresh token valid and omit a new one
+    let new_refresh_token = token.refresh_token.unwrap_or_else(|| refresh_token.to_string());
+    let expires_at = token
+        .expires_at
+        .unwrap_or_else(|| Utc::now() + chrono::Duration::seconds(3600));
+
+    Ok((token.access_token, new_refresh_token, expires_at))
+}
+
+/// `audio_cache_limit` caps the audio file cache in bytes (`None` = unlimited)
+pub async fn perform_oauth_flow(
+    endpoints: &Endpoints,
+    cache_dir: &Path,
+    audio_cache_limit: Option<u64>,
+) -> Result<AuthResult> {
+    let cache = Cache::new(
+        Some(cache_dir.to_path_buf()),
+        Some(cache_dir.to_path_buf()),
+        Some(cache_dir.join(CACHE_FILES_DIR)),
+        audio_cache_limit,
+    )?;
+
+    let stored_refresh_token = fs::read_to_string(cache_dir.join(REFRESH_TOKEN_FILE)).ok();
 
     let (credentials, access_token, refresh_token) =
         if let (Some(creds), Some(refresh_token)) = (cache.credentials(), stored_refresh_token) {
             tracing::info!("Found cached Librespot credentials and refresh token");
 
-            let oauth_client = librespot_oauth::OAuthClientBuilder::new(
-                SPOTIFY_CLIENT_ID,
-                SPOTIFY_REDIRECT_URI,
-                SCOPES.split_whitespace().collect(),
-            )
-            .build()?;
-
-            match oauth_client.refresh_token_async(&refresh_token).await {
-                Ok(new_token) => {
-                    let new_refresh_token = new_token.refresh_token.clone();
-                    let _ = fs::write(REFRESH_TOKEN_FILE, &new_refresh_token);
+            match request_token_refresh(&refresh_token, endpoints).await {
+                Ok((access_token, new_refresh_token, _)) => {
+                    let _ = fs::write(cache_dir.join(REFRESH_TOKEN_FILE), &new_refresh_token);
                     tracing::debug!("Token refreshed successfully");
 
-                    (creds, new_token.access_token, new_refresh_token)
+                    (creds, access_token, new_refresh_token)
                 }
                 Err(e) => {
                     tracing::warn!(error = %e, "Cached refresh token failed, re-authenticating");
-                    perform_browser_auth().await?
+                    perform_browser_auth(endpoints, cache_dir).await?
                 }
             }
         } else {
             tracing::info!("No cached credentials found, starting browser authentication");
-            perform_browser_auth().await?
+            perform_browser_auth(endpoints, cache_dir).await?
         };
 
     Ok(AuthResult {
@@ -110,25 +156,43 @@ pub async fn perform_oauth_flow() -> Result<AuthResult> {
 }
 
 /// Refresh the access token using the stored refresh token
-pub async fn refresh_access_token(refresh_token: &str) -> Result<(String, String, chrono::DateTime<Utc>)> {
+pub async fn refresh_access_token(
+    refresh_token: &str,
+    endpoints: &Endpoints,
+    cache_dir: &Path,
+) -> Result<(String, String, chrono::DateTime<Utc>)> {
     tracing::debug!("Refreshing access token");
 
-    let oauth_client = librespot_oauth::OAuthClientBuilder::new(
-        SPOTIFY_CLIENT_ID,
-        SPOTIFY_REDIRECT_URI,
-        SCOPES.split_whitespace().collect(),
-    )
-    .build()?;
-
-    let new_token = oauth_client.refresh_token_async(refresh_token).await?;
+    let (access_token, new_refresh_token, expires_at) = request_token_refresh(refresh_token, endpoints).await?;
 
     // Save the new refresh token to disk
-    let new_refresh_token = new_token.refresh_token.clone();
-    let _ = fs::write(REFRESH_TOKEN_FILE, &new_refresh_token);
+    let _ = fs::write(cache_dir.join(REFRESH_TOKEN_FILE), &new_refresh_token);
 
-    let expires_at = Utc::now() + chrono::Duration::seconds(3600);
     tracing::info!("Access token refreshed successfully, expires at {}", expires_at);
 
-    Ok((new_token.access_token, new_refresh_token, expires_at))
+    Ok((access_token, new_refresh_token, expires_at))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::{MockData, MockSpotifyServer};
+
+    #[tokio::test]
+    async fn token_refresh_uses_configured_accounts_endpoint() {
+        let server = MockSpotifyServer::start(MockData::default()).await;
+
+        let (access_token, refresh_token, expires_at) =
+            request_token_refresh("old-refresh-token", &server.endpoints()).await.unwrap();
+
+        assert_eq!(access_token, "mock-access-token");
+        assert_eq!(refresh_token, "mock-refresh-token");
+        assert!(expires_at > Utc::now());
+
+        let requests = server.requests_to("/api/token");
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].body.contains("grant_type=refresh_token"));
+        assert!(requests[0].body.contains("refresh_token=old-refresh-token"));
+        assert!(requests[0].body.contains(SPOTIFY_CLIENT_ID));
+    }
+}
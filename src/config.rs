@@ -0,0 +1,409 @@
+//! User configuration loaded from a TOML file
+//!
+//! Every section and key is optional; anything missing falls back to the
+//! built-in defaults. The file is validated once at startup so mistakes are
+//! reported before the TUI takes over the terminal. The settings overlay
+//! writes changes back with `Config::save`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::keymap::{KeyList, Keymap};
+use crate::paths::AppPaths;
+
+const SUPPORTED_BITRATES: [u16; 3] = [96, 160, 320];
+
+const MAX_SEEK_STEP_SECS: u32 = 300;
+
+const MIN_LIVE_SEARCH_DELAY_MS: u64 = 50;
+const MAX_LIVE_SEARCH_DELAY_MS: u64 = 2000;
+
+/// Upper bound the Web API accepts for a single page
+const MAX_API_PAGE: u32 = 50;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub device: DeviceConfig,
+    pub audio: AudioConfig,
+    pub paths: PathsConfig,
+    pub ui: UiConfig,
+    pub library: LibraryConfig,
+    /// Keybinding overrides: action name -> key or list of keys
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, KeyList>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeviceConfig {
+    /// Name of the local Spotify Connect device
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct AudioConfig {
+    /// Streaming bitrate in kbps: 96, 160 or 320
+    pub bitrate: u16,
+    /// Volume (0-100) the local device starts with
+    pub initial_volume: u8,
+    /// Percentage points per volume up/down key press
+    pub volume_step: u8,
+    /// Seconds to jump per seek forward/back key press
+    pub seek_step_secs: u32,
+    /// Normalise track loudness (ReplayGain)
+    pub normalisation: bool,
+    /// Size limit of the audio file cache in MB, 0 for unlimited
+    pub cache_limit_mb: u64,
+}
+
+/// Overrides for the XDG locations resolved in `paths`
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathsConfig {
+    /// Credentials, audio files and the liked songs cache
+    pub cache_dir: Option<PathBuf>,
+    /// Daily rotating log files
+    pub log_dir: Option<PathBuf>,
+    /// Playlist exports and other files written for the user
+    pub data_dir: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    /// Seconds an error stays in the status bar
+    pub error_timeout_secs: u64,
+    pub theme: ThemeName,
+    /// Show results under the search bar while typing
+    pub live_search: bool,
+    /// Pause in typing before a live search is sent
+    pub live_search_delay_ms: u64,
+}
+
+/// Colour palettes available to the view
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    #[default]
+    Default,
+    Ocean,
+    Mono,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 3] = [ThemeName::Default, ThemeName::Ocean, ThemeName::Mono];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemeName::Default => "default",
+            ThemeName::Ocean => "ocean",
+            ThemeName::Mono => "mono",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LibraryConfig {
+    pub playlists_limit: u32,
+    pub liked_songs_limit: u32,
+    pub saved_albums_limit: u32,
+    pub followed_artists_limit: u32,
+    pub saved_shows_limit: u32,
+    pub episodes_limit: u32,
+    pub recently_played_limit: u32,
+    pub search_limit: u32,
+    pub home_limit: u32,
+    pub top_limit: u32,
+}
+
+impl Default for DeviceConfig {
+    fn default() -> Self {
+        Self {
+            name: crate::audio::DEFAULT_DEVICE_NAME.to_string(),
+        }
+    }
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            bitrate: 320,
+            initial_volume: crate::audio::DEFAULT_VOLUME_PERCENT,
+            volume_step: 5,
+            seek_step_secs: 10,
+            normalisation: false,
+            cache_limit_mb: 0,
+        }
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            error_timeout_secs: 5,
+            theme: ThemeName::Default,
+            live_search: false,
+            live_search_delay_ms: 300,
+        }
+    }
+}
+
+impl Default for LibraryConfig {
+    fn default() -> Self {
+        Self {
+            playlists_limit: 50,
+            liked_songs_limit: 100,
+            saved_albums_limit: 50,
+            followed_artists_limit: 50,
+            saved_shows_limit: 50,
+            episodes_limit: 50,
+            recently_played_limit: 50,
+            search_limit: 40,
+            home_limit: 20,
+            top_limit: 50,
+        }
+    }
+}
+
+impl Config {
+    /// Load the config from `path`, or from the default config file if it exists.
+    /// An explicitly requested file must exist; a missing default file means
+    /// "use the defaults".
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let (path, explicit) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => (AppPaths::default_config_file(), false),
+        };
+
+        if !explicit && !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Could not read config file {}", path.display()))?;
+        let config = Self::parse(&content)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+        Ok(config)
+    }
+
+    /// Write the config to `path`, creating its directory if needed
+    pub fn save(&self, path: &Path) -> Result<()> {
+        self.validate()?;
+        let content = toml::to_string_pretty(self).context("Could not serialize config")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Could not create config directory {}", dir.display()))?;
+        }
+        fs::write(path, content)
+            .with_context(|| format!("Could not write config file {}", path.display()))?;
+        tracing::info!(path = %path.display(), "Saved config");
+        Ok(())
+    }
+
+    /// Parse and validate TOML config contents
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check value ranges, reporting every problem at once
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+
+        if self.device.name.trim().is_empty() {
+            problems.push("device.name must not be empty".to_string());
+        }
+        if !SUPPORTED_BITRATES.contains(&self.audio.bitrate) {
+            problems.push(format!(
+                "audio.bitrate must be one of 96, 160 or 320 (got {})",
+                self.audio.bitrate
+            ));
+        }
+        if self.audio.initial_volume > 100 {
+            problems.push(format!(
+                "audio.initial_volume must be between 0 and 100 (got {})",
+                self.audio.initial_volume
+            ));
+        }
+        if self.audio.volume_step == 0 || self.audio.volume_step > 50 {
+            problems.push(format!(
+                "audio.volume_step must be between 1 and 50 (got {})",
+                self.audio.volume_step
+            ));
+        }
+        if self.audio.seek_step_secs == 0 || self.audio.seek_step_secs > MAX_SEEK_STEP_SECS {
+            problems.push(format!(
+                "audio.seek_step_secs must be between 1 and {} (got {})",
+                MAX_SEEK_STEP_SECS, self.audio.seek_step_secs
+            ));
+        }
+        if self.paths.cache_dir.as_ref().is_some_and(|dir| dir.as_os_str().is_empty()) {
+            problems.push("paths.cache_dir must not be empty".to_string());
+        }
+        if self.paths.log_dir.as_ref().is_some_and(|dir| dir.as_os_str().is_empty()) {
+            problems.push("paths.log_dir must not be empty".to_string());
+        }
+        if self.paths.data_dir.as_ref().is_some_and(|dir| dir.as_os_str().is_empty()) {
+            problems.push("paths.data_dir must not be empty".to_string());
+        }
+        if self.ui.error_timeout_secs == 0 {
+            problems.push("ui.error_timeout_secs must be at least 1".to_string());
+        }
+        if !(MIN_LIVE_SEARCH_DELAY_MS..=MAX_LIVE_SEARCH_DELAY_MS).contains(&self.ui.live_search_delay_ms) {
+            problems.push(format!(
+                "ui.live_search_delay_ms must be between {} and {} (got {})",
+                MIN_LIVE_SEARCH_DELAY_MS, MAX_LIVE_SEARCH_DELAY_MS, self.ui.live_search_delay_ms
+            ));
+        }
+
+        let library = &self.library;
+        let limits = [
+            ("playlists_limit", library.playlists_limit, None),
+            ("liked_songs_limit", library.liked_songs_limit, None),
+            ("saved_albums_limit", library.saved_albums_limit, None),
+            ("followed_artists_limit", library.followed_artists_limit, Some(MAX_API_PAGE)),
+            ("saved_shows_limit", library.saved_shows_limit, None),
+            ("episodes_limit", library.episodes_limit, None),
+            ("recently_played_limit", library.recently_played_limit, Some(MAX_API_PAGE)),
+            ("search_limit", library.search_limit, Some(MAX_API_PAGE)),
+            ("home_limit", library.home_limit, Some(MAX_API_PAGE)),
+            ("top_limit", library.top_limit, Some(MAX_API_PAGE)),
+        ];
+        for (key, value, max) in limits {
+            match max {
+                Some(max) if value == 0 || value > max => {
+                    problems.push(format!("library.{} must be between 1 and {} (got {})", key, max, value));
+                }
+                None if value == 0 => {
+                    problems.push(format!("library.{} must be at least 1", key));
+                }
+                _ => {}
+            }
+        }
+
+        if let Err(e) = Keymap::from_config(&self.keys) {
+            problems.push(e.to_string());
+        }
+
+        if !problems.is_empty() {
+            bail!("{}", problems.join("; "));
+        }
+        Ok(())
+    }
+
+    pub fn error_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.ui.error_timeout_secs)
+    }
+
+    pub fn live_search_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.ui.live_search_delay_ms)
+    }
+
+    /// Audio cache size limit in bytes, `None` when unlimited
+    pub fn audio_cache_limit(&self) -> Option<u64> {
+        (self.audio.cache_limit_mb > 0).then(|| self.audio.cache_limit_mb * 1024 * 1024)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_file_uses_defaults() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn partial_sections_keep_other_defaults() {
+        let config = Config::parse(
+            r#"
+            [audio]
+            bitrate = 160
+
+            [library]
+            liked_songs_limit = 500
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.audio.bitrate, 160);
+        assert_eq!(config.audio.initial_volume, crate::audio::DEFAULT_VOLUME_PERCENT);
+        assert_eq!(config.library.liked_songs_limit, 500);
+        assert_eq!(config.library.saved_albums_limit, 50);
+        assert_eq!(config.device, DeviceConfig::default());
+    }
+
+    #[test]
+    fn invalid_values_are_all_reported() {
+        let err = Config::parse(
+            r#"
+            [audio]
+            bitrate = 256
+            initial_volume = 120
+
+            [library]
+            search_limit = 80
+            "#,
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("audio.bitrate"), "{}", err);
+        assert!(err.contains("audio.initial_volume"), "{}", err);
+        assert!(err.contains("library.search_limit must be between 1 and 50"), "{}", err);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let err = Config::parse("[audio]\nbitrat = 320\n").unwrap_err().to_string();
+        assert!(err.contains("bitrat"), "{}", err);
+    }
+
+    #[test]
+    fn key_overrides_are_validated() {
+        let config = Config::parse(
+            r#"
+            [keys]
+            next_track = "ctrl-n"
+            quit = ["q", "ctrl-c"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.keys.len(), 2);
+
+        let err = Config::parse("[keys]\nnext_trak = \"n\"\n").unwrap_err().to_string();
+        assert!(err.contains("keys.next_trak: unknown action"), "{}", err);
+    }
+
+    #[test]
+    fn saved_config_round_trips() {
+        let mut config = Config::default();
+        config.audio.volume_step = 10;
+        config.audio.normalisation = true;
+        config.ui.theme = ThemeName::Ocean;
+        config.paths.log_dir = Some(PathBuf::from("/tmp/logs"));
+
+        let path = std::env::temp_dir()
+            .join(format!("spotify-rs-test-config-{}", std::process::id()))
+            .join("config.toml");
+        config.save(&path).unwrap();
+
+        assert_eq!(Config::load(Some(&path)).unwrap(), config);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn missing_explicit_file_is_an_error() {
+        let err = Config::load(Some(Path::new("does-not-exist.toml"))).unwrap_err();
+        assert!(err.to_string().contains("does-not-exist.toml"));
+    }
+}
@@ -0,0 +1,263 @@
+//! Whole-library backup and restore
+//!
+//! A backup fetches every liked song, saved album, followed artist and
+//! playlist (with all of its tracks) and writes them to a timestamped archive
+//! in the data directory's `backups` folder. Archives are restored through
+//! the import prompt: the archive is compared with the account and the
+//! missing items are listed for confirmation before anything is added.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::model::{
+    ActiveSection, BackupPlaylist, CurrentLibrary, LibraryBackup, PlaylistRestore, RestorePlan, SpotifyApi,
+};
+use super::AppController;
+
+impl AppController {
+    /// Write the whole library to a new archive
+    pub async fn backup_library(&self) {
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        let dir = model.get_backup_dir().to_path_buf();
+        model.set_info("Backing up the library…".to_string()).await;
+        drop(model);
+
+        let result: Result<_> = async {
+            let liked_songs = spotify.get_all_liked_songs().await?;
+            let saved_albums = spotify.get_saved_albums(u32::MAX).await?;
+            let followed_artists = spotify.get_followed_artists(u32::MAX).await?;
+            let mut playlists = Vec::new();
+            for playlist in spotify.get_user_playlists(u32::MAX).await? {
+                let detail = spotify.get_playlist(&playlist.id).await?;
+                let tracks = spotify.get_all_playlist_tracks(&playlist.id).await?;
+                playlists.push(BackupPlaylist::new(&detail, &tracks));
+            }
+            let backup = LibraryBackup::new(&liked_songs, &saved_albums, &followed_artists, playlists);
+            Ok((backup.write_to_dir(&dir)?, backup))
+        }
+        .await;
+
+        let model = self.model.lock().await;
+        match result {
+            Ok((path, backup)) => {
+                tracing::info!(
+                    path = %path.display(),
+                    liked_songs = backup.liked_songs.len(),
+                    saved_albums = backup.saved_albums.len(),
+                    followed_artists = backup.followed_artists.len(),
+                    playlists = backup.playlists.len(),
+                    "Backed up library"
+                );
+                model.set_info(format!(
+                    "Backed up {} liked songs, {} albums, {} artists and {} playlists to {}",
+                    backup.liked_songs.len(),
+                    backup.saved_albums.len(),
+                    backup.followed_artists.len(),
+                    backup.playlists.len(),
+                    path.display()
+                )).await;
+            }
+            Err(e) => model.set_error(format!("Backup failed: {:#}", e)).await,
+        }
+    }
+
+    /// Compare the archive in `text` with the account and show what a
+    /// restore would add
+    pub(crate) async fn open_restore(&self, path: &Path, text: &str) {
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        let backup = match LibraryBackup::from_json(text) {
+            Ok(backup) => backup,
+            Err(e) => {
+                model.set_error(format!("Restore failed: {:#}", e)).await;
+                return;
+            }
+        };
+        model.set_content_loading(true).await;
+        drop(model);
+
+        let source = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        let result = current_library(spotify.as_ref(), &backup).await;
+
+        let model = self.model.lock().await;
+        model.set_content_loading(false).await;
+        let plan = match result {
+            Ok(current) => backup.plan_restore(source, &current),
+            Err(e) => {
+                model.set_error(Self::format_error(&e)).await;
+                return;
+            }
+        };
+        if plan.is_empty() {
+            model.set_info(format!("Nothing to restore: everything in {} is already in the library", plan.source)).await;
+            return;
+        }
+        tracing::info!(path = %path.display(), changes = plan.lines().len(), "Planned library restore");
+        model.show_restore_plan(plan).await;
+        model.ui_state.lock().await.active_section = ActiveSection::MainContent;
+    }
+
+    /// Apply the confirmed restore plan
+    pub async fn commit_restore(&self) {
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        let Some(plan) = model.take_restore_plan().await else {
+            return;
+        };
+        drop(model);
+
+        let result = apply_restore(spotify.as_ref(), &plan).await;
+        self.refresh_user_playlists(None).await;
+
+        let model = self.model.lock().await;
+        match result {
+            Ok(()) => {
+                tracing::info!(source = %plan.source, summary = %plan.summary(), "Restored library");
+                model.set_info(format!("Restored {}", plan.summary())).await;
+            }
+            Err(e) => model.set_error(format!("Restore stopped: {}", Self::format_error(&e))).await,
+        }
+    }
+}
+
+/// The parts of the account's library the archive could add to
+async fn current_library(spotify: &dyn SpotifyApi, backup: &LibraryBackup) -> Result<CurrentLibrary> {
+    let mut current = CurrentLibrary {
+        liked_songs: spotify.get_all_liked_songs().await?.into_iter().map(|saved| saved.track.uri).collect(),
+        saved_albums: spotify
+            .get_saved_albums(u32::MAX)
+            .await?
+            .into_iter()
+            .map(|album| format!("spotify:album:{}", album.id))
+            .collect(),
+        followed_artists: spotify
+            .get_followed_artists(u32::MAX)
+            .await?
+            .into_iter()
+            .map(|artist| format!("spotify:artist:{}", artist.id))
+            .collect(),
+        playlists: spotify.get_user_playlists(u32::MAX).await?,
+        ..Default::default()
+    };
+
+    // Only playlists the restore might add tracks to need their contents
+    let archived: HashSet<&str> = backup.playlists.iter().map(|playlist| playlist.id.as_str()).collect();
+    for playlist in current.playlists.iter().filter(|p| p.editable && archived.contains(p.id.as_str())) {
+        let tracks = spotify.get_all_playlist_tracks(&playlist.id).await?;
+        current.playlist_tracks.insert(playlist.id.clone(), tracks.into_iter().map(|track| track.uri).collect());
+    }
+    Ok(current)
+}
+
+async fn apply_restore(spotify: &dyn SpotifyApi, plan: &RestorePlan) -> Result<()> {
+    let id = |uri: &str| uri.split(':').next_back().unwrap_or(uri).to_string();
+
+    if !plan.followed_artists.is_empty() {
+        let ids: Vec<String> = plan.followed_artists.iter().map(|artist| id(&artist.uri)).collect();
+        spotify.follow_artists(&ids).await?;
+    }
+    if !plan.saved_albums.is_empty() {
+        let ids: Vec<String> = plan.saved_albums.iter().map(|album| id(&album.uri)).collect();
+        spotify.save_albums(&ids).await?;
+    }
+    if !plan.liked_songs.is_empty() {
+        let songs: Vec<(String, String)> = plan
+            .liked_songs
+            .iter()
+            .map(|song| (song.track.uri.clone(), song.added_at.clone()))
+            .collect();
+        spotify.save_liked_songs(&songs).await?;
+    }
+
+    for playlist in &plan.playlists {
+        match playlist {
+            PlaylistRestore::Follow { id, .. } => spotify.follow_playlist(id).await?,
+            PlaylistRestore::Create { name, description, visibility, uris } => {
+                let created = spotify.create_playlist(name, *visibility).await?;
+                if !description.is_empty() {
+                    spotify.update_playlist_details(&created.id, None, Some(description)).await?;
+                }
+                if !uris.is_empty() {
+                    spotify.add_tracks_to_playlist(&created.id, uris).await?;
+                }
+            }
+            PlaylistRestore::AddTracks { id, uris, .. } => {
+                spotify.add_tracks_to_playlist(id, uris).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::controller::testing::fake_controller;
+    use crate::model::{ContentView, FakeLibrary};
+
+    #[tokio::test]
+    async fn restoring_a_backup_adds_back_only_what_was_removed() {
+        let dir = std::env::temp_dir().join(format!("spotify-rs-test-backup-{}", std::process::id()));
+        let mut library = FakeLibrary::demo("test");
+        library.playlists[0].collaborative = true;
+        let (controller, spotify) = fake_controller(library, Config::default(), Some(&dir));
+
+        controller.backup_library().await;
+        let path = std::fs::read_dir(dir.join("backups")).unwrap().next().unwrap().unwrap().path();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let backup = LibraryBackup::from_json(&text).unwrap();
+        assert!(!backup.liked_songs.is_empty());
+        assert!(!backup.followed_artists.is_empty());
+        let playlist = &backup.playlists[0];
+        let playlist_len = playlist.tracks.len();
+        assert!(playlist.collaborative && !playlist.public);
+
+        // Lose a liked song and a playlist track
+        let liked_id = backup.liked_songs[0].track.uri.rsplit(':').next().unwrap().to_string();
+        spotify.toggle_liked_song(&liked_id).await.unwrap();
+        spotify.remove_playlist_track(&playlist.id, &playlist.tracks[0].uri, 0, "").await.unwrap();
+
+        controller.open_restore(&path, &text).await;
+        let view = controller.model.lock().await.content_state.lock().await.view.clone();
+        let ContentView::LibraryRestore { plan, .. } = view else {
+            panic!("restore screen should be open");
+        };
+        assert_eq!(plan.summary(), "1 liked songs · 0 albums · 0 artists · 1 playlists");
+        controller.commit_restore().await;
+
+        let liked = spotify.get_all_liked_songs().await.unwrap();
+        assert_eq!(liked.len(), backup.liked_songs.len());
+        let tracks = spotify.get_all_playlist_tracks(&playlist.id).await.unwrap();
+        assert_eq!(tracks.len(), playlist_len);
+        assert_eq!(tracks.last().unwrap().uri, playlist.tracks[0].uri);
+        let state = controller.model.lock().await.get_ui_state().await;
+        assert!(state.message_is_info, "{:?}", state.error_message);
+
+        // A playlist dropped from the library comes back with its tracks
+        spotify.unfollow_playlist(&playlist.id).await.unwrap();
+        controller.open_restore(&path, &text).await;
+        controller.commit_restore().await;
+        let playlists = spotify.get_user_playlists(u32::MAX).await.unwrap();
+        assert_eq!(playlists.len(), 1);
+        let tracks = spotify.get_all_playlist_tracks(&playlists[0].id).await.unwrap();
+        assert_eq!(tracks.len(), playlist_len);
+        let detail = spotify.get_playlist(&playlists[0].id).await.unwrap();
+        assert!(detail.collaborative && !detail.public);
+
+        // Now nothing is missing
+        controller.open_restore(&path, &text).await;
+        assert!(!controller.model.lock().await.is_restore_plan_open().await);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,17 +1,34 @@
-//! Key event handling
+//! Key and mouse event handling
+//!
+//! Keys are resolved to `Action`s through the keymap, then dispatched based on
+//! the focused section. Popups, the search field and the list filter keep
+//! their fixed editing keys (Esc, Enter, arrows, text input, and `n`/`N` to
+//! step through filter matches) so they work with any keymap.
+//!
+//! Mouse events are hit-tested against the `ViewLayout` of the last frame: a
+//! click focuses a section and selects a row, a double-click acts like Enter
+//! and the wheel moves the selection of the list under the cursor.
 
+use std::time::{Duration, Instant};
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Margin, Position};
 
-use crate::model::ActiveSection;
+use crate::keymap::{Action, KeyLookup, KeyPress};
+use crate::model::{ActiveSection, AppModel, SelectedItem};
+use crate::view::{ListRegion, ViewLayout};
 use super::AppController;
 
+/// Two clicks on the same cell within this time count as a double-click
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
 impl AppController {
     pub async fn handle_key_event(&self, key: KeyEvent) -> Result<()> {
         if key.kind != KeyEventKind::Press {
             return Ok(());
         }
 
+        let pressed = KeyPress::from_event(&key);
         let model = self.model.lock().await;
 
         // Handle error message first (blocks all other interactions)
@@ -27,112 +44,258 @@ impl AppController {
 
         // Handle help popup
         if model.is_help_popup_open().await {
-            return match key.code {
-                KeyCode::Esc | KeyCode::Char('h') | KeyCode::Char('H') => {
-                    model.hide_help_popup().await;
-                    Ok(())
-                }
-                _ => Ok(()),
+            if key.code == KeyCode::Esc || self.keymap.is_bound(Action::ToggleHelp, &pressed) {
+                model.hide_help_popup().await;
             }
+            return Ok(());
         }
 
         // Handle device picker modal
         if model.is_device_picker_open().await {
-            return match key.code {
-                KeyCode::Up => {
-                    model.device_picker_move_up().await;
-                    Ok(())
-                }
-                KeyCode::Down => {
-                    model.device_picker_move_down().await;
-                    Ok(())
+            let is = |action, code| key.code == code || self.keymap.is_bound(action, &pressed);
+            if is(Action::MoveUp, KeyCode::Up) {
+                model.device_picker_move_up().await;
+            } else if is(Action::MoveDown, KeyCode::Down) {
+                model.device_picker_move_down().await;
+            } else if is(Action::Select, KeyCode::Enter) {
+                if let Some(device) = model.get_selected_device().await {
+                    let local_device_name = model.get_local_device_name().await;
+                    model.hide_device_picker().await;
+                    drop(model);
+                    self.select_device(&device, &local_device_name).await;
                 }
-                KeyCode::Enter => {
-                    if let Some(device) = model.get_selected_device().await {
-                        let local_device_name = model.get_local_device_name().await;
-                        model.hide_device_picker().await;
+            } else if is(Action::OpenDevicePicker, KeyCode::Esc) {
+                model.hide_device_picker().await;
+            }
+            return Ok(());
+        }
+
+        // Handle "add to playlist" picker
+        if model.is_playlist_picker_open().await {
+            let is = |action, code| key.code == code || self.keymap.is_bound(action, &pressed);
+            if is(Action::MoveUp, KeyCode::Up) {
+                model.playlist_picker_move_up().await;
+            } else if is(Action::MoveDown, KeyCode::Down) {
+                model.playlist_picker_move_down().await;
+            } else if is(Action::Select, KeyCode::Enter) {
+                drop(model);
+                self.commit_playlist_picker().await;
+            } else if is(Action::AddToPlaylist, KeyCode::Esc) {
+                model.hide_playlist_picker().await;
+            }
+            return Ok(());
+        }
+
+        // Handle settings overlay
+        if model.is_settings_open().await {
+            if model.is_editing_setting().await {
+                match key.code {
+                    KeyCode::Enter => {
                         drop(model);
-                        self.select_device(&device, &local_device_name).await;
+                        self.commit_setting_edit().await;
                     }
-                    Ok(())
+                    KeyCode::Esc => {
+                        model.take_setting_edit().await;
+                    }
+                    KeyCode::Backspace => model.setting_edit_backspace().await,
+                    KeyCode::Char(c) if !pressed.is_command() => model.setting_edit_push(c).await,
+                    _ => {}
                 }
-                KeyCode::Esc | KeyCode::Char('d') | KeyCode::Char('D') => {
-                    model.hide_device_picker().await;
-                    Ok(())
+                return Ok(());
+            }
+
+            let is = |action, code| key.code == code || self.keymap.is_bound(action, &pressed);
+            if is(Action::MoveUp, KeyCode::Up) {
+                model.settings_move_up().await;
+            } else if is(Action::MoveDown, KeyCode::Down) {
+                model.settings_move_down().await;
+            } else if is(Action::PrevCategory, KeyCode::Left) {
+                drop(model);
+                self.adjust_selected_setting(false).await;
+            } else if is(Action::NextCategory, KeyCode::Right) || is(Action::Select, KeyCode::Enter) {
+                drop(model);
+                self.adjust_selected_setting(true).await;
+            } else if is(Action::OpenSettings, KeyCode::Esc) {
+                model.hide_settings().await;
+            }
+            return Ok(());
+        }
+
+        // Handle "go to position" prompt
+        if model.is_seek_prompt_open().await {
+            match key.code {
+                KeyCode::Enter => {
+                    drop(model);
+                    self.commit_seek_prompt().await;
                 }
-                _ => Ok(()),
+                KeyCode::Esc => {
+                    model.take_seek_prompt().await;
+                }
+                KeyCode::Backspace => model.seek_prompt_backspace().await,
+                KeyCode::Char(c) if c.is_ascii_digit() || c == ':' => model.seek_prompt_push(c).await,
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        // Handle "insert into queue" prompt
+        if model.is_queue_insert_prompt_open().await {
+            match key.code {
+                KeyCode::Enter => {
+                    drop(model);
+                    self.commit_queue_insert_prompt().await;
+                }
+                KeyCode::Esc => {
+                    model.take_queue_insert_prompt().await;
+                }
+                KeyCode::Backspace => model.queue_insert_prompt_backspace().await,
+                KeyCode::Char(c) if c.is_ascii_digit() => model.queue_insert_prompt_push(c).await,
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        // Handle playlist create/rename/describe/unfollow/import prompt
+        if model.is_playlist_prompt_open().await {
+            match key.code {
+                KeyCode::Enter => {
+                    drop(model);
+                    self.commit_playlist_prompt().await;
+                }
+                KeyCode::Esc => {
+                    model.take_playlist_prompt().await;
+                }
+                KeyCode::Tab => model.cycle_playlist_prompt_option().await,
+                KeyCode::Backspace => model.playlist_prompt_backspace().await,
+                KeyCode::Char(c) if !pressed.is_command() => model.playlist_prompt_push(c).await,
+                _ => {}
             }
+            return Ok(());
         }
 
         let ui_state = model.get_ui_state().await;
 
-        // Handle search input when in search section
-        if ui_state.active_section == ActiveSection::Search {
+        // Handle the list filter: while typing, characters go to the query;
+        // afterwards n/N step through the matches and Esc drops the filter
+        if ui_state.active_section == ActiveSection::MainContent
+            && let Some(filter) = model.get_list_filter().await
+        {
+            let text = matches!(key.code, KeyCode::Char(_)) && !pressed.is_command();
             match key.code {
-                KeyCode::Tab => {
-                    if key.modifiers.contains(KeyModifiers::SHIFT) {
-                        model.cycle_section_backward().await;
-                    } else {
-                        model.cycle_section_forward().await;
-                    }
+                KeyCode::Enter if filter.editing => {
+                    model.finish_list_filter().await;
                     return Ok(());
                 }
+                KeyCode::Esc => {
+                    model.close_list_filter().await;
+                    return Ok(());
+                }
+                KeyCode::Backspace if filter.editing => {
+                    model.list_filter_backspace().await;
+                    return Ok(());
+                }
+                // A filtered list loads its remaining pages so that rows
+                // further down can match too
+                KeyCode::Char(c) if filter.editing && text => {
+                    self.chord.lock().await.clear();
+                    model.list_filter_push(c).await;
+                    self.spawn_page_loads();
+                    return Ok(());
+                }
+                KeyCode::Char(c @ ('n' | 'N')) if text => {
+                    self.chord.lock().await.clear();
+                    model.jump_to_filter_match(c == 'n').await;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        // Handle search input when in search section: plain characters are
+        // text, everything else (Tab, Ctrl+Q, ...) goes through the keymap
+        if ui_state.active_section == ActiveSection::Search {
+            match key.code {
                 KeyCode::Enter => {
-                    let query = ui_state.search_query.clone();
                     drop(model);
-                    if !query.is_empty() {
-                        self.perform_search(&query).await;
-                    }
+                    self.submit_search().await;
                     return Ok(());
                 }
                 KeyCode::Esc => {
-                    model.update_search_query(String::new()).await;
+                    // The first Esc closes the suggestions
+                    let suggestions_open = !ui_state.search_suggestions.is_empty();
+                    if !suggestions_open {
+                        model.update_search_query(String::new()).await;
+                    }
+                    drop(model);
+                    self.cancel_live_search().await;
+                    return Ok(());
+                }
+                KeyCode::Up | KeyCode::Down => {
+                    drop(model);
+                    self.search_bar_move(key.code == KeyCode::Up).await;
                     return Ok(());
                 }
                 KeyCode::Backspace => {
                     model.backspace_search().await;
+                    drop(model);
+                    self.search_text_changed().await;
                     return Ok(());
                 }
-                KeyCode::Char(c) => {
-                    // Q still quits even in search mode when Ctrl is pressed
-                    if (c == 'q' || c == 'Q') && key.modifiers.contains(KeyModifiers::CONTROL) {
-                        model.set_should_quit(true).await;
-                        return Ok(());
-                    }
+                KeyCode::Char(c) if !pressed.is_command() => {
+                    self.chord.lock().await.clear();
                     model.append_to_search(c).await;
+                    drop(model);
+                    self.search_text_changed().await;
                     return Ok(());
                 }
                 _ => {}
             }
         }
 
+        let action = match self.chord.lock().await.feed(&self.keymap, pressed) {
+            KeyLookup::Action(action) => action,
+            KeyLookup::Pending | KeyLookup::Unbound => return Ok(()),
+        };
+
         // Handle MainContent section navigation
         if ui_state.active_section == ActiveSection::MainContent {
-            match key.code {
-                KeyCode::Up => {
+            match action {
+                Action::MoveUp => {
                     model.content_move_up().await;
                     return Ok(());
                 }
-                KeyCode::Down => {
-                    model.content_move_down().await;
-                    // Check if we need to load more playlist tracks (spawn in background)
-                    if let Some((playlist_id, offset)) = model.should_load_more_playlist_tracks().await {
-                        let controller = self.clone();
-                        tokio::spawn(async move {
-                            controller.load_more_playlist_tracks(&playlist_id, offset).await;
-                        });
-                    }
+                Action::MoveDown => {
+                    self.content_move_down(&model).await;
                     return Ok(());
                 }
-                KeyCode::Left => {
-                    model.navigate_search_section(false).await;
+                Action::PrevCategory => {
+                    model.navigate_category(false).await;
                     return Ok(());
                 }
-                KeyCode::Right => {
-                    model.navigate_search_section(true).await;
+                Action::NextCategory => {
+                    model.navigate_category(true).await;
                     return Ok(());
                 }
-                KeyCode::Enter => {
+                Action::ShowAllResults => {
+                    drop(model);
+                    self.show_all_search_results().await;
+                    return Ok(());
+                }
+                Action::FilterList => {
+                    model.open_list_filter().await;
+                    return Ok(());
+                }
+                Action::Select => {
+                    if model.is_import_review_open().await {
+                        drop(model);
+                        self.commit_import_review().await;
+                        return Ok(());
+                    }
+                    if model.is_restore_plan_open().await {
+                        drop(model);
+                        self.commit_restore().await;
+                        return Ok(());
+                    }
                     let selected = model.get_selected_content_item().await;
                     drop(model);
                     if let Some(item) = selected {
@@ -140,139 +303,610 @@ impl AppController {
                     }
                     return Ok(());
                 }
-                KeyCode::Backspace | KeyCode::Esc => {
+                Action::Back => {
                     model.navigate_back().await;
                     return Ok(());
                 }
-                KeyCode::Char('x') | KeyCode::Char('X') => {
+                Action::ToggleLike => {
                     if let Some((track_id, _is_liked)) = model.get_selected_track_for_like().await {
                         drop(model);
                         self.toggle_liked_track(&track_id).await;
+                    } else if let Some(item @ (SelectedItem::Album { .. } | SelectedItem::Artist { .. })) =
+                        model.get_selected_content_item().await
+                    {
+                        drop(model);
+                        self.toggle_library_item(item).await;
                     }
                     return Ok(());
                 }
-                KeyCode::Char('k') | KeyCode::Char('K') => {
-                    if let Some(track_uri) = model.get_selected_track_uri().await {
+                Action::ToggleSaved => {
+                    if let Some(item) = model.get_open_album_or_artist().await {
                         drop(model);
-                        self.add_track_to_queue(&track_uri).await;
+                        self.toggle_library_item(item).await;
                     }
                     return Ok(());
                 }
-                KeyCode::Delete => {
-                    if let Some(index) = model.get_selected_queue_index().await {
-                        if let Some(uri) = model.remove_from_queue_view(index).await {
-                            model.add_to_queue_skip_list(uri).await;
-                            tracing::info!("Track added to skip list (will be auto-skipped)");
-                        }
+                Action::CycleTopRange => {
+                    drop(model);
+                    self.cycle_top_range().await;
+                    return Ok(());
+                }
+                Action::ExportTop => {
+                    drop(model);
+                    self.open_export_top_prompt().await;
+                    return Ok(());
+                }
+                Action::MarkPlayed => {
+                    if let Some(episode) = model.get_selected_episode().await {
+                        drop(model);
+                        self.toggle_episode_played(episode).await;
+                    }
+                    return Ok(());
+                }
+                Action::AddToQueue => {
+                    if model.is_import_review_open().await {
+                        drop(model);
+                        self.queue_import_review().await;
+                        return Ok(());
+                    }
+                    if let Some(track) = model.get_selected_track().await {
+                        drop(model);
+                        self.add_track_to_queue(track).await;
+                    }
+                    return Ok(());
+                }
+                Action::InsertIntoQueue => {
+                    if let Some(track) = model.get_selected_track().await {
+                        drop(model);
+                        self.open_queue_insert_prompt(track).await;
+                    }
+                    return Ok(());
+                }
+                Action::AddToPlaylist => {
+                    if let Some(track) = model.get_selected_track().await {
+                        drop(model);
+                        self.open_playlist_picker(track).await;
+                    }
+                    return Ok(());
+                }
+                Action::RemoveFromQueue => {
+                    let queue_index = model.get_selected_queue_index().await;
+                    drop(model);
+                    match queue_index {
+                        Some(index) => self.remove_from_queue(index).await,
+                        None => self.remove_selected_playlist_track().await,
+                    }
+                    return Ok(());
+                }
+                Action::MoveQueueUp | Action::MoveQueueDown => {
+                    let up = action == Action::MoveQueueUp;
+                    let queue_index = model.get_selected_queue_index().await;
+                    drop(model);
+                    match queue_index {
+                        Some(index) => self.move_queue_entry(index, up).await,
+                        None => self.move_selected_playlist_track(up).await,
                     }
                     return Ok(());
                 }
                 _ => {}
             }
         }
-        
-        // Global keybindings
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Char('Q') => {
+
+        // Global actions
+        match action {
+            Action::Quit => {
                 model.set_should_quit(true).await;
             }
-            KeyCode::Tab => {
-                if key.modifiers.contains(KeyModifiers::SHIFT) {
-                    model.cycle_section_backward().await;
-                } else {
-                    model.cycle_section_forward().await;
-                }
+            Action::NextSection => {
+                model.cycle_section_forward().await;
             }
-            KeyCode::BackTab => {
+            Action::PrevSection => {
                 model.cycle_section_backward().await;
             }
-            KeyCode::Up => {
+            Action::MoveUp => {
                 model.move_selection_up().await;
             }
-            KeyCode::Down => {
+            Action::MoveDown => {
                 model.move_selection_down().await;
             }
-            KeyCode::Enter => {
+            Action::Select => {
                 // Handle Enter based on active section
-                let ui_state = model.get_ui_state().await;
                 match ui_state.active_section {
                     ActiveSection::Library => {
                         // Open selected library item
                         let selected = ui_state.library_selected;
                         drop(model);
                         self.open_library_item(selected).await;
-                        return Ok(());
                     }
                     ActiveSection::Playlists => {
                         // Open selected playlist
                         if let Some(playlist) = model.get_selected_playlist().await {
                             drop(model);
                             self.open_playlist(&playlist.id).await;
-                            return Ok(());
                         }
                     }
                     _ => {}
                 }
             }
-            // Play/Pause toggle
-            KeyCode::Char(' ') => {
+            Action::PlayPause => {
                 drop(model);
                 self.toggle_playback().await;
             }
-            // Next track
-            KeyCode::Char('n') | KeyCode::Char('N') => {
+            Action::NextTrack => {
                 drop(model);
                 self.next_track().await;
             }
-            // Previous track
-            KeyCode::Char('p') | KeyCode::Char('P') => {
+            Action::PreviousTrack => {
                 drop(model);
                 self.previous_track().await;
             }
-            // Toggle shuffle
-            KeyCode::Char('s') | KeyCode::Char('S') => {
+            Action::ToggleShuffle => {
                 drop(model);
                 self.toggle_shuffle().await;
             }
-            // Cycle repeat mode
-            KeyCode::Char('r') | KeyCode::Char('R') => {
+            Action::CycleRepeat => {
                 drop(model);
                 self.cycle_repeat().await;
             }
-            // Volume up
-            KeyCode::Char('+') | KeyCode::Char('=') => {
+            Action::VolumeUp => {
                 drop(model);
                 self.volume_up().await;
             }
-            // Volume down
-            KeyCode::Char('-') => {
+            Action::VolumeDown => {
                 drop(model);
                 self.volume_down().await;
             }
-            // Open device picker
-            KeyCode::Char('d') | KeyCode::Char('D') => {
+            Action::SeekForward => {
+                drop(model);
+                self.seek_relative(true).await;
+            }
+            Action::SeekBackward => {
+                drop(model);
+                self.seek_relative(false).await;
+            }
+            Action::SeekToPosition => {
+                model.open_seek_prompt().await;
+            }
+            Action::OpenDevicePicker => {
                 drop(model);
                 self.open_device_picker().await;
             }
-            // Focus search
-            KeyCode::Char('g') | KeyCode::Char('G') => {
+            Action::FocusSearch => {
                 model.set_active_section(ActiveSection::Search).await;
             }
-            // Focus playlists
-            KeyCode::Char('l') | KeyCode::Char('L') => {
+            Action::FocusPlaylists => {
                 model.set_active_section(ActiveSection::Playlists).await;
             }
-            // Show queue
-            KeyCode::Char('u') | KeyCode::Char('U') => {
+            Action::GoHome => {
+                drop(model);
+                if self.open_home().await {
+                    self.model.lock().await.set_active_section(ActiveSection::MainContent).await;
+                }
+            }
+            Action::ShowQueue => {
                 drop(model);
                 self.show_queue().await;
             }
-            // Show help popup
-            KeyCode::Char('h') | KeyCode::Char('H') => {
+            Action::ClearQueue => {
+                drop(model);
+                self.clear_queue().await;
+            }
+            Action::CreatePlaylist => {
+                drop(model);
+                self.open_create_playlist_prompt().await;
+            }
+            Action::RenamePlaylist | Action::DescribePlaylist | Action::UnfollowPlaylist => {
+                drop(model);
+                self.open_playlist_change_prompt(action).await;
+            }
+            Action::ExportPlaylist => {
+                drop(model);
+                self.export_playlist().await;
+            }
+            Action::ImportPlaylist => {
+                drop(model);
+                self.open_import_prompt().await;
+            }
+            Action::BackupLibrary => {
+                drop(model);
+                self.backup_library().await;
+            }
+            Action::ToggleHelp => {
                 model.show_help_popup().await;
             }
-            _ => {}
+            Action::OpenSettings => {
+                drop(model);
+                self.open_settings().await;
+            }
+            // Only meaningful in the main content area
+            Action::PrevCategory
+            | Action::NextCategory
+            | Action::ShowAllResults
+            | Action::FilterList
+            | Action::Back
+            | Action::ToggleLike
+            | Action::ToggleSaved
+            | Action::MarkPlayed
+            | Action::CycleTopRange
+            | Action::ExportTop
+            | Action::AddToQueue
+            | Action::InsertIntoQueue
+            | Action::AddToPlaylist
+            | Action::RemoveFromQueue
+            | Action::MoveQueueUp
+            | Action::MoveQueueDown => {}
         }
         Ok(())
     }
+
+    /// Move the content selection down, loading more playlist tracks or
+    /// library items if needed
+    async fn content_move_down(&self, model: &AppModel) {
+        model.content_move_down().await;
+        self.spawn_page_loads();
+    }
+
+    /// Load the pages the open list wants in the background
+    fn spawn_page_loads(&self) {
+        let controller = self.clone();
+        tokio::spawn(async move {
+            controller.load_wanted_pages().await;
+        });
+    }
+
+    pub async fn handle_mouse_event(&self, mouse: MouseEvent, layout: &ViewLayout) {
+        let scroll_down = match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => None,
+            MouseEventKind::ScrollDown => Some(true),
+            MouseEventKind::ScrollUp => Some(false),
+            _ => return,
+        };
+        let (column, row) = (mouse.column, mouse.row);
+
+        let ui_state = self.model.lock().await.get_ui_state().await;
+        if ui_state.error_message.is_some()
+            || ui_state.show_help_popup
+            || ui_state.show_settings
+            || ui_state.seek_prompt.is_some()
+            || ui_state.queue_insert_prompt.is_some()
+            || ui_state.playlist_prompt.is_some()
+        {
+            return;
+        }
+
+        // The device picker is modal: it takes every mouse event while open
+        if ui_state.show_device_picker {
+            if let Some(picker) = layout.device_picker {
+                self.handle_device_picker_mouse(picker, scroll_down, column, row).await;
+            }
+            return;
+        }
+
+        // So is the playlist picker
+        if ui_state.show_playlist_picker {
+            if let Some(picker) = layout.playlist_picker {
+                self.handle_playlist_picker_mouse(picker, scroll_down, column, row).await;
+            }
+            return;
+        }
+
+        match scroll_down {
+            Some(down) => self.handle_scroll(layout, column, row, down).await,
+            None => self.handle_click(layout, column, row).await,
+        }
+    }
+
+    async fn handle_device_picker_mouse(&self, picker: ListRegion, scroll_down: Option<bool>, column: u16, row: u16) {
+        let model = self.model.lock().await;
+        match scroll_down {
+            Some(true) => model.device_picker_move_down().await,
+            Some(false) => model.device_picker_move_up().await,
+            None if !picker.contains(column, row) => model.hide_device_picker().await,
+            None => {
+                if let Some(index) = picker.item_at(column, row) {
+                    model.device_picker_select(index).await;
+                    if let Some(device) = model.get_selected_device().await {
+                        let local_device_name = model.get_local_device_name().await;
+                        model.hide_device_picker().await;
+                        drop(model);
+                        self.select_device(&device, &local_device_name).await;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn handle_playlist_picker_mouse(&self, picker: ListRegion, scroll_down: Option<bool>, column: u16, row: u16) {
+        let model = self.model.lock().await;
+        match scroll_down {
+            Some(true) => model.playlist_picker_move_down().await,
+            Some(false) => model.playlist_picker_move_up().await,
+            None if !picker.contains(column, row) => model.hide_playlist_picker().await,
+            None => {
+                if let Some(index) = picker.item_at(column, row) {
+                    model.playlist_picker_select(index).await;
+                    drop(model);
+                    self.commit_playlist_picker().await;
+                }
+            }
+        }
+    }
+
+    async fn handle_click(&self, layout: &ViewLayout, column: u16, row: u16) {
+        let double_click = self.register_click(column, row).await;
+        let model = self.model.lock().await;
+
+        if layout.search_bar.contains(Position::new(column, row)) {
+            model.set_active_section(ActiveSection::Search).await;
+        } else if layout.device_name.contains(Position::new(column, row)) {
+            drop(model);
+            self.open_device_picker().await;
+        } else if layout.library.contains(column, row) {
+            match layout.library.item_at(column, row) {
+                Some(index) => {
+                    model.select_library_item(index).await;
+                    if double_click {
+                        drop(model);
+                        self.open_library_item(index).await;
+                    }
+                }
+                None => model.set_active_section(ActiveSection::Library).await,
+            }
+        } else if layout.playlists.contains(column, row) {
+            match layout.playlists.item_at(column, row) {
+                Some(index) => {
+                    model.select_playlist(index).await;
+                    if double_click
+                        && let Some(playlist) = model.get_selected_playlist().await
+                    {
+                        drop(model);
+                        self.open_playlist(&playlist.id).await;
+                    }
+                }
+                None => model.set_active_section(ActiveSection::Playlists).await,
+            }
+        } else if layout.content.contains(Position::new(column, row)) {
+            model.set_active_section(ActiveSection::MainContent).await;
+            let hit = layout
+                .content_lists
+                .iter()
+                .enumerate()
+                .find(|(_, region)| region.contains(column, row));
+            if let Some((list, region)) = hit {
+                model.content_focus_list(list).await;
+                if let Some(index) = region.item_at(column, row)
+                    && model.content_select(list, index).await
+                    && double_click
+                    && let Some(item) = model.get_selected_content_item().await
+                {
+                    drop(model);
+                    self.handle_selected_item(item).await;
+                }
+            }
+        } else {
+            drop(model);
+            // Click on the progress bar seeks to the matching position
+            let bar = layout.progress_bar.inner(Margin::new(1, 1));
+            if bar.width > 0 && bar.contains(Position::new(column, row)) {
+                let ratio = (column - bar.x) as f64 / bar.width.saturating_sub(1).max(1) as f64;
+                self.seek_to_ratio(ratio).await;
+            }
+        }
+    }
+
+    /// Record a left click and report whether it completes a double-click
+    async fn register_click(&self, column: u16, row: u16) -> bool {
+        let mut last_click = self.last_click.lock().await;
+        let now = Instant::now();
+        let double_click = matches!(
+            *last_click,
+            Some((at, c, r)) if c == column && r == row && now.duration_since(at) <= DOUBLE_CLICK_TIME
+        );
+        // A third click starts a new pair instead of triggering again
+        *last_click = if double_click { None } else { Some((now, column, row)) };
+        double_click
+    }
+
+    /// Scroll the list under the cursor, focusing its section
+    async fn handle_scroll(&self, layout: &ViewLayout, column: u16, row: u16, down: bool) {
+        let model = self.model.lock().await;
+
+        let section = if layout.library.contains(column, row) {
+            ActiveSection::Library
+        } else if layout.playlists.contains(column, row) {
+            ActiveSection::Playlists
+        } else if let Some(list) = layout.content_lists.iter().position(|region| region.contains(column, row)) {
+            model.content_focus_list(list).await;
+            ActiveSection::MainContent
+        } else {
+            return;
+        };
+        model.set_active_section(section).await;
+
+        match (section, down) {
+            (ActiveSection::MainContent, true) => self.content_move_down(&model).await,
+            (ActiveSection::MainContent, false) => model.content_move_up().await,
+            (_, true) => model.move_selection_down().await,
+            (_, false) => model.move_selection_up().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::controller::testing::{demo_controller, fake_controller};
+    use crate::model::{ContentView, FakeLibrary, SelectedItem};
+
+    async fn press(controller: &AppController, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\n' => KeyCode::Enter,
+                '\u{1b}' => KeyCode::Esc,
+                c => KeyCode::Char(c),
+            };
+            controller.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE)).await.unwrap();
+        }
+    }
+
+    async fn selected_index(controller: &AppController) -> usize {
+        match controller.model.lock().await.content_state.lock().await.view {
+            ContentView::AlbumDetail { selected_index, .. } => selected_index,
+            _ => panic!("album should be open"),
+        }
+    }
+
+    #[tokio::test]
+    async fn list_filter_steps_through_matches_of_the_whole_list() {
+        let (controller, _) = demo_controller();
+        controller.handle_selected_item(SelectedItem::Album { id: "fakealbum02".to_string() }).await;
+        controller.model.lock().await.set_active_section(ActiveSection::MainContent).await;
+
+        // "Low Tide" and "Lighthouse" match; "Undertow" and "Salt" don't
+        press(&controller, "/li\n").await;
+        let model = controller.model.lock().await;
+        assert_eq!(model.content_state.lock().await.filtered_rows(), Some(vec![0, 2]));
+        drop(model);
+
+        press(&controller, "n").await;
+        assert_eq!(selected_index(&controller).await, 2);
+        press(&controller, "n").await;
+        assert_eq!(selected_index(&controller).await, 0);
+        press(&controller, "N").await;
+        assert_eq!(selected_index(&controller).await, 2);
+
+        // The second row shown is the third track
+        let model = controller.model.lock().await;
+        assert!(model.content_select(0, 0).await);
+        assert!(model.content_select(0, 1).await);
+        assert!(!model.content_select(0, 2).await);
+        let item = model.get_selected_content_item().await;
+        assert!(matches!(item, Some(SelectedItem::AlbumTrack { track_uri, .. }) if track_uri == "spotify:track:faketrack0203"));
+        drop(model);
+
+        // Esc shows the whole list again, keeping the selection
+        press(&controller, "\u{1b}").await;
+        assert_eq!(controller.model.lock().await.get_list_filter().await, None);
+        assert_eq!(selected_index(&controller).await, 2);
+    }
+
+    #[tokio::test]
+    async fn list_filter_loads_the_pages_of_a_list_not_scrolled_to() {
+        let mut library = FakeLibrary::demo("test");
+        library.liked_track_ids = ["faketrack0101", "faketrack0102", "faketrack0201"].map(String::from).to_vec();
+        let mut config = Config::default();
+        config.library.liked_songs_limit = 1;
+        let (controller, _) = fake_controller(library, config, None);
+        controller.open_library_item(1).await;
+        controller.model.lock().await.set_active_section(ActiveSection::MainContent).await;
+
+        // Only "Polar Night" is loaded; "Low Tide" is two pages further
+        press(&controller, "/tide").await;
+        let all_loaded = async {
+            loop {
+                let model = controller.model.lock().await;
+                if let ContentView::LikedSongs { tracks, paging, .. } = &model.content_state.lock().await.view
+                    && !paging.has_more
+                {
+                    return tracks.len();
+                }
+                drop(model);
+                tokio::task::yield_now().await;
+            }
+        };
+        let loaded = tokio::time::timeout(Duration::from_secs(5), all_loaded).await;
+        assert_eq!(loaded.expect("the rest of the list should load"), 3);
+        let model = controller.model.lock().await;
+        assert_eq!(model.content_state.lock().await.filtered_rows(), Some(vec![2]));
+    }
+
+    #[tokio::test]
+    async fn albums_and_artists_are_saved_and_followed_where_they_are_shown() {
+        let (controller, _) = demo_controller();
+        controller.handle_selected_item(SelectedItem::Artist { id: "fakeartist03".to_string() }).await;
+        controller.model.lock().await.set_active_section(ActiveSection::MainContent).await;
+        let artist_state = |controller: &AppController| {
+            let model = controller.model.clone();
+            async move {
+                match &model.lock().await.content_state.lock().await.view {
+                    ContentView::ArtistDetail { detail, .. } => (detail.followed, detail.albums[0].saved),
+                    _ => panic!("artist should be open"),
+                }
+            }
+        };
+        assert_eq!(artist_state(&controller).await, (false, true));
+
+        // f follows the open artist, x unsaves the selected album
+        press(&controller, "f").await;
+        controller.handle_key_event(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE)).await.unwrap();
+        press(&controller, "x").await;
+        assert_eq!(artist_state(&controller).await, (true, false));
+
+        // Saving the album from its own view shows up on going back
+        press(&controller, "\n").await;
+        press(&controller, "f").await;
+        let model = controller.model.lock().await;
+        let spotify = model.spotify.clone().unwrap();
+        assert!(spotify.is_album_saved("fakealbum03").await);
+        assert!(spotify.is_artist_followed("fakeartist03").await);
+        drop(model);
+        press(&controller, "\u{1b}").await;
+        assert_eq!(artist_state(&controller).await, (true, true));
+    }
+
+    #[tokio::test]
+    async fn podcast_episodes_resume_and_are_marked_played() {
+        let (controller, _) = demo_controller();
+        let down = |controller: &AppController| {
+            let controller = controller.clone();
+            async move {
+                controller.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)).await.unwrap();
+            }
+        };
+        let marks = |controller: &AppController| {
+            let model = controller.model.clone();
+            async move {
+                match &model.lock().await.content_state.lock().await.view {
+                    ContentView::ShowDetail { detail, .. } => {
+                        detail.episodes.iter().map(|e| e.played()).collect::<Vec<_>>()
+                    }
+                    _ => panic!("show should be open"),
+                }
+            }
+        };
+
+        // Podcasts, then the saved show
+        controller.open_library_item(4).await;
+        press(&controller, "\n").await;
+        let item = controller.model.lock().await.get_selected_content_item().await;
+        assert!(matches!(
+            item,
+            Some(SelectedItem::Episode { ref episode_uri, position_ms: 900_000, .. })
+                if episode_uri == "spotify:episode:fakeepisode05"
+        ));
+        press(&controller, "\n").await;
+        let spotify = controller.model.lock().await.spotify.clone().unwrap();
+        let (playing, _) = spotify.get_queue().await.unwrap();
+        assert_eq!(playing.unwrap().uri, "spotify:episode:fakeepisode05");
+        assert_eq!(marks(&controller).await, [false, false, true, false, false]);
+
+        // Episodes Spotify counts as played can't be unmarked
+        down(&controller).await;
+        down(&controller).await;
+        press(&controller, "e").await;
+        assert_eq!(marks(&controller).await, [false, false, true, false, false]);
+        assert!(controller.model.lock().await.get_ui_state().await.message_is_info);
+        press(&controller, "\u{1b}").await;
+
+        down(&controller).await;
+        press(&controller, "e").await;
+        assert_eq!(marks(&controller).await, [false, false, true, true, false]);
+        let item = controller.model.lock().await.get_selected_content_item().await;
+        assert!(matches!(item, Some(SelectedItem::Episode { position_ms: 0, .. })));
+        press(&controller, "e").await;
+        assert_eq!(marks(&controller).await, [false, false, true, false, false]);
+    }
 }
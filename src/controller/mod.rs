@@ -4,20 +4,35 @@
 //! coordinates between the model and view, and manages playback operations.
 //! It is organized into submodules by responsibility:
 //!
-//! - `input`: Key event handling
+//! - `input`: Key and mouse event handling
 //! - `playback`: Playback control methods
 //! - `navigation`: Library/playlist/search navigation
+//! - `search`: Search bar history and live search suggestions
 //! - `player_events`: Librespot player event listener
+//! - `queue`: Client-side queue editing and feeding the local player
+//! - `playlists`: Adding, removing and reordering playlist tracks
+//! - `playlist_files`: Playlist export and import (JSON, CSV, M3U, text)
+//! - `backup`: Whole-library backup and restore
+//! - `settings`: Settings overlay changes and persistence
 
 mod input;
 mod playback;
 mod navigation;
+mod search;
 mod player_events;
+mod queue;
+mod playlists;
+mod playlist_files;
+mod backup;
+mod settings;
 
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 
 use crate::audio::AudioBackend;
+use crate::keymap::{ChordState, Keymap};
 use crate::model::AppModel;
 
 #[derive(Clone)]
@@ -25,14 +40,29 @@ pub struct AppController {
     pub(crate) model: Arc<Mutex<AppModel>>,
     pub(crate) audio_backend: Arc<Mutex<Option<AudioBackend>>>,
     event_listener_started: Arc<Mutex<bool>>,
+    pub(crate) keymap: Arc<Keymap>,
+    /// Keys typed so far towards a multi-key binding
+    chord: Arc<Mutex<ChordState>>,
+    /// Time and cell of the last left click, for double-click detection
+    last_click: Arc<Mutex<Option<(Instant, u16, u16)>>>,
+    /// Live search waiting for typing to pause, or in flight
+    live_search: Arc<Mutex<Option<JoinHandle<()>>>>,
 }
 
 impl AppController {
-    pub fn new(model: Arc<Mutex<AppModel>>, audio_backend: Arc<Mutex<Option<AudioBackend>>>) -> Self {
+    pub fn new(
+        model: Arc<Mutex<AppModel>>,
+        audio_backend: Arc<Mutex<Option<AudioBackend>>>,
+        keymap: Arc<Keymap>,
+    ) -> Self {
         Self {
             model,
             audio_backend,
             event_listener_started: Arc::new(Mutex::new(false)),
+            keymap,
+            chord: Arc::new(Mutex::new(ChordState::default())),
+            last_click: Arc::new(Mutex::new(None)),
+            live_search: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -44,14 +74,14 @@ impl AppController {
         }
 
         let backend_guard = self.audio_backend.lock().await;
-        if let Some(backend) = backend_guard.as_ref() {
-            if let Some(event_channel) = backend.get_player_event_channel().await {
-                *started = true;
-                let audio_backend = self.audio_backend.clone();
-                drop(backend_guard);
-                drop(started);
-                self.start_player_event_listener(event_channel, audio_backend);
-            }
+        if let Some(backend) = backend_guard.as_ref()
+            && let Some(event_channel) = backend.get_player_event_channel().await
+        {
+            *started = true;
+            let audio_backend = self.audio_backend.clone();
+            drop(backend_guard);
+            drop(started);
+            self.start_player_event_listener(event_channel, audio_backend);
         }
     }
 
@@ -74,3 +104,46 @@ impl AppController {
         }
     }
 }
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use std::path::Path;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    use super::AppController;
+    use crate::config::Config;
+    use crate::keymap::Keymap;
+    use crate::model::{AppModel, FakeLibrary, FakeSpotifyClient};
+
+    /// Controller over the demo library with the default config
+    pub(crate) fn demo_controller() -> (AppController, Arc<FakeSpotifyClient>) {
+        fake_controller(FakeLibrary::demo("test"), Config::default(), None)
+    }
+
+    /// Controller over `library`. With a `data_dir`, the config file,
+    /// playlist exports and backups are written there.
+    pub(crate) fn fake_controller(
+        library: FakeLibrary,
+        config: Config,
+        data_dir: Option<&Path>,
+    ) -> (AppController, Arc<FakeSpotifyClient>) {
+        let spotify = Arc::new(FakeSpotifyClient::new(library));
+        let mut model = AppModel::new();
+        model.set_spotify_client(spotify.clone());
+        match data_dir {
+            Some(dir) => {
+                model.set_config(config, dir.join("config.toml"));
+                model.set_export_dir(dir.join("playlists"));
+                model.set_backup_dir(dir.join("backups"));
+            }
+            None => model.set_config(config, Default::default()),
+        }
+        let controller = AppController::new(
+            Arc::new(Mutex::new(model)),
+            Arc::new(Mutex::new(None)),
+            Arc::new(Keymap::default()),
+        );
+        (controller, spotify)
+    }
+}
@@ -1,18 +1,128 @@
-//! Navigation-related controller methods (library, playlists, search)
+//! Navigation-related controller methods (home, library, playlists, search)
 
-use crate::model::ActiveSection;
+use std::collections::HashSet;
+use crate::model::{
+    ActiveSection, Category, HomePage, HomeSection, LibraryPageRequest, ListPaging, SearchQuery, SearchResultSection,
+    SpotifyApi, TopItems, TopRange,
+};
 use super::AppController;
 
-pub const SEARCH_LIMIT: usize = 40;
-
 impl AppController {
+    /// Open the home page. Returns false if it could not be loaded.
+    pub async fn open_home(&self) -> bool {
+        let Some(home) = self.load_home().await else {
+            return false;
+        };
+        self.model.lock().await.set_home(home).await;
+        true
+    }
+
+    /// Open the home page once it has loaded, unless something else was
+    /// opened in the meantime
+    pub async fn open_home_at_startup(&self) {
+        if let Some(home) = self.load_home().await {
+            self.model.lock().await.set_home_if_empty(home).await;
+        }
+    }
+
+    /// Load the home page. Sections load independently: one that fails is
+    /// left empty, and an error is shown only if all of them fail.
+    async fn load_home(&self) -> Option<HomePage> {
+        let model = self.model.lock().await;
+        let spotify = model.spotify.clone()?;
+        model.set_content_loading(true).await;
+        let limits = model.get_config().await.library;
+        drop(model);
+
+        let (recent, new_releases, top_tracks, top_artists, categories) = futures::join!(
+            spotify.get_recently_played(limits.recently_played_limit),
+            spotify.get_new_releases(limits.home_limit),
+            spotify.get_top_tracks(TopRange::SixMonths, limits.home_limit),
+            spotify.get_top_artists(TopRange::SixMonths, limits.home_limit),
+            spotify.get_categories(limits.home_limit),
+        );
+
+        fn section<T>(name: &str, result: anyhow::Result<Vec<T>>, errors: &mut Vec<anyhow::Error>) -> Vec<T> {
+            result.unwrap_or_else(|e| {
+                tracing::warn!(section = name, error = %e, "Failed to load home section");
+                errors.push(e);
+                Vec::new()
+            })
+        }
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+        let mut home = HomePage {
+            jump_back_in: section("jump back in", recent, &mut errors)
+                .into_iter()
+                .filter(|track| seen.insert(track.uri.clone()))
+                .take(limits.home_limit as usize)
+                .collect(),
+            new_releases: section("new releases", new_releases, &mut errors),
+            top_tracks: section("top tracks", top_tracks, &mut errors),
+            top_artists: section("top artists", top_artists, &mut errors),
+            categories: section("categories", categories, &mut errors),
+        };
+
+        if errors.len() == HomeSection::ALL.len() {
+            let model = self.model.lock().await;
+            model.set_content_loading(false).await;
+            model.set_error(Self::format_error(&errors.remove(0))).await;
+            return None;
+        }
+        spotify.mark_tracks_liked(&mut home.jump_back_in).await;
+        spotify.mark_tracks_liked(&mut home.top_tracks).await;
+        spotify.mark_albums_saved(&mut home.new_releases).await;
+        spotify.mark_artists_followed(&mut home.top_artists).await;
+        Some(home)
+    }
+
+    /// Show the next range (4 weeks, 6 months, a year) in the open "Your Top"
+    pub async fn cycle_top_range(&self) {
+        let model = self.model.lock().await;
+        let Some(range) = model.get_top_range().await else {
+            return;
+        };
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        model.set_content_loading(true).await;
+        let limit = model.get_config().await.library.top_limit;
+        drop(model);
+
+        let result = Self::load_top(spotify.as_ref(), range.next(), limit).await;
+        let model = self.model.lock().await;
+        match result {
+            Ok(top) => model.set_top(top).await,
+            Err(e) => {
+                model.set_content_loading(false).await;
+                model.set_error(Self::format_error(&e)).await;
+            }
+        }
+    }
+
+    async fn load_top(spotify: &dyn SpotifyApi, range: TopRange, limit: u32) -> anyhow::Result<TopItems> {
+        let (tracks, artists) = futures::try_join!(
+            spotify.get_top_tracks(range, limit),
+            spotify.get_top_artists(range, limit),
+        )?;
+        let mut top = TopItems { range, tracks, artists };
+        spotify.mark_tracks_liked(&mut top.tracks).await;
+        spotify.mark_artists_followed(&mut top.artists).await;
+        Ok(top)
+    }
+
     pub async fn perform_search(&self, query: &str) {
         tracing::debug!(query, "Performing search");
         let model = self.model.lock().await;
+        if let Err(e) = SearchQuery::parse(query) {
+            model.set_error(e.to_string()).await;
+            return;
+        }
         model.set_content_loading(true).await;
+        let limit = model.get_config().await.library.search_limit;
 
         if let Some(spotify) = &model.spotify {
-            match spotify.search(query, SEARCH_LIMIT as u32).await {
+            match spotify.search(query, limit).await {
                 Ok(mut results) => {
                     tracing::info!(
                         query,
@@ -23,6 +133,8 @@ impl AppController {
                         "Search completed successfully"
                     );
                     spotify.mark_tracks_liked(&mut results.tracks).await;
+                    spotify.mark_albums_saved(&mut results.albums).await;
+                    spotify.mark_artists_followed(&mut results.artists).await;
                     model.set_search_results(results).await;
                     // Switch to MainContent section to show results
                     let mut ui_state = model.ui_state.lock().await;
@@ -38,11 +150,76 @@ impl AppController {
         }
     }
 
+    /// Open every result of the search category being viewed, starting
+    /// with a page the size of the search limit
+    pub async fn show_all_search_results(&self) {
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        let Some((query, section)) = model.get_search_category().await else {
+            return;
+        };
+        let limit = model.get_config().await.library.search_limit;
+        model.set_content_loading(true).await;
+        drop(model);
+
+        let result = spotify.search_category(&query, section, 0, limit).await;
+        let model = self.model.lock().await;
+        match result {
+            Ok((mut results, total, has_more)) => {
+                tracing::info!(query, ?section, loaded = results.len(section), total, "Opened search category");
+                spotify.mark_tracks_liked(&mut results.tracks).await;
+                spotify.mark_albums_saved(&mut results.albums).await;
+                spotify.mark_artists_followed(&mut results.artists).await;
+                model.set_search_category(results, section, ListPaging::new(total, has_more)).await;
+            }
+            Err(e) => {
+                tracing::error!(query, ?section, error = %e, "Failed to open search category");
+                model.set_content_loading(false).await;
+                model.set_error(Self::format_error(&e)).await;
+            }
+        }
+    }
+
+    /// Load the next page of the open search category, returning whether it
+    /// loaded
+    pub async fn load_more_search_results(&self, query: &str, section: SearchResultSection, offset: usize) -> bool {
+        tracing::debug!(query, ?section, offset, "Loading more search results");
+
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return false;
+        };
+        model.set_search_loading_more(true).await;
+        let limit = model.get_config().await.library.search_limit;
+        drop(model);
+
+        match spotify.search_category(query, section, offset, limit).await {
+            Ok((mut results, _total, has_more)) => {
+                tracing::info!(query, loaded = results.len(section), has_more, "Loaded more search results");
+                spotify.mark_tracks_liked(&mut results.tracks).await;
+                spotify.mark_albums_saved(&mut results.albums).await;
+                spotify.mark_artists_followed(&mut results.artists).await;
+                self.model.lock().await.append_search_results(results, has_more).await;
+                true
+            }
+            Err(e) => {
+                tracing::error!(query, error = %e, "Failed to load more search results");
+                let model = self.model.lock().await;
+                model.set_search_loading_more(false).await;
+                model.set_error(Self::format_error(&e)).await;
+                false
+            }
+        }
+    }
+
     pub async fn load_user_playlists(&self) {
         let model = self.model.lock().await;
+        let limit = model.get_config().await.library.playlists_limit;
 
         if let Some(spotify) = &model.spotify {
-            match spotify.get_user_playlists(50).await {
+            match spotify.get_user_playlists(limit).await {
                 Ok(playlists) => {
                     model.set_playlists(playlists).await;
                 }
@@ -76,7 +253,8 @@ impl AppController {
         }
     }
 
-    pub async fn load_more_playlist_tracks(&self, playlist_id: &str, offset: usize) {
+    /// Load the next page of the open playlist, returning whether it loaded
+    pub async fn load_more_playlist_tracks(&self, playlist_id: &str, offset: usize) -> bool {
         tracing::debug!(playlist_id, offset, "Loading more playlist tracks");
 
         let model = self.model.lock().await;
@@ -99,6 +277,7 @@ impl AppController {
 
                     let model = self.model.lock().await;
                     model.append_playlist_tracks(tracks, has_more).await;
+                    true
                 }
                 Err(e) => {
                     tracing::error!(playlist_id, error = %e, "Failed to load more playlist tracks");
@@ -106,20 +285,117 @@ impl AppController {
                     model.set_playlist_loading_more(false).await;
                     let error_msg = Self::format_error(&e);
                     model.set_error(error_msg).await;
+                    false
+                }
+            }
+        } else {
+            false
+        }
+    }
+
+    /// Load the next page of the open library list, sized like its first,
+    /// returning whether it loaded
+    pub async fn load_more_library_items(&self, request: LibraryPageRequest) -> bool {
+        tracing::debug!(?request, "Loading more library items");
+
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return false;
+        };
+        model.set_library_loading_more(true).await;
+        let limits = model.get_config().await.library;
+        drop(model);
+
+        let result = async {
+            match &request {
+                LibraryPageRequest::LikedSongs { offset } => {
+                    let (tracks, _total, has_more) =
+                        spotify.get_liked_songs_page(*offset, limits.liked_songs_limit).await?;
+                    tracing::info!(loaded = tracks.len(), has_more, "Loaded more liked songs");
+                    self.model.lock().await.append_liked_songs(*offset, tracks, has_more).await;
+                }
+                LibraryPageRequest::SavedAlbums { offset } => {
+                    let (albums, _total, has_more) =
+                        spotify.get_saved_albums_page(*offset, limits.saved_albums_limit).await?;
+                    tracing::info!(loaded = albums.len(), has_more, "Loaded more saved albums");
+                    self.model.lock().await.append_saved_albums(*offset, albums, has_more).await;
+                }
+                LibraryPageRequest::FollowedArtists { after } => {
+                    let (artists, _total, has_more) =
+                        spotify.get_followed_artists_page(Some(after), limits.followed_artists_limit).await?;
+                    tracing::info!(loaded = artists.len(), has_more, "Loaded more followed artists");
+                    self.model.lock().await.append_followed_artists(after, artists, has_more).await;
+                }
+                LibraryPageRequest::SavedShows { offset } => {
+                    let (shows, _total, has_more) =
+                        spotify.get_saved_shows_page(*offset, limits.saved_shows_limit).await?;
+                    tracing::info!(loaded = shows.len(), has_more, "Loaded more saved shows");
+                    self.model.lock().await.append_saved_shows(*offset, shows, has_more).await;
+                }
+                LibraryPageRequest::CategoryPlaylists { category_id, offset } => {
+                    let (playlists, _total, has_more) =
+                        spotify.get_category_playlists_page(category_id, *offset, limits.home_limit).await?;
+                    tracing::info!(loaded = playlists.len(), has_more, "Loaded more category playlists");
+                    self.model.lock().await.append_category_playlists(category_id, *offset, playlists, has_more).await;
+                }
+                LibraryPageRequest::ShowEpisodes { show_id, offset } => {
+                    let (episodes, _total, has_more) =
+                        spotify.get_show_episodes_page(show_id, *offset, limits.episodes_limit).await?;
+                    tracing::info!(loaded = episodes.len(), has_more, "Loaded more episodes");
+                    self.model.lock().await.append_show_episodes(show_id, *offset, episodes, has_more).await;
                 }
             }
+            anyhow::Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            tracing::error!(?request, error = %e, "Failed to load more library items");
+            let model = self.model.lock().await;
+            model.set_library_loading_more(false).await;
+            let error_msg = Self::format_error(&e);
+            model.set_error(error_msg).await;
+            return false;
+        }
+        true
+    }
+
+    /// Load pages of the open list for as long as it wants them: while the
+    /// selection is near the end of what's loaded, or while a `/` filter
+    /// should see the whole list. Stops at the first page that fails.
+    pub async fn load_wanted_pages(&self) {
+        loop {
+            let model = self.model.lock().await;
+            let playlist = model.should_load_more_playlist_tracks().await;
+            let library = model.should_load_more_library_items().await;
+            let search = model.should_load_more_search_results().await;
+            drop(model);
+
+            let loaded = if let Some((playlist_id, offset)) = playlist {
+                self.load_more_playlist_tracks(&playlist_id, offset).await
+            } else if let Some(request) = library {
+                self.load_more_library_items(request).await
+            } else if let Some((query, section, offset)) = search {
+                self.load_more_search_results(&query, section, offset).await
+            } else {
+                return;
+            };
+            if !loaded {
+                return;
+            }
         }
     }
 
     pub async fn open_library_item(&self, index: usize) {
         let model = self.model.lock().await;
         model.set_content_loading(true).await;
+        let limits = model.get_config().await.library;
 
         if let Some(spotify) = &model.spotify {
             let result = match index {
                 0 => {
                     // Recently played
-                    match spotify.get_recently_played(50).await {
+                    match spotify.get_recently_played(limits.recently_played_limit).await {
                         Ok(mut tracks) => {
                             spotify.mark_tracks_liked(&mut tracks).await;
                             model.set_recently_played(tracks).await;
@@ -128,11 +404,13 @@ impl AppController {
                         Err(e) => Err(e),
                     }
                 }
+                // The other lists load their first page; the rest follows
+                // as they are scrolled
                 1 => {
-                    // Liked songs (already marked as liked in get_liked_songs)
-                    match spotify.get_liked_songs(100).await {
-                        Ok(tracks) => {
-                            model.set_liked_songs(tracks).await;
+                    // Liked songs (already marked as liked in get_liked_songs_page)
+                    match spotify.get_liked_songs_page(0, limits.liked_songs_limit).await {
+                        Ok((tracks, total, has_more)) => {
+                            model.set_liked_songs(tracks, ListPaging::new(total, has_more)).await;
                             Ok(())
                         }
                         Err(e) => Err(e),
@@ -140,9 +418,9 @@ impl AppController {
                 }
                 2 => {
                     // Albums
-                    match spotify.get_saved_albums(50).await {
-                        Ok(albums) => {
-                            model.set_saved_albums(albums).await;
+                    match spotify.get_saved_albums_page(0, limits.saved_albums_limit).await {
+                        Ok((albums, total, has_more)) => {
+                            model.set_saved_albums(albums, ListPaging::new(total, has_more)).await;
                             Ok(())
                         }
                         Err(e) => Err(e),
@@ -150,9 +428,29 @@ impl AppController {
                 }
                 3 => {
                     // Artists
-                    match spotify.get_followed_artists(50).await {
-                        Ok(artists) => {
-                            model.set_followed_artists(artists).await;
+                    match spotify.get_followed_artists_page(None, limits.followed_artists_limit).await {
+                        Ok((artists, total, has_more)) => {
+                            model.set_followed_artists(artists, ListPaging::new(total, has_more)).await;
+                            Ok(())
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                4 => {
+                    // Podcasts
+                    match spotify.get_saved_shows_page(0, limits.saved_shows_limit).await {
+                        Ok((shows, total, has_more)) => {
+                            model.set_saved_shows(shows, ListPaging::new(total, has_more)).await;
+                            Ok(())
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                5 => {
+                    // Your Top
+                    match Self::load_top(spotify.as_ref(), TopRange::default(), limits.top_limit).await {
+                        Ok(top) => {
+                            model.set_top(top).await;
                             Ok(())
                         }
                         Err(e) => Err(e),
@@ -180,87 +478,39 @@ impl AppController {
         use crate::model::SelectedItem;
         
         match item {
-            SelectedItem::Track { uri, .. } => {
-                if !self.ensure_device_available().await {
-                    return;
-                }
-
-                let model = self.model.lock().await;
-                model.clear_queue_skip_list().await;
-
-                if let Some(spotify) = &model.spotify {
-                    let spotify_clone = spotify.clone();
-                    let uri_clone = uri.clone();
-                    drop(model);
-
-                    let operation = move || {
-                        let spotify = spotify_clone.clone();
-                        let uri = uri_clone.clone();
-                        async move { spotify.play_track(&uri).await }
-                    };
-
-                    if let Err(e) = self.with_backend_recovery(operation).await {
-                        let model = self.model.lock().await;
-                        let error_msg = Self::format_error(&e);
-                        model.set_error(error_msg).await;
+            SelectedItem::TrackList { uris, index } => {
+                self.play_selected(move |spotify| {
+                    let uris = uris.clone();
+                    async move { spotify.play_tracks(&uris, index).await }
+                })
+                .await;
+            }
+            SelectedItem::LikedTrack { track_uri } => {
+                self.play_selected(move |spotify| {
+                    let track_uri = track_uri.clone();
+                    async move {
+                        let user_id = spotify.current_user_id().await?;
+                        let collection_uri = format!("spotify:user:{}:collection", user_id);
+                        spotify.play_context_from_track_uri(&collection_uri, &track_uri).await
                     }
-                }
+                })
+                .await;
             }
             SelectedItem::PlaylistTrack { playlist_uri, track_uri, .. } => {
-                if !self.ensure_device_available().await {
-                    return;
-                }
-
-                let model = self.model.lock().await;
-                model.clear_queue_skip_list().await;
-
-                if let Some(spotify) = &model.spotify {
-                    let spotify_clone = spotify.clone();
-                    let playlist_uri_clone = playlist_uri.clone();
-                    let track_uri_clone = track_uri.clone();
-                    drop(model);
-
-                    let operation = move || {
-                        let spotify = spotify_clone.clone();
-                        let playlist_uri = playlist_uri_clone.clone();
-                        let track_uri = track_uri_clone.clone();
-                        async move { spotify.play_context_from_track_uri(&playlist_uri, &track_uri).await }
-                    };
-
-                    if let Err(e) = self.with_backend_recovery(operation).await {
-                        let model = self.model.lock().await;
-                        let error_msg = Self::format_error(&e);
-                        model.set_error(error_msg).await;
-                    }
-                }
+                self.play_selected(move |spotify| {
+                    let playlist_uri = playlist_uri.clone();
+                    let track_uri = track_uri.clone();
+                    async move { spotify.play_context_from_track_uri(&playlist_uri, &track_uri).await }
+                })
+                .await;
             }
             SelectedItem::AlbumTrack { album_uri, track_uri, .. } => {
-                if !self.ensure_device_available().await {
-                    return;
-                }
-
-                let model = self.model.lock().await;
-                model.clear_queue_skip_list().await;
-
-                if let Some(spotify) = &model.spotify {
-                    let spotify_clone = spotify.clone();
-                    let album_uri_clone = album_uri.clone();
-                    let track_uri_clone = track_uri.clone();
-                    drop(model);
-
-                    let operation = move || {
-                        let spotify = spotify_clone.clone();
-                        let album_uri = album_uri_clone.clone();
-                        let track_uri = track_uri_clone.clone();
-                        async move { spotify.play_context_from_track_uri(&album_uri, &track_uri).await }
-                    };
-
-                    if let Err(e) = self.with_backend_recovery(operation).await {
-                        let model = self.model.lock().await;
-                        let error_msg = Self::format_error(&e);
-                        model.set_error(error_msg).await;
-                    }
-                }
+                self.play_selected(move |spotify| {
+                    let album_uri = album_uri.clone();
+                    let track_uri = track_uri.clone();
+                    async move { spotify.play_context_from_track_uri(&album_uri, &track_uri).await }
+                })
+                .await;
             }
             SelectedItem::Album { id, .. } => {
                 // Open album detail
@@ -270,6 +520,7 @@ impl AppController {
                     match spotify.get_album(&id).await {
                         Ok(mut detail) => {
                             spotify.mark_tracks_liked(&mut detail.tracks).await;
+                            detail.saved = spotify.is_album_saved(&detail.id).await;
                             model.set_album_detail(detail).await;
                         }
                         Err(e) => {
@@ -288,6 +539,11 @@ impl AppController {
                     match spotify.get_artist(&id).await {
                         Ok(mut detail) => {
                             spotify.mark_tracks_liked(&mut detail.top_tracks).await;
+                            for releases in [&mut detail.albums, &mut detail.singles, &mut detail.compilations, &mut detail.appears_on] {
+                                spotify.mark_albums_saved(releases).await;
+                            }
+                            spotify.mark_artists_followed(&mut detail.related).await;
+                            detail.followed = spotify.is_artist_followed(&detail.id).await;
                             model.set_artist_detail(detail).await;
                         }
                         Err(e) => {
@@ -316,6 +572,307 @@ impl AppController {
                     }
                 }
             }
+            SelectedItem::Category { id, name } => {
+                // Open the category's playlists
+                let model = self.model.lock().await;
+                model.set_content_loading(true).await;
+                let limit = model.get_config().await.library.home_limit;
+                if let Some(spotify) = &model.spotify {
+                    match spotify.get_category_playlists_page(&id, 0, limit).await {
+                        Ok((playlists, total, has_more)) => {
+                            let category = Category { id, name };
+                            model.set_category_playlists(category, playlists, ListPaging::new(total, has_more)).await;
+                        }
+                        Err(e) => {
+                            model.set_content_loading(false).await;
+                            let error_msg = Self::format_error(&e);
+                            model.set_error(error_msg).await;
+                        }
+                    }
+                }
+            }
+            SelectedItem::Show { id } => {
+                // Open show detail with the first page of its episodes
+                let model = self.model.lock().await;
+                model.set_content_loading(true).await;
+                let limit = model.get_config().await.library.episodes_limit;
+                if let Some(spotify) = &model.spotify {
+                    let result = async {
+                        let mut detail = spotify.get_show(&id).await?;
+                        let (episodes, total, has_more) = spotify.get_show_episodes_page(&id, 0, limit).await?;
+                        detail.episodes = episodes;
+                        anyhow::Ok((detail, ListPaging::new(total, has_more)))
+                    };
+                    match result.await {
+                        Ok((detail, paging)) => model.set_show_detail(detail, paging).await,
+                        Err(e) => {
+                            model.set_content_loading(false).await;
+                            let error_msg = Self::format_error(&e);
+                            model.set_error(error_msg).await;
+                        }
+                    }
+                }
+            }
+            SelectedItem::Episode { show_uri, episode_uri, position_ms } => {
+                // Play the episode in its show, from where it was left off
+                self.play_selected(move |spotify| {
+                    let show_uri = show_uri.clone();
+                    let episode_uri = episode_uri.clone();
+                    async move { spotify.play_episode(&show_uri, &episode_uri, position_ms).await }
+                })
+                .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::controller::testing::fake_controller;
+    use crate::model::{ArtistDetailSection, ContentView, FakeLibrary, PlaylistPromptKind, SelectedItem, TopTab};
+
+    #[tokio::test]
+    async fn library_lists_load_more_as_they_are_scrolled() {
+        let mut config = Config::default();
+        config.library.saved_albums_limit = 1;
+        let (controller, _) = fake_controller(FakeLibrary::demo("test"), config, None);
+
+        controller.open_library_item(2).await;
+        let request = controller.model.lock().await.should_load_more_library_items().await;
+        assert_eq!(request, Some(LibraryPageRequest::SavedAlbums { offset: 1 }));
+
+        controller.load_more_library_items(request.unwrap()).await;
+        let model = controller.model.lock().await;
+        let view = model.content_state.lock().await.view.clone();
+        let ContentView::SavedAlbums { albums, paging, .. } = view else {
+            panic!("saved albums should be open");
+        };
+        let names: Vec<&str> = albums.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["Northern Lights", "Paper Cities"]);
+        assert_eq!(paging, ListPaging { total: 2, has_more: false, loading_more: false });
+        assert_eq!(model.should_load_more_library_items().await, None);
+    }
+
+    #[tokio::test]
+    async fn pages_for_a_list_opened_afresh_are_dropped() {
+        let mut library = FakeLibrary::demo("test");
+        library.liked_track_ids = ["faketrack0101", "faketrack0102", "faketrack0103"].map(String::from).to_vec();
+        library.followed_artist_ids = ["fakeartist01", "fakeartist02", "fakeartist03"].map(String::from).to_vec();
+        let mut config = Config::default();
+        config.library.liked_songs_limit = 1;
+        config.library.followed_artists_limit = 1;
+        let (controller, spotify) = fake_controller(library, config, None);
+        let next_page = || async { controller.model.lock().await.should_load_more_library_items().await.unwrap() };
+        let rows = || async {
+            match controller.model.lock().await.content_state.lock().await.view.clone() {
+                ContentView::LikedSongs { tracks, .. } => tracks.into_iter().map(|t| t.id).collect::<Vec<_>>(),
+                ContentView::FollowedArtists { artists, .. } => artists.into_iter().map(|a| a.id).collect(),
+                _ => panic!("a library list should be open"),
+            }
+        };
+
+        // The third liked song arrives after the list was left and opened again
+        controller.open_library_item(1).await;
+        controller.load_more_library_items(next_page().await).await;
+        assert_eq!(next_page().await, LibraryPageRequest::LikedSongs { offset: 2 });
+        let (late, _, _) = spotify.get_liked_songs_page(2, 1).await.unwrap();
+        controller.open_library_item(1).await;
+        controller.model.lock().await.append_liked_songs(2, late, false).await;
+        assert_eq!(rows().await.len(), 1);
+        assert_eq!(next_page().await, LibraryPageRequest::LikedSongs { offset: 1 });
+
+        // Followed artists are paged by the last one shown
+        controller.open_library_item(3).await;
+        controller.load_more_library_items(next_page().await).await;
+        let LibraryPageRequest::FollowedArtists { after } = next_page().await else {
+            panic!("followed artists should page by cursor");
+        };
+        let (late, _, _) = spotify.get_followed_artists_page(Some(&after), 1).await.unwrap();
+        controller.open_library_item(3).await;
+        controller.model.lock().await.append_followed_artists(&after, late, false).await;
+        assert_eq!(rows().await, ["fakeartist01"]);
+        controller.load_more_library_items(next_page().await).await;
+        assert_eq!(rows().await, ["fakeartist01", "fakeartist02"]);
+    }
+
+    #[tokio::test]
+    async fn search_category_loads_more_and_goes_back_to_the_results() {
+        let mut config = Config::default();
+        config.library.search_limit = 5;
+        let (controller, _) = fake_controller(FakeLibrary::demo("test"), config, None);
+
+        controller.perform_search("t").await;
+        let model = controller.model.lock().await;
+        while model.get_search_category().await.map(|(_, section)| section) != Some(SearchResultSection::Tracks) {
+            model.navigate_search_section(true).await;
+        }
+        model.content_move_down().await;
+        drop(model);
+        controller.show_all_search_results().await;
+
+        for _ in 0..2 {
+            let request = controller.model.lock().await.should_load_more_search_results().await;
+            let (query, section, offset) = request.expect("more results should be wanted");
+            controller.load_more_search_results(&query, section, offset).await;
         }
+        let model = controller.model.lock().await;
+        let view = model.content_state.lock().await.view.clone();
+        let ContentView::SearchCategory { results, section, paging, .. } = view else {
+            panic!("search category should be open");
+        };
+        assert_eq!(section, SearchResultSection::Tracks);
+        assert_eq!(results.tracks.len(), 12);
+        assert_eq!(paging, ListPaging { total: 12, has_more: false, loading_more: false });
+        assert_eq!(model.should_load_more_search_results().await, None);
+
+        model.navigate_back().await;
+        let view = model.content_state.lock().await.view.clone();
+        let ContentView::SearchResults { results, track_index, .. } = view else {
+            panic!("search results should be back");
+        };
+        assert_eq!(results.query, "t");
+        assert_eq!(results.tracks.len(), 5);
+        assert_eq!(track_index, 1);
+    }
+
+    #[tokio::test]
+    async fn home_opens_at_startup_and_drills_into_categories() {
+        let mut library = FakeLibrary::demo("test");
+        library.recently_played_ids = ["faketrack0302", "faketrack0101", "faketrack0302"].map(String::from).to_vec();
+        let (controller, _) = fake_controller(library, Config::default(), None);
+
+        controller.open_home_at_startup().await;
+        let model = controller.model.lock().await;
+        let view = model.content_state.lock().await.view.clone();
+        let ContentView::Home { home, section, .. } = view else {
+            panic!("home should be open");
+        };
+        assert_eq!(section, HomeSection::JumpBackIn);
+        let uris: Vec<&str> = home.jump_back_in.iter().map(|t| t.uri.as_str()).collect();
+        assert_eq!(uris, ["spotify:track:faketrack0302", "spotify:track:faketrack0101"]);
+        assert_eq!(home.new_releases.len(), 2);
+        assert_eq!(home.top_tracks.len(), 3);
+        assert_eq!(home.top_artists[0].id, "fakeartist02");
+
+        for _ in 0..4 {
+            model.navigate_home_section(true).await;
+        }
+        let item = model.get_selected_content_item().await;
+        assert!(matches!(&item, Some(SelectedItem::Category { name, .. }) if name == "Late Night"));
+        drop(model);
+        controller.handle_selected_item(item.unwrap()).await;
+
+        let model = controller.model.lock().await;
+        let view = model.content_state.lock().await.view.clone();
+        let ContentView::CategoryPlaylists { category, playlists, .. } = view else {
+            panic!("category playlists should be open");
+        };
+        assert_eq!(category.name, "Late Night");
+        assert_eq!(playlists.iter().map(|p| p.name.as_str()).collect::<Vec<_>>(), ["Offline Mix"]);
+
+        model.navigate_back().await;
+        let view = model.content_state.lock().await.view.clone();
+        assert!(matches!(view, ContentView::Home { section: HomeSection::Categories, .. }));
+        model.set_saved_albums(Vec::new(), ListPaging::default()).await;
+        drop(model);
+
+        // A view opened while the home page loads is kept
+        controller.open_home_at_startup().await;
+        let view = controller.model.lock().await.content_state.lock().await.view.clone();
+        assert!(matches!(view, ContentView::SavedAlbums { .. }));
+    }
+
+    #[tokio::test]
+    async fn your_top_switches_tabs_and_ranges_and_exports_its_tracks() {
+        let (controller, spotify) = fake_controller(FakeLibrary::demo("test"), Config::default(), None);
+        let view = || async { controller.model.lock().await.content_state.lock().await.view.clone() };
+
+        controller.open_library_item(5).await;
+        let ContentView::Top { top, tab: TopTab::Tracks, .. } = view().await else {
+            panic!("top tracks should be open");
+        };
+        assert_eq!(top.range, TopRange::SixMonths);
+        assert_eq!(top.tracks.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), ["Lighthouse", "Polar Night", "Legend"]);
+        assert!(top.tracks[0].liked);
+
+        // The range changes under the open tab
+        let model = controller.model.lock().await;
+        model.switch_top_tab().await;
+        model.content_move_down().await;
+        drop(model);
+        controller.cycle_top_range().await;
+        let ContentView::Top { top, tab: TopTab::Artists, artist_index: 0, .. } = view().await else {
+            panic!("top artists should still be open");
+        };
+        assert_eq!(top.range, TopRange::Year);
+        assert_eq!(top.artists.len(), 3);
+        controller.cycle_top_range().await;
+        let ContentView::Top { top, .. } = view().await else {
+            panic!("your top should still be open");
+        };
+        assert_eq!(top.range, TopRange::FourWeeks);
+        assert_eq!(top.artists.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(), ["fakeartist01"]);
+
+        // The tracks play as a list and export to a new playlist
+        let model = controller.model.lock().await;
+        model.switch_top_tab().await;
+        model.content_move_down().await;
+        let item = model.get_selected_content_item().await;
+        assert!(matches!(item, Some(SelectedItem::TrackList { uris, index: 1 }) if uris.len() == 2));
+        drop(model);
+        // A plain new playlist is still empty and unnamed here
+        controller.open_create_playlist_prompt().await;
+        let prompt = controller.model.lock().await.take_playlist_prompt().await.unwrap();
+        assert_eq!(prompt.kind, PlaylistPromptKind::Create { visibility: Default::default() });
+        assert_eq!(prompt.input, "");
+        controller.open_export_top_prompt().await;
+        let prompt = controller.model.lock().await.get_ui_state().await.playlist_prompt.unwrap();
+        assert!(matches!(prompt.kind, PlaylistPromptKind::ExportTop { uris, .. } if uris.len() == 2));
+        assert_eq!(prompt.input, "Your Top Tracks (Last 4 weeks)");
+        controller.commit_playlist_prompt().await;
+
+        let created = spotify.get_user_playlists(50).await.unwrap();
+        let created = created.iter().find(|p| p.name == "Your Top Tracks (Last 4 weeks)").expect("playlist should exist");
+        let tracks = spotify.get_playlist(&created.id).await.unwrap().tracks;
+        let uris: Vec<&str> = tracks.iter().map(|t| t.uri.as_str()).collect();
+        assert_eq!(uris, ["spotify:track:faketrack0101", "spotify:track:faketrack0302"]);
+    }
+
+    #[tokio::test]
+    async fn artist_sections_skip_empty_ones_and_open_related_artists() {
+        let mut library = FakeLibrary::demo("test");
+        library.release_groups.insert("fakealbum01".to_string(), ArtistDetailSection::Singles);
+        let (controller, _) = fake_controller(library, Config::default(), None);
+        let section = || async {
+            match controller.model.lock().await.content_state.lock().await.view.clone() {
+                ContentView::ArtistDetail { detail, section, .. } => (detail.name, section),
+                _ => panic!("artist should be open"),
+            }
+        };
+
+        controller.handle_selected_item(SelectedItem::Artist { id: "fakeartist01".to_string() }).await;
+        let model = controller.model.lock().await;
+        // Clicking the list beside the top tracks picks the first section with releases
+        model.content_focus_list(1).await;
+        drop(model);
+        assert_eq!(section().await.1, ArtistDetailSection::Singles);
+        let model = controller.model.lock().await;
+        model.navigate_artist_section(true).await;
+        drop(model);
+        assert_eq!(section().await.1, ArtistDetailSection::Related);
+        let model = controller.model.lock().await;
+        model.navigate_artist_section(true).await;
+        model.navigate_artist_section(false).await;
+        model.content_move_down().await;
+        let item = model.get_selected_content_item().await;
+        assert!(matches!(&item, Some(SelectedItem::Artist { id }) if id == "fakeartist02"));
+        drop(model);
+
+        controller.handle_selected_item(item.unwrap()).await;
+        assert_eq!(section().await, ("Harbor Static".to_string(), ArtistDetailSection::TopTracks));
+        controller.model.lock().await.navigate_back().await;
+        assert_eq!(section().await, ("Aurora Fields".to_string(), ArtistDetailSection::Related));
     }
 }
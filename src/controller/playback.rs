@@ -2,9 +2,9 @@
 
 use anyhow::Result;
 use std::future::Future;
+use std::sync::Arc;
 
-use crate::audio::AudioBackend;
-use crate::model::{ActiveSection, RepeatState};
+use crate::model::{parse_timestamp, ActiveSection, Episode, RepeatState, SelectedItem, SpotifyApi};
 
 use super::AppController;
 
@@ -55,6 +55,8 @@ impl AppController {
 
     pub async fn next_track(&self) {
         tracing::debug!("Skipping to next track");
+        // Make sure a locally queued track is what we skip to
+        self.feed_local_queue(true).await;
         let model = self.model.lock().await;
 
         if let Some(spotify) = &model.spotify {
@@ -151,8 +153,9 @@ impl AppController {
         let model = self.model.lock().await;
 
         if let Some(spotify) = &model.spotify {
+            let step = model.get_config().await.audio.volume_step;
             let current_volume = model.get_volume().await;
-            let new_volume = (current_volume + 5).min(100);
+            let new_volume = current_volume.saturating_add(step).min(100);
 
             if let Err(e) = spotify.set_volume(new_volume).await {
                 let error_msg = Self::format_error(&e);
@@ -167,8 +170,9 @@ impl AppController {
         let model = self.model.lock().await;
 
         if let Some(spotify) = &model.spotify {
+            let step = model.get_config().await.audio.volume_step;
             let current_volume = model.get_volume().await;
-            let new_volume = current_volume.saturating_sub(5);
+            let new_volume = current_volume.saturating_sub(step);
 
             if let Err(e) = spotify.set_volume(new_volume).await {
                 let error_msg = Self::format_error(&e);
@@ -179,6 +183,66 @@ impl AppController {
         }
     }
 
+    /// Seek by the configured step
+    pub async fn seek_relative(&self, forward: bool) {
+        let (step_ms, position_ms, duration_ms) = {
+            let model = self.model.lock().await;
+            let step_ms = model.get_config().await.audio.seek_step_secs * 1000;
+            let (position_ms, duration_ms) = model.get_playback_position().await;
+            (step_ms, position_ms, duration_ms)
+        };
+        if duration_ms == 0 {
+            return;
+        }
+
+        let target = if forward {
+            position_ms.saturating_add(step_ms).min(duration_ms.saturating_sub(1000))
+        } else {
+            position_ms.saturating_sub(step_ms)
+        };
+        self.seek_to(target).await;
+    }
+
+    /// Seek to a fraction (0.0-1.0) of the current track
+    pub async fn seek_to_ratio(&self, ratio: f64) {
+        let (_, duration_ms) = self.model.lock().await.get_playback_position().await;
+        if duration_ms > 0 {
+            self.seek_to((duration_ms as f64 * ratio.clamp(0.0, 1.0)) as u32).await;
+        }
+    }
+
+    /// Seek to the position typed into the "go to" prompt
+    pub async fn commit_seek_prompt(&self) {
+        let model = self.model.lock().await;
+        let Some(input) = model.take_seek_prompt().await else {
+            return;
+        };
+        let (_, duration_ms) = model.get_playback_position().await;
+
+        match parse_timestamp(&input) {
+            Some(position_ms) if duration_ms == 0 || position_ms < duration_ms => {
+                drop(model);
+                self.seek_to(position_ms).await;
+            }
+            Some(_) => model.set_error(format!("{} is past the end of the track", input.trim())).await,
+            None => model.set_error(format!("Invalid position '{}', use mm:ss", input.trim())).await,
+        }
+    }
+
+    pub async fn seek_to(&self, position_ms: u32) {
+        let model = self.model.lock().await;
+
+        if let Some(spotify) = &model.spotify {
+            tracing::debug!(position_ms, "Seeking");
+            if let Err(e) = spotify.seek(position_ms).await {
+                let error_msg = Self::format_error(&e);
+                model.set_error(error_msg).await;
+            } else {
+                model.seek_position(position_ms).await;
+            }
+        }
+    }
+
     pub async fn toggle_liked_track(&self, track_id: &str) {
         if track_id.is_empty() {
             tracing::warn!("Cannot toggle liked status: track ID is empty");
@@ -207,6 +271,53 @@ impl AppController {
         }
     }
 
+    /// Save or unsave an album, or follow or unfollow an artist
+    pub async fn toggle_library_item(&self, item: SelectedItem) {
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+
+        match item {
+            SelectedItem::Album { id } => match spotify.toggle_saved_album(&id).await {
+                Ok(saved) => {
+                    tracing::info!(album_id = %id, saved, "Album saved status toggled");
+                    model.update_album_saved_status(&id, saved).await;
+                }
+                Err(e) => model.set_error(Self::format_error(&e)).await,
+            },
+            SelectedItem::Artist { id } => match spotify.toggle_followed_artist(&id).await {
+                Ok(followed) => {
+                    tracing::info!(artist_id = %id, followed, "Artist followed status toggled");
+                    model.update_artist_followed_status(&id, followed).await;
+                }
+                Err(e) => model.set_error(Self::format_error(&e)).await,
+            },
+            _ => {}
+        }
+    }
+
+    /// Mark the episode as played or unplayed in spotify-rs
+    pub async fn toggle_episode_played(&self, episode: Episode) {
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        // The Web API can't change what Spotify counts as played
+        if episode.fully_played && !episode.marked_played {
+            model.set_info("Spotify already counts this episode as played".to_string()).await;
+            return;
+        }
+
+        match spotify.toggle_episode_played(&episode.id).await {
+            Ok(marked) => {
+                tracing::info!(episode_id = %episode.id, marked, "Episode played mark toggled");
+                model.update_episode_marked_played(&episode.id, marked).await;
+            }
+            Err(e) => model.set_error(Self::format_error(&e)).await,
+        }
+    }
+
     pub async fn show_queue(&self) {
         let model = self.model.lock().await;
         model.set_content_loading(true).await;
@@ -259,22 +370,6 @@ impl AppController {
         }
     }
 
-    pub async fn add_track_to_queue(&self, track_uri: &str) {
-        let model = self.model.lock().await;
-
-        if let Some(spotify) = &model.spotify {
-            match spotify.add_to_queue(track_uri).await {
-                Ok(()) => {
-                    tracing::info!(track_uri, "Track added to queue");
-                }
-                Err(e) => {
-                    let error_msg = Self::format_error(&e);
-                    model.set_error(error_msg).await;
-                }
-            }
-        }
-    }
-
     pub async fn open_device_picker(&self) {
         let model = self.model.lock().await;
 
@@ -340,12 +435,14 @@ impl AppController {
         }
 
         let model = self.model.lock().await;
+        let mut transferred = false;
 
         if let Some(spotify) = &model.spotify {
             match spotify.transfer_playback_to_device(&device.id, true).await {
                 Ok(()) => {
                     tracing::info!(device_name = %device.name, "Playback transferred successfully");
                     model.update_device_name(device.name.clone()).await;
+                    transferred = true;
                 }
                 Err(e) => {
                     tracing::error!(device_name = %device.name, error = %e, "Failed to transfer playback");
@@ -354,6 +451,12 @@ impl AppController {
                 }
             }
         }
+        drop(model);
+
+        // Remote devices only know Spotify's queue
+        if transferred && !is_local_device {
+            self.hand_off_local_queue().await;
+        }
 
         self.refresh_playback().await;
     }
@@ -363,22 +466,22 @@ impl AppController {
         // First check if our local backend is already active
         {
             let backend_guard = self.audio_backend.lock().await;
-            if let Some(backend) = backend_guard.as_ref() {
-                if backend.is_active().await {
-                    tracing::debug!("Local backend already active");
-                    return true;
-                }
+            if let Some(backend) = backend_guard.as_ref()
+                && backend.is_active().await
+            {
+                tracing::debug!("Local backend already active");
+                return true;
             }
         }
 
         // Check if there's an active device on Spotify
         {
             let model = self.model.lock().await;
-            if let Some(spotify) = &model.spotify {
-                if spotify.has_active_device().await {
-                    tracing::debug!("Found active device on Spotify");
-                    return true;
-                }
+            if let Some(spotify) = &model.spotify
+                && spotify.has_active_device().await
+            {
+                tracing::debug!("Found active device on Spotify");
+                return true;
             }
         }
 
@@ -398,12 +501,12 @@ impl AppController {
         if let Some(backend) = backend_guard.as_ref() {
             match backend.activate().await {
                 Ok(()) => {
+                    let local_device_name = backend.device_name().to_string();
                     drop(backend_guard);
                     
                     self.try_start_event_listener().await;
                     
                     let model = self.model.lock().await;
-                    let local_device_name = AudioBackend::get_device_name().to_string();
                     model.update_device_name(local_device_name.clone()).await;
 
                     drop(model);
@@ -411,17 +514,17 @@ impl AppController {
 
                     // Transfer playback to our local device to make it active
                     let model = self.model.lock().await;
-                    if let Some(spotify) = &model.spotify {
-                        if let Ok(devices) = spotify.get_available_devices().await {
-                            if let Some(our_device) = devices.iter().find(|d| d.name == local_device_name) {
-                                tracing::info!(device_name = %local_device_name, device_id = %our_device.id, "Transferring playback to local device");
-                                if let Err(e) = spotify.transfer_playback_to_device(&our_device.id, false).await {
-                                    tracing::warn!(error = %e, "Failed to transfer playback to local device, will try during play");
-                                }
-                            } else {
-                                tracing::warn!(device_name = %local_device_name, "Local device not found in available devices list");
-                                tracing::debug!(?devices, "Available devices");
+                    if let Some(spotify) = &model.spotify
+                        && let Ok(devices) = spotify.get_available_devices().await
+                    {
+                        if let Some(our_device) = devices.iter().find(|d| d.name == local_device_name) {
+                            tracing::info!(device_name = %local_device_name, device_id = %our_device.id, "Transferring playback to local device");
+                            if let Err(e) = spotify.transfer_playback_to_device(&our_device.id, false).await {
+                                tracing::warn!(error = %e, "Failed to transfer playback to local device, will try during play");
                             }
+                        } else {
+                            tracing::warn!(device_name = %local_device_name, "Local device not found in available devices list");
+                            tracing::debug!(?devices, "Available devices");
                         }
                     }
 
@@ -482,10 +585,11 @@ impl AppController {
         let backend_guard = self.audio_backend.lock().await;
         if let Some(backend) = backend_guard.as_ref() {
             if backend.activate().await.is_ok() {
+                let local_device_name = backend.device_name().to_string();
                 drop(backend_guard);
                 self.try_start_event_listener().await;
                 let model = self.model.lock().await;
-                model.update_device_name(AudioBackend::get_device_name().to_string()).await;
+                model.update_device_name(local_device_name).await;
 
                 drop(model);
                 tokio::time::sleep(std::time::Duration::from_millis(500)).await;
@@ -564,6 +668,32 @@ impl AppController {
         }
     }
 
+    /// Start playback of something picked in a list. `play` is given the
+    /// Spotify client and called again if the local backend had to restart.
+    pub(crate) async fn play_selected<F, Fut>(&self, play: F)
+    where
+        F: Fn(Arc<dyn SpotifyApi>) -> Fut + Clone,
+        Fut: Future<Output = Result<()>>,
+    {
+        if !self.ensure_device_available().await {
+            return;
+        }
+
+        let model = self.model.lock().await;
+        model.clear_queue_skip_list().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        drop(model);
+
+        let operation = move || play(spotify.clone());
+        if let Err(e) = self.with_backend_recovery(operation).await {
+            let model = self.model.lock().await;
+            let error_msg = Self::format_error(&e);
+            model.set_error(error_msg).await;
+        }
+    }
+
     fn is_device_unavailable_error(error: &anyhow::Error) -> bool {
         let error_str = error.to_string().to_lowercase();
         error_str.contains("404")
@@ -605,3 +735,35 @@ impl AppController {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::testing::demo_controller;
+    use crate::model::{SelectedItem, SpotifyApi};
+
+    #[tokio::test]
+    async fn album_tracks_play_in_order_and_skip_both_ways() {
+        let (controller, spotify) = demo_controller();
+        let current = || async { spotify.get_queue().await.unwrap().0.map(|track| track.name) };
+
+        controller
+            .handle_selected_item(SelectedItem::AlbumTrack {
+                album_uri: "spotify:album:fakealbum01".to_string(),
+                track_uri: "spotify:track:faketrack0102".to_string(),
+            })
+            .await;
+        assert_eq!(current().await.as_deref(), Some("Magnetic"));
+
+        controller.next_track().await;
+        controller.next_track().await;
+        assert_eq!(current().await.as_deref(), Some("Green Veil"));
+        controller.previous_track().await;
+        assert_eq!(current().await.as_deref(), Some("Solar Wind"));
+
+        // Past the last track with repeat off, playback stops
+        controller.next_track().await;
+        controller.next_track().await;
+        assert_eq!(current().await, None);
+        assert!(controller.model.lock().await.get_ui_state().await.error_message.is_none());
+    }
+}
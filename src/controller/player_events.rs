@@ -83,14 +83,16 @@ impl AppController {
                             drop(model_guard);
 
                             let backend_guard = audio_backend.lock().await;
-                            if let Some(backend) = backend_guard.as_ref() {
-                                if let Err(e) = backend.skip_to_next().await {
-                                    tracing::error!(error = %e, "Failed to auto-skip track");
-                                }
+                            if let Some(backend) = backend_guard.as_ref()
+                                && let Err(e) = backend.skip_to_next().await
+                            {
+                                tracing::error!(error = %e, "Failed to auto-skip track");
                             }
                             continue;
                         }
 
+                        model_guard.local_queue_track_started(&uri).await;
+
                         tracing::info!(
                             track = %audio_item.name,
                             artist = %artist,
@@ -122,6 +124,12 @@ impl AppController {
                         tracing::debug!(position_ms, "PlayerEvent::Loading");
                         model_guard.update_playback_position(position_ms, false).await;
                     }
+                    PlayerEvent::TimeToPreloadNextTrack { .. } => {
+                        tracing::debug!("PlayerEvent::TimeToPreloadNextTrack");
+                        drop(model_guard);
+                        controller.feed_local_queue(true).await;
+                        continue;
+                    }
                     PlayerEvent::EndOfTrack { .. } => {
                         tracing::debug!("PlayerEvent::EndOfTrack");
                         model_guard.set_playing(false).await;
@@ -0,0 +1,482 @@
+//! Playlist export to and import from files
+//!
+//! Exports are JSON files written to the data directory's `playlists` folder,
+//! which is also where relative paths typed in the import prompt are looked
+//! up. An import either creates a new playlist or replaces the tracks of the
+//! playlist it was started on; tracks that can't be found are skipped and
+//! reported. CSV, M3U and text track lists have no Spotify IDs, so their lines
+//! are matched through search and shown on a review screen first. Library
+//! backups opened here are handed over to the restore in `backup`.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use futures::{stream, StreamExt};
+
+use crate::model::{
+    ActiveSection, ImportEntry, ImportReview, LibraryBackup, MatchStatus, PlaylistExport, PlaylistItem, PlaylistPromptKind,
+    SearchResultSection, SearchTrack, TrackListFormat, TrackQuery, IMPORT_CANDIDATES, parse_track_list,
+};
+use crate::paths::expand_user_path;
+use super::AppController;
+
+/// Skipped URIs listed in the import report before it is cut short
+const REPORTED_MISSING: usize = 3;
+/// Track list lines searched for at once
+const IMPORT_LOOKUPS_IN_FLIGHT: usize = 4;
+
+impl AppController {
+    /// Write every track of the open or selected playlist to a JSON file
+    pub async fn export_playlist(&self) {
+        let model = self.model.lock().await;
+        let Some((playlist, _)) = model.get_playlist_command_target().await else {
+            return;
+        };
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        let dir = model.get_export_dir().to_path_buf();
+        drop(model);
+
+        let result: Result<_> = async {
+            let detail = spotify.get_playlist(&playlist.id).await?;
+            let tracks = spotify.get_all_playlist_tracks(&playlist.id).await?;
+            let path = PlaylistExport::new(&detail, &tracks).write_to_dir(&dir)?;
+            Ok((path, tracks.len()))
+        }
+        .await;
+
+        let model = self.model.lock().await;
+        match result {
+            Ok((path, count)) => {
+                tracing::info!(playlist = %playlist.name, count, path = %path.display(), "Exported playlist");
+                model.set_info(format!("Exported {} tracks to {}", count, path.display())).await;
+            }
+            Err(e) => model.set_error(format!("Export failed: {:#}", e)).await,
+        }
+    }
+
+    /// Ask for a file to import, offering to replace the open or selected
+    /// playlist when it is editable
+    pub async fn open_import_prompt(&self) {
+        let model = self.model.lock().await;
+        let target = model
+            .get_playlist_command_target()
+            .await
+            .map(|(playlist, _)| playlist)
+            .filter(|playlist| playlist.editable);
+        let dir = model.get_export_dir();
+        let input = if dir.as_os_str().is_empty() { String::new() } else { format!("{}/", dir.display()) };
+        model.open_playlist_prompt(PlaylistPromptKind::Import { target, replace: false }, input).await;
+    }
+
+    /// Import `file`, replacing the tracks of `replace` or else creating a
+    /// playlist named after the export. Track lists (CSV, M3U, text) are
+    /// matched against search and shown for review first.
+    pub(crate) async fn import_playlist(&self, file: &str, replace: Option<PlaylistItem>) {
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        let path = expand_user_path(Path::new(file));
+        let path = if path.is_relative() { model.get_export_dir().join(path) } else { path };
+
+        if let Some(format) = TrackListFormat::from_path(&path) {
+            drop(model);
+            self.import_track_list(&path, format, replace).await;
+            return;
+        }
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                model.set_error(format!("Import failed: Could not read {}: {}", path.display(), e)).await;
+                return;
+            }
+        };
+        if LibraryBackup::is_backup(&text) {
+            drop(model);
+            self.open_restore(&path, &text).await;
+            return;
+        }
+        let export = match PlaylistExport::from_json(&text) {
+            Ok(export) => export,
+            Err(e) => {
+                model.set_error(format!("Import failed: {:#}", e)).await;
+                return;
+            }
+        };
+        drop(model);
+
+        let uris = export.uris();
+        let found = match spotify.get_tracks(&uris).await {
+            Ok(found) => found,
+            Err(e) => {
+                self.model.lock().await.set_error(Self::format_error(&e)).await;
+                return;
+            }
+        };
+        let (resolved, missing): (Vec<_>, Vec<_>) = uris.into_iter().zip(found).partition(|(_, track)| track.is_some());
+        let resolved: Vec<String> = resolved.into_iter().map(|(uri, _)| uri).collect();
+        let missing: Vec<String> = missing.into_iter().map(|(uri, _)| uri).collect();
+
+        let target = match replace {
+            Some(playlist) => ImportTarget::Replace(playlist),
+            None => ImportTarget::Create { name: export.playlist.name.clone(), description: export.playlist.description.clone() },
+        };
+        self.finish_import(target, &resolved, &missing, &path).await;
+    }
+
+    /// Search for every line of a track list and open the review screen
+    async fn import_track_list(&self, path: &Path, format: TrackListFormat, replace: Option<PlaylistItem>) {
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        let queries = match fs::read_to_string(path) {
+            Ok(text) => parse_track_list(&text, format),
+            Err(e) => {
+                model.set_error(format!("Import failed: Could not read {}: {}", path.display(), e)).await;
+                return;
+            }
+        };
+        if queries.is_empty() {
+            model.set_error(format!("No tracks found in {}", path.display())).await;
+            return;
+        }
+        model.set_content_loading(true).await;
+        drop(model);
+
+        // Tracks only, a few lines at a time, keeping the file's order
+        let lookups: Vec<(TrackQuery, Result<Vec<SearchTrack>>)> = stream::iter(queries)
+            .map(|query| {
+                let spotify = spotify.clone();
+                async move {
+                    let result = spotify
+                        .search_category(&query.search_query(), SearchResultSection::Tracks, 0, IMPORT_CANDIDATES)
+                        .await
+                        .map(|(results, _, _)| results.tracks);
+                    (query, result)
+                }
+            })
+            .buffered(IMPORT_LOOKUPS_IN_FLIGHT)
+            .collect()
+            .await;
+
+        let mut entries = Vec::with_capacity(lookups.len());
+        let mut first_error = None;
+        for (query, result) in lookups {
+            match result {
+                Ok(tracks) => entries.push(ImportEntry::resolve(query, tracks)),
+                Err(e) => {
+                    tracing::warn!(line = query.line, query = %query.label(), error = %e, "Track list lookup failed");
+                    entries.push(ImportEntry::failed(query));
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        if let Some(e) = &first_error
+            && entries.iter().all(|entry| entry.status == MatchStatus::LookupFailed)
+        {
+            let model = self.model.lock().await;
+            model.set_content_loading(false).await;
+            model.set_error(Self::format_error(e)).await;
+            return;
+        }
+
+        let name = |part: Option<&std::ffi::OsStr>| part.map(|p| p.to_string_lossy().into_owned()).unwrap_or_default();
+        let review = ImportReview {
+            source: name(path.file_name()),
+            playlist_name: name(path.file_stem()),
+            replace,
+            entries,
+        };
+        tracing::info!(
+            path = %path.display(),
+            matched = review.count(MatchStatus::Matched),
+            to_review = review.count(MatchStatus::NeedsReview),
+            not_found = review.count(MatchStatus::NotFound),
+            failed = review.count(MatchStatus::LookupFailed),
+            "Matched track list"
+        );
+
+        let failed = review.count(MatchStatus::LookupFailed);
+        let model = self.model.lock().await;
+        model.show_import_review(review).await;
+        model.ui_state.lock().await.active_section = ActiveSection::MainContent;
+        if let Some(e) = first_error {
+            model.set_error(format!("{} lines could not be looked up: {}", failed, Self::format_error(&e))).await;
+        }
+    }
+
+    /// Create or fill the playlist with the reviewed choices
+    pub async fn commit_import_review(&self) {
+        let Some(review) = self.model.lock().await.take_import_review().await else {
+            return;
+        };
+
+        let uris: Vec<String> = review.chosen_tracks().into_iter().map(|track| track.uri).collect();
+        let skipped: Vec<String> = review
+            .entries
+            .iter()
+            .filter(|entry| entry.chosen().is_none())
+            .map(|entry| match entry.status {
+                MatchStatus::LookupFailed => format!("line {} ({}, lookup failed)", entry.query.line, entry.query.label()),
+                _ => format!("line {} ({})", entry.query.line, entry.query.label()),
+            })
+            .collect();
+        let target = match review.replace {
+            Some(playlist) => ImportTarget::Replace(playlist),
+            None => ImportTarget::Create { name: review.playlist_name, description: String::new() },
+        };
+        self.finish_import(target, &uris, &skipped, Path::new(&review.source)).await;
+    }
+
+    /// Queue the reviewed choices instead of making a playlist of them
+    pub async fn queue_import_review(&self) {
+        let Some(review) = self.model.lock().await.take_import_review().await else {
+            return;
+        };
+
+        let tracks = review.chosen_tracks();
+        let count = tracks.len();
+        for track in tracks {
+            self.add_track_to_queue(track).await;
+        }
+        let model = self.model.lock().await;
+        if !model.has_error().await {
+            model.set_info(format!("Added {} tracks to the queue", count)).await;
+        }
+    }
+
+    /// Write the imported tracks, reload the sidebar and the playlist, and
+    /// report what was left out
+    async fn finish_import(&self, target: ImportTarget, uris: &[String], missing: &[String], source: &Path) {
+        let Some(spotify) = self.model.lock().await.spotify.clone() else {
+            return;
+        };
+
+        let result: Result<PlaylistItem> = async {
+            match target {
+                ImportTarget::Replace(playlist) => {
+                    spotify.replace_playlist_tracks(&playlist.id, uris).await?;
+                    Ok(playlist)
+                }
+                ImportTarget::Create { name, description } => {
+                    let name = match name.trim() {
+                        "" => "Imported playlist",
+                        name => name,
+                    };
+                    let playlist = spotify.create_playlist(name, Default::default()).await?;
+                    if !description.is_empty() {
+                        spotify.update_playlist_details(&playlist.id, None, Some(&description)).await?;
+                    }
+                    if !uris.is_empty() {
+                        spotify.add_tracks_to_playlist(&playlist.id, uris).await?;
+                    }
+                    Ok(playlist)
+                }
+            }
+        }
+        .await;
+
+        let playlist = match result {
+            Ok(playlist) => playlist,
+            Err(e) => {
+                self.model.lock().await.set_error(Self::format_error(&e)).await;
+                return;
+            }
+        };
+
+        self.refresh_user_playlists(Some(&playlist.id)).await;
+        if let Ok(mut detail) = spotify.get_playlist(&playlist.id).await {
+            spotify.mark_tracks_liked(&mut detail.tracks).await;
+            self.model.lock().await.playlist_reloaded(detail).await;
+        }
+
+        tracing::info!(
+            playlist = %playlist.name,
+            source = %source.display(),
+            added = uris.len(),
+            skipped = missing.len(),
+            "Imported playlist"
+        );
+        for entry in missing {
+            tracing::warn!(entry, "Imported track not added");
+        }
+        self.model.lock().await.set_info(import_report(&playlist.name, uris.len(), missing)).await;
+    }
+}
+
+/// Where imported tracks go
+enum ImportTarget {
+    Replace(PlaylistItem),
+    Create { name: String, description: String },
+}
+
+fn import_report(playlist: &str, added: usize, missing: &[String]) -> String {
+    let mut report = format!("Imported {} tracks into \"{}\"", added, playlist);
+    if !missing.is_empty() {
+        let listed = missing.iter().take(REPORTED_MISSING).cloned().collect::<Vec<_>>().join(", ");
+        report.push_str(&format!(". Could not add {}: {}", missing.len(), listed));
+        if missing.len() > REPORTED_MISSING {
+            report.push_str(&format!(" and {} more (see log)", missing.len() - REPORTED_MISSING));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::controller::testing::fake_controller;
+    use crate::model::{ContentView, FakeLibrary, SpotifyApi};
+
+    #[tokio::test]
+    async fn exported_playlists_import_as_copies_and_replacements() {
+        let dir = std::env::temp_dir().join(format!("spotify-rs-test-import-{}", std::process::id()));
+        let (controller, spotify) = fake_controller(FakeLibrary::demo("test"), Config::default(), Some(&dir));
+        let original = spotify.get_all_playlist_tracks("fakeplaylist01").await.unwrap();
+        let uris = |tracks: &[crate::model::SearchTrack]| tracks.iter().map(|t| t.uri.clone()).collect::<Vec<_>>();
+        let controller = &controller;
+        let import = |replace: bool| async move {
+            controller.open_import_prompt().await;
+            let model = controller.model.lock().await;
+            for c in "Offline Mix.json".chars() {
+                model.playlist_prompt_push(c).await;
+            }
+            if replace {
+                model.cycle_playlist_prompt_option().await;
+            }
+            drop(model);
+            controller.commit_playlist_prompt().await;
+            controller.model.lock().await.get_ui_state().await
+        };
+
+        controller.load_user_playlists().await;
+        controller.open_playlist("fakeplaylist01").await;
+        controller.export_playlist().await;
+        let path = dir.join("playlists").join("Offline Mix.json");
+        let mut export = PlaylistExport::from_json(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(export.uris(), uris(&original));
+
+        // A track that no longer exists is skipped and reported
+        export.tracks.truncate(3);
+        export.tracks[1].uri = "spotify:track:gone".to_string();
+        std::fs::write(&path, export.to_json().unwrap()).unwrap();
+
+        let state = import(false).await;
+        assert!(state.message_is_info);
+        assert_eq!(
+            state.error_message.as_deref(),
+            Some("Imported 2 tracks into \"Offline Mix\". Could not add 1: spotify:track:gone")
+        );
+        let copy = &state.playlists[state.playlist_selected];
+        assert_ne!(copy.id, "fakeplaylist01");
+        let copied = spotify.get_all_playlist_tracks(&copy.id).await.unwrap();
+        assert_eq!(uris(&copied), [original[0].uri.clone(), original[2].uri.clone()]);
+
+        // Replacing the open playlist's tracks reloads it
+        import(true).await;
+        let remote = spotify.get_all_playlist_tracks("fakeplaylist01").await.unwrap();
+        assert_eq!(uris(&remote), uris(&copied));
+        let view = controller.model.lock().await.content_state.lock().await.view.clone();
+        let ContentView::PlaylistDetail { detail, .. } = view else {
+            panic!("playlist should still be open");
+        };
+        assert_eq!(uris(&detail.tracks), uris(&copied));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn track_lists_are_reviewed_before_import() {
+        let dir = std::env::temp_dir().join(format!("spotify-rs-test-track-list-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("playlists")).unwrap();
+        std::fs::write(dir.join("playlists").join("Road Trip.txt"), "Harbor Static - Low Tide\nPolar Night\nNobody - Nothing\n").unwrap();
+        let (controller, spotify) = fake_controller(FakeLibrary::demo("test"), Config::default(), Some(&dir));
+
+        controller.import_playlist("Road Trip.txt", None).await;
+        let view = controller.model.lock().await.content_state.lock().await.view.clone();
+        let ContentView::ImportReview { review, .. } = view else {
+            panic!("review screen should be open");
+        };
+        let statuses: Vec<MatchStatus> = review.entries.iter().map(|entry| entry.status).collect();
+        assert_eq!(statuses, [MatchStatus::Matched, MatchStatus::NeedsReview, MatchStatus::NotFound]);
+        assert_eq!(review.playlist_name, "Road Trip");
+
+        // Skip the title-only line
+        let model = controller.model.lock().await;
+        model.content_move_down().await;
+        model.cycle_import_choice(true).await;
+        drop(model);
+        controller.commit_import_review().await;
+
+        let state = controller.model.lock().await.get_ui_state().await;
+        assert_eq!(
+            state.error_message.as_deref(),
+            Some("Imported 1 tracks into \"Road Trip\". Could not add 2: line 2 (Polar Night), line 3 (Nobody - Nothing)")
+        );
+        let created = &state.playlists[state.playlist_selected];
+        let tracks = spotify.get_all_playlist_tracks(&created.id).await.unwrap();
+        let names: Vec<&str> = tracks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Low Tide"]);
+        assert!(!controller.model.lock().await.is_import_review_open().await);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn import_report_lists_a_few_missing_tracks() {
+        let missing: Vec<String> = (1..=5).map(|n| format!("spotify:track:{}", n)).collect();
+        assert_eq!(
+            import_report("Mix", 10, &missing),
+            "Imported 10 tracks into \"Mix\". Could not add 5: spotify:track:1, spotify:track:2, \
+             spotify:track:3 and 2 more (see log)"
+        );
+        assert_eq!(import_report("Mix", 1, &[]), "Imported 1 tracks into \"Mix\"");
+    }
+
+    #[tokio::test]
+    async fn failed_lookups_are_reported_apart_from_misses() {
+        let dir = std::env::temp_dir().join(format!("spotify-rs-test-lookup-failed-{}", std::process::id()));
+        let list_dir = dir.join("playlists");
+        std::fs::create_dir_all(&list_dir).unwrap();
+        std::fs::write(list_dir.join("Mixed.txt"), "Harbor Static - Low Tide\nPolar Night\nNobody - Nothing\n").unwrap();
+        std::fs::write(list_dir.join("Offline.txt"), "Polar Night\n").unwrap();
+        let mut library = FakeLibrary::demo("test");
+        library.failing_queries = vec!["Polar Night".to_string()];
+        let (controller, _) = fake_controller(library, Config::default(), Some(&dir));
+
+        controller.import_playlist("Mixed.txt", None).await;
+        let model = controller.model.lock().await;
+        let view = model.content_state.lock().await.view.clone();
+        let ContentView::ImportReview { review, .. } = view else {
+            panic!("review screen should be open");
+        };
+        let statuses: Vec<MatchStatus> = review.entries.iter().map(|entry| entry.status).collect();
+        assert_eq!(statuses, [MatchStatus::Matched, MatchStatus::LookupFailed, MatchStatus::NotFound]);
+        assert_eq!(
+            model.get_ui_state().await.error_message.as_deref(),
+            Some("1 lines could not be looked up: Rate limited. Please wait a moment.")
+        );
+        drop(model);
+        controller.commit_import_review().await;
+        let state = controller.model.lock().await.get_ui_state().await;
+        assert_eq!(
+            state.error_message.as_deref(),
+            Some("Imported 1 tracks into \"Mixed\". Could not add 2: line 2 (Polar Night, lookup failed), line 3 (Nobody - Nothing)")
+        );
+
+        // With every lookup failing there is nothing to review
+        controller.import_playlist("Offline.txt", None).await;
+        let model = controller.model.lock().await;
+        assert!(!model.is_import_review_open().await);
+        assert_eq!(model.get_ui_state().await.error_message.as_deref(), Some("Rate limited. Please wait a moment."));
+        drop(model);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
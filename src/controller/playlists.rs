@@ -0,0 +1,291 @@
+//! Playlist editing: adding tracks through the playlist picker, removing and
+//! reordering tracks of the open playlist, and creating, renaming, describing
+//! and unfollowing playlists
+//!
+//! Removals and moves are sent with the snapshot ID the open playlist was
+//! loaded at, and the view adopts the snapshot each edit returns, so edits
+//! made elsewhere in the meantime are not overwritten. After playlists are
+//! created or changed the sidebar is reloaded in place, keeping its selection.
+
+use std::sync::Arc;
+
+use crate::keymap::Action;
+use crate::model::{PlaylistPromptKind, PlaylistTrackRef, SearchTrack, SpotifyApi};
+use super::AppController;
+
+impl AppController {
+    pub async fn open_playlist_picker(&self, track: SearchTrack) {
+        let model = self.model.lock().await;
+        if !model.show_playlist_picker(track).await {
+            model.set_error("None of your playlists can be edited".to_string()).await;
+        }
+    }
+
+    /// Add the picker's track to the chosen playlist
+    pub async fn commit_playlist_picker(&self) {
+        let model = self.model.lock().await;
+        let Some((playlist, track)) = model.take_playlist_picker_choice().await else {
+            return;
+        };
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        drop(model);
+
+        let result = spotify.add_tracks_to_playlist(&playlist.id, std::slice::from_ref(&track.uri)).await;
+        let model = self.model.lock().await;
+        match result {
+            Ok(snapshot_id) => {
+                tracing::info!(playlist = %playlist.name, track_uri = %track.uri, "Track added to playlist");
+                model.playlist_tracks_added(&playlist.id, vec![track], snapshot_id).await;
+            }
+            Err(e) => model.set_error(Self::format_error(&e)).await,
+        }
+    }
+
+    pub async fn remove_selected_playlist_track(&self) {
+        let Some((selected, spotify)) = self.editable_playlist_track().await else {
+            return;
+        };
+
+        let result = spotify
+            .remove_playlist_track(&selected.playlist_id, &selected.uri, selected.index, &selected.snapshot_id)
+            .await;
+        let model = self.model.lock().await;
+        match result {
+            Ok(snapshot_id) => {
+                model.playlist_track_removed(&selected.playlist_id, selected.index, snapshot_id).await;
+            }
+            Err(e) => model.set_error(Self::format_error(&e)).await,
+        }
+    }
+
+    /// Swap the selected track with its neighbour above or below
+    pub async fn move_selected_playlist_track(&self, up: bool) {
+        let Some((selected, spotify)) = self.editable_playlist_track().await else {
+            return;
+        };
+
+        let (to, insert_before) = if up {
+            match selected.index.checked_sub(1) {
+                Some(to) => (to, to),
+                None => return,
+            }
+        } else if selected.index + 1 < selected.loaded {
+            (selected.index + 1, selected.index + 2)
+        } else {
+            return;
+        };
+
+        let result = spotify
+            .move_playlist_track(&selected.playlist_id, selected.index, insert_before, &selected.snapshot_id)
+            .await;
+        let model = self.model.lock().await;
+        match result {
+            Ok(snapshot_id) => {
+                model.playlist_track_moved(&selected.playlist_id, selected.index, to, snapshot_id).await;
+            }
+            Err(e) => model.set_error(Self::format_error(&e)).await,
+        }
+    }
+
+    pub async fn open_create_playlist_prompt(&self) {
+        let kind = PlaylistPromptKind::Create { visibility: Default::default() };
+        self.model.lock().await.open_playlist_prompt(kind, String::new()).await;
+    }
+
+    /// Open the prompt for a playlist of the top tracks shown, named after
+    /// their time range
+    pub async fn open_export_top_prompt(&self) {
+        let model = self.model.lock().await;
+        if let Some((name, uris)) = model.get_top_tracks_export().await {
+            let kind = PlaylistPromptKind::ExportTop { visibility: Default::default(), uris };
+            model.open_playlist_prompt(kind, name).await;
+        }
+    }
+
+    /// Open the rename, describe or unfollow prompt for the open playlist
+    /// (content focused) or the one selected in the sidebar
+    pub async fn open_playlist_change_prompt(&self, action: Action) {
+        let model = self.model.lock().await;
+        let Some((playlist, description)) = model.get_playlist_command_target().await else {
+            return;
+        };
+
+        let (kind, input) = match action {
+            Action::RenamePlaylist => (PlaylistPromptKind::Rename { playlist_id: playlist.id }, playlist.name),
+            Action::DescribePlaylist => (PlaylistPromptKind::Describe { playlist_id: playlist.id }, description),
+            Action::UnfollowPlaylist => {
+                let kind = PlaylistPromptKind::Unfollow { playlist_id: playlist.id, name: playlist.name };
+                (kind, String::new())
+            }
+            _ => return,
+        };
+        if !playlist.editable && !matches!(kind, PlaylistPromptKind::Unfollow { .. }) {
+            model.set_error("Only your own playlists can be renamed or described".to_string()).await;
+            return;
+        }
+        model.open_playlist_prompt(kind, input).await;
+    }
+
+    pub async fn commit_playlist_prompt(&self) {
+        let model = self.model.lock().await;
+        let Some(prompt) = model.take_playlist_prompt().await else {
+            return;
+        };
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        let input = prompt.input.trim().to_string();
+        if let PlaylistPromptKind::Import { target, replace } = prompt.kind {
+            drop(model);
+            if input.is_empty() {
+                self.model.lock().await.set_error("Enter a file to import".to_string()).await;
+            } else {
+                self.import_playlist(&input, target.filter(|_| replace)).await;
+            }
+            return;
+        }
+        let needs_name = matches!(
+            prompt.kind,
+            PlaylistPromptKind::Create { .. } | PlaylistPromptKind::ExportTop { .. } | PlaylistPromptKind::Rename { .. }
+        );
+        if needs_name && input.is_empty() {
+            model.set_error("Playlist name can't be empty".to_string()).await;
+            return;
+        }
+        drop(model);
+
+        // Playlist to select once the sidebar is reloaded
+        let result = match prompt.kind {
+            PlaylistPromptKind::Create { visibility } => {
+                spotify.create_playlist(&input, visibility).await.map(|playlist| Some(playlist.id))
+            }
+            PlaylistPromptKind::ExportTop { visibility, uris } => match spotify.create_playlist(&input, visibility).await {
+                Ok(playlist) => spotify.add_tracks_to_playlist(&playlist.id, &uris).await.map(|_| Some(playlist.id)),
+                Err(e) => Err(e),
+            },
+            PlaylistPromptKind::Rename { playlist_id } => {
+                let result = spotify.update_playlist_details(&playlist_id, Some(&input), None).await;
+                if result.is_ok() {
+                    self.model.lock().await.playlist_details_changed(&playlist_id, Some(&input), None).await;
+                }
+                result.map(|()| None)
+            }
+            PlaylistPromptKind::Describe { playlist_id } => {
+                let result = spotify.update_playlist_details(&playlist_id, None, Some(&input)).await;
+                if result.is_ok() {
+                    self.model.lock().await.playlist_details_changed(&playlist_id, None, Some(&input)).await;
+                }
+                result.map(|()| None)
+            }
+            PlaylistPromptKind::Unfollow { playlist_id, .. } => {
+                spotify.unfollow_playlist(&playlist_id).await.map(|()| None)
+            }
+            PlaylistPromptKind::Import { .. } => return,
+        };
+
+        match result {
+            Ok(select_id) => self.refresh_user_playlists(select_id.as_deref()).await,
+            Err(e) => self.model.lock().await.set_error(Self::format_error(&e)).await,
+        }
+    }
+
+    /// Reload the sidebar playlists, selecting `select_id` if given
+    pub async fn refresh_user_playlists(&self, select_id: Option<&str>) {
+        let model = self.model.lock().await;
+        let limit = model.get_config().await.library.playlists_limit;
+
+        if let Some(spotify) = &model.spotify {
+            match spotify.get_user_playlists(limit).await {
+                Ok(playlists) => model.refresh_playlists(playlists, select_id).await,
+                Err(e) => model.set_error(Self::format_error(&e)).await,
+            }
+        }
+    }
+
+    /// The selected track of the open playlist, if the user may edit it
+    async fn editable_playlist_track(&self) -> Option<(PlaylistTrackRef, Arc<dyn SpotifyApi>)> {
+        let model = self.model.lock().await;
+        let selected = model.get_selected_playlist_track().await?;
+        if !selected.editable {
+            model.set_error("Only your own or collaborative playlists can be edited".to_string()).await;
+            return None;
+        }
+        let spotify = model.spotify.clone()?;
+        Some((selected, spotify))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::testing::demo_controller;
+    use crate::model::ContentView;
+
+    #[tokio::test]
+    async fn edits_follow_the_open_playlist() {
+        let (controller, spotify) = demo_controller();
+        let original = spotify.get_playlist("fakeplaylist01").await.unwrap().tracks;
+
+        controller.open_playlist("fakeplaylist01").await;
+        controller.move_selected_playlist_track(false).await;
+        controller.move_selected_playlist_track(false).await;
+        controller.remove_selected_playlist_track().await;
+
+        let view = controller.model.lock().await.content_state.lock().await.view.clone();
+        let ContentView::PlaylistDetail { detail, selected_index } = view else {
+            panic!("playlist should still be open");
+        };
+        assert_eq!(detail.snapshot_id, "fake-snapshot-4");
+        assert_eq!(selected_index, 2);
+        assert_eq!(detail.total_tracks as usize, original.len() - 1);
+
+        let remote = spotify.get_playlist("fakeplaylist01").await.unwrap();
+        let uris = |tracks: &[SearchTrack]| tracks.iter().map(|t| t.uri.clone()).collect::<Vec<_>>();
+        assert_eq!(uris(&remote.tracks), uris(&detail.tracks));
+        assert_eq!(uris(&remote.tracks[..2]), uris(&original[1..3]));
+        assert_eq!(uris(&remote.tracks[2..]), uris(&original[3..]));
+    }
+
+    #[tokio::test]
+    async fn sidebar_keeps_selection_across_playlist_changes() {
+        let (controller, _) = demo_controller();
+        controller.load_user_playlists().await;
+        let sidebar = || async { controller.model.lock().await.get_ui_state().await };
+
+        for name in ["First", "Second"] {
+            controller.open_create_playlist_prompt().await;
+            let model = controller.model.lock().await;
+            for c in name.chars() {
+                model.playlist_prompt_push(c).await;
+            }
+            drop(model);
+            controller.commit_playlist_prompt().await;
+        }
+        let state = sidebar().await;
+        let names: Vec<&str> = state.playlists.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Second", "First", "Offline Mix"]);
+        assert_eq!(state.playlist_selected, 0);
+
+        // Rename "First", which keeps its row
+        controller.model.lock().await.select_playlist(1).await;
+        controller.open_playlist_change_prompt(Action::RenamePlaylist).await;
+        {
+            let model = controller.model.lock().await;
+            model.playlist_prompt_backspace().await;
+            model.playlist_prompt_push('!').await;
+        }
+        controller.commit_playlist_prompt().await;
+        let state = sidebar().await;
+        assert_eq!(state.playlists[1].name, "Firs!");
+        assert_eq!(state.playlist_selected, 1);
+
+        // Unfollowing it moves the selection to the playlist below
+        controller.open_playlist_change_prompt(Action::UnfollowPlaylist).await;
+        controller.commit_playlist_prompt().await;
+        let state = sidebar().await;
+        assert_eq!(state.playlists.len(), 2);
+        assert_eq!(state.playlists[state.playlist_selected].name, "Offline Mix");
+    }
+}
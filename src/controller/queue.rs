@@ -0,0 +1,183 @@
+//! Queue editing and feeding the local player
+//!
+//! While the local device plays, queued tracks stay in the model's local
+//! queue and are handed to Spotify's queue one at a time, just before they
+//! are due. Remote devices get tracks added to Spotify's queue directly, and
+//! removal falls back to the skip list.
+
+use crate::model::SearchTrack;
+use super::AppController;
+
+/// librespot preloads the next track this long before the current one ends,
+/// so the local queue head has to be in Spotify's queue by then
+const FEED_BEFORE_END_MS: u32 = 30_000;
+
+impl AppController {
+    /// Whether playback is on the local librespot device
+    pub(crate) async fn is_local_playback(&self) -> bool {
+        let local_device_name = {
+            let backend_guard = self.audio_backend.lock().await;
+            match backend_guard.as_ref() {
+                Some(backend) if backend.is_active().await => backend.device_name().to_string(),
+                _ => return false,
+            }
+        };
+        self.model.lock().await.get_local_device_name().await == local_device_name
+    }
+
+    pub async fn add_track_to_queue(&self, track: SearchTrack) {
+        if self.is_local_playback().await {
+            tracing::info!(track_uri = %track.uri, "Track added to local queue");
+            self.model.lock().await.queue_locally(track).await;
+            self.feed_local_queue(false).await;
+            return;
+        }
+
+        let model = self.model.lock().await;
+
+        if let Some(spotify) = &model.spotify {
+            match spotify.add_to_queue(&track.uri).await {
+                Ok(()) => {
+                    tracing::info!(track_uri = %track.uri, "Track added to queue");
+                }
+                Err(e) => {
+                    let error_msg = Self::format_error(&e);
+                    model.set_error(error_msg).await;
+                }
+            }
+        }
+    }
+
+    pub async fn open_queue_insert_prompt(&self, track: SearchTrack) {
+        if !self.is_local_playback().await {
+            let model = self.model.lock().await;
+            model.set_error("Inserting at a position needs playback on this device".to_string()).await;
+            return;
+        }
+        self.model.lock().await.open_queue_insert_prompt(track).await;
+    }
+
+    /// Insert the prompt's track at the typed (1-based) position
+    pub async fn commit_queue_insert_prompt(&self) {
+        let model = self.model.lock().await;
+        let Some((input, track)) = model.take_queue_insert_prompt().await else {
+            return;
+        };
+
+        match input.trim().parse::<usize>() {
+            Ok(position) if position > 0 => {
+                model.insert_into_local_queue(position - 1, track).await;
+                drop(model);
+                self.feed_local_queue(false).await;
+            }
+            _ => model.set_error(format!("Invalid queue position '{}'", input.trim())).await,
+        }
+    }
+
+    pub async fn remove_from_queue(&self, index: usize) {
+        self.model.lock().await.remove_from_queue(index).await;
+        self.feed_local_queue(false).await;
+    }
+
+    pub async fn move_queue_entry(&self, index: usize, up: bool) {
+        let model = self.model.lock().await;
+        if !model.move_queue_entry(index, up).await && index >= model.get_queue_local_len().await {
+            model.set_error("Only tracks queued while playing on this device can be moved".to_string()).await;
+        }
+    }
+
+    pub async fn clear_queue(&self) {
+        let model = self.model.lock().await;
+        if !model.clear_local_queue().await {
+            model.set_error("Only tracks queued while playing on this device can be cleared".to_string()).await;
+        }
+    }
+
+    /// Hand the head of the local queue to the player once the current track
+    /// is about to end, or straight away with `now` (e.g. before skipping)
+    pub(crate) async fn feed_local_queue(&self, now: bool) {
+        if !self.is_local_playback().await {
+            return;
+        }
+
+        let model = self.model.lock().await;
+        if !now {
+            let (position_ms, duration_ms) = model.get_playback_position().await;
+            if duration_ms.saturating_sub(position_ms) > FEED_BEFORE_END_MS {
+                return;
+            }
+        }
+
+        let Some(track) = model.submit_next_local_track().await else {
+            return;
+        };
+        if let Some(spotify) = &model.spotify {
+            match spotify.add_to_queue(&track.uri).await {
+                Ok(()) => tracing::debug!(track_uri = %track.uri, "Handed local queue head to player"),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Could not hand queued track to the player");
+                    model.cancel_local_submit().await;
+                    model.set_error(Self::format_error(&e)).await;
+                }
+            }
+        }
+    }
+
+    /// Move the local queue into Spotify's queue, e.g. when switching to a
+    /// remote device
+    pub(crate) async fn hand_off_local_queue(&self) {
+        let model = self.model.lock().await;
+        let Some(spotify) = &model.spotify else {
+            return;
+        };
+        let tracks = model.take_pending_local_queue().await;
+        if tracks.is_empty() {
+            return;
+        }
+
+        tracing::info!(count = tracks.len(), "Handing local queue to Spotify");
+        let mut tracks = tracks.into_iter();
+        while let Some(track) = tracks.next() {
+            if let Err(e) = spotify.add_to_queue(&track.uri).await {
+                tracing::warn!(error = %e, track_uri = %track.uri, "Could not queue track on new device");
+                // Keep the unsent tracks rather than dropping them
+                model.restore_pending_local_queue(std::iter::once(track).chain(tracks).collect()).await;
+                model.set_error(Self::format_error(&e)).await;
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::controller::testing::demo_controller;
+    use crate::model::{SearchTrack, SelectedItem, SpotifyApi};
+
+    #[tokio::test]
+    async fn tracks_spotify_does_not_take_stay_in_the_local_queue() {
+        let (controller, spotify) = demo_controller();
+        controller
+            .handle_selected_item(SelectedItem::AlbumTrack {
+                album_uri: "spotify:album:fakealbum01".to_string(),
+                track_uri: "spotify:track:faketrack0101".to_string(),
+            })
+            .await;
+        let (_, upcoming) = spotify.get_queue().await.unwrap();
+        let missing = SearchTrack { uri: "spotify:track:missing".to_string(), ..upcoming[0].clone() };
+        let model = controller.model.lock().await;
+        for track in [upcoming[1].clone(), missing, upcoming[0].clone()] {
+            model.queue_locally(track).await;
+        }
+        drop(model);
+
+        // The first track is handed over; the rejected one and the rest are kept
+        controller.hand_off_local_queue().await;
+        let (_, queued) = spotify.get_queue().await.unwrap();
+        assert_eq!(queued[0].uri, upcoming[1].uri);
+        let model = controller.model.lock().await;
+        let kept: Vec<String> = model.take_pending_local_queue().await.into_iter().map(|t| t.uri).collect();
+        assert_eq!(kept, ["spotify:track:missing", upcoming[0].uri.as_str()]);
+        assert!(model.get_ui_state().await.error_message.is_some());
+    }
+}
@@ -0,0 +1,173 @@
+//! Search bar history and live search
+//!
+//! Searches run from the search bar are added to the history, which Up/Down
+//! walk through. With `ui.live_search` on, typing starts a search once the
+//! typing pauses for `ui.live_search_delay_ms`, and its top results are
+//! offered in a dropdown under the search bar. Each keystroke aborts the
+//! previous live search, whether it is still waiting or already sent.
+
+use crate::model::SearchQuery;
+use super::AppController;
+
+/// Live search results shown per category
+const SUGGESTIONS_PER_SECTION: usize = 2;
+
+impl AppController {
+    /// Run the search typed into the search bar, or open the suggestion
+    /// picked from the dropdown
+    pub(crate) async fn submit_search(&self) {
+        self.cancel_live_search().await;
+        let model = self.model.lock().await;
+        let query = model.ui_state.lock().await.search_query.clone();
+        let suggestion = model.take_selected_suggestion().await;
+        model.close_search_suggestions().await;
+        if query.trim().is_empty() {
+            return;
+        }
+        model.record_search(&query).await;
+        drop(model);
+
+        match suggestion {
+            Some(item) => self.handle_selected_item(item).await,
+            None => self.perform_search(&query).await,
+        }
+    }
+
+    /// Up/Down in the search bar: move through the suggestions when they
+    /// are open, otherwise recall an older or newer search
+    pub(crate) async fn search_bar_move(&self, up: bool) {
+        let model = self.model.lock().await;
+        if model.move_search_suggestion(up).await {
+            return;
+        }
+        if model.recall_search(up).await {
+            drop(model);
+            self.cancel_live_search().await;
+        }
+    }
+
+    /// The search text changed: restart the live search timer
+    pub(crate) async fn search_text_changed(&self) {
+        self.cancel_live_search().await;
+        let model = self.model.lock().await;
+        let config = model.get_config().await;
+        let query = model.ui_state.lock().await.search_query.clone();
+        if !config.ui.live_search || query.trim().is_empty() || SearchQuery::parse(&query).is_err() {
+            model.close_search_suggestions().await;
+            return;
+        }
+        drop(model);
+
+        let controller = self.clone();
+        let delay = config.live_search_delay();
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            controller.run_live_search(&query).await;
+        });
+        *self.live_search.lock().await = Some(task);
+    }
+
+    /// Abort the pending or running live search and close the dropdown
+    pub(crate) async fn cancel_live_search(&self) {
+        if let Some(task) = self.live_search.lock().await.take() {
+            task.abort();
+        }
+        self.model.lock().await.close_search_suggestions().await;
+    }
+
+    async fn run_live_search(&self, query: &str) {
+        let model = self.model.lock().await;
+        let Some(spotify) = model.spotify.clone() else {
+            return;
+        };
+        drop(model);
+
+        tracing::debug!(query, "Live search");
+        match spotify.search(query, SUGGESTIONS_PER_SECTION as u32).await {
+            Ok(results) => {
+                let suggestions = results.suggestions(SUGGESTIONS_PER_SECTION);
+                self.model.lock().await.set_search_suggestions(query, suggestions).await;
+            }
+            // Suggestions are a convenience; Enter still reports errors
+            Err(e) => tracing::debug!(query, error = %e, "Live search failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::controller::testing::fake_controller;
+    use crate::model::{FakeLibrary, SelectedItem};
+
+    fn controller(live_search: bool) -> AppController {
+        let mut config = Config::default();
+        config.ui.live_search = live_search;
+        config.ui.live_search_delay_ms = 50;
+        fake_controller(FakeLibrary::demo("test"), config, None).0
+    }
+
+    async fn type_text(controller: &AppController, text: &str) {
+        for c in text.chars() {
+            controller.model.lock().await.append_to_search(c).await;
+            controller.search_text_changed().await;
+        }
+    }
+
+    async fn search_text(controller: &AppController) -> String {
+        controller.model.lock().await.get_ui_state().await.search_query
+    }
+
+    #[tokio::test]
+    async fn searches_are_recalled_with_up_and_down() {
+        let controller = controller(false);
+        for query in ["magnetic", "salt"] {
+            controller.model.lock().await.update_search_query(query.to_string()).await;
+            controller.submit_search().await;
+        }
+        controller.model.lock().await.update_search_query("leg".to_string()).await;
+
+        controller.search_bar_move(true).await;
+        assert_eq!(search_text(&controller).await, "salt");
+        controller.search_bar_move(true).await;
+        controller.search_bar_move(true).await;
+        assert_eq!(search_text(&controller).await, "magnetic");
+        controller.search_bar_move(false).await;
+        controller.search_bar_move(false).await;
+        assert_eq!(search_text(&controller).await, "leg");
+    }
+
+    #[tokio::test]
+    async fn live_search_suggests_results_for_the_latest_text() {
+        let controller = controller(true);
+        type_text(&controller, "pol").await;
+        // The search for "p" was aborted by the next keystroke
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        let state = controller.model.lock().await.get_ui_state().await;
+        let labels: Vec<&str> = state.search_suggestions.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, ["Polar Night - Aurora Fields"]);
+
+        // Down picks the suggestion and Enter plays it
+        controller.search_bar_move(false).await;
+        let model = controller.model.lock().await;
+        assert_eq!(model.get_ui_state().await.suggestion_selected, Some(0));
+        let item = model.take_selected_suggestion().await;
+        assert!(matches!(
+            item,
+            Some(SelectedItem::TrackList { uris, index: 0 }) if uris == ["spotify:track:faketrack0101"]
+        ));
+        assert!(model.get_ui_state().await.search_suggestions.is_empty());
+        drop(model);
+
+        // Results for text that has since changed are dropped
+        type_text(&controller, "x").await;
+        controller.cancel_live_search().await;
+        let model = controller.model.lock().await;
+        model.set_search_suggestions("pol", state.search_suggestions.clone()).await;
+        assert!(model.get_ui_state().await.search_suggestions.is_empty());
+    }
+}
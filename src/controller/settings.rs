@@ -0,0 +1,90 @@
+//! Settings overlay actions
+//!
+//! Every change is validated and written to the config file straight away.
+//! Live settings (theme, volume step, library limits) are picked up on their
+//! next use; the rest are flagged "applies on restart" in the overlay.
+
+use crate::config::Config;
+use crate::model::AppModel;
+use super::AppController;
+
+impl AppController {
+    pub async fn open_settings(&self) {
+        let model = self.model.lock().await;
+        model.show_settings().await;
+    }
+
+    /// Cycle the selected setting, or start editing it if it is free text
+    pub async fn adjust_selected_setting(&self, forward: bool) {
+        let model = self.model.lock().await;
+        let setting = model.get_selected_setting().await;
+
+        if setting.is_text() {
+            if let Some(text) = setting.text(&model.get_config().await) {
+                model.start_setting_edit(text).await;
+            }
+            return;
+        }
+
+        self.update_config(&model, |config| setting.adjust(config, forward)).await;
+    }
+
+    /// Apply the text typed for the selected setting
+    pub async fn commit_setting_edit(&self) {
+        let model = self.model.lock().await;
+        let setting = model.get_selected_setting().await;
+        if let Some(text) = model.take_setting_edit().await {
+            self.update_config(&model, |config| setting.set_text(config, &text)).await;
+        }
+    }
+
+    async fn update_config(&self, model: &AppModel, change: impl FnOnce(&mut Config)) {
+        let mut config = model.get_config().await;
+        change(&mut config);
+
+        if let Err(e) = config.save(model.get_config_path()) {
+            tracing::warn!(error = %e, "Could not save settings");
+            model.set_error(format!("Settings not saved: {}", e)).await;
+            return;
+        }
+        model.apply_config(config).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ThemeName;
+    use crate::controller::testing::fake_controller;
+    use crate::model::FakeLibrary;
+
+    #[tokio::test]
+    async fn changes_are_persisted_and_restart_settings_flagged() {
+        let dir = std::env::temp_dir().join(format!("spotify-rs-test-settings-{}", std::process::id()));
+        let path = dir.join("config.toml");
+        let (controller, _) = fake_controller(FakeLibrary::demo("test"), Config::default(), Some(&dir));
+
+        controller.open_settings().await;
+        // Bitrate (restart) then Theme (live)
+        controller.adjust_selected_setting(false).await;
+        {
+            let model = controller.model.lock().await;
+            for _ in 0..5 {
+                model.settings_move_down().await;
+            }
+            assert_eq!(model.get_selected_setting().await.label(), "Theme");
+        }
+        controller.adjust_selected_setting(true).await;
+
+        let saved = Config::load(Some(&path)).unwrap();
+        assert_eq!(saved.audio.bitrate, 160);
+        assert_eq!(saved.ui.theme, ThemeName::Ocean);
+
+        let ui_state = controller.model.lock().await.get_ui_state().await;
+        assert_eq!(ui_state.theme, ThemeName::Ocean);
+        assert!(ui_state.settings[0].pending_restart);
+        assert!(!ui_state.settings[5].pending_restart);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
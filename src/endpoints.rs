@@ -0,0 +1,84 @@
+//! Spotify service endpoints
+//!
+//! Both the Web API and the accounts service default to the public Spotify
+//! hosts but can be redirected (e.g. to a local mock server) through
+//! environment variables.
+
+const API_URL_ENV: &str = "SPOTIFY_RS_API_URL";
+const ACCOUNTS_URL_ENV: &str = "SPOTIFY_RS_ACCOUNTS_URL";
+
+/// Base URLs used for Web API calls and token requests
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Endpoints {
+    /// Web API root, e.g. `https://api.spotify.com/v1/`
+    pub api_base_url: String,
+    /// Accounts service root, e.g. `https://accounts.spotify.com/`
+    pub accounts_base_url: String,
+}
+
+impl Endpoints {
+    /// Defaults, overridden by `SPOTIFY_RS_API_URL` / `SPOTIFY_RS_ACCOUNTS_URL`
+    pub fn from_env() -> Self {
+        let mut endpoints = Self::default();
+
+        if let Ok(url) = std::env::var(API_URL_ENV)
+            && !url.trim().is_empty()
+        {
+            tracing::info!(url = %url, "Using custom Web API endpoint");
+            endpoints.api_base_url = with_trailing_slash(url.trim());
+        }
+        if let Ok(url) = std::env::var(ACCOUNTS_URL_ENV)
+            && !url.trim().is_empty()
+        {
+            tracing::info!(url = %url, "Using custom accounts endpoint");
+            endpoints.accounts_base_url = with_trailing_slash(url.trim());
+        }
+
+        endpoints
+    }
+
+    /// Endpoints for a server that serves both the API and accounts routes,
+    /// with the API mounted under `/v1/` like the real service
+    #[cfg(test)]
+    pub fn local(base_url: &str) -> Self {
+        let base = with_trailing_slash(base_url);
+        Self {
+            api_base_url: format!("{}v1/", base),
+            accounts_base_url: base,
+        }
+    }
+
+    /// Whether token requests go to the real Spotify accounts service
+    pub fn uses_default_accounts(&self) -> bool {
+        self.accounts_base_url == rspotify::DEFAULT_AUTH_BASE_URL
+    }
+
+    /// rspotify configuration pointing at these endpoints.
+    /// Token caching and automatic refresh are handled by the app itself.
+    pub fn rspotify_config(&self) -> rspotify::Config {
+        rspotify::Config {
+            api_base_url: self.api_base_url.clone(),
+            auth_base_url: self.accounts_base_url.clone(),
+            token_cached: false,
+            token_refreshing: false,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            api_base_url: rspotify::DEFAULT_API_BASE_URL.to_string(),
+            accounts_base_url: rspotify::DEFAULT_AUTH_BASE_URL.to_string(),
+        }
+    }
+}
+
+fn with_trailing_slash(url: &str) -> String {
+    if url.ends_with('/') {
+        url.to_string()
+    } else {
+        format!("{}/", url)
+    }
+}
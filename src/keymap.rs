@@ -0,0 +1,682 @@
+//! Keybindings: the action table, default keymap and user overrides
+//!
+//! Every user-triggerable command is an `Action`. `ACTIONS` lists each one
+//! with its config name, help text and default keys; the controller dispatches
+//! on actions and the help popup is generated from the same table, so the two
+//! cannot drift apart.
+//!
+//! Key syntax (config file and defaults):
+//! - single characters: `n`, `+`, `/`
+//! - named keys: `space`, `enter`, `esc`, `tab`, `backtab`, `backspace`,
+//!   `delete`, `insert`, `up`, `down`, `left`, `right`, `home`, `end`,
+//!   `pageup`, `pagedown`, `f1`..`f12`
+//! - modifiers: `ctrl-`, `alt-`, `shift-` (or `+` as separator), e.g. `ctrl-q`
+//! - chords: keys separated by spaces, pressed one after another, e.g. `g p`
+//!
+//! A lowercase letter matches both cases (`n` fires on `n` and `N`); an
+//! uppercase letter or `shift-` binding matches only the shifted key.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use anyhow::{anyhow, bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+/// How long to wait for the next key of a chord
+pub const CHORD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    // Navigation
+    NextSection,
+    PrevSection,
+    MoveUp,
+    MoveDown,
+    PrevCategory,
+    NextCategory,
+    ShowAllResults,
+    CycleTopRange,
+    FilterList,
+    Select,
+    Back,
+    FocusSearch,
+    FocusPlaylists,
+    GoHome,
+    // Playback
+    PlayPause,
+    NextTrack,
+    PreviousTrack,
+    ToggleShuffle,
+    CycleRepeat,
+    VolumeUp,
+    VolumeDown,
+    SeekForward,
+    SeekBackward,
+    SeekToPosition,
+    // Actions
+    ToggleLike,
+    ToggleSaved,
+    MarkPlayed,
+    AddToQueue,
+    RemoveFromQueue,
+    InsertIntoQueue,
+    AddToPlaylist,
+    CreatePlaylist,
+    RenamePlaylist,
+    DescribePlaylist,
+    UnfollowPlaylist,
+    ExportTop,
+    ExportPlaylist,
+    ImportPlaylist,
+    BackupLibrary,
+    MoveQueueUp,
+    MoveQueueDown,
+    ClearQueue,
+    ShowQueue,
+    OpenDevicePicker,
+    // General
+    ToggleHelp,
+    OpenSettings,
+    Quit,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionCategory {
+    Navigation,
+    Playback,
+    Actions,
+    General,
+}
+
+impl ActionCategory {
+    pub const ALL: [ActionCategory; 4] = [
+        ActionCategory::Navigation,
+        ActionCategory::Playback,
+        ActionCategory::Actions,
+        ActionCategory::General,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ActionCategory::Navigation => "Navigation",
+            ActionCategory::Playback => "Playback",
+            ActionCategory::Actions => "Actions",
+            ActionCategory::General => "General",
+        }
+    }
+}
+
+/// One row of the action table
+pub struct ActionInfo {
+    pub action: Action,
+    /// Name used in the `[keys]` config section
+    pub name: &'static str,
+    pub description: &'static str,
+    pub category: ActionCategory,
+    pub default_keys: &'static [&'static str],
+}
+
+pub const ACTIONS: &[ActionInfo] = &[
+    ActionInfo { action: Action::NextSection, name: "next_section", description: "Next section", category: ActionCategory::Navigation, default_keys: &["tab"] },
+    ActionInfo { action: Action::PrevSection, name: "prev_section", description: "Previous section", category: ActionCategory::Navigation, default_keys: &["backtab"] },
+    ActionInfo { action: Action::MoveUp, name: "move_up", description: "Move selection up", category: ActionCategory::Navigation, default_keys: &["up"] },
+    ActionInfo { action: Action::MoveDown, name: "move_down", description: "Move selection down", category: ActionCategory::Navigation, default_keys: &["down"] },
+    ActionInfo { action: Action::PrevCategory, name: "prev_category", description: "Previous search category / home section", category: ActionCategory::Navigation, default_keys: &["left"] },
+    ActionInfo { action: Action::NextCategory, name: "next_category", description: "Next search category / home section", category: ActionCategory::Navigation, default_keys: &["right"] },
+    ActionInfo { action: Action::ShowAllResults, name: "show_all_results", description: "All results of search category", category: ActionCategory::Navigation, default_keys: &["m"] },
+    ActionInfo { action: Action::CycleTopRange, name: "cycle_top_range", description: "Your Top: next time range", category: ActionCategory::Navigation, default_keys: &["w"] },
+    ActionInfo { action: Action::FilterList, name: "filter_list", description: "Filter the list (n/N: next/previous match)", category: ActionCategory::Navigation, default_keys: &["/"] },
+    ActionInfo { action: Action::Select, name: "select", description: "Select / Play", category: ActionCategory::Navigation, default_keys: &["enter"] },
+    ActionInfo { action: Action::Back, name: "back", description: "Go back", category: ActionCategory::Navigation, default_keys: &["backspace", "esc"] },
+    ActionInfo { action: Action::FocusSearch, name: "focus_search", description: "Focus search", category: ActionCategory::Navigation, default_keys: &["g"] },
+    ActionInfo { action: Action::FocusPlaylists, name: "focus_playlists", description: "Focus playlists", category: ActionCategory::Navigation, default_keys: &["l"] },
+    ActionInfo { action: Action::GoHome, name: "go_home", description: "Home page", category: ActionCategory::Navigation, default_keys: &["o"] },
+    ActionInfo { action: Action::PlayPause, name: "play_pause", description: "Play / Pause", category: ActionCategory::Playback, default_keys: &["space"] },
+    ActionInfo { action: Action::NextTrack, name: "next_track", description: "Next track", category: ActionCategory::Playback, default_keys: &["n"] },
+    ActionInfo { action: Action::PreviousTrack, name: "previous_track", description: "Previous track", category: ActionCategory::Playback, default_keys: &["p"] },
+    ActionInfo { action: Action::ToggleShuffle, name: "toggle_shuffle", description: "Toggle shuffle", category: ActionCategory::Playback, default_keys: &["s"] },
+    ActionInfo { action: Action::CycleRepeat, name: "cycle_repeat", description: "Cycle repeat (off → all → one)", category: ActionCategory::Playback, default_keys: &["r"] },
+    ActionInfo { action: Action::VolumeUp, name: "volume_up", description: "Volume up", category: ActionCategory::Playback, default_keys: &["+", "="] },
+    ActionInfo { action: Action::VolumeDown, name: "volume_down", description: "Volume down", category: ActionCategory::Playback, default_keys: &["-"] },
+    ActionInfo { action: Action::SeekForward, name: "seek_forward", description: "Seek forward", category: ActionCategory::Playback, default_keys: &[">", "."] },
+    ActionInfo { action: Action::SeekBackward, name: "seek_backward", description: "Seek backward", category: ActionCategory::Playback, default_keys: &["<", ","] },
+    ActionInfo { action: Action::SeekToPosition, name: "seek_to", description: "Go to position (mm:ss)", category: ActionCategory::Playback, default_keys: &["t"] },
+    ActionInfo { action: Action::ToggleLike, name: "toggle_like", description: "Like track / Save album / Follow artist", category: ActionCategory::Actions, default_keys: &["x"] },
+    ActionInfo { action: Action::ToggleSaved, name: "toggle_saved", description: "Save open album / Follow open artist", category: ActionCategory::Actions, default_keys: &["f"] },
+    ActionInfo { action: Action::MarkPlayed, name: "mark_played", description: "Mark episode as played / unplayed", category: ActionCategory::Actions, default_keys: &["e"] },
+    ActionInfo { action: Action::AddToQueue, name: "add_to_queue", description: "Add to queue", category: ActionCategory::Actions, default_keys: &["k"] },
+    ActionInfo { action: Action::RemoveFromQueue, name: "remove_from_queue", description: "Remove from queue / playlist", category: ActionCategory::Actions, default_keys: &["delete"] },
+    ActionInfo { action: Action::InsertIntoQueue, name: "queue_insert", description: "Insert into queue at position", category: ActionCategory::Actions, default_keys: &["i"] },
+    ActionInfo { action: Action::AddToPlaylist, name: "add_to_playlist", description: "Add to playlist…", category: ActionCategory::Actions, default_keys: &["a"] },
+    ActionInfo { action: Action::CreatePlaylist, name: "create_playlist", description: "New playlist", category: ActionCategory::Actions, default_keys: &["ctrl-n"] },
+    ActionInfo { action: Action::RenamePlaylist, name: "rename_playlist", description: "Rename playlist", category: ActionCategory::Actions, default_keys: &["ctrl-r"] },
+    ActionInfo { action: Action::DescribePlaylist, name: "describe_playlist", description: "Edit playlist description", category: ActionCategory::Actions, default_keys: &["ctrl-e"] },
+    ActionInfo { action: Action::UnfollowPlaylist, name: "unfollow_playlist", description: "Remove playlist from library", category: ActionCategory::Actions, default_keys: &["ctrl-d"] },
+    ActionInfo { action: Action::ExportTop, name: "export_top", description: "Your Top: tracks to a new playlist", category: ActionCategory::Actions, default_keys: &["ctrl-t"] },
+    ActionInfo { action: Action::ExportPlaylist, name: "export_playlist", description: "Export playlist to JSON", category: ActionCategory::Actions, default_keys: &["ctrl-s"] },
+    ActionInfo { action: Action::ImportPlaylist, name: "import_playlist", description: "Import playlist, track list or backup…", category: ActionCategory::Actions, default_keys: &["ctrl-o"] },
+    ActionInfo { action: Action::BackupLibrary, name: "backup_library", description: "Back up the whole library", category: ActionCategory::Actions, default_keys: &["ctrl-b"] },
+    ActionInfo { action: Action::MoveQueueUp, name: "queue_move_up", description: "Move track up (queue / playlist)", category: ActionCategory::Actions, default_keys: &["shift-up"] },
+    ActionInfo { action: Action::MoveQueueDown, name: "queue_move_down", description: "Move track down (queue / playlist)", category: ActionCategory::Actions, default_keys: &["shift-down"] },
+    ActionInfo { action: Action::ClearQueue, name: "clear_queue", description: "Clear queued tracks", category: ActionCategory::Actions, default_keys: &["C"] },
+    ActionInfo { action: Action::ShowQueue, name: "show_queue", description: "Show queue", category: ActionCategory::Actions, default_keys: &["u"] },
+    ActionInfo { action: Action::OpenDevicePicker, name: "device_picker", description: "Device picker", category: ActionCategory::Actions, default_keys: &["d"] },
+    ActionInfo { action: Action::ToggleHelp, name: "help", description: "Toggle this help", category: ActionCategory::General, default_keys: &["h"] },
+    ActionInfo { action: Action::OpenSettings, name: "settings", description: "Settings", category: ActionCategory::General, default_keys: &["y"] },
+    ActionInfo { action: Action::Quit, name: "quit", description: "Quit", category: ActionCategory::General, default_keys: &["q", "ctrl-q"] },
+];
+
+impl Action {
+    pub fn info(&self) -> &'static ActionInfo {
+        ACTIONS
+            .iter()
+            .find(|info| info.action == *self)
+            .expect("every action has an entry in ACTIONS")
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        ACTIONS.iter().find(|info| info.name == name).map(|info| info.action)
+    }
+}
+
+/// A single key press with modifiers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyPress {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    /// Normalise a terminal key event so it can be compared with bindings
+    pub fn from_event(event: &KeyEvent) -> Self {
+        let mut modifiers = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match event.code {
+            // Shift is already encoded in the character itself
+            KeyCode::Char(c) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c)
+            }
+            // Terminals report Shift+Tab as BackTab (some with SHIFT set) or as Tab+SHIFT
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            other => other,
+        };
+        Self { code, modifiers }
+    }
+
+    /// Whether this binding key matches a (normalised) pressed key
+    fn matches(&self, pressed: &KeyPress) -> bool {
+        if self.modifiers != pressed.modifiers {
+            return false;
+        }
+        match (self.code, pressed.code) {
+            (KeyCode::Char(bound), KeyCode::Char(typed)) if bound.is_lowercase() => {
+                typed.to_lowercase().eq(bound.to_lowercase())
+            }
+            (bound, typed) => bound == typed,
+        }
+    }
+
+    /// Whether the key has Ctrl or Alt held, i.e. is not plain text input
+    pub fn is_command(&self) -> bool {
+        self.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+
+    fn parse(input: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = input;
+
+        // `ctrl-`, `alt-`, `shift-` (or with `+`); the remainder must be non-empty
+        // so that `-` and `ctrl--` still parse as the minus key
+        'modifiers: loop {
+            for (name, modifier) in [
+                ("ctrl", KeyModifiers::CONTROL),
+                ("alt", KeyModifiers::ALT),
+                ("shift", KeyModifiers::SHIFT),
+            ] {
+                let bytes = rest.as_bytes();
+                if bytes.len() > name.len() + 1
+                    && bytes[..name.len()].eq_ignore_ascii_case(name.as_bytes())
+                    && matches!(bytes[name.len()], b'-' | b'+')
+                {
+                    modifiers |= modifier;
+                    rest = &rest[name.len() + 1..];
+                    continue 'modifiers;
+                }
+            }
+            break;
+        }
+
+        let code = match rest.to_ascii_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            name if name.len() > 1 && name.starts_with('f') && name[1..].parse::<u8>().is_ok_and(|n| (1..=12).contains(&n)) => {
+                KeyCode::F(name[1..].parse().unwrap_or(1))
+            }
+            _ => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => bail!("unknown key '{}'", input),
+                }
+            }
+        };
+
+        // Express shifted letters as the uppercase character
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) && c.is_alphabetic() => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            other => other,
+        };
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            // Lowercase letters match either case, so show them like key caps
+            KeyCode::Char(c) if c.is_lowercase() => write!(f, "{}", c.to_uppercase()),
+            KeyCode::Char(c) if c.is_uppercase() => write!(f, "Shift+{}", c),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Insert => write!(f, "Insert"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// A key or a chord (sequence of keys)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyBinding(pub Vec<KeyPress>);
+
+impl KeyBinding {
+    pub fn parse(input: &str) -> Result<Self> {
+        let keys = input
+            .split_whitespace()
+            .map(KeyPress::parse)
+            .collect::<Result<Vec<_>>>()?;
+        if keys.is_empty() {
+            bail!("empty key binding");
+        }
+        Ok(Self(keys))
+    }
+
+    fn starts_with(&self, pressed: &[KeyPress]) -> bool {
+        pressed.len() <= self.0.len() && self.0.iter().zip(pressed).all(|(bound, key)| bound.matches(key))
+    }
+
+    /// Whether some key sequence could trigger both bindings
+    fn overlaps(&self, other: &KeyBinding) -> bool {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .all(|(a, b)| a.matches(b) || b.matches(a))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<String> = self.0.iter().map(|k| k.to_string()).collect();
+        write!(f, "{}", keys.join(" "))
+    }
+}
+
+/// Value of an entry in the `[keys]` config section
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    fn keys(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(key) => vec![key.as_str()],
+            KeyList::Many(keys) => keys.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// Result of feeding a key press to the keymap
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyLookup {
+    Action(Action),
+    /// The key started a chord; wait for the next key
+    Pending,
+    Unbound,
+}
+
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new()).expect("default keymap is valid")
+    }
+}
+
+impl Keymap {
+    /// Build the keymap from the defaults plus `[keys]` overrides.
+    /// An overridden action loses its default keys, and a key claimed by an
+    /// override is removed from whichever action had it by default.
+    pub fn from_config(overrides: &BTreeMap<String, KeyList>) -> Result<Self> {
+        let mut problems = Vec::new();
+        let mut user_bindings: Vec<(KeyBinding, Action)> = Vec::new();
+
+        for (name, keys) in overrides {
+            let Some(action) = Action::from_name(name) else {
+                problems.push(format!("keys.{}: unknown action", name));
+                continue;
+            };
+            for key in keys.keys() {
+                match KeyBinding::parse(key) {
+                    Ok(binding) => user_bindings.push((binding, action)),
+                    Err(e) => problems.push(format!("keys.{}: {}", name, e)),
+                }
+            }
+        }
+
+        for (i, (binding, action)) in user_bindings.iter().enumerate() {
+            for (other, other_action) in &user_bindings[i + 1..] {
+                if binding.overlaps(other) && action != other_action {
+                    problems.push(format!(
+                        "'{}' ({}) conflicts with '{}' ({})",
+                        binding,
+                        action.info().name,
+                        other,
+                        other_action.info().name
+                    ));
+                }
+            }
+        }
+
+        if !problems.is_empty() {
+            return Err(anyhow!("{}", problems.join("; ")));
+        }
+
+        let overridden: Vec<Action> = user_bindings.iter().map(|(_, action)| *action).collect();
+        let mut bindings = Vec::new();
+        for info in ACTIONS {
+            if overridden.contains(&info.action) || overrides.contains_key(info.name) {
+                continue;
+            }
+            for key in info.default_keys {
+                let binding = KeyBinding::parse(key)?;
+                if !user_bindings.iter().any(|(user, _)| user.overlaps(&binding)) {
+                    bindings.push((binding, info.action));
+                }
+            }
+        }
+        bindings.extend(user_bindings);
+
+        Ok(Self { bindings })
+    }
+
+    /// Resolve a key sequence typed so far
+    pub fn lookup(&self, pressed: &[KeyPress]) -> KeyLookup {
+        let mut pending = false;
+        for (binding, action) in &self.bindings {
+            if binding.starts_with(pressed) {
+                if binding.0.len() == pressed.len() {
+                    return KeyLookup::Action(*action);
+                }
+                pending = true;
+            }
+        }
+        if pending { KeyLookup::Pending } else { KeyLookup::Unbound }
+    }
+
+    /// Bindings for `action`, in definition order
+    pub fn bindings_for(&self, action: Action) -> Vec<&KeyBinding> {
+        self.bindings
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(binding, _)| binding)
+            .collect()
+    }
+
+    /// Whether a single key press triggers `action`
+    pub fn is_bound(&self, action: Action, key: &KeyPress) -> bool {
+        self.bindings
+            .iter()
+            .any(|(binding, a)| *a == action && binding.0.len() == 1 && binding.0[0].matches(key))
+    }
+
+    /// `(keys, description)` rows for the help popup, grouped by category.
+    /// Category headers have empty keys.
+    pub fn help_entries(&self) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        for category in ActionCategory::ALL {
+            if !entries.is_empty() {
+                entries.push((String::new(), String::new()));
+            }
+            entries.push((String::new(), format!("── {} ──", category.title())));
+            for info in ACTIONS.iter().filter(|info| info.category == category) {
+                let keys: Vec<String> = self.bindings_for(info.action).iter().map(|b| b.to_string()).collect();
+                let keys = if keys.is_empty() { "(unbound)".to_string() } else { keys.join(" / ") };
+                entries.push((keys, info.description.to_string()));
+            }
+        }
+        entries
+    }
+}
+
+/// Keys typed so far towards a chord
+#[derive(Clone, Debug, Default)]
+pub struct ChordState {
+    keys: Vec<KeyPress>,
+    last_key: Option<Instant>,
+}
+
+impl ChordState {
+    /// Feed a key press, returning the resolved lookup. Stale or dead-end
+    /// prefixes are dropped and the key is retried on its own.
+    pub fn feed(&mut self, keymap: &Keymap, key: KeyPress) -> KeyLookup {
+        if self.last_key.is_some_and(|t| t.elapsed() > CHORD_TIMEOUT) {
+            self.keys.clear();
+        }
+        self.last_key = Some(Instant::now());
+        self.keys.push(key);
+
+        match keymap.lookup(&self.keys) {
+            KeyLookup::Pending => KeyLookup::Pending,
+            KeyLookup::Action(action) => {
+                self.keys.clear();
+                KeyLookup::Action(action)
+            }
+            KeyLookup::Unbound if self.keys.len() > 1 => {
+                self.keys.clear();
+                self.feed(keymap, key)
+            }
+            KeyLookup::Unbound => {
+                self.keys.clear();
+                KeyLookup::Unbound
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyPress {
+        KeyPress::from_event(&KeyEvent::new(code, modifiers))
+    }
+
+    fn ch(c: char) -> KeyPress {
+        let modifiers = if c.is_uppercase() { KeyModifiers::SHIFT } else { KeyModifiers::NONE };
+        press(KeyCode::Char(c), modifiers)
+    }
+
+    fn overrides(entries: &[(&str, &[&str])]) -> BTreeMap<String, KeyList> {
+        entries
+            .iter()
+            .map(|(name, keys)| (name.to_string(), KeyList::Many(keys.iter().map(|k| k.to_string()).collect())))
+            .collect()
+    }
+
+    #[test]
+    fn every_action_has_unique_name_and_default_keys_parse() {
+        for (i, info) in ACTIONS.iter().enumerate() {
+            assert!(ACTIONS[i + 1..].iter().all(|other| other.name != info.name && other.action != info.action));
+            for key in info.default_keys {
+                KeyBinding::parse(key).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn default_letters_match_both_cases() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.lookup(&[ch('n')]), KeyLookup::Action(Action::NextTrack));
+        assert_eq!(keymap.lookup(&[ch('N')]), KeyLookup::Action(Action::NextTrack));
+        assert_eq!(
+            keymap.lookup(&[press(KeyCode::BackTab, KeyModifiers::SHIFT)]),
+            KeyLookup::Action(Action::PrevSection)
+        );
+        assert_eq!(
+            keymap.lookup(&[press(KeyCode::Tab, KeyModifiers::SHIFT)]),
+            KeyLookup::Action(Action::PrevSection)
+        );
+        assert_eq!(
+            keymap.lookup(&[press(KeyCode::Char('q'), KeyModifiers::CONTROL)]),
+            KeyLookup::Action(Action::Quit)
+        );
+    }
+
+    #[test]
+    fn parses_modifiers_and_named_keys() {
+        let binding = KeyBinding::parse("ctrl-alt-x").unwrap();
+        assert_eq!(binding.0[0].modifiers, KeyModifiers::CONTROL | KeyModifiers::ALT);
+        assert_eq!(binding.0[0].code, KeyCode::Char('x'));
+
+        assert_eq!(KeyBinding::parse("shift+n").unwrap().0[0].code, KeyCode::Char('N'));
+        assert_eq!(KeyBinding::parse("F5").unwrap().0[0].code, KeyCode::F(5));
+        assert_eq!(KeyBinding::parse("-").unwrap().0[0].code, KeyCode::Char('-'));
+        assert_eq!(KeyBinding::parse("ctrl--").unwrap().0[0].code, KeyCode::Char('-'));
+        assert!(KeyBinding::parse("hyper-x").is_err());
+    }
+
+    #[test]
+    fn override_replaces_defaults_and_steals_keys() {
+        let keymap = Keymap::from_config(&overrides(&[("next_track", &["ctrl-right", "l"])])).unwrap();
+
+        assert_eq!(keymap.lookup(&[ch('n')]), KeyLookup::Unbound);
+        assert_eq!(keymap.lookup(&[ch('l')]), KeyLookup::Action(Action::NextTrack));
+        assert!(keymap.bindings_for(Action::FocusPlaylists).is_empty());
+        assert_eq!(
+            keymap.lookup(&[press(KeyCode::Right, KeyModifiers::CONTROL)]),
+            KeyLookup::Action(Action::NextTrack)
+        );
+    }
+
+    #[test]
+    fn uppercase_binding_does_not_match_lowercase() {
+        let keymap = Keymap::from_config(&overrides(&[("show_queue", &["U"])])).unwrap();
+        assert_eq!(keymap.lookup(&[ch('U')]), KeyLookup::Action(Action::ShowQueue));
+        assert_eq!(keymap.lookup(&[ch('u')]), KeyLookup::Unbound);
+    }
+
+    #[test]
+    fn chords_wait_for_the_full_sequence() {
+        let keymap = Keymap::from_config(&overrides(&[("show_queue", &["g q"]), ("focus_search", &["/"])])).unwrap();
+        let mut chord = ChordState::default();
+
+        assert_eq!(chord.feed(&keymap, ch('g')), KeyLookup::Pending);
+        assert_eq!(chord.feed(&keymap, ch('q')), KeyLookup::Action(Action::ShowQueue));
+
+        // A dead-end prefix is dropped and the key is retried alone
+        assert_eq!(chord.feed(&keymap, ch('g')), KeyLookup::Pending);
+        assert_eq!(chord.feed(&keymap, ch('n')), KeyLookup::Action(Action::NextTrack));
+    }
+
+    #[test]
+    fn invalid_overrides_are_reported() {
+        let err = Keymap::from_config(&overrides(&[
+            ("launch_rockets", &["z"]),
+            ("next_track", &["z"]),
+            ("previous_track", &["Z", "bogus-key"]),
+        ]))
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("keys.launch_rockets: unknown action"), "{}", err);
+        assert!(err.contains("unknown key 'bogus-key'"), "{}", err);
+        assert!(err.contains("conflicts"), "{}", err);
+    }
+
+    #[test]
+    fn help_is_generated_from_the_keymap() {
+        let keymap = Keymap::from_config(&overrides(&[("quit", &["ctrl-c"])])).unwrap();
+        let help = keymap.help_entries();
+
+        assert!(help.contains(&("Ctrl+C".to_string(), "Quit".to_string())));
+        assert!(help.contains(&("+ / =".to_string(), "Volume up".to_string())));
+        assert!(help.contains(&(String::new(), "── Playback ──".to_string())));
+    }
+}
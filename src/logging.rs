@@ -7,22 +7,20 @@ use tracing_subscriber::{
     EnvFilter,
 };
 
-const LOG_DIR: &str = ".logs";
 const LOG_FILE_PREFIX: &str = "spotify-rs";
 const LOG_FILE_SUFFIX: &str = "log";
 
 /// Initialize the logging system.
 ///
-/// Logs are written to `.logs/spotify-rs-YYYY-MM-DD.log` with daily rotation.
+/// Logs are written to `<log_dir>/spotify-rs-YYYY-MM-DD.log` with daily rotation.
 /// The log level can be controlled via the `RUST_LOG` environment variable.
 ///
 /// Default log levels:
 /// - `spotify_rs` modules: DEBUG
 /// - `librespot`: INFO
 /// - Other crates: WARN
-pub fn init_logging() -> anyhow::Result<()> {
+pub fn init_logging(log_dir: &Path) -> anyhow::Result<()> {
     // Ensure log directory exists
-    let log_dir = Path::new(LOG_DIR);
     if !log_dir.exists() {
         std::fs::create_dir_all(log_dir)?;
     }
@@ -32,7 +30,7 @@ pub fn init_logging() -> anyhow::Result<()> {
         .rotation(Rotation::DAILY)
         .filename_prefix(LOG_FILE_PREFIX)
         .filename_suffix(LOG_FILE_SUFFIX)
-        .build(LOG_DIR)?;
+        .build(log_dir)?;
 
     // Create a non-blocking writer to avoid blocking the async runtime
     let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
@@ -62,7 +60,7 @@ pub fn init_logging() -> anyhow::Result<()> {
         .with(fmt_layer)
         .init();
 
-    tracing::info!("Logging initialized - logs written to {}/", LOG_DIR);
+    tracing::info!("Logging initialized - logs written to {}/", log_dir.display());
 
     Ok(())
 }
@@ -1,138 +1,180 @@
 mod audio;
 mod auth;
+mod config;
 mod controller;
+mod endpoints;
+mod keymap;
 mod logging;
+#[cfg(test)]
+mod mock_server;
 mod model;
+mod paths;
 mod view;
 
 use std::io;
 use std::sync::Arc;
-use anyhow::Result;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, bail, Result};
 use std::time::Duration;
 use tokio::sync::Mutex;
 use crossterm::{
-    event::{self, Event},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use rspotify::{clients::OAuthClient, AuthCodeSpotify, Config, Token};
-
-use view::AppView;
-use audio::AudioBackend;
+use rspotify::{clients::OAuthClient, AuthCodeSpotify, Token};
+
+use view::{AppView, ViewLayout};
+use audio::{AudioBackend, AudioSettings};
+use config::Config;
+use endpoints::Endpoints;
+use keymap::Keymap;
+use paths::AppPaths;
 use controller::AppController;
-use model::{AppModel, SpotifyClient};
+use model::{AppModel, SearchHistory, SpotifyApi, SpotifyClient, FakeSpotifyClient, FakeLibrary};
+
+/// Command line options
+struct CliArgs {
+    /// Config file given with `--config <path>`
+    config_path: Option<PathBuf>,
+    /// Run against the in-memory demo library instead of Spotify
+    offline: bool,
+}
+
+impl CliArgs {
+    fn parse() -> Result<Self> {
+        let mut parsed = Self {
+            config_path: None,
+            offline: false,
+        };
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--offline" => parsed.offline = true,
+                "--config" => {
+                    let path = args.next().ok_or_else(|| anyhow!("--config requires a file path"))?;
+                    parsed.config_path = Some(PathBuf::from(path));
+                }
+                other => match other.strip_prefix("--config=") {
+                    Some(path) => parsed.config_path = Some(PathBuf::from(path)),
+                    None => bail!("Unknown argument: {}", other),
+                },
+            }
+        }
+
+        Ok(parsed)
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    if let Err(e) = logging::init_logging() {
+    let args = CliArgs::parse()?;
+    let config = Config::load(args.config_path.as_deref())?;
+    let paths = AppPaths::resolve(&config.paths);
+
+    if let Err(e) = logging::init_logging(&paths.log_dir) {
         eprintln!("Warning: Failed to initialize logging: {}", e);
     }
 
     tracing::info!("=== Spotify-RS Client Starting ===");
+    tracing::debug!(cache_dir = %paths.cache_dir.display(), log_dir = %paths.log_dir.display(), "Resolved paths");
 
-    // Step 1: Get credentials
-    let auth_result = auth::perform_oauth_flow().await?;
-
-    // Step 2: Authenticate with rspotify
-    let rspotify_client = setup_rspotify(auth_result.rspotify_token.clone()).await?;
-
-    match rspotify_client.me().await {
-        Ok(user) => tracing::info!(user_id = %user.id, "rspotify authorized successfully"),
-        Err(e) => {
-            tracing::error!(error = %e, "rspotify authentication failed");
-            return Err(anyhow::anyhow!("rspotify init failed"));
-        }
+    if let Err(e) = paths.migrate_legacy_cache() {
+        tracing::warn!(error = %e, "Could not migrate ./.cache, starting with a fresh cache");
     }
 
-    // Create the SpotifyClient with our local device name
-    let local_device_name = AudioBackend::get_device_name().to_string();
-    let token_expires_at = auth_result.rspotify_token.expires_at;
-    let spotify_client = SpotifyClient::new(
-        rspotify_client,
-        Some(local_device_name.clone()),
-        auth_result.refresh_token.clone(),
-        token_expires_at,
-    );
+    let local_device_name = config.device.name.clone();
 
-    // Initialize liked songs cache from disk
-    let cache_loaded = spotify_client.init_liked_songs_cache().await.is_ok();
-
-    // If cache wasn't loaded from disk, refresh synchronously (first run)
-    // Otherwise refresh in background
-    if !cache_loaded || !std::path::Path::new(".cache/liked_songs.json").exists() {
-        tracing::info!("Loading liked songs from API (first run or cache miss)...");
-        if let Err(e) = spotify_client.refresh_liked_songs_cache().await {
-            tracing::warn!(error = %e, "Could not load liked songs");
-        }
+    let (spotify_client, auth_result): (Arc<dyn SpotifyApi>, Option<auth::AuthResult>) = if args.offline {
+        tracing::info!("Offline mode: using in-memory demo library");
+        (Arc::new(FakeSpotifyClient::new(FakeLibrary::demo(&local_device_name))), None)
     } else {
-        tracing::debug!("Liked songs cache found, refreshing in background");
-        // Refresh liked songs cache in background (async API call)
-        let spotify_for_cache = spotify_client.clone();
-        tokio::spawn(async move {
-            let _ = spotify_for_cache.refresh_liked_songs_cache().await;
-        });
-    }
+        let (client, auth_result) = connect_spotify(&local_device_name, &paths.cache_dir, config.audio_cache_limit()).await?;
+        (Arc::new(client), Some(auth_result))
+    };
 
     let mut app_model = AppModel::new();
-    app_model.set_spotify_client(spotify_client.clone());
+    app_model.set_spotify_client(spotify_client);
+    let config_path = args.config_path.clone().unwrap_or_else(AppPaths::default_config_file);
+    app_model.set_config(config.clone(), config_path);
+    app_model.set_export_dir(paths.data_dir.join("playlists"));
+    app_model.set_backup_dir(paths.data_dir.join("backups"));
+    app_model.set_search_history(SearchHistory::load(&paths.cache_dir));
 
     tracing::info!("Starting TUI...");
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let model = Arc::new(Mutex::new(app_model));
 
-    // Set initial device name
-    model.lock().await.update_device_name(local_device_name).await;
+    // Set initial device name and volume
+    {
+        let model_guard = model.lock().await;
+        model_guard.update_device_name(local_device_name).await;
+        model_guard.set_volume(config.audio.initial_volume).await;
+        model_guard.set_theme(config.ui.theme).await;
+    }
 
     let audio_backend: Arc<Mutex<Option<AudioBackend>>> = Arc::new(Mutex::new(None));
 
     let audio_backend_init = audio_backend.clone();
-    let auth_for_backend = auth_result.clone();
     let model_for_init = model.clone();
 
-    // Initialize audio backend in background
-    tokio::spawn(async move {
-        match AudioBackend::new(auth_for_backend).await {
-            Ok(backend) => {
-                // Get event channel before moving backend
-                let event_channel = backend.get_player_event_channel().await;
-                
-                // Store the backend
-                *audio_backend_init.lock().await = Some(backend);
-                
-                // Note: We can't start the event listener here because we don't have access to controller
-                // The controller will check and start it when needed
-                if event_channel.is_some() {
-                    // Backend initialized successfully
+    // Initialize audio backend in background (no streaming session when offline)
+    if let Some(auth_for_backend) = auth_result {
+        let audio_settings = AudioSettings::from_config(&config);
+        tokio::spawn(async move {
+            match AudioBackend::new(auth_for_backend, audio_settings).await {
+                Ok(backend) => {
+                    // Get event channel before moving backend
+                    let event_channel = backend.get_player_event_channel().await;
+
+                    // Store the backend
+                    *audio_backend_init.lock().await = Some(backend);
+
+                    // Note: We can't start the event listener here because we don't have access to controller
+                    // The controller will check and start it when needed
+                    if event_channel.is_some() {
+                        // Backend initialized successfully
+                    }
+                }
+                Err(e) => {
+                    let model = model_for_init.lock().await;
+                    model.set_error(format!("Audio init failed: {}", e)).await;
                 }
             }
-            Err(e) => {
-                let model = model_for_init.lock().await;
-                model.set_error(format!("Audio init failed: {}", e)).await;
-            }
-        }
-    });
+        });
+    }
 
-    let controller = AppController::new(model.clone(), audio_backend.clone());
+    let keymap = Arc::new(Keymap::from_config(&config.keys)?);
+    let controller = AppController::new(model.clone(), audio_backend.clone(), keymap);
 
     controller.load_user_playlists().await;
 
-    let controller_for_init = controller.clone();
+    // The home page fills in once its sections have loaded
+    let controller_for_home = controller.clone();
     tokio::spawn(async move {
-        controller_for_init.initialize_playback().await;
+        controller_for_home.open_home_at_startup().await;
     });
 
+    if !args.offline {
+        let controller_for_init = controller.clone();
+        tokio::spawn(async move {
+            controller_for_init.initialize_playback().await;
+        });
+    }
+
     let res = run_app(&mut terminal, model.clone(), controller).await;
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
     terminal.show_cursor()?;
 
     if let Err(err) = res {
@@ -143,20 +185,84 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn setup_rspotify(access_token: Token) -> Result<AuthCodeSpotify> {
+/// Authenticate against Spotify and build the real API client
+async fn connect_spotify(
+    local_device_name: &str,
+    cache_dir: &Path,
+    audio_cache_limit: Option<u64>,
+) -> Result<(SpotifyClient, auth::AuthResult)> {
+    let endpoints = Endpoints::from_env();
+
+    // Step 1: Get credentials
+    let auth_result = auth::perform_oauth_flow(&endpoints, cache_dir, audio_cache_limit).await?;
+
+    // Step 2: Authenticate with rspotify
+    let rspotify_client = setup_rspotify(auth_result.rspotify_token.clone(), &endpoints).await?;
+
+    match rspotify_client.me().await {
+        Ok(user) => tracing::info!(user_id = %user.id, "rspotify authorized successfully"),
+        Err(e) => {
+            tracing::error!(error = %e, "rspotify authentication failed");
+            return Err(anyhow::anyhow!("rspotify init failed"));
+        }
+    }
+
+    // Create the SpotifyClient with our local device name
+    let token_expires_at = auth_result.rspotify_token.expires_at;
+    let spotify_client = SpotifyClient::new(
+        rspotify_client,
+        Some(local_device_name.to_string()),
+        auth_result.refresh_token.clone(),
+        token_expires_at,
+        cache_dir.to_path_buf(),
+    );
+
+    // Initialize liked songs cache from disk
+    let cache_loaded = spotify_client.init_liked_songs_cache().await.is_ok();
+
+    // If cache wasn't loaded from disk, refresh synchronously (first run)
+    // Otherwise refresh in background
+    if !cache_loaded || !spotify_client.liked_songs_cache_exists() {
+        tracing::info!("Loading liked songs from API (first run or cache miss)...");
+        if let Err(e) = spotify_client.refresh_liked_songs_cache().await {
+            tracing::warn!(error = %e, "Could not load liked songs");
+        }
+    } else {
+        tracing::debug!("Liked songs cache found, refreshing in background");
+        // Refresh liked songs cache in background (async API call)
+        let spotify_for_cache = spotify_client.clone();
+        tokio::spawn(async move {
+            let _ = spotify_for_cache.refresh_liked_songs_cache().await;
+        });
+    }
+
+    // Saved albums and followed artists only mark list entries, so their
+    // caches are always refreshed in the background
+    spotify_client.init_library_caches().await;
+    let spotify_for_cache = spotify_client.clone();
+    tokio::spawn(async move {
+        if let Err(e) = spotify_for_cache.refresh_library_caches().await {
+            tracing::warn!(error = %e, "Could not refresh saved albums and followed artists");
+        }
+    });
+
+    Ok((spotify_client, auth_result))
+}
+
+async fn setup_rspotify(access_token: Token, endpoints: &Endpoints) -> Result<AuthCodeSpotify> {
     let spotify = AuthCodeSpotify::with_config(
         Default::default(),
         Default::default(),
-        Config {
-            token_cached: false,
-            token_refreshing: false,
-            ..Default::default()
-        },
+        endpoints.rspotify_config(),
     );
 
     tracing::debug!("rspotify client initialized");
 
-    *spotify.token.lock().await.unwrap() = Some(access_token);
+    *spotify
+        .token
+        .lock()
+        .await
+        .map_err(|_| anyhow::anyhow!("Token lock is poisoned"))? = Some(access_token);
     tracing::debug!("rspotify token set");
     Ok(spotify)
 }
@@ -204,16 +310,23 @@ async fn run_app(
             )
         };
 
-        // Draw UI
+        // Draw UI, keeping the layout for mouse hit-testing
+        let mut layout = ViewLayout::default();
         terminal.draw(|f| {
-            AppView::render(f, &playback, &ui_state, &content_state);
+            layout = AppView::render(f, &playback, &ui_state, &content_state, &controller.keymap);
         })?;
 
         // Handle input with shorter poll time for smoother UI updates
         if event::poll(Duration::from_millis(50))? {
-            if let Event::Key(key) = event::read()? {
-                // Errors are now handled internally, no need to log
-                let _ = controller.handle_key_event(key).await;
+            // Errors are now handled internally, no need to log
+            match event::read()? {
+                Event::Key(key) => {
+                    let _ = controller.handle_key_event(key).await;
+                }
+                Event::Mouse(mouse) => {
+                    controller.handle_mouse_event(mouse, &layout).await;
+                }
+                _ => {}
             }
         }
 
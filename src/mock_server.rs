@@ -0,0 +1,635 @@
+//! Local mock of the Spotify Web API and accounts service (tests only)
+//!
+//! A tiny HTTP/1.1 server that serves canned JSON for the routes used by
+//! `SpotifyClient`. Paged endpoints honour `limit`/`offset` and fill in
+//! `next`, so rspotify's paginators walk real page boundaries. Every request
+//! is recorded so tests can assert which pages were fetched.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::endpoints::Endpoints;
+
+/// Largest page the real API hands out for the routes we mock
+const MAX_PAGE_LIMIT: usize = 50;
+
+/// Canned data served by the mock
+#[derive(Clone, Debug, Default)]
+pub struct MockData {
+    pub playlists: Vec<MockPlaylist>,
+    /// Saved tracks in `/v1/me/tracks` order (most recent first)
+    pub liked_tracks: Vec<Value>,
+    /// Full artist objects served by the cursor-paged `/v1/me/following`
+    pub followed_artists: Vec<Value>,
+    /// `None` makes `/v1/me/player` answer `204 No Content`
+    pub currently_playing: Option<Value>,
+    pub is_playing: bool,
+    pub queue: Vec<Value>,
+    /// Simplified episode objects served for any show's `/episodes`
+    pub episodes: Vec<Value>,
+    /// Also served as any artist's top tracks
+    pub top_tracks: Vec<Value>,
+    pub top_artists: Vec<Value>,
+    /// Full artist objects served by `/v1/artists/{id}`
+    pub artists: Vec<Value>,
+    /// Simplified albums served for any artist's `/albums`
+    pub artist_albums: Vec<Value>,
+    /// Served for any artist's `/related-artists`; `None` answers 404 like
+    /// Spotify does for newer API clients
+    pub related_artists: Option<Vec<Value>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MockPlaylist {
+    pub id: String,
+    pub name: String,
+    pub owner: String,
+    pub tracks: Vec<Value>,
+}
+
+/// A request as seen by the mock server
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn query_usize(&self, key: &str) -> Option<usize> {
+        self.query.get(key).and_then(|v| v.parse().ok())
+    }
+}
+
+struct Response {
+    status: u16,
+    body: Option<Value>,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body: Some(body) }
+    }
+
+    fn no_content() -> Self {
+        Self { status: 204, body: None }
+    }
+
+    fn not_found(message: &str) -> Self {
+        Self {
+            status: 404,
+            body: Some(json!({ "error": { "status": 404, "message": message } })),
+        }
+    }
+}
+
+pub struct MockSpotifyServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    task: JoinHandle<()>,
+}
+
+impl MockSpotifyServer {
+    /// Bind to an ephemeral localhost port and start serving `data`
+    pub async fn start(data: MockData) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind mock server");
+        let addr = listener.local_addr().expect("mock server address");
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let data = Arc::new(data);
+        let base_url = format!("http://{}", addr);
+
+        let requests_for_task = requests.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let data = data.clone();
+                let requests = requests_for_task.clone();
+                let base_url = base_url.clone();
+                tokio::spawn(async move {
+                    let _ = handle_connection(stream, &data, &requests, &base_url).await;
+                });
+            }
+        });
+
+        Self { addr, requests, task }
+    }
+
+    /// Endpoints pointing both the API and the accounts service at this server
+    pub fn endpoints(&self) -> Endpoints {
+        Endpoints::local(&format!("http://{}", self.addr))
+    }
+
+    /// All requests made to `path` so far, in arrival order
+    pub fn requests_to(&self, path: &str) -> Vec<RecordedRequest> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.path == path)
+            .cloned()
+            .collect()
+    }
+}
+
+impl Drop for MockSpotifyServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    data: &MockData,
+    requests: &Mutex<Vec<RecordedRequest>>,
+    base_url: &str,
+) -> std::io::Result<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+    requests.lock().unwrap().push(request.clone());
+
+    let response = route(&request, data, base_url);
+    let body = response.body.map(|b| b.to_string()).unwrap_or_default();
+    let reason = match response.status {
+        200 => "OK",
+        204 => "No Content",
+        _ => "Not Found",
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason,
+        body.len()
+    );
+
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<RecordedRequest>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+
+    while buffer.len() < header_end + content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    }
+
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    Ok(Some(RecordedRequest {
+        method,
+        path: path.to_string(),
+        query: parse_form(query),
+        body: String::from_utf8_lossy(&buffer[header_end..]).to_string(),
+    }))
+}
+
+fn route(request: &RecordedRequest, data: &MockData, base_url: &str) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["api", "token"]) => {
+            let form = parse_form(&request.body);
+            match form.get("grant_type").map(String::as_str) {
+                Some("refresh_token") => Response::ok(json!({
+                    "access_token": "mock-access-token",
+                    "token_type": "Bearer",
+                    "expires_in": 3600,
+                    "refresh_token": "mock-refresh-token",
+                    "scope": "user-library-read",
+                })),
+                _ => Response::not_found("unsupported grant type"),
+            }
+        }
+        ("GET", ["v1", "me"]) => Response::ok(json!({
+            "id": "mock-user",
+            "display_name": "Mock User",
+            "external_urls": {},
+            "href": format!("{}/v1/users/mock-user", base_url),
+        })),
+        ("GET", ["v1", "me", "player"]) => match &data.currently_playing {
+            Some(track) => Response::ok(playback_json(track, data.is_playing)),
+            None => Response::no_content(),
+        },
+        ("PUT", ["v1", "me", "player", "seek"]) => Response::no_content(),
+        ("PUT", ["v1", "me", "player", "play"]) => Response::no_content(),
+        ("GET", ["v1", "me", "player", "queue"]) => Response::ok(json!({
+            "currently_playing": data.currently_playing,
+            "queue": data.queue,
+        })),
+        ("GET", ["v1", "me", "tracks"]) => {
+            let saved: Vec<Value> = data
+                .liked_tracks
+                .iter()
+                .map(|track| json!({ "added_at": "2024-01-01T00:00:00Z", "track": track }))
+                .collect();
+            Response::ok(page_json(&saved, request, base_url))
+        }
+        ("PUT", ["v1", "me", "tracks" | "albums" | "following"]) => Response::no_content(),
+        ("DELETE", ["v1", "me", "albums" | "following"]) => Response::no_content(),
+        ("GET", ["v1", "me", "following"]) => Response::ok(json!({
+            "artists": cursor_page_json(&data.followed_artists, request, base_url),
+        })),
+        ("GET", ["v1", "shows", _, "episodes"]) => Response::ok(page_json(&data.episodes, request, base_url)),
+        ("GET", ["v1", "me", "top", "tracks"]) => Response::ok(page_json(&data.top_tracks, request, base_url)),
+        ("GET", ["v1", "me", "top", "artists"]) => Response::ok(page_json(&data.top_artists, request, base_url)),
+        // Every playlist belongs to every category
+        ("GET", ["v1", "browse", "categories", _, "playlists"]) => {
+            let playlists: Vec<Value> = data.playlists.iter().map(|p| simplified_playlist_json(p, base_url)).collect();
+            Response::ok(json!({ "playlists": page_json(&playlists, request, base_url) }))
+        }
+        ("GET", ["v1", "artists", id]) => match data.artists.iter().find(|artist| artist["id"] == *id) {
+            Some(artist) => Response::ok(artist.clone()),
+            None => Response::not_found("artist not found"),
+        },
+        ("GET", ["v1", "artists", _, "top-tracks"]) => Response::ok(json!({ "tracks": data.top_tracks })),
+        ("GET", ["v1", "artists", _, "albums"]) => Response::ok(page_json(&data.artist_albums, request, base_url)),
+        ("GET", ["v1", "artists", _, "related-artists"]) => match &data.related_artists {
+            Some(artists) => Response::ok(json!({ "artists": artists })),
+            None => Response::not_found("related artists are not available"),
+        },
+        ("GET", ["v1", "tracks"]) => {
+            // Unknown IDs come back as `null`, like the real endpoint
+            let ids = request.query.get("ids").map(String::as_str).unwrap_or_default();
+            let tracks: Vec<Value> = ids
+                .split(',')
+                .map(|id| {
+                    data.playlists
+                        .iter()
+                        .flat_map(|p| p.tracks.iter())
+                        .chain(data.liked_tracks.iter())
+                        .find(|track| track["id"] == id)
+                        .cloned()
+                        .unwrap_or(Value::Null)
+                })
+                .collect();
+            Response::ok(json!({ "tracks": tracks }))
+        }
+        ("GET", ["v1", "playlists", id]) => match data.playlists.iter().find(|p| p.id == *id) {
+            Some(playlist) => {
+                let mut body = playlist_json(playlist, base_url);
+                let first_page = RecordedRequest { query: HashMap::new(), ..request.clone() };
+                body["tracks"] = page_json(&playlist_items(playlist), &first_page, base_url);
+                Response::ok(body)
+            }
+            None => Response::not_found("playlist not found"),
+        },
+        ("GET", ["v1", "playlists", id, "tracks"]) => match data.playlists.iter().find(|p| p.id == *id) {
+            Some(playlist) => Response::ok(page_json(&playlist_items(playlist), request, base_url)),
+            None => Response::not_found("playlist not found"),
+        },
+        ("POST" | "PUT" | "DELETE", ["v1", "playlists", id, "tracks"]) => {
+            match data.playlists.iter().find(|p| p.id == *id) {
+                Some(_) => Response::ok(json!({ "snapshot_id": "mock-snapshot-2" })),
+                None => Response::not_found("playlist not found"),
+            }
+        }
+        ("PUT", ["v1", "playlists", id])
+        | ("PUT" | "DELETE", ["v1", "playlists", id, "followers"]) => {
+            match data.playlists.iter().find(|p| p.id == *id) {
+                Some(_) => Response::no_content(),
+                None => Response::not_found("playlist not found"),
+            }
+        }
+        ("POST", ["v1", "users", user, "playlists"]) => {
+            let request_body: Value = serde_json::from_str(&request.body).unwrap_or_default();
+            let created = MockPlaylist {
+                id: fixtures::id("playlist", 999),
+                name: request_body["name"].as_str().unwrap_or_default().to_string(),
+                owner: user.to_string(),
+                tracks: Vec::new(),
+            };
+            let mut body = playlist_json(&created, base_url);
+            body["collaborative"] = request_body["collaborative"].clone();
+            body["public"] = request_body["public"].clone();
+            body["tracks"] = page_json(&[], request, base_url);
+            Response::ok(body)
+        }
+        ("GET", ["v1", "search"]) => {
+            let needle = request.query.get("q").cloned().unwrap_or_default().to_lowercase();
+            let body = match request.query.get("type").map(String::as_str) {
+                Some("track") => {
+                    let mut seen = std::collections::HashSet::new();
+                    let tracks: Vec<Value> = data
+                        .playlists
+                        .iter()
+                        .flat_map(|p| p.tracks.iter())
+                        .chain(data.liked_tracks.iter())
+                        .filter(|t| t["name"].as_str().unwrap_or_default().to_lowercase().contains(&needle))
+                        .filter(|t| seen.insert(t["id"].to_string()))
+                        .cloned()
+                        .collect();
+                    json!({ "tracks": page_json(&tracks, request, base_url) })
+                }
+                Some("playlist") => {
+                    let playlists: Vec<Value> = data
+                        .playlists
+                        .iter()
+                        .filter(|p| p.name.to_lowercase().contains(&needle))
+                        .map(|p| simplified_playlist_json(p, base_url))
+                        .collect();
+                    json!({ "playlists": page_json(&playlists, request, base_url) })
+                }
+                Some(kind) => json!({ format!("{}s", kind): page_json(&[], request, base_url) }),
+                None => return Response::not_found("missing search type"),
+            };
+            Response::ok(body)
+        }
+        _ => Response::not_found("no mock for this route"),
+    }
+}
+
+/// Slice `items` according to the request's `limit`/`offset` into a paging object
+fn page_json(items: &[Value], request: &RecordedRequest, base_url: &str) -> Value {
+    let limit = request.query_usize("limit").unwrap_or(20).min(MAX_PAGE_LIMIT);
+    let offset = request.query_usize("offset").unwrap_or(0);
+    let page: Vec<Value> = items.iter().skip(offset).take(limit).cloned().collect();
+    let href = |offset: usize| format!("{}{}?offset={}&limit={}", base_url, request.path, offset, limit);
+
+    json!({
+        "href": href(offset),
+        "items": page,
+        "limit": limit,
+        "next": (offset + limit < items.len()).then(|| href(offset + limit)),
+        "offset": offset,
+        "previous": (offset > 0).then(|| href(offset.saturating_sub(limit))),
+        "total": items.len(),
+    })
+}
+
+/// Cursor-based page of `items` continuing after the item whose `id` is the
+/// request's `after`
+fn cursor_page_json(items: &[Value], request: &RecordedRequest, base_url: &str) -> Value {
+    let limit = request.query_usize("limit").unwrap_or(20).min(MAX_PAGE_LIMIT);
+    let start = match request.query.get("after") {
+        Some(after) => items.iter().position(|item| item["id"] == after.as_str()).map_or(items.len(), |i| i + 1),
+        None => 0,
+    };
+    let page: Vec<Value> = items.iter().skip(start).take(limit).cloned().collect();
+    let after = page.last().filter(|_| start + limit < items.len()).map(|item| item["id"].clone());
+    let href = |after: &Value| format!("{}{}?type=artist&after={}&limit={}", base_url, request.path, after.as_str().unwrap_or_default(), limit);
+
+    json!({
+        "href": format!("{}{}", base_url, request.path),
+        "items": page,
+        "limit": limit,
+        "next": after.as_ref().map(href),
+        "cursors": { "after": after },
+        "total": items.len(),
+    })
+}
+
+fn playlist_items(playlist: &MockPlaylist) -> Vec<Value> {
+    playlist
+        .tracks
+        .iter()
+        .map(|track| {
+            let is_local = track["is_local"].as_bool().unwrap_or(false);
+            json!({ "added_at": "2024-01-01T00:00:00Z", "added_by": null, "is_local": is_local, "track": track })
+        })
+        .collect()
+}
+
+fn playlist_json(playlist: &MockPlaylist, base_url: &str) -> Value {
+    json!({
+        "collaborative": false,
+        "description": null,
+        "external_urls": {},
+        "followers": { "total": 0 },
+        "href": format!("{}/v1/playlists/{}", base_url, playlist.id),
+        "id": playlist.id,
+        "images": [],
+        "name": playlist.name,
+        "owner": {
+            "display_name": playlist.owner,
+            "external_urls": {},
+            "href": format!("{}/v1/users/{}", base_url, playlist.owner),
+            "id": playlist.owner,
+        },
+        "public": true,
+        "snapshot_id": "mock-snapshot",
+    })
+}
+
+/// Playlist object as listed in search results and browse pages
+fn simplified_playlist_json(playlist: &MockPlaylist, base_url: &str) -> Value {
+    let mut simplified = playlist_json(playlist, base_url);
+    simplified["tracks"] = json!({
+        "href": format!("{}/v1/playlists/{}/tracks", base_url, playlist.id),
+        "total": playlist.tracks.len(),
+    });
+    simplified
+}
+
+fn playback_json(track: &Value, is_playing: bool) -> Value {
+    json!({
+        "device": {
+            "id": "mock-device",
+            "is_active": true,
+            "is_private_session": false,
+            "is_restricted": false,
+            "name": "Mock Device",
+            "type": "Computer",
+            "volume_percent": 70,
+        },
+        "repeat_state": "off",
+        "shuffle_state": false,
+        "context": null,
+        "timestamp": 1_700_000_000_000u64,
+        "progress_ms": 42_000,
+        "is_playing": is_playing,
+        "item": track,
+        "currently_playing_type": "track",
+        "actions": { "disallows": {} },
+    })
+}
+
+/// Decode an `application/x-www-form-urlencoded` string
+fn parse_form(input: &str) -> HashMap<String, String> {
+    input
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Canned JSON objects in the shape the Web API returns
+pub mod fixtures {
+    use serde_json::{json, Value};
+
+    /// Base62-looking 22 character ID, unique per `prefix`/`n`
+    pub fn id(prefix: &str, n: usize) -> String {
+        format!("{}{:0>width$}", prefix, n, width = 22 - prefix.len())
+    }
+
+    /// Full track object named `Track {n}`
+    pub fn track(n: usize) -> Value {
+        let artist = json!({
+            "external_urls": {},
+            "href": null,
+            "id": id("artist", n % 5),
+            "name": format!("Artist {}", n % 5),
+        });
+        json!({
+            "album": {
+                "album_type": "album",
+                "artists": [artist],
+                "external_urls": {},
+                "href": null,
+                "id": id("album", n / 10),
+                "images": [],
+                "name": format!("Album {}", n / 10),
+                "release_date": "2020-01-01",
+                "release_date_precision": "day",
+            },
+            "artists": [artist],
+            "disc_number": 1,
+            "duration_ms": 180_000 + n as u64 * 1000,
+            "explicit": false,
+            "external_ids": {},
+            "external_urls": {},
+            "href": null,
+            "id": id("track", n),
+            "is_local": false,
+            "name": format!("Track {}", n),
+            "popularity": 50,
+            "preview_url": null,
+            "track_number": (n % 10) as u32 + 1,
+            "type": "track",
+        })
+    }
+
+    /// A local file added to a playlist, which has no Spotify ID
+    pub fn local_track(name: &str) -> Value {
+        let mut track = track(0);
+        track["id"] = Value::Null;
+        track["album"]["id"] = Value::Null;
+        track["artists"][0]["id"] = Value::Null;
+        track["album"]["artists"][0]["id"] = Value::Null;
+        track["is_local"] = json!(true);
+        track["name"] = json!(name);
+        track["uri"] = json!(format!("spotify:local:::{}:180", name.replace(' ', "+")));
+        track
+    }
+
+    /// Tracks `1..=count`
+    pub fn tracks(count: usize) -> Vec<Value> {
+        (1..=count).map(track).collect()
+    }
+
+    /// Simplified episode object named `Episode {n}`, with a resume point
+    /// when it has been listened to
+    pub fn episode(n: usize, resume_position_ms: Option<u64>, fully_played: bool) -> Value {
+        json!({
+            "audio_preview_url": null,
+            "description": format!("Episode {} description", n),
+            "duration_ms": 1_800_000 + n as u64 * 1000,
+            "explicit": false,
+            "external_urls": {},
+            "href": format!("https://api.spotify.com/v1/episodes/{}", id("episode", n)),
+            "id": id("episode", n),
+            "images": [],
+            "is_externally_hosted": false,
+            "is_playable": true,
+            "language": "en",
+            "languages": ["en"],
+            "name": format!("Episode {}", n),
+            "release_date": format!("2024-01-{:02}", n),
+            "release_date_precision": "day",
+            "resume_point": resume_position_ms.map(|ms| json!({
+                "fully_played": fully_played,
+                "resume_position_ms": ms,
+            })),
+        })
+    }
+
+    /// Simplified album named `Album {n}`, listed in `group` of an artist's
+    /// albums ("album", "single", "compilation" or "appears_on")
+    pub fn album(n: usize, group: &str, release_date: &str) -> Value {
+        json!({
+            "album_group": group,
+            "album_type": if group == "appears_on" { "album" } else { group },
+            "artists": [{ "external_urls": {}, "href": null, "id": id("artist", n % 5), "name": format!("Artist {}", n % 5) }],
+            "external_urls": {},
+            "href": null,
+            "id": id("album", n),
+            "images": [],
+            "name": format!("Album {}", n),
+            "release_date": release_date,
+            "release_date_precision": "day",
+        })
+    }
+
+    /// Full artist object named `Artist {n}`
+    pub fn artist(n: usize) -> Value {
+        json!({
+            "external_urls": {},
+            "followers": { "href": null, "total": 1000 },
+            "genres": ["ambient"],
+            "href": format!("https://api.spotify.com/v1/artists/{}", id("artist", n)),
+            "id": id("artist", n),
+            "images": [],
+            "name": format!("Artist {}", n),
+            "popularity": 40,
+        })
+    }
+}
@@ -2,25 +2,39 @@
 
 use std::sync::Arc;
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 use rspotify::model::CurrentPlaybackContext;
 
 use super::types::{
-    ActiveSection, ArtistDetailSection, DeviceInfo, PlaylistItem, 
-    RepeatState, SearchResultSection, SelectedItem, UiState,
+    ActiveSection, ArtistDetailSection, DeviceInfo, HomeSection, PlaylistItem, PlaylistPrompt,
+    PlaylistPromptKind, RepeatState, SearchResultSection, SearchSuggestion, SelectedItem, TopRange, TopTab, UiState,
 };
 use super::playback::{PlaybackInfo, PlaybackSettings, PlaybackTiming, TrackMetadata};
 use super::content::{
-    AlbumDetail, ArtistDetail, ContentState, ContentView, PlaylistDetail,
-    SearchAlbum, SearchArtist, SearchResults, SearchTrack,
+    AlbumDetail, ArtistDetail, Category, ContentState, ContentView, Episode, HomePage, LibraryPageRequest, ListPaging,
+    PlaylistDetail, PlaylistTrackRef, SearchAlbum, SearchArtist, SearchPlaylist, SearchResults, SearchTrack, Show,
+    ShowDetail, TopItems,
 };
-use super::spotify_client::SpotifyClient;
+use super::queue::LocalQueue;
+use super::track_list_import::ImportReview;
+use super::library_backup::RestorePlan;
+use super::search_history::SearchHistory;
+use super::list_filter::ListFilter;
+use super::settings::{Setting, SettingRow};
+use super::spotify_api::SpotifyApi;
+use crate::config::{Config, ThemeName};
 
 /// Main application model containing all state
 pub struct AppModel {
-    pub spotify: Option<SpotifyClient>,
+    pub spotify: Option<Arc<dyn SpotifyApi>>,
+    config: Arc<Mutex<Config>>,
+    /// Config the running session was started with
+    startup_config: Config,
+    /// File the settings overlay saves to
+    config_path: PathBuf,
     track_metadata: Arc<Mutex<TrackMetadata>>,
     playback_timing: Arc<Mutex<PlaybackTiming>>,
     playback_settings: Arc<Mutex<PlaybackSettings>>,
@@ -28,12 +42,25 @@ pub struct AppModel {
     pub content_state: Arc<Mutex<ContentState>>,
     pub should_quit: Arc<Mutex<bool>>,
     queue_skip_list: Arc<RwLock<HashSet<String>>>,
+    local_queue: Arc<Mutex<LocalQueue>>,
+    /// Track waiting for a position in the "insert into queue" prompt
+    queue_insert_track: Arc<Mutex<Option<SearchTrack>>>,
+    /// Track waiting for a playlist in the "add to playlist" picker
+    playlist_picker_track: Arc<Mutex<Option<SearchTrack>>>,
+    /// Where playlist exports are written and relative imports are read from
+    export_dir: PathBuf,
+    /// Where library backups are written
+    backup_dir: PathBuf,
+    search_history: Arc<Mutex<SearchHistory>>,
 }
 
 impl AppModel {
     pub fn new() -> Self {
         Self {
             spotify: None,
+            config: Arc::new(Mutex::new(Config::default())),
+            startup_config: Config::default(),
+            config_path: PathBuf::new(),
             track_metadata: Arc::new(Mutex::new(TrackMetadata::default())),
             playback_timing: Arc::new(Mutex::new(PlaybackTiming::default())),
             playback_settings: Arc::new(Mutex::new(PlaybackSettings::default())),
@@ -41,17 +68,69 @@ impl AppModel {
             content_state: Arc::new(Mutex::new(ContentState::default())),
             should_quit: Arc::new(Mutex::new(false)),
             queue_skip_list: Arc::new(RwLock::new(HashSet::new())),
+            local_queue: Arc::new(Mutex::new(LocalQueue::default())),
+            queue_insert_track: Arc::new(Mutex::new(None)),
+            playlist_picker_track: Arc::new(Mutex::new(None)),
+            export_dir: PathBuf::new(),
+            backup_dir: PathBuf::new(),
+            search_history: Arc::new(Mutex::new(SearchHistory::default())),
         }
     }
 
-    pub fn set_spotify_client(&mut self, client: SpotifyClient) {
+    pub fn set_spotify_client(&mut self, client: Arc<dyn SpotifyApi>) {
         self.spotify = Some(client);
     }
 
-    pub async fn get_spotify_client(&self) -> Option<SpotifyClient> {
+    pub async fn get_spotify_client(&self) -> Option<Arc<dyn SpotifyApi>> {
         self.spotify.clone()
     }
 
+    pub fn set_config(&mut self, config: Config, path: PathBuf) {
+        self.startup_config = config.clone();
+        self.config = Arc::new(Mutex::new(config));
+        self.config_path = path;
+    }
+
+    pub async fn get_config(&self) -> Config {
+        self.config.lock().await.clone()
+    }
+
+    pub fn get_config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    pub fn set_export_dir(&mut self, dir: PathBuf) {
+        self.export_dir = dir;
+    }
+
+    pub fn get_export_dir(&self) -> &Path {
+        &self.export_dir
+    }
+
+    pub fn set_backup_dir(&mut self, dir: PathBuf) {
+        self.backup_dir = dir;
+    }
+
+    pub fn get_backup_dir(&self) -> &Path {
+        &self.backup_dir
+    }
+
+    pub fn set_search_history(&mut self, history: SearchHistory) {
+        self.search_history = Arc::new(Mutex::new(history));
+    }
+
+    /// Replace the current config (already saved) and apply live settings
+    pub async fn apply_config(&self, config: Config) {
+        *self.config.lock().await = config.clone();
+        let mut state = self.ui_state.lock().await;
+        state.theme = config.ui.theme;
+        state.settings = self.setting_rows(&config);
+    }
+
+    pub async fn set_theme(&self, theme: ThemeName) {
+        self.ui_state.lock().await.theme = theme;
+    }
+
     // ========================================================================
     // Device & Playback State
     // ========================================================================
@@ -74,6 +153,17 @@ impl AppModel {
         timing.update_position(position_ms, is_playing);
     }
 
+    /// Apply a successful seek to the progress bar right away
+    pub async fn seek_position(&self, position_ms: u32) {
+        self.playback_timing.lock().await.seek(position_ms);
+    }
+
+    /// Current `(position, duration)` of the playing track in milliseconds
+    pub async fn get_playback_position(&self) -> (u32, u32) {
+        let timing = self.playback_timing.lock().await;
+        (timing.current_position_ms(), timing.duration_ms)
+    }
+
     pub async fn set_playing(&self, is_playing: bool) {
         let mut timing = self.playback_timing.lock().await;
         timing.position_ms = timing.current_position_ms();
@@ -92,10 +182,8 @@ impl AppModel {
         *self.track_metadata.lock().await = track.clone();
 
         let mut timing = self.playback_timing.lock().await;
-        timing.position_ms = progress_ms;
         timing.duration_ms = track.duration_ms;
-        timing.is_playing = is_playing;
-        timing.last_update = Instant::now();
+        timing.sync_position(progress_ms, is_playing);
         drop(timing);
 
         let mut settings = self.playback_settings.lock().await;
@@ -186,15 +274,11 @@ impl AppModel {
     pub async fn move_selection_up(&self) {
         let mut state = self.ui_state.lock().await;
         match state.active_section {
-            ActiveSection::Library => {
-                if state.library_selected > 0 {
-                    state.library_selected -= 1;
-                }
+            ActiveSection::Library if state.library_selected > 0 => {
+                state.library_selected -= 1;
             }
-            ActiveSection::Playlists => {
-                if state.playlist_selected > 0 {
-                    state.playlist_selected -= 1;
-                }
+            ActiveSection::Playlists if state.playlist_selected > 0 => {
+                state.playlist_selected -= 1;
             }
             _ => {}
         }
@@ -203,35 +287,117 @@ impl AppModel {
     pub async fn move_selection_down(&self) {
         let mut state = self.ui_state.lock().await;
         match state.active_section {
-            ActiveSection::Library => {
-                if state.library_selected < state.library_items.len().saturating_sub(1) {
-                    state.library_selected += 1;
-                }
+            ActiveSection::Library if state.library_selected < state.library_items.len().saturating_sub(1) => {
+                state.library_selected += 1;
             }
-            ActiveSection::Playlists => {
-                if state.playlist_selected < state.playlists.len().saturating_sub(1) {
-                    state.playlist_selected += 1;
-                }
+            ActiveSection::Playlists if state.playlist_selected < state.playlists.len().saturating_sub(1) => {
+                state.playlist_selected += 1;
             }
             _ => {}
         }
     }
 
+    /// Focus the library and select an item (mouse click)
+    pub async fn select_library_item(&self, index: usize) {
+        let mut state = self.ui_state.lock().await;
+        state.active_section = ActiveSection::Library;
+        if index < state.library_items.len() {
+            state.library_selected = index;
+        }
+    }
+
+    /// Focus the playlists and select one (mouse click)
+    pub async fn select_playlist(&self, index: usize) {
+        let mut state = self.ui_state.lock().await;
+        state.active_section = ActiveSection::Playlists;
+        if index < state.playlists.len() {
+            state.playlist_selected = index;
+        }
+    }
+
     pub async fn update_search_query(&self, query: String) {
+        self.search_history.lock().await.reset_recall();
         let mut state = self.ui_state.lock().await;
         state.search_query = query;
     }
 
     pub async fn append_to_search(&self, c: char) {
+        self.search_history.lock().await.reset_recall();
         let mut state = self.ui_state.lock().await;
         state.search_query.push(c);
     }
 
     pub async fn backspace_search(&self) {
+        self.search_history.lock().await.reset_recall();
         let mut state = self.ui_state.lock().await;
         state.search_query.pop();
     }
 
+    /// Replace the search text with an older or newer search from the
+    /// history. Returns false when there is none.
+    pub async fn recall_search(&self, older: bool) -> bool {
+        let mut history = self.search_history.lock().await;
+        let mut state = self.ui_state.lock().await;
+        let recalled = if older { history.older(&state.search_query) } else { history.newer() };
+        let Some(query) = recalled else {
+            return false;
+        };
+        state.search_query = query;
+        state.search_suggestions.clear();
+        state.suggestion_selected = None;
+        true
+    }
+
+    pub async fn record_search(&self, query: &str) {
+        if let Err(e) = self.search_history.lock().await.record(query) {
+            tracing::warn!(error = %e, "Could not save search history");
+        }
+    }
+
+    /// Show live search results, unless the text has changed since they
+    /// were asked for
+    pub async fn set_search_suggestions(&self, query: &str, suggestions: Vec<SearchSuggestion>) {
+        let mut state = self.ui_state.lock().await;
+        if state.search_query == query {
+            state.search_suggestions = suggestions;
+            state.suggestion_selected = None;
+        }
+    }
+
+    /// Close the suggestion dropdown. Returns false if it wasn't open.
+    pub async fn close_search_suggestions(&self) -> bool {
+        let mut state = self.ui_state.lock().await;
+        state.suggestion_selected = None;
+        !std::mem::take(&mut state.search_suggestions).is_empty()
+    }
+
+    /// Move through the suggestion dropdown: Down enters it, Up from the
+    /// first suggestion goes back to the text. Returns false when the key
+    /// is left for the history instead.
+    pub async fn move_search_suggestion(&self, up: bool) -> bool {
+        let mut state = self.ui_state.lock().await;
+        let len = state.search_suggestions.len();
+        if len == 0 {
+            return false;
+        }
+        state.suggestion_selected = match (state.suggestion_selected, up) {
+            (None, true) => return false,
+            (None, false) => Some(0),
+            (Some(0), true) => None,
+            (Some(i), true) => Some(i - 1),
+            (Some(i), false) => Some((i + 1).min(len - 1)),
+        };
+        true
+    }
+
+    /// The chosen suggestion, closing the dropdown
+    pub async fn take_selected_suggestion(&self) -> Option<SelectedItem> {
+        let mut state = self.ui_state.lock().await;
+        let index = state.suggestion_selected.take()?;
+        let suggestions = std::mem::take(&mut state.search_suggestions);
+        suggestions.into_iter().nth(index).map(|suggestion| suggestion.item)
+    }
+
     pub async fn set_playlists(&self, playlists: Vec<PlaylistItem>) {
         let mut state = self.ui_state.lock().await;
         state.playlists = playlists;
@@ -243,10 +409,90 @@ impl AppModel {
         state.playlists.get(state.playlist_selected).cloned()
     }
 
+    /// Replace the sidebar playlists without losing the selection: `select_id`
+    /// (or else the previously selected playlist) stays selected, and if it is
+    /// gone the selection stays at the same row
+    pub async fn refresh_playlists(&self, playlists: Vec<PlaylistItem>, select_id: Option<&str>) {
+        let mut state = self.ui_state.lock().await;
+        let previous_id = state.playlists.get(state.playlist_selected).map(|p| p.id.clone());
+        let wanted = select_id.map(str::to_string).or(previous_id);
+
+        state.playlist_selected = wanted
+            .and_then(|id| playlists.iter().position(|p| p.id == id))
+            .unwrap_or_else(|| state.playlist_selected.min(playlists.len().saturating_sub(1)));
+        state.playlists = playlists;
+    }
+
+    /// Playlist the create/rename/describe/unfollow commands act on: the open
+    /// playlist while the content is focused, otherwise the sidebar selection.
+    /// Returns the playlist and its description, if known.
+    pub async fn get_playlist_command_target(&self) -> Option<(PlaylistItem, String)> {
+        let ui_state = self.ui_state.lock().await;
+        let content_state = self.content_state.lock().await;
+        let open = match &content_state.view {
+            ContentView::PlaylistDetail { detail, .. } => Some(detail),
+            _ => None,
+        };
+
+        if ui_state.active_section == ActiveSection::MainContent {
+            let detail = open?;
+            let item = PlaylistItem {
+                id: detail.id.clone(),
+                name: detail.name.clone(),
+                editable: detail.editable,
+            };
+            return Some((item, detail.description.clone()));
+        }
+
+        let item = ui_state.playlists.get(ui_state.playlist_selected).cloned()?;
+        let description = open
+            .filter(|detail| detail.id == item.id)
+            .map(|detail| detail.description.clone())
+            .unwrap_or_default();
+        Some((item, description))
+    }
+
+    /// Apply a rename/new description to the sidebar and the open playlist
+    pub async fn playlist_details_changed(&self, playlist_id: &str, name: Option<&str>, description: Option<&str>) {
+        if let Some(name) = name
+            && let Some(item) = self.ui_state.lock().await.playlists.iter_mut().find(|p| p.id == playlist_id)
+        {
+            item.name = name.to_string();
+        }
+
+        let mut state = self.content_state.lock().await;
+        if let Some((detail, _)) = Self::open_playlist_mut(&mut state, playlist_id) {
+            if let Some(name) = name {
+                detail.name = name.to_string();
+            }
+            if let Some(description) = description {
+                detail.description = description.to_string();
+            }
+        }
+    }
+
+    /// Show the reloaded playlist if it is still open, from the top
+    pub async fn playlist_reloaded(&self, reloaded: PlaylistDetail) {
+        let mut state = self.content_state.lock().await;
+        if let Some((detail, selected_index)) = Self::open_playlist_mut(&mut state, &reloaded.id) {
+            *detail = reloaded;
+            *selected_index = 0;
+        }
+    }
+
     pub async fn set_error(&self, message: String) {
         let mut state = self.ui_state.lock().await;
         state.error_message = Some(message);
         state.error_timestamp = Some(Instant::now());
+        state.message_is_info = false;
+    }
+
+    /// Show a notice in the error popup's place, dismissed the same way
+    pub async fn set_info(&self, message: String) {
+        let mut state = self.ui_state.lock().await;
+        state.error_message = Some(message);
+        state.error_timestamp = Some(Instant::now());
+        state.message_is_info = true;
     }
 
     pub async fn clear_error(&self) {
@@ -260,12 +506,13 @@ impl AppModel {
     }
 
     pub async fn auto_clear_old_errors(&self) {
+        let timeout = self.config.lock().await.error_timeout();
         let mut state = self.ui_state.lock().await;
-        if let Some(timestamp) = state.error_timestamp {
-            if timestamp.elapsed().as_secs() > 5 {
-                state.error_message = None;
-                state.error_timestamp = None;
-            }
+        if let Some(timestamp) = state.error_timestamp
+            && timestamp.elapsed() > timeout
+        {
+            state.error_message = None;
+            state.error_timestamp = None;
         }
     }
 
@@ -300,6 +547,68 @@ impl AppModel {
         }
     }
 
+    pub async fn device_picker_select(&self, index: usize) {
+        let mut state = self.ui_state.lock().await;
+        if index < state.available_devices.len() {
+            state.device_selected = index;
+        }
+    }
+
+    /// Open the "add to playlist" picker with the playlists the user can edit.
+    /// Returns `false` if there are none.
+    pub async fn show_playlist_picker(&self, track: SearchTrack) -> bool {
+        let mut state = self.ui_state.lock().await;
+        let playlists: Vec<PlaylistItem> = state.playlists.iter().filter(|p| p.editable).cloned().collect();
+        if playlists.is_empty() {
+            return false;
+        }
+
+        *self.playlist_picker_track.lock().await = Some(track);
+        state.playlist_picker_items = playlists;
+        state.playlist_picker_selected = 0;
+        state.show_playlist_picker = true;
+        true
+    }
+
+    pub async fn hide_playlist_picker(&self) {
+        self.ui_state.lock().await.show_playlist_picker = false;
+        self.playlist_picker_track.lock().await.take();
+    }
+
+    pub async fn is_playlist_picker_open(&self) -> bool {
+        self.ui_state.lock().await.show_playlist_picker
+    }
+
+    pub async fn playlist_picker_move_up(&self) {
+        let mut state = self.ui_state.lock().await;
+        if state.playlist_picker_selected > 0 {
+            state.playlist_picker_selected -= 1;
+        }
+    }
+
+    pub async fn playlist_picker_move_down(&self) {
+        let mut state = self.ui_state.lock().await;
+        if state.playlist_picker_selected < state.playlist_picker_items.len().saturating_sub(1) {
+            state.playlist_picker_selected += 1;
+        }
+    }
+
+    pub async fn playlist_picker_select(&self, index: usize) {
+        let mut state = self.ui_state.lock().await;
+        if index < state.playlist_picker_items.len() {
+            state.playlist_picker_selected = index;
+        }
+    }
+
+    /// Close the picker, returning the chosen playlist and the track to add
+    pub async fn take_playlist_picker_choice(&self) -> Option<(PlaylistItem, SearchTrack)> {
+        let mut state = self.ui_state.lock().await;
+        state.show_playlist_picker = false;
+        let playlist = state.playlist_picker_items.get(state.playlist_picker_selected).cloned()?;
+        let track = self.playlist_picker_track.lock().await.take()?;
+        Some((playlist, track))
+    }
+
     pub async fn get_selected_device(&self) -> Option<DeviceInfo> {
         let state = self.ui_state.lock().await;
         state.available_devices.get(state.device_selected).cloned()
@@ -309,6 +618,180 @@ impl AppModel {
         self.playback_settings.lock().await.device_name.clone()
     }
 
+    // ========================================================================
+    // Settings Overlay
+    // ========================================================================
+
+    fn setting_rows(&self, config: &Config) -> Vec<SettingRow> {
+        Setting::ALL
+            .iter()
+            .map(|setting| setting.row(config, &self.startup_config))
+            .collect()
+    }
+
+    pub async fn show_settings(&self) {
+        let rows = self.setting_rows(&*self.config.lock().await);
+        let mut state = self.ui_state.lock().await;
+        state.settings = rows;
+        state.settings_selected = 0;
+        state.settings_edit = None;
+        state.show_settings = true;
+    }
+
+    pub async fn hide_settings(&self) {
+        let mut state = self.ui_state.lock().await;
+        state.show_settings = false;
+        state.settings_edit = None;
+    }
+
+    pub async fn is_settings_open(&self) -> bool {
+        self.ui_state.lock().await.show_settings
+    }
+
+    pub async fn settings_move_up(&self) {
+        let mut state = self.ui_state.lock().await;
+        if state.settings_selected > 0 {
+            state.settings_selected -= 1;
+        }
+    }
+
+    pub async fn settings_move_down(&self) {
+        let mut state = self.ui_state.lock().await;
+        if state.settings_selected < Setting::ALL.len() - 1 {
+            state.settings_selected += 1;
+        }
+    }
+
+    pub async fn get_selected_setting(&self) -> Setting {
+        Setting::ALL[self.ui_state.lock().await.settings_selected]
+    }
+
+    pub async fn is_editing_setting(&self) -> bool {
+        self.ui_state.lock().await.settings_edit.is_some()
+    }
+
+    pub async fn start_setting_edit(&self, text: String) {
+        self.ui_state.lock().await.settings_edit = Some(text);
+    }
+
+    pub async fn setting_edit_push(&self, c: char) {
+        if let Some(text) = self.ui_state.lock().await.settings_edit.as_mut() {
+            text.push(c);
+        }
+    }
+
+    pub async fn setting_edit_backspace(&self) {
+        if let Some(text) = self.ui_state.lock().await.settings_edit.as_mut() {
+            text.pop();
+        }
+    }
+
+    /// End text editing, returning the typed text
+    pub async fn take_setting_edit(&self) -> Option<String> {
+        self.ui_state.lock().await.settings_edit.take()
+    }
+
+    // ========================================================================
+    // Seek Prompt
+    // ========================================================================
+
+    pub async fn open_seek_prompt(&self) {
+        self.ui_state.lock().await.seek_prompt = Some(String::new());
+    }
+
+    pub async fn is_seek_prompt_open(&self) -> bool {
+        self.ui_state.lock().await.seek_prompt.is_some()
+    }
+
+    pub async fn seek_prompt_push(&self, c: char) {
+        if let Some(text) = self.ui_state.lock().await.seek_prompt.as_mut() {
+            text.push(c);
+        }
+    }
+
+    pub async fn seek_prompt_backspace(&self) {
+        if let Some(text) = self.ui_state.lock().await.seek_prompt.as_mut() {
+            text.pop();
+        }
+    }
+
+    /// Close the prompt, returning the typed text
+    pub async fn take_seek_prompt(&self) -> Option<String> {
+        self.ui_state.lock().await.seek_prompt.take()
+    }
+
+    // ========================================================================
+    // Queue Insert Prompt
+    // ========================================================================
+
+    pub async fn open_queue_insert_prompt(&self, track: SearchTrack) {
+        *self.queue_insert_track.lock().await = Some(track);
+        self.ui_state.lock().await.queue_insert_prompt = Some(String::new());
+    }
+
+    pub async fn is_queue_insert_prompt_open(&self) -> bool {
+        self.ui_state.lock().await.queue_insert_prompt.is_some()
+    }
+
+    pub async fn queue_insert_prompt_push(&self, c: char) {
+        if let Some(text) = self.ui_state.lock().await.queue_insert_prompt.as_mut() {
+            text.push(c);
+        }
+    }
+
+    pub async fn queue_insert_prompt_backspace(&self) {
+        if let Some(text) = self.ui_state.lock().await.queue_insert_prompt.as_mut() {
+            text.pop();
+        }
+    }
+
+    /// Close the prompt, returning the typed text and the track to insert
+    pub async fn take_queue_insert_prompt(&self) -> Option<(String, SearchTrack)> {
+        let input = self.ui_state.lock().await.queue_insert_prompt.take()?;
+        let track = self.queue_insert_track.lock().await.take()?;
+        Some((input, track))
+    }
+
+    // ========================================================================
+    // Playlist Prompt
+    // ========================================================================
+
+    pub async fn open_playlist_prompt(&self, kind: PlaylistPromptKind, input: String) {
+        self.ui_state.lock().await.playlist_prompt = Some(PlaylistPrompt { kind, input });
+    }
+
+    pub async fn is_playlist_prompt_open(&self) -> bool {
+        self.ui_state.lock().await.playlist_prompt.is_some()
+    }
+
+    pub async fn playlist_prompt_push(&self, c: char) {
+        if let Some(prompt) = self.ui_state.lock().await.playlist_prompt.as_mut() {
+            prompt.input.push(c);
+        }
+    }
+
+    pub async fn playlist_prompt_backspace(&self) {
+        if let Some(prompt) = self.ui_state.lock().await.playlist_prompt.as_mut() {
+            prompt.input.pop();
+        }
+    }
+
+    /// Switch a new playlist between private, public and collaborative, or an
+    /// import between creating a playlist and replacing the target's tracks
+    pub async fn cycle_playlist_prompt_option(&self) {
+        match self.ui_state.lock().await.playlist_prompt.as_mut().map(|prompt| &mut prompt.kind) {
+            Some(PlaylistPromptKind::Create { visibility } | PlaylistPromptKind::ExportTop { visibility, .. }) => {
+                *visibility = visibility.next()
+            }
+            Some(PlaylistPromptKind::Import { target: Some(_), replace }) => *replace = !*replace,
+            _ => {}
+        }
+    }
+
+    pub async fn take_playlist_prompt(&self) -> Option<PlaylistPrompt> {
+        self.ui_state.lock().await.playlist_prompt.take()
+    }
+
     pub async fn show_help_popup(&self) {
         let mut state = self.ui_state.lock().await;
         state.show_help_popup = true;
@@ -335,6 +818,7 @@ impl AppModel {
         }
         // Use the best matching category as the initial section
         let initial_section = results.best_match;
+        state.filter = None;
         state.view = ContentView::SearchResults {
             results,
             section: initial_section,
@@ -346,6 +830,33 @@ impl AppModel {
         state.is_loading = false;
     }
 
+    /// The query and category of the open search results
+    pub async fn get_search_category(&self) -> Option<(String, SearchResultSection)> {
+        let state = self.content_state.lock().await;
+        match &state.view {
+            ContentView::SearchResults { results, section, .. } => Some((results.query.clone(), *section)),
+            _ => None,
+        }
+    }
+
+    /// Open every result of one category on top of the search results
+    pub async fn set_search_category(&self, results: SearchResults, section: SearchResultSection, paging: ListPaging) {
+        let mut state = self.content_state.lock().await;
+
+        if !matches!(state.view, ContentView::Empty) {
+            let previous_view = state.view.clone();
+            state.navigation_stack.push(previous_view);
+        }
+        state.filter = None;
+        state.view = ContentView::SearchCategory {
+            results,
+            section,
+            selected_index: 0,
+            paging,
+        };
+        state.is_loading = false;
+    }
+
     pub async fn set_album_detail(&self, detail: AlbumDetail) {
         let mut state = self.content_state.lock().await;
 
@@ -353,6 +864,7 @@ impl AppModel {
             let previous_view = state.view.clone();
             state.navigation_stack.push(previous_view);
         }
+        state.filter = None;
         state.view = ContentView::AlbumDetail {
             detail,
             selected_index: 0,
@@ -367,6 +879,7 @@ impl AppModel {
             let previous_view = state.view.clone();
             state.navigation_stack.push(previous_view);
         }
+        state.filter = None;
         state.view = ContentView::PlaylistDetail {
             detail,
             selected_index: 0,
@@ -381,106 +894,325 @@ impl AppModel {
             let previous_view = state.view.clone();
             state.navigation_stack.push(previous_view);
         }
+        state.filter = None;
         state.view = ContentView::ArtistDetail {
             detail,
             section: ArtistDetailSection::TopTracks,
-            track_index: 0,
-            album_index: 0,
+            selected: [0; ArtistDetailSection::ALL.len()],
         };
         state.is_loading = false;
     }
 
-    pub async fn set_liked_songs(&self, tracks: Vec<SearchTrack>) {
+    pub async fn set_liked_songs(&self, tracks: Vec<SearchTrack>, paging: ListPaging) {
         let mut state = self.content_state.lock().await;
 
         state.navigation_stack.clear();
+        state.filter = None;
         state.view = ContentView::LikedSongs {
             tracks,
             selected_index: 0,
+            paging,
         };
         state.is_loading = false;
     }
 
-    pub async fn set_saved_albums(&self, albums: Vec<SearchAlbum>) {
+    pub async fn set_saved_albums(&self, albums: Vec<SearchAlbum>, paging: ListPaging) {
         let mut state = self.content_state.lock().await;
 
         state.navigation_stack.clear();
+        state.filter = None;
         state.view = ContentView::SavedAlbums {
             albums,
             selected_index: 0,
+            paging,
         };
         state.is_loading = false;
     }
 
-    pub async fn set_followed_artists(&self, artists: Vec<SearchArtist>) {
+    pub async fn set_followed_artists(&self, artists: Vec<SearchArtist>, paging: ListPaging) {
         let mut state = self.content_state.lock().await;
 
         state.navigation_stack.clear();
+        state.filter = None;
         state.view = ContentView::FollowedArtists {
             artists,
             selected_index: 0,
+            paging,
         };
         state.is_loading = false;
     }
 
-    pub async fn set_recently_played(&self, tracks: Vec<SearchTrack>) {
+    /// Open the home page, starting over like the library views
+    pub async fn set_home(&self, home: HomePage) {
         let mut state = self.content_state.lock().await;
 
         state.navigation_stack.clear();
-        state.view = ContentView::RecentlyPlayed {
-            tracks,
-            selected_index: 0,
+        state.filter = None;
+        state.view = ContentView::Home {
+            home,
+            section: HomeSection::default(),
+            selected: [0; HomeSection::ALL.len()],
         };
         state.is_loading = false;
     }
 
-    pub async fn set_queue(&self, currently_playing: Option<SearchTrack>, queue: Vec<SearchTrack>) {
+    /// Open "Your Top", or show another range in it while it is open,
+    /// keeping the tab
+    pub async fn set_top(&self, top: TopItems) {
         let mut state = self.content_state.lock().await;
 
-        if !matches!(state.view, ContentView::Empty | ContentView::Queue { .. }) {
-            let previous_view = state.view.clone();
-            state.navigation_stack.push(previous_view);
-        }
-        state.view = ContentView::Queue {
-            currently_playing,
-            queue,
-            selected_index: 0,
+        let tab = match &state.view {
+            ContentView::Top { tab, .. } => *tab,
+            _ => {
+                state.navigation_stack.clear();
+                TopTab::default()
+            }
+        };
+        state.filter = None;
+        state.view = ContentView::Top {
+            top,
+            tab,
+            track_index: 0,
+            artist_index: 0,
         };
         state.is_loading = false;
     }
 
-    pub async fn is_queue_view_visible(&self) -> bool {
-        let state = self.content_state.lock().await;
-        matches!(state.view, ContentView::Queue { .. })
+    /// Range shown by the open "Your Top" view
+    pub async fn get_top_range(&self) -> Option<TopRange> {
+        match &self.content_state.lock().await.view {
+            ContentView::Top { top, .. } => Some(top.range),
+            _ => None,
+        }
     }
 
-    pub async fn update_queue_if_visible(&self, currently_playing: Option<SearchTrack>, queue: Vec<SearchTrack>) {
-        let mut state = self.content_state.lock().await;
-        if let ContentView::Queue { selected_index, .. } = &state.view {
-            let current_index = *selected_index;
-            // Preserve selection but clamp to new queue size
-            let new_index = current_index.min(queue.len().saturating_sub(1));
-            state.view = ContentView::Queue {
+    /// Name and track URIs for a playlist of the top tracks, when their
+    /// tab of "Your Top" is open
+    pub async fn get_top_tracks_export(&self) -> Option<(String, Vec<String>)> {
+        match &self.content_state.lock().await.view {
+            ContentView::Top { top, tab: TopTab::Tracks, .. } if !top.tracks.is_empty() => {
+                let name = format!("Your Top Tracks ({})", top.range.label());
+                Some((name, top.tracks.iter().map(|t| t.uri.clone()).collect()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Open the home page if nothing else is open
+    pub async fn set_home_if_empty(&self, home: HomePage) {
+        if matches!(self.content_state.lock().await.view, ContentView::Empty) {
+            self.set_home(home).await;
+        }
+    }
+
+    pub async fn set_category_playlists(&self, category: Category, playlists: Vec<SearchPlaylist>, paging: ListPaging) {
+        let mut state = self.content_state.lock().await;
+
+        if !matches!(state.view, ContentView::Empty) {
+            let previous_view = state.view.clone();
+            state.navigation_stack.push(previous_view);
+        }
+        state.filter = None;
+        state.view = ContentView::CategoryPlaylists {
+            category,
+            playlists,
+            selected_index: 0,
+            paging,
+        };
+        state.is_loading = false;
+    }
+
+    /// Add playlists loaded from `offset` to the open category, unless
+    /// another view or the category afresh was opened while they loaded
+    pub async fn append_category_playlists(
+        &self,
+        category_id: &str,
+        offset: usize,
+        mut new_playlists: Vec<SearchPlaylist>,
+        has_more: bool,
+    ) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::CategoryPlaylists { category, playlists, paging, .. } = &mut state.view
+            && category.id == category_id
+            && playlists.len() == offset
+        {
+            playlists.append(&mut new_playlists);
+            paging.has_more = has_more;
+            paging.loading_more = false;
+        }
+    }
+
+    pub async fn set_saved_shows(&self, shows: Vec<Show>, paging: ListPaging) {
+        let mut state = self.content_state.lock().await;
+
+        state.navigation_stack.clear();
+        state.filter = None;
+        state.view = ContentView::SavedShows {
+            shows,
+            selected_index: 0,
+            paging,
+        };
+        state.is_loading = false;
+    }
+
+    pub async fn set_show_detail(&self, detail: ShowDetail, paging: ListPaging) {
+        let mut state = self.content_state.lock().await;
+
+        if !matches!(state.view, ContentView::Empty) {
+            let previous_view = state.view.clone();
+            state.navigation_stack.push(previous_view);
+        }
+        state.filter = None;
+        state.view = ContentView::ShowDetail {
+            detail,
+            selected_index: 0,
+            paging,
+        };
+        state.is_loading = false;
+    }
+
+    pub async fn set_recently_played(&self, tracks: Vec<SearchTrack>) {
+        let mut state = self.content_state.lock().await;
+
+        state.navigation_stack.clear();
+        state.filter = None;
+        state.view = ContentView::RecentlyPlayed {
+            tracks,
+            selected_index: 0,
+        };
+        state.is_loading = false;
+    }
+
+    /// Open the review screen for a matched track list
+    pub async fn show_import_review(&self, review: ImportReview) {
+        let mut state = self.content_state.lock().await;
+
+        if !matches!(state.view, ContentView::Empty) {
+            let previous_view = state.view.clone();
+            state.navigation_stack.push(previous_view);
+        }
+        state.filter = None;
+        state.view = ContentView::ImportReview {
+            review,
+            selected_index: 0,
+        };
+        state.is_loading = false;
+    }
+
+    pub async fn is_import_review_open(&self) -> bool {
+        matches!(self.content_state.lock().await.view, ContentView::ImportReview { .. })
+    }
+
+    /// Close the review screen, returning it with the user's choices
+    pub async fn take_import_review(&self) -> Option<ImportReview> {
+        let review = match &self.content_state.lock().await.view {
+            ContentView::ImportReview { review, .. } => review.clone(),
+            _ => return None,
+        };
+        self.navigate_back().await;
+        Some(review)
+    }
+
+    /// Open the confirmation screen for a library restore
+    pub async fn show_restore_plan(&self, plan: RestorePlan) {
+        let mut state = self.content_state.lock().await;
+
+        if !matches!(state.view, ContentView::Empty) {
+            let previous_view = state.view.clone();
+            state.navigation_stack.push(previous_view);
+        }
+        state.filter = None;
+        state.view = ContentView::LibraryRestore {
+            plan,
+            selected_index: 0,
+        };
+        state.is_loading = false;
+    }
+
+    pub async fn is_restore_plan_open(&self) -> bool {
+        matches!(self.content_state.lock().await.view, ContentView::LibraryRestore { .. })
+    }
+
+    /// Close the restore screen, returning the plan to apply
+    pub async fn take_restore_plan(&self) -> Option<RestorePlan> {
+        let plan = match &self.content_state.lock().await.view {
+            ContentView::LibraryRestore { plan, .. } => plan.clone(),
+            _ => return None,
+        };
+        self.navigate_back().await;
+        Some(plan)
+    }
+
+    /// Show the queue: local entries first, then Spotify's upcoming tracks
+    pub async fn set_queue(&self, currently_playing: Option<SearchTrack>, queue: Vec<SearchTrack>) {
+        let (queue, local_len) = self.local_queue.lock().await.merge(queue);
+        let mut state = self.content_state.lock().await;
+
+        if !matches!(state.view, ContentView::Empty | ContentView::Queue { .. }) {
+            let previous_view = state.view.clone();
+            state.navigation_stack.push(previous_view);
+        }
+        state.filter = None;
+        state.view = ContentView::Queue {
+            currently_playing,
+            queue,
+            local_len,
+            selected_index: 0,
+        };
+        state.is_loading = false;
+    }
+
+    pub async fn is_queue_view_visible(&self) -> bool {
+        let state = self.content_state.lock().await;
+        matches!(state.view, ContentView::Queue { .. })
+    }
+
+    pub async fn update_queue_if_visible(&self, currently_playing: Option<SearchTrack>, queue: Vec<SearchTrack>) {
+        let (queue, local_len) = self.local_queue.lock().await.merge(queue);
+        let mut state = self.content_state.lock().await;
+        if let ContentView::Queue { selected_index, .. } = &state.view {
+            let current_index = *selected_index;
+            // Preserve selection but clamp to new queue size
+            let new_index = current_index.min(queue.len().saturating_sub(1));
+            state.view = ContentView::Queue {
                 currently_playing,
                 queue,
+                local_len,
                 selected_index: new_index,
             };
         }
     }
 
-    pub async fn remove_from_queue_view(&self, index: usize) -> Option<String> {
+    /// Remove entry `index` of the queue view. Tracks in the local queue are
+    /// dropped outright; anything already in Spotify's queue can't be removed
+    /// there, so it goes to the skip list instead.
+    pub async fn remove_from_queue(&self, index: usize) {
+        if index < self.get_queue_local_len().await {
+            let mut local_queue = self.local_queue.lock().await;
+            let submitted = local_queue.is_submitted(index);
+            if let Some(track) = local_queue.remove(index)
+                && submitted
+            {
+                self.add_to_queue_skip_list(track.uri).await;
+            }
+            drop(local_queue);
+            self.sync_queue_view().await;
+            return;
+        }
+
         let mut state = self.content_state.lock().await;
-        if let ContentView::Queue { queue, selected_index, .. } = &mut state.view {
-            if index < queue.len() {
-                let removed = queue.remove(index);
-                // Adjust selected index if needed
-                if *selected_index >= queue.len() && !queue.is_empty() {
-                    *selected_index = queue.len() - 1;
-                }
-                return Some(removed.uri);
+        if let ContentView::Queue { queue, selected_index, .. } = &mut state.view
+            && index < queue.len()
+        {
+            let removed = queue.remove(index);
+            // Adjust selected index if needed
+            if *selected_index >= queue.len() && !queue.is_empty() {
+                *selected_index = queue.len() - 1;
             }
+            drop(state);
+            self.add_to_queue_skip_list(removed.uri).await;
+            tracing::info!("Track added to skip list (will be auto-skipped)");
         }
-        None
     }
 
     pub async fn add_to_queue_skip_list(&self, uri: String) {
@@ -507,6 +1239,96 @@ impl AppModel {
         }
     }
 
+    // ========================================================================
+    // Local Queue
+    // ========================================================================
+
+    pub async fn queue_locally(&self, track: SearchTrack) {
+        self.local_queue.lock().await.push(track);
+        self.sync_queue_view().await;
+    }
+
+    pub async fn insert_into_local_queue(&self, index: usize, track: SearchTrack) {
+        self.local_queue.lock().await.insert(index, track);
+        self.sync_queue_view().await;
+    }
+
+    /// Move entry `index` of the queue view one place, keeping it selected.
+    /// Returns false if it is not a local entry that can move that way.
+    pub async fn move_queue_entry(&self, index: usize, up: bool) -> bool {
+        if !self.local_queue.lock().await.move_entry(index, up) {
+            return false;
+        }
+        self.sync_queue_view().await;
+        let mut state = self.content_state.lock().await;
+        if let ContentView::Queue { selected_index, .. } = &mut state.view {
+            *selected_index = if up { index - 1 } else { index + 1 };
+        }
+        true
+    }
+
+    /// Empty the local queue. Returns false if there was nothing in it.
+    pub async fn clear_local_queue(&self) -> bool {
+        let mut local_queue = self.local_queue.lock().await;
+        if local_queue.is_empty() {
+            return false;
+        }
+        if let Some(track) = local_queue.clear() {
+            self.add_to_queue_skip_list(track.uri).await;
+        }
+        drop(local_queue);
+        self.sync_queue_view().await;
+        true
+    }
+
+    /// Next local track to hand to the player, if none is handed over yet
+    pub async fn submit_next_local_track(&self) -> Option<SearchTrack> {
+        self.local_queue.lock().await.submit_next()
+    }
+
+    pub async fn cancel_local_submit(&self) {
+        self.local_queue.lock().await.cancel_submit();
+    }
+
+    pub async fn local_queue_track_started(&self, uri: &str) {
+        self.local_queue.lock().await.track_started(uri);
+    }
+
+    /// Take the local tracks the player hasn't been given yet
+    pub async fn take_pending_local_queue(&self) -> Vec<SearchTrack> {
+        let tracks = self.local_queue.lock().await.take_pending();
+        self.sync_queue_view().await;
+        tracks
+    }
+
+    /// Return tracks taken with `take_pending_local_queue` that could not be
+    /// handed over, keeping their order
+    pub async fn restore_pending_local_queue(&self, tracks: Vec<SearchTrack>) {
+        self.local_queue.lock().await.restore_pending(tracks);
+        self.sync_queue_view().await;
+    }
+
+    /// Number of local entries at the top of the queue view (0 if not shown)
+    pub async fn get_queue_local_len(&self) -> usize {
+        match &self.content_state.lock().await.view {
+            ContentView::Queue { local_len, .. } => *local_len,
+            _ => 0,
+        }
+    }
+
+    /// Rebuild the local part of a visible queue view after a local edit
+    async fn sync_queue_view(&self) {
+        let local = self.local_queue.lock().await.tracks();
+        let mut state = self.content_state.lock().await;
+        if let ContentView::Queue { queue, local_len, selected_index, .. } = &mut state.view {
+            let upcoming = queue.split_off((*local_len).min(queue.len()));
+            *local_len = local.len();
+            *queue = local;
+            queue.extend(upcoming);
+            *selected_index = (*selected_index).min(queue.len().saturating_sub(1));
+        }
+    }
+
     pub async fn set_content_loading(&self, loading: bool) {
         let mut state = self.content_state.lock().await;
         state.is_loading = loading;
@@ -514,6 +1336,7 @@ impl AppModel {
 
     pub async fn navigate_back(&self) -> bool {
         let mut state = self.content_state.lock().await;
+        state.filter = None;
         if let Some(previous_view) = state.navigation_stack.pop() {
             // Restore the previous view
             state.view = previous_view;
@@ -525,22 +1348,69 @@ impl AppModel {
         }
     }
 
-    pub async fn navigate_search_section(&self, forward: bool) {
+    /// Left/right in the main content: switch the open view's section, tab
+    /// or choice. Only the method for the open view does anything.
+    pub async fn navigate_category(&self, forward: bool) {
+        self.navigate_search_section(forward).await;
+        self.navigate_home_section(forward).await;
+        self.switch_top_tab().await;
+        self.navigate_artist_section(forward).await;
+        self.cycle_import_choice(forward).await;
+    }
+
+    /// Move to the next or previous section of the home page
+    pub async fn navigate_home_section(&self, forward: bool) {
         let mut state = self.content_state.lock().await;
-        match &mut state.view {
-            ContentView::SearchResults { section, .. } => {
+        if let ContentView::Home { section, .. } = &mut state.view {
+            *section = if forward { section.next() } else { section.prev() };
+        }
+    }
+
+    /// Switch "Your Top" between its tracks and artists
+    pub async fn switch_top_tab(&self) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::Top { tab, .. } = &mut state.view {
+            *tab = tab.next();
+        }
+    }
+
+    /// Move to the next or previous section of the open artist. Empty
+    /// sections are skipped; top tracks are always there to come back to.
+    pub async fn navigate_artist_section(&self, forward: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::ArtistDetail { detail, section, .. } = &mut state.view {
+            loop {
                 *section = if forward { section.next() } else { section.prev() };
+                if *section == ArtistDetailSection::TopTracks || detail.len(*section) > 0 {
+                    break;
+                }
             }
-            ContentView::ArtistDetail { section, .. } => {
-                // Artist detail only has 2 sections, so forward/backward both toggle
-                *section = section.next();
-            }
-            _ => {}
+        }
+    }
+
+    /// Step the selected import review line through its matches and "skip"
+    pub async fn cycle_import_choice(&self, forward: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::ImportReview { review, selected_index } = &mut state.view
+            && let Some(entry) = review.entries.get_mut(*selected_index)
+        {
+            entry.cycle_choice(forward);
+        }
+    }
+
+    pub async fn navigate_search_section(&self, forward: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::SearchResults { section, .. } = &mut state.view {
+            *section = if forward { section.next() } else { section.prev() };
         }
     }
 
     pub async fn content_move_up(&self) {
         let mut state = self.content_state.lock().await;
+        if let Some(rows) = state.filtered_rows() {
+            Self::select_filtered_row(&mut state.view, &rows, false, false);
+            return;
+        }
         match &mut state.view {
             ContentView::SearchResults {
                 section,
@@ -560,7 +1430,8 @@ impl AppModel {
                     *idx -= 1;
                 }
             }
-            ContentView::AlbumDetail { selected_index, .. } => {
+            ContentView::SearchCategory { selected_index, .. }
+            | ContentView::AlbumDetail { selected_index, .. } => {
                 if *selected_index > 0 {
                     *selected_index -= 1;
                 }
@@ -570,16 +1441,8 @@ impl AppModel {
                     *selected_index -= 1;
                 }
             }
-            ContentView::ArtistDetail {
-                section,
-                track_index,
-                album_index,
-                ..
-            } => {
-                let idx = match section {
-                    ArtistDetailSection::TopTracks => track_index,
-                    ArtistDetailSection::Albums => album_index,
-                };
+            ContentView::ArtistDetail { section, selected, .. } => {
+                let idx = &mut selected[section.index()];
                 if *idx > 0 {
                     *idx -= 1;
                 }
@@ -600,6 +1463,34 @@ impl AppModel {
                     *selected_index -= 1;
                 }
             }
+            ContentView::SavedShows { selected_index, .. }
+            | ContentView::ShowDetail { selected_index, .. }
+            | ContentView::CategoryPlaylists { selected_index, .. } => {
+                if *selected_index > 0 {
+                    *selected_index -= 1;
+                }
+            }
+            ContentView::Home { section, selected, .. } => {
+                let idx = &mut selected[section.index()];
+                if *idx > 0 {
+                    *idx -= 1;
+                }
+            }
+            ContentView::Top { tab, track_index, artist_index, .. } => {
+                let idx = match tab {
+                    TopTab::Tracks => track_index,
+                    TopTab::Artists => artist_index,
+                };
+                if *idx > 0 {
+                    *idx -= 1;
+                }
+            }
+            ContentView::ImportReview { selected_index, .. }
+            | ContentView::LibraryRestore { selected_index, .. } => {
+                if *selected_index > 0 {
+                    *selected_index -= 1;
+                }
+            }
             ContentView::Queue { queue, selected_index, .. } => {
                 if *selected_index > 0 {
                     *selected_index -= 1;
@@ -612,6 +1503,10 @@ impl AppModel {
 
     pub async fn content_move_down(&self) {
         let mut state = self.content_state.lock().await;
+        if let Some(rows) = state.filtered_rows() {
+            Self::select_filtered_row(&mut state.view, &rows, true, false);
+            return;
+        }
         match &mut state.view {
             ContentView::SearchResults {
                 results,
@@ -631,6 +1526,11 @@ impl AppModel {
                     *idx += 1;
                 }
             }
+            ContentView::SearchCategory { results, section, selected_index, .. } => {
+                if *selected_index < results.len(*section).saturating_sub(1) {
+                    *selected_index += 1;
+                }
+            }
             ContentView::AlbumDetail { detail, selected_index } => {
                 if *selected_index < detail.tracks.len().saturating_sub(1) {
                     *selected_index += 1;
@@ -641,21 +1541,13 @@ impl AppModel {
                     *selected_index += 1;
                 }
             }
-            ContentView::ArtistDetail {
-                detail,
-                section,
-                track_index,
-                album_index,
-            } => {
-                let (idx, max) = match section {
-                    ArtistDetailSection::TopTracks => (track_index, detail.top_tracks.len()),
-                    ArtistDetailSection::Albums => (album_index, detail.albums.len()),
-                };
-                if *idx < max.saturating_sub(1) {
+            ContentView::ArtistDetail { detail, section, selected } => {
+                let idx = &mut selected[section.index()];
+                if *idx < detail.len(*section).saturating_sub(1) {
                     *idx += 1;
                 }
             }
-            ContentView::LikedSongs { tracks, selected_index } => {
+            ContentView::LikedSongs { tracks, selected_index, .. } => {
                 if *selected_index < tracks.len().saturating_sub(1) {
                     *selected_index += 1;
                 }
@@ -665,16 +1557,56 @@ impl AppModel {
                     *selected_index += 1;
                 }
             }
-            ContentView::SavedAlbums { albums, selected_index } => {
+            ContentView::SavedAlbums { albums, selected_index, .. } => {
                 if *selected_index < albums.len().saturating_sub(1) {
                     *selected_index += 1;
                 }
             }
-            ContentView::FollowedArtists { artists, selected_index } => {
+            ContentView::FollowedArtists { artists, selected_index, .. } => {
                 if *selected_index < artists.len().saturating_sub(1) {
                     *selected_index += 1;
                 }
             }
+            ContentView::SavedShows { shows, selected_index, .. } => {
+                if *selected_index < shows.len().saturating_sub(1) {
+                    *selected_index += 1;
+                }
+            }
+            ContentView::ShowDetail { detail, selected_index, .. } => {
+                if *selected_index < detail.episodes.len().saturating_sub(1) {
+                    *selected_index += 1;
+                }
+            }
+            ContentView::CategoryPlaylists { playlists, selected_index, .. } => {
+                if *selected_index < playlists.len().saturating_sub(1) {
+                    *selected_index += 1;
+                }
+            }
+            ContentView::Home { home, section, selected } => {
+                let idx = &mut selected[section.index()];
+                if *idx < home.len(*section).saturating_sub(1) {
+                    *idx += 1;
+                }
+            }
+            ContentView::Top { top, tab, track_index, artist_index } => {
+                let (idx, len) = match tab {
+                    TopTab::Tracks => (track_index, top.tracks.len()),
+                    TopTab::Artists => (artist_index, top.artists.len()),
+                };
+                if *idx < len.saturating_sub(1) {
+                    *idx += 1;
+                }
+            }
+            ContentView::ImportReview { review, selected_index } => {
+                if *selected_index < review.entries.len().saturating_sub(1) {
+                    *selected_index += 1;
+                }
+            }
+            ContentView::LibraryRestore { plan, selected_index } => {
+                if *selected_index < plan.lines().len().saturating_sub(1) {
+                    *selected_index += 1;
+                }
+            }
             ContentView::Queue { queue, selected_index, .. } => {
                 if *selected_index < queue.len().saturating_sub(1) {
                     *selected_index += 1;
@@ -684,6 +1616,165 @@ impl AppModel {
         }
     }
 
+    /// Make one of the content lists current (only artist detail has two:
+    /// 0 is top tracks, 1 is the section beside them)
+    pub async fn content_focus_list(&self, list: usize) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::ArtistDetail { detail, section, .. } = &mut state.view {
+            if list == 0 {
+                *section = ArtistDetailSection::TopTracks;
+            } else if *section == ArtistDetailSection::TopTracks {
+                *section = detail.side_section();
+            }
+        }
+    }
+
+    /// Select an item in one of the content lists (mouse click).
+    /// Returns false if the index is past the end of the list.
+    pub async fn content_select(&self, list: usize, index: usize) -> bool {
+        self.content_focus_list(list).await;
+        let mut state = self.content_state.lock().await;
+        // Rows shown by the filter stand for their place in the whole list
+        let index = match state.filtered_rows() {
+            Some(rows) => match rows.get(index) {
+                Some(&row) => row,
+                None => return false,
+            },
+            None => index,
+        };
+        let (selected, len) = match &mut state.view {
+            ContentView::SearchResults {
+                results,
+                section,
+                track_index,
+                album_index,
+                artist_index,
+                playlist_index,
+            } => match section {
+                SearchResultSection::Tracks => (track_index, results.tracks.len()),
+                SearchResultSection::Albums => (album_index, results.albums.len()),
+                SearchResultSection::Artists => (artist_index, results.artists.len()),
+                SearchResultSection::Playlists => (playlist_index, results.playlists.len()),
+            },
+            ContentView::SearchCategory { results, section, selected_index, .. } => {
+                (selected_index, results.len(*section))
+            }
+            ContentView::AlbumDetail { detail, selected_index } => (selected_index, detail.tracks.len()),
+            ContentView::PlaylistDetail { detail, selected_index } => (selected_index, detail.tracks.len()),
+            ContentView::ArtistDetail { detail, section, selected } => (&mut selected[section.index()], detail.len(*section)),
+            ContentView::LikedSongs { tracks, selected_index, .. }
+            | ContentView::RecentlyPlayed { tracks, selected_index } => (selected_index, tracks.len()),
+            ContentView::SavedAlbums { albums, selected_index, .. } => (selected_index, albums.len()),
+            ContentView::FollowedArtists { artists, selected_index, .. } => (selected_index, artists.len()),
+            ContentView::SavedShows { shows, selected_index, .. } => (selected_index, shows.len()),
+            ContentView::ShowDetail { detail, selected_index, .. } => (selected_index, detail.episodes.len()),
+            ContentView::CategoryPlaylists { playlists, selected_index, .. } => (selected_index, playlists.len()),
+            ContentView::Home { home, section, selected } => (&mut selected[section.index()], home.len(*section)),
+            ContentView::Top { top, tab, track_index, artist_index } => match tab {
+                TopTab::Tracks => (track_index, top.tracks.len()),
+                TopTab::Artists => (artist_index, top.artists.len()),
+            },
+            ContentView::ImportReview { review, selected_index } => (selected_index, review.entries.len()),
+            ContentView::LibraryRestore { plan, selected_index } => (selected_index, plan.lines().len()),
+            ContentView::Queue { queue, selected_index, .. } => (selected_index, queue.len()),
+            ContentView::Empty => return false,
+        };
+        if index >= len {
+            return false;
+        }
+        *selected = index;
+        true
+    }
+
+    /// Open the `/` filter of the focused list, keeping its query if it is
+    /// already filtered. Returns false if the view has no list to filter.
+    pub async fn open_list_filter(&self) -> bool {
+        let mut state = self.content_state.lock().await;
+        if state.view.filter_fields().is_none() {
+            return false;
+        }
+        state.filter.get_or_insert_with(ListFilter::default).editing = true;
+        true
+    }
+
+    pub async fn get_list_filter(&self) -> Option<ListFilter> {
+        self.content_state.lock().await.filter.clone()
+    }
+
+    pub async fn list_filter_push(&self, c: char) {
+        let mut state = self.content_state.lock().await;
+        if let Some(filter) = &mut state.filter {
+            filter.query.push(c);
+            Self::snap_to_filtered_row(&mut state);
+        }
+    }
+
+    /// Delete the last character of the filter, closing it once empty
+    pub async fn list_filter_backspace(&self) {
+        let mut state = self.content_state.lock().await;
+        let Some(filter) = &mut state.filter else {
+            return;
+        };
+        if filter.query.pop().is_none() {
+            state.filter = None;
+            return;
+        }
+        Self::snap_to_filtered_row(&mut state);
+    }
+
+    /// Stop typing into the filter, keeping the list filtered (Enter)
+    pub async fn finish_list_filter(&self) {
+        let mut state = self.content_state.lock().await;
+        if let Some(filter) = &mut state.filter {
+            if filter.query.trim().is_empty() {
+                state.filter = None;
+            } else {
+                filter.editing = false;
+            }
+        }
+    }
+
+    /// Drop the filter and show the whole list again, keeping the selection
+    pub async fn close_list_filter(&self) {
+        self.content_state.lock().await.filter = None;
+    }
+
+    /// Select the next or previous row kept by the filter, wrapping around
+    pub async fn jump_to_filter_match(&self, forward: bool) {
+        let mut state = self.content_state.lock().await;
+        if let Some(rows) = state.filtered_rows() {
+            Self::select_filtered_row(&mut state.view, &rows, forward, true);
+        }
+    }
+
+    fn select_filtered_row(view: &mut ContentView, rows: &[usize], forward: bool, wrap: bool) {
+        let Some(selected) = view.selected_index_mut() else {
+            return;
+        };
+        let target = if forward {
+            rows.iter().find(|&&row| row > *selected).or(if wrap { rows.first() } else { None })
+        } else {
+            rows.iter().rev().find(|&&row| row < *selected).or(if wrap { rows.last() } else { None })
+        };
+        if let Some(&row) = target {
+            *selected = row;
+        }
+    }
+
+    /// After the query changed, move the selection to the first match at or
+    /// after it unless it still matches
+    fn snap_to_filtered_row(state: &mut ContentState) {
+        let Some(rows) = state.filtered_rows() else {
+            return;
+        };
+        if let Some(selected) = state.view.selected_index_mut()
+            && !rows.contains(&*selected)
+            && let Some(&row) = rows.iter().find(|&&row| row > *selected).or(rows.first())
+        {
+            *selected = row;
+        }
+    }
+
     pub async fn get_selected_content_item(&self) -> Option<SelectedItem> {
         let state = self.content_state.lock().await;
         match &state.view {
@@ -694,20 +1785,18 @@ impl AppModel {
                 album_index,
                 artist_index,
                 playlist_index,
-            } => match section {
-                SearchResultSection::Tracks => results.tracks.get(*track_index).map(|t| {
-                    SelectedItem::Track { uri: t.uri.clone() }
-                }),
-                SearchResultSection::Albums => results.albums.get(*album_index).map(|a| {
-                    SelectedItem::Album { id: a.id.clone() }
-                }),
-                SearchResultSection::Artists => results.artists.get(*artist_index).map(|a| {
-                    SelectedItem::Artist { id: a.id.clone() }
-                }),
-                SearchResultSection::Playlists => results.playlists.get(*playlist_index).map(|p| {
-                    SelectedItem::Playlist { id: p.id.clone() }
-                }),
-            },
+            } => {
+                let index = match section {
+                    SearchResultSection::Tracks => track_index,
+                    SearchResultSection::Albums => album_index,
+                    SearchResultSection::Artists => artist_index,
+                    SearchResultSection::Playlists => playlist_index,
+                };
+                Self::search_result_item(results, *section, *index)
+            }
+            ContentView::SearchCategory { results, section, selected_index, .. } => {
+                Self::search_result_item(results, *section, *selected_index)
+            }
             ContentView::AlbumDetail { detail, selected_index } => {
                 detail.tracks.get(*selected_index).map(|t| SelectedItem::AlbumTrack {
                     album_uri: format!("spotify:album:{}", detail.id),
@@ -720,40 +1809,101 @@ impl AppModel {
                     track_uri: t.uri.clone(),
                 })
             }
-            ContentView::ArtistDetail {
-                detail,
-                section,
-                track_index,
-                album_index,
-            } => match section {
-                ArtistDetailSection::TopTracks => detail.top_tracks.get(*track_index).map(|t| {
-                    SelectedItem::Track { uri: t.uri.clone() }
-                }),
-                ArtistDetailSection::Albums => detail.albums.get(*album_index).map(|a| {
-                    SelectedItem::Album { id: a.id.clone() }
-                }),
-            },
-            ContentView::LikedSongs { tracks, selected_index } => {
-                tracks.get(*selected_index).map(|t| SelectedItem::Track { uri: t.uri.clone(), })
+            ContentView::ArtistDetail { detail, section, selected } => {
+                let index = selected[section.index()];
+                match section {
+                    ArtistDetailSection::TopTracks => Self::track_list_item(&detail.top_tracks, index),
+                    ArtistDetailSection::Related => {
+                        detail.related.get(index).map(|a| SelectedItem::Artist { id: a.id.clone() })
+                    }
+                    _ => detail
+                        .releases(*section)
+                        .and_then(|releases| releases.get(index))
+                        .map(|a| SelectedItem::Album { id: a.id.clone() }),
+                }
+            }
+            ContentView::LikedSongs { tracks, selected_index, .. } => {
+                tracks.get(*selected_index).map(|t| SelectedItem::LikedTrack { track_uri: t.uri.clone() })
             }
             ContentView::RecentlyPlayed { tracks, selected_index } => {
-                tracks.get(*selected_index).map(|t| SelectedItem::Track { uri: t.uri.clone() })
+                Self::track_list_item(tracks, *selected_index)
             }
-            ContentView::SavedAlbums { albums, selected_index } => {
+            ContentView::SavedAlbums { albums, selected_index, .. } => {
                 albums.get(*selected_index).map(|a| SelectedItem::Album { id: a.id.clone() })
             }
-            ContentView::FollowedArtists { artists, selected_index } => {
+            ContentView::FollowedArtists { artists, selected_index, .. } => {
                 artists.get(*selected_index).map(|a| SelectedItem::Artist { id: a.id.clone() })
             }
-            ContentView::Queue { queue, selected_index, .. } => {
-                queue.get(*selected_index).map(|t| SelectedItem::Track { uri: t.uri.clone() })
+            ContentView::SavedShows { shows, selected_index, .. } => {
+                shows.get(*selected_index).map(|s| SelectedItem::Show { id: s.id.clone() })
+            }
+            ContentView::ShowDetail { detail, selected_index, .. } => {
+                detail.episodes.get(*selected_index).map(|e| SelectedItem::Episode {
+                    show_uri: detail.show.uri.clone(),
+                    episode_uri: e.uri.clone(),
+                    position_ms: e.start_position_ms(),
+                })
+            }
+            ContentView::Home { home, section, selected } => {
+                let index = selected[section.index()];
+                match section {
+                    HomeSection::JumpBackIn => Self::track_list_item(&home.jump_back_in, index),
+                    HomeSection::TopTracks => Self::track_list_item(&home.top_tracks, index),
+                    HomeSection::NewReleases => {
+                        home.new_releases.get(index).map(|a| SelectedItem::Album { id: a.id.clone() })
+                    }
+                    HomeSection::TopArtists => {
+                        home.top_artists.get(index).map(|a| SelectedItem::Artist { id: a.id.clone() })
+                    }
+                    HomeSection::Categories => home.categories.get(index).map(|c| SelectedItem::Category {
+                        id: c.id.clone(),
+                        name: c.name.clone(),
+                    }),
+                }
+            }
+            ContentView::Top { top, tab, track_index, artist_index } => match tab {
+                TopTab::Tracks => Self::track_list_item(&top.tracks, *track_index),
+                TopTab::Artists => top.artists.get(*artist_index).map(|a| SelectedItem::Artist { id: a.id.clone() }),
+            },
+            ContentView::CategoryPlaylists { playlists, selected_index, .. } => {
+                playlists.get(*selected_index).map(|p| SelectedItem::Playlist { id: p.id.clone() })
             }
+            ContentView::Queue { queue, selected_index, .. } => Self::track_list_item(queue, *selected_index),
+            // Enter confirms the whole review or restore instead
+            ContentView::ImportReview { .. } | ContentView::LibraryRestore { .. } => None,
             ContentView::Empty => None,
         }
     }
 
+    fn search_result_item(results: &SearchResults, section: SearchResultSection, index: usize) -> Option<SelectedItem> {
+        match section {
+            SearchResultSection::Tracks => Self::track_list_item(&results.tracks, index),
+            SearchResultSection::Albums => results.albums.get(index).map(|a| SelectedItem::Album { id: a.id.clone() }),
+            SearchResultSection::Artists => {
+                results.artists.get(index).map(|a| SelectedItem::Artist { id: a.id.clone() })
+            }
+            SearchResultSection::Playlists => {
+                results.playlists.get(index).map(|p| SelectedItem::Playlist { id: p.id.clone() })
+            }
+        }
+    }
+
+    /// Select a track so that the whole list plays from it
+    fn track_list_item(tracks: &[SearchTrack], index: usize) -> Option<SelectedItem> {
+        (index < tracks.len()).then(|| SelectedItem::TrackList {
+            uris: tracks.iter().map(|t| t.uri.clone()).collect(),
+            index,
+        })
+    }
+
     const PAGINATION_THRESHOLD: usize = 10;
 
+    /// Whether the open list wants its next page: the selection is near the
+    /// end of what's loaded, or a `/` filter should see the rows not loaded yet
+    fn wants_next_page(state: &ContentState, selected_index: usize, loaded: usize) -> bool {
+        state.is_filtered() || selected_index + Self::PAGINATION_THRESHOLD >= loaded
+    }
+
     pub async fn should_load_more_playlist_tracks(&self) -> Option<(String, usize)> {
         let state = self.content_state.lock().await;
         if let ContentView::PlaylistDetail { detail, selected_index } = &state.view {
@@ -762,9 +1912,8 @@ impl AppModel {
                 return None;
             }
 
-            // Check if we're within threshold of the end
             let loaded_count = detail.tracks.len();
-            if *selected_index + Self::PAGINATION_THRESHOLD >= loaded_count {
+            if Self::wants_next_page(&state, *selected_index, loaded_count) {
                 return Some((detail.id.clone(), loaded_count));
             }
         }
@@ -787,6 +1936,241 @@ impl AppModel {
         }
     }
 
+    /// The next page of the open library list, once the selection is near
+    /// the end of what has been loaded
+    pub async fn should_load_more_library_items(&self) -> Option<LibraryPageRequest> {
+        let state = self.content_state.lock().await;
+        let (paging, loaded, selected_index) = match &state.view {
+            ContentView::LikedSongs { tracks, selected_index, paging } => (paging, tracks.len(), *selected_index),
+            ContentView::SavedAlbums { albums, selected_index, paging } => (paging, albums.len(), *selected_index),
+            ContentView::FollowedArtists { artists, selected_index, paging } => (paging, artists.len(), *selected_index),
+            ContentView::SavedShows { shows, selected_index, paging } => (paging, shows.len(), *selected_index),
+            ContentView::ShowDetail { detail, selected_index, paging } => (paging, detail.episodes.len(), *selected_index),
+            ContentView::CategoryPlaylists { playlists, selected_index, paging, .. } => {
+                (paging, playlists.len(), *selected_index)
+            }
+            _ => return None,
+        };
+        if paging.loading_more || !paging.has_more || !Self::wants_next_page(&state, selected_index, loaded) {
+            return None;
+        }
+
+        match &state.view {
+            ContentView::LikedSongs { .. } => Some(LibraryPageRequest::LikedSongs { offset: loaded }),
+            ContentView::SavedAlbums { .. } => Some(LibraryPageRequest::SavedAlbums { offset: loaded }),
+            ContentView::FollowedArtists { artists, .. } => {
+                artists.last().map(|artist| LibraryPageRequest::FollowedArtists { after: artist.id.clone() })
+            }
+            ContentView::SavedShows { .. } => Some(LibraryPageRequest::SavedShows { offset: loaded }),
+            ContentView::ShowDetail { detail, .. } => {
+                Some(LibraryPageRequest::ShowEpisodes { show_id: detail.show.id.clone(), offset: loaded })
+            }
+            ContentView::CategoryPlaylists { category, .. } => {
+                Some(LibraryPageRequest::CategoryPlaylists { category_id: category.id.clone(), offset: loaded })
+            }
+            _ => None,
+        }
+    }
+
+    fn library_paging_mut(view: &mut ContentView) -> Option<&mut ListPaging> {
+        match view {
+            ContentView::LikedSongs { paging, .. }
+            | ContentView::SavedAlbums { paging, .. }
+            | ContentView::FollowedArtists { paging, .. }
+            | ContentView::SavedShows { paging, .. }
+            | ContentView::ShowDetail { paging, .. }
+            | ContentView::CategoryPlaylists { paging, .. } => Some(paging),
+            _ => None,
+        }
+    }
+
+    pub async fn set_library_loading_more(&self, loading: bool) {
+        let mut state = self.content_state.lock().await;
+        if let Some(paging) = Self::library_paging_mut(&mut state.view) {
+            paging.loading_more = loading;
+        }
+    }
+
+    /// Add a page loaded from `offset`, unless the list was opened afresh
+    /// while it loaded and no longer ends there
+    pub async fn append_liked_songs(&self, offset: usize, mut new_tracks: Vec<SearchTrack>, has_more: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::LikedSongs { tracks, paging, .. } = &mut state.view
+            && tracks.len() == offset
+        {
+            tracks.append(&mut new_tracks);
+            paging.has_more = has_more;
+            paging.loading_more = false;
+        }
+    }
+
+    /// Add a page loaded from `offset`, like `append_liked_songs`
+    pub async fn append_saved_albums(&self, offset: usize, mut new_albums: Vec<SearchAlbum>, has_more: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::SavedAlbums { albums, paging, .. } = &mut state.view
+            && albums.len() == offset
+        {
+            albums.append(&mut new_albums);
+            paging.has_more = has_more;
+            paging.loading_more = false;
+        }
+    }
+
+    /// Add a page loaded after the artist `after`, unless the list no longer
+    /// ends with that artist
+    pub async fn append_followed_artists(&self, after: &str, mut new_artists: Vec<SearchArtist>, has_more: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::FollowedArtists { artists, paging, .. } = &mut state.view
+            && artists.last().is_some_and(|artist| artist.id == after)
+        {
+            artists.append(&mut new_artists);
+            paging.has_more = has_more;
+            paging.loading_more = false;
+        }
+    }
+
+    /// Add a page loaded from `offset`, like `append_liked_songs`
+    pub async fn append_saved_shows(&self, offset: usize, mut new_shows: Vec<Show>, has_more: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::SavedShows { shows, paging, .. } = &mut state.view
+            && shows.len() == offset
+        {
+            shows.append(&mut new_shows);
+            paging.has_more = has_more;
+            paging.loading_more = false;
+        }
+    }
+
+    /// Add episodes loaded from `offset` to the open show, unless another
+    /// view or the show afresh was opened while they loaded
+    pub async fn append_show_episodes(&self, show_id: &str, offset: usize, mut new_episodes: Vec<Episode>, has_more: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::ShowDetail { detail, paging, .. } = &mut state.view
+            && detail.show.id == show_id
+            && detail.episodes.len() == offset
+        {
+            detail.episodes.append(&mut new_episodes);
+            paging.has_more = has_more;
+            paging.loading_more = false;
+        }
+    }
+
+    /// The selected episode of the open show
+    pub async fn get_selected_episode(&self) -> Option<Episode> {
+        let state = self.content_state.lock().await;
+        match &state.view {
+            ContentView::ShowDetail { detail, selected_index, .. } => detail.episodes.get(*selected_index).cloned(),
+            _ => None,
+        }
+    }
+
+    pub async fn update_episode_marked_played(&self, episode_id: &str, marked: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::ShowDetail { detail, .. } = &mut state.view
+            && let Some(episode) = detail.episodes.iter_mut().find(|e| e.id == episode_id)
+        {
+            episode.marked_played = marked;
+        }
+    }
+
+    /// Query, category and offset of the next page of the open search
+    /// category, once the selection is near the end of what has been loaded
+    pub async fn should_load_more_search_results(&self) -> Option<(String, SearchResultSection, usize)> {
+        let state = self.content_state.lock().await;
+        let ContentView::SearchCategory { results, section, selected_index, paging } = &state.view else {
+            return None;
+        };
+        let loaded = results.len(*section);
+        if paging.loading_more || !paging.has_more || !Self::wants_next_page(&state, *selected_index, loaded) {
+            return None;
+        }
+        Some((results.query.clone(), *section, loaded))
+    }
+
+    pub async fn set_search_loading_more(&self, loading: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::SearchCategory { paging, .. } = &mut state.view {
+            paging.loading_more = loading;
+        }
+    }
+
+    pub async fn append_search_results(&self, more: SearchResults, has_more: bool) {
+        let mut state = self.content_state.lock().await;
+        if let ContentView::SearchCategory { results, paging, .. } = &mut state.view
+            && results.query == more.query
+        {
+            results.append(more);
+            paging.has_more = has_more;
+            paging.loading_more = false;
+        }
+    }
+
+    // ========================================================================
+    // Playlist Editing
+    // ========================================================================
+
+    pub async fn get_selected_playlist_track(&self) -> Option<PlaylistTrackRef> {
+        let state = self.content_state.lock().await;
+        let ContentView::PlaylistDetail { detail, selected_index } = &state.view else {
+            return None;
+        };
+        detail.tracks.get(*selected_index).map(|track| PlaylistTrackRef {
+            playlist_id: detail.id.clone(),
+            snapshot_id: detail.snapshot_id.clone(),
+            editable: detail.editable,
+            index: *selected_index,
+            uri: track.uri.clone(),
+            loaded: detail.tracks.len(),
+        })
+    }
+
+    /// The open playlist, if it is `playlist_id`
+    fn open_playlist_mut<'a>(state: &'a mut ContentState, playlist_id: &str) -> Option<(&'a mut PlaylistDetail, &'a mut usize)> {
+        match &mut state.view {
+            ContentView::PlaylistDetail { detail, selected_index } if detail.id == playlist_id => {
+                Some((detail, selected_index))
+            }
+            _ => None,
+        }
+    }
+
+    pub async fn playlist_tracks_added(&self, playlist_id: &str, mut tracks: Vec<SearchTrack>, snapshot_id: String) {
+        let mut state = self.content_state.lock().await;
+        if let Some((detail, _)) = Self::open_playlist_mut(&mut state, playlist_id) {
+            detail.total_tracks += tracks.len() as u32;
+            detail.snapshot_id = snapshot_id;
+            // Otherwise they arrive with the last page
+            if !detail.has_more {
+                detail.tracks.append(&mut tracks);
+            }
+        }
+    }
+
+    pub async fn playlist_track_removed(&self, playlist_id: &str, index: usize, snapshot_id: String) {
+        let mut state = self.content_state.lock().await;
+        if let Some((detail, selected_index)) = Self::open_playlist_mut(&mut state, playlist_id)
+            && index < detail.tracks.len()
+        {
+            detail.tracks.remove(index);
+            detail.total_tracks = detail.total_tracks.saturating_sub(1);
+            detail.snapshot_id = snapshot_id;
+            *selected_index = (*selected_index).min(detail.tracks.len().saturating_sub(1));
+        }
+    }
+
+    /// Swap the tracks at `from` and `to`, keeping the moved track selected
+    pub async fn playlist_track_moved(&self, playlist_id: &str, from: usize, to: usize, snapshot_id: String) {
+        let mut state = self.content_state.lock().await;
+        if let Some((detail, selected_index)) = Self::open_playlist_mut(&mut state, playlist_id)
+            && from < detail.tracks.len()
+            && to < detail.tracks.len()
+        {
+            detail.tracks.swap(from, to);
+            detail.snapshot_id = snapshot_id;
+            *selected_index = to;
+        }
+    }
+
     pub async fn get_selected_track_for_like(&self) -> Option<(String, bool)> {
         let state = self.content_state.lock().await;
         let result = match &state.view {
@@ -797,25 +2181,35 @@ impl AppModel {
                     None
                 }
             }
+            ContentView::SearchCategory { results, section, selected_index, .. } => {
+                if *section == SearchResultSection::Tracks {
+                    results.tracks.get(*selected_index).map(|t| (t.id.clone(), t.liked))
+                } else {
+                    None
+                }
+            }
             ContentView::AlbumDetail { detail, selected_index } => {
                 detail.tracks.get(*selected_index).map(|t| (t.id.clone(), t.liked))
             }
             ContentView::PlaylistDetail { detail, selected_index } => {
                 detail.tracks.get(*selected_index).map(|t| (t.id.clone(), t.liked))
             }
-            ContentView::ArtistDetail { detail, section, track_index, .. } => {
-                if *section == ArtistDetailSection::TopTracks {
-                    detail.top_tracks.get(*track_index).map(|t| (t.id.clone(), t.liked))
-                } else {
-                    None
-                }
+            ContentView::ArtistDetail { detail, section: ArtistDetailSection::TopTracks, selected } => {
+                detail.top_tracks.get(selected[ArtistDetailSection::TopTracks.index()]).map(|t| (t.id.clone(), t.liked))
             }
-            ContentView::LikedSongs { tracks, selected_index } => {
+            ContentView::LikedSongs { tracks, selected_index, .. } => {
                 tracks.get(*selected_index).map(|t| (t.id.clone(), t.liked))
             }
             ContentView::RecentlyPlayed { tracks, selected_index } => {
                 tracks.get(*selected_index).map(|t| (t.id.clone(), t.liked))
             }
+            ContentView::Home { home, section, selected } => home
+                .tracks(*section)
+                .and_then(|tracks| tracks.get(selected[section.index()]))
+                .map(|t| (t.id.clone(), t.liked)),
+            ContentView::Top { top, tab: TopTab::Tracks, track_index, .. } => {
+                top.tracks.get(*track_index).map(|t| (t.id.clone(), t.liked))
+            }
             ContentView::Queue { queue, selected_index, .. } => {
                 queue.get(*selected_index).map(|t| (t.id.clone(), t.liked))
             }
@@ -832,7 +2226,7 @@ impl AppModel {
     pub async fn update_track_liked_status(&self, track_id: &str, liked: bool) {
         let mut state = self.content_state.lock().await;
         match &mut state.view {
-            ContentView::SearchResults { results, .. } => {
+            ContentView::SearchResults { results, .. } | ContentView::SearchCategory { results, .. } => {
                 if let Some(track) = results.tracks.iter_mut().find(|t| t.id == track_id) {
                     track.liked = liked;
                 }
@@ -862,20 +2256,99 @@ impl AppModel {
                     track.liked = liked;
                 }
             }
+            ContentView::Home { home, .. } => {
+                for track in home.jump_back_in.iter_mut().chain(home.top_tracks.iter_mut()).filter(|t| t.id == track_id) {
+                    track.liked = liked;
+                }
+            }
+            ContentView::Top { top, .. } => {
+                if let Some(track) = top.tracks.iter_mut().find(|t| t.id == track_id) {
+                    track.liked = liked;
+                }
+            }
             ContentView::Queue { queue, currently_playing, .. } => {
                 if let Some(track) = queue.iter_mut().find(|t| t.id == track_id) {
                     track.liked = liked;
                 }
-                if let Some(cp) = currently_playing {
-                    if cp.id == track_id {
-                        cp.liked = liked;
-                    }
+                if let Some(cp) = currently_playing
+                    && cp.id == track_id
+                {
+                    cp.liked = liked;
                 }
             }
             _ => {}
         }
     }
 
+    /// The album or artist whose detail view is open
+    pub async fn get_open_album_or_artist(&self) -> Option<SelectedItem> {
+        let state = self.content_state.lock().await;
+        match &state.view {
+            ContentView::AlbumDetail { detail, .. } => Some(SelectedItem::Album { id: detail.id.clone() }),
+            ContentView::ArtistDetail { detail, .. } => Some(SelectedItem::Artist { id: detail.id.clone() }),
+            _ => None,
+        }
+    }
+
+    /// Show an album's saved status everywhere it appears, including the
+    /// views to go back to
+    pub async fn update_album_saved_status(&self, album_id: &str, saved: bool) {
+        let mut state = self.content_state.lock().await;
+        let state = &mut *state;
+        for view in std::iter::once(&mut state.view).chain(state.navigation_stack.iter_mut()) {
+            let albums = match view {
+                ContentView::SearchResults { results, .. } | ContentView::SearchCategory { results, .. } => {
+                    &mut results.albums
+                }
+                ContentView::ArtistDetail { detail, .. } => {
+                    for album in detail.releases_mut().filter(|a| a.id == album_id) {
+                        album.saved = saved;
+                    }
+                    continue;
+                }
+                ContentView::SavedAlbums { albums, .. } => albums,
+                ContentView::Home { home, .. } => &mut home.new_releases,
+                ContentView::AlbumDetail { detail, .. } => {
+                    if detail.id == album_id {
+                        detail.saved = saved;
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            for album in albums.iter_mut().filter(|a| a.id == album_id) {
+                album.saved = saved;
+            }
+        }
+    }
+
+    /// Show an artist's followed status everywhere it appears, including
+    /// the views to go back to
+    pub async fn update_artist_followed_status(&self, artist_id: &str, followed: bool) {
+        let mut state = self.content_state.lock().await;
+        let state = &mut *state;
+        for view in std::iter::once(&mut state.view).chain(state.navigation_stack.iter_mut()) {
+            let artists = match view {
+                ContentView::SearchResults { results, .. } | ContentView::SearchCategory { results, .. } => {
+                    &mut results.artists
+                }
+                ContentView::FollowedArtists { artists, .. } => artists,
+                ContentView::Home { home, .. } => &mut home.top_artists,
+                ContentView::Top { top, .. } => &mut top.artists,
+                ContentView::ArtistDetail { detail, .. } => {
+                    if detail.id == artist_id {
+                        detail.followed = followed;
+                    }
+                    &mut detail.related
+                }
+                _ => continue,
+            };
+            for artist in artists.iter_mut().filter(|a| a.id == artist_id) {
+                artist.followed = followed;
+            }
+        }
+    }
+
     pub async fn get_selected_queue_index(&self) -> Option<usize> {
         let state = self.content_state.lock().await;
         if let ContentView::Queue { selected_index, .. } = &state.view {
@@ -885,37 +2358,44 @@ impl AppModel {
         }
     }
 
-    pub async fn get_selected_track_uri(&self) -> Option<String> {
+    pub async fn get_selected_track(&self) -> Option<SearchTrack> {
         let state = self.content_state.lock().await;
         match &state.view {
             ContentView::SearchResults { results, section, track_index, .. } => {
                 if *section == SearchResultSection::Tracks {
-                    results.tracks.get(*track_index).map(|t| t.uri.clone())
+                    results.tracks.get(*track_index).cloned()
+                } else {
+                    None
+                }
+            }
+            ContentView::SearchCategory { results, section, selected_index, .. } => {
+                if *section == SearchResultSection::Tracks {
+                    results.tracks.get(*selected_index).cloned()
                 } else {
                     None
                 }
             }
             ContentView::AlbumDetail { detail, selected_index } => {
-                detail.tracks.get(*selected_index).map(|t| t.uri.clone())
+                detail.tracks.get(*selected_index).cloned()
             }
             ContentView::PlaylistDetail { detail, selected_index } => {
-                detail.tracks.get(*selected_index).map(|t| t.uri.clone())
+                detail.tracks.get(*selected_index).cloned()
             }
-            ContentView::ArtistDetail { detail, section, track_index, .. } => {
-                if *section == ArtistDetailSection::TopTracks {
-                    detail.top_tracks.get(*track_index).map(|t| t.uri.clone())
-                } else {
-                    None
-                }
+            ContentView::ArtistDetail { detail, section: ArtistDetailSection::TopTracks, selected } => {
+                detail.top_tracks.get(selected[ArtistDetailSection::TopTracks.index()]).cloned()
             }
-            ContentView::LikedSongs { tracks, selected_index } => {
-                tracks.get(*selected_index).map(|t| t.uri.clone())
+            ContentView::LikedSongs { tracks, selected_index, .. } => {
+                tracks.get(*selected_index).cloned()
             }
             ContentView::RecentlyPlayed { tracks, selected_index } => {
-                tracks.get(*selected_index).map(|t| t.uri.clone())
+                tracks.get(*selected_index).cloned()
+            }
+            ContentView::Home { home, section, selected } => {
+                home.tracks(*section).and_then(|tracks| tracks.get(selected[section.index()])).cloned()
             }
+            ContentView::Top { top, tab: TopTab::Tracks, track_index, .. } => top.tracks.get(*track_index).cloned(),
             ContentView::Queue { queue, selected_index, .. } => {
-                queue.get(*selected_index).map(|t| t.uri.clone())
+                queue.get(*selected_index).cloned()
             }
             _ => None,
         }
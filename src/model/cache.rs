@@ -1,37 +1,69 @@
-//! Cache for liked songs to enable fast lookup without API calls
+//! Caches of library membership (liked songs, saved albums, followed
+//! artists) to enable fast lookup without API calls
 
 use std::sync::Arc;
+use std::path::{Path, PathBuf};
 use std::collections::HashSet;
 use anyhow::Result;
 use tokio::sync::RwLock;
 
-const LIKED_SONGS_CACHE_FILE: &str = ".cache/liked_songs.json";
+/// Liked songs cache file, relative to the cache directory
+pub const LIKED_SONGS_CACHE_FILE: &str = "liked_songs.json";
 
-/// Cache for liked song IDs to enable fast lookup without API calls
+/// Saved albums cache file, relative to the cache directory
+pub const SAVED_ALBUMS_CACHE_FILE: &str = "saved_albums.json";
+
+/// Followed artists cache file, relative to the cache directory
+pub const FOLLOWED_ARTISTS_CACHE_FILE: &str = "followed_artists.json";
+
+/// Episodes marked as played in spotify-rs, relative to the cache directory
+pub const PLAYED_EPISODES_CACHE_FILE: &str = "played_episodes.json";
+
+/// Cache of the IDs in one part of the library, e.g. the liked song IDs
 #[derive(Clone)]
-pub struct LikedSongsCache {
-    liked_ids: Arc<RwLock<HashSet<String>>>,
+pub struct LibraryCache {
+    ids: Arc<RwLock<HashSet<String>>>,
     loaded: Arc<RwLock<bool>>,
+    /// Backing file, or `None` for a cache that never touches the disk
+    path: Option<PathBuf>,
 }
 
-impl LikedSongsCache {
-    pub fn new() -> Self {
+impl LibraryCache {
+    /// A cache saved as `file` in `cache_dir`
+    pub fn new(cache_dir: &Path, file: &str) -> Self {
         Self {
-            liked_ids: Arc::new(RwLock::new(HashSet::new())),
+            ids: Arc::new(RwLock::new(HashSet::new())),
             loaded: Arc::new(RwLock::new(false)),
+            path: Some(cache_dir.join(file)),
         }
     }
 
+    /// A cache that is never loaded from or saved to disk
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        Self {
+            ids: Arc::new(RwLock::new(HashSet::new())),
+            loaded: Arc::new(RwLock::new(false)),
+            path: None,
+        }
+    }
+
+    /// Whether a previously saved cache file exists
+    pub fn exists_on_disk(&self) -> bool {
+        self.path.as_ref().is_some_and(|path| path.exists())
+    }
+
     pub async fn load_from_disk(&self) -> Result<()> {
         use std::fs;
-        use std::path::Path;
 
-        let path = Path::new(LIKED_SONGS_CACHE_FILE);
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
         if path.exists() {
             let content = fs::read_to_string(path)?;
-            let ids: Vec<String> = serde_json::from_str(&content)?;
-            let mut liked_ids = self.liked_ids.write().await;
-            *liked_ids = ids.into_iter().collect();
+            let loaded_ids: Vec<String> = serde_json::from_str(&content)?;
+            let mut ids = self.ids.write().await;
+            *ids = loaded_ids.into_iter().collect();
             let mut loaded = self.loaded.write().await;
             *loaded = true;
         }
@@ -40,45 +72,42 @@ impl LikedSongsCache {
 
     pub async fn save_to_disk(&self) -> Result<()> {
         use std::fs;
-        use std::path::Path;
 
-        let cache_dir = Path::new(".cache");
-        if !cache_dir.exists() {
+        let Some(path) = self.path.as_ref() else {
+            return Ok(());
+        };
+        if let Some(cache_dir) = path.parent()
+            && !cache_dir.exists()
+        {
             fs::create_dir_all(cache_dir)?;
         }
 
-        let liked_ids = self.liked_ids.read().await;
-        let ids: Vec<&String> = liked_ids.iter().collect();
+        let ids = self.ids.read().await;
+        let ids: Vec<&String> = ids.iter().collect();
         let content = serde_json::to_string(&ids)?;
-        fs::write(LIKED_SONGS_CACHE_FILE, content)?;
+        fs::write(path, content)?;
         Ok(())
     }
 
-    pub async fn update(&self, track_ids: Vec<String>) {
-        let mut liked_ids = self.liked_ids.write().await;
-        *liked_ids = track_ids.into_iter().collect();
+    pub async fn update(&self, new_ids: Vec<String>) {
+        let mut ids = self.ids.write().await;
+        *ids = new_ids.into_iter().collect();
         let mut loaded = self.loaded.write().await;
         *loaded = true;
     }
 
-    pub async fn is_liked(&self, track_id: &str) -> bool {
-        let liked_ids = self.liked_ids.read().await;
-        liked_ids.contains(track_id)
+    pub async fn contains(&self, id: &str) -> bool {
+        let ids = self.ids.read().await;
+        ids.contains(id)
     }
 
-    pub async fn add(&self, track_id: String) {
-        let mut liked_ids = self.liked_ids.write().await;
-        liked_ids.insert(track_id);
+    pub async fn add(&self, id: String) {
+        let mut ids = self.ids.write().await;
+        ids.insert(id);
     }
 
-    pub async fn remove(&self, track_id: &str) {
-        let mut liked_ids = self.liked_ids.write().await;
-        liked_ids.remove(track_id);
-    }
-}
-
-impl Default for LikedSongsCache {
-    fn default() -> Self {
-        Self::new()
+    pub async fn remove(&self, id: &str) {
+        let mut ids = self.ids.write().await;
+        ids.remove(id);
     }
 }
@@ -1,6 +1,9 @@
 //! Content view state and data structures for search results, playlists, albums, etc.
 
-use super::types::{ArtistDetailSection, SearchResultSection};
+use super::types::{ArtistDetailSection, HomeSection, SearchResultSection, SearchSuggestion, SelectedItem, TopRange, TopTab};
+use super::track_list_import::ImportReview;
+use super::library_backup::RestorePlan;
+use super::list_filter::ListFilter;
 
 /// A track from search results or playlist
 #[derive(Clone, Debug)]
@@ -15,6 +18,13 @@ pub struct SearchTrack {
     pub liked: bool,
 }
 
+/// A liked song with the time it was saved (RFC 3339)
+#[derive(Clone, Debug)]
+pub struct SavedTrack {
+    pub track: SearchTrack,
+    pub added_at: String,
+}
+
 /// An album from search results
 #[derive(Clone, Debug)]
 pub struct SearchAlbum {
@@ -22,6 +32,7 @@ pub struct SearchAlbum {
     pub name: String,
     pub artist: String,
     pub year: String,
+    pub saved: bool,
 }
 
 /// An artist from search results
@@ -30,6 +41,7 @@ pub struct SearchArtist {
     pub id: String,
     pub name: String,
     pub genres: Vec<String>,
+    pub followed: bool,
 }
 
 /// A playlist from search results
@@ -41,9 +53,59 @@ pub struct SearchPlaylist {
     pub total_tracks: u32,
 }
 
+/// A browse category, e.g. "Chill" or "Hip-Hop"
+#[derive(Clone, Debug)]
+pub struct Category {
+    pub id: String,
+    pub name: String,
+}
+
+/// Sections of the home page
+#[derive(Clone, Debug, Default)]
+pub struct HomePage {
+    /// Recently played tracks, without repeats
+    pub jump_back_in: Vec<SearchTrack>,
+    pub new_releases: Vec<SearchAlbum>,
+    pub top_tracks: Vec<SearchTrack>,
+    pub top_artists: Vec<SearchArtist>,
+    pub categories: Vec<Category>,
+}
+
+impl HomePage {
+    /// Number of items in `section`
+    pub fn len(&self, section: HomeSection) -> usize {
+        match section {
+            HomeSection::JumpBackIn => self.jump_back_in.len(),
+            HomeSection::NewReleases => self.new_releases.len(),
+            HomeSection::TopTracks => self.top_tracks.len(),
+            HomeSection::TopArtists => self.top_artists.len(),
+            HomeSection::Categories => self.categories.len(),
+        }
+    }
+
+    /// Tracks of `section`, if it lists tracks
+    pub fn tracks(&self, section: HomeSection) -> Option<&[SearchTrack]> {
+        match section {
+            HomeSection::JumpBackIn => Some(&self.jump_back_in),
+            HomeSection::TopTracks => Some(&self.top_tracks),
+            _ => None,
+        }
+    }
+}
+
+/// The user's top tracks and artists over one period, most played first
+#[derive(Clone, Debug, Default)]
+pub struct TopItems {
+    pub range: TopRange,
+    pub tracks: Vec<SearchTrack>,
+    pub artists: Vec<SearchArtist>,
+}
+
 /// Combined search results
 #[derive(Clone, Debug, Default)]
 pub struct SearchResults {
+    /// What was searched for
+    pub query: String,
     pub tracks: Vec<SearchTrack>,
     pub albums: Vec<SearchAlbum>,
     pub artists: Vec<SearchArtist>,
@@ -52,6 +114,73 @@ pub struct SearchResults {
 }
 
 impl SearchResults {
+    /// Number of results loaded in one category
+    pub fn len(&self, section: SearchResultSection) -> usize {
+        match section {
+            SearchResultSection::Tracks => self.tracks.len(),
+            SearchResultSection::Albums => self.albums.len(),
+            SearchResultSection::Artists => self.artists.len(),
+            SearchResultSection::Playlists => self.playlists.len(),
+        }
+    }
+
+    /// The first `per_section` results of each category as live search
+    /// suggestions, best matching category first
+    pub fn suggestions(&self, per_section: usize) -> Vec<SearchSuggestion> {
+        let mut sections = vec![self.best_match];
+        let mut section = self.best_match.next();
+        while section != self.best_match {
+            sections.push(section);
+            section = section.next();
+        }
+
+        let mut suggestions = Vec::new();
+        for section in sections {
+            let items: Vec<(String, SelectedItem)> = match section {
+                SearchResultSection::Tracks => {
+                    let uris: Vec<String> = self.tracks.iter().map(|t| t.uri.clone()).collect();
+                    self.tracks
+                        .iter()
+                        .take(per_section)
+                        .enumerate()
+                        .map(|(index, t)| {
+                            let item = SelectedItem::TrackList { uris: uris.clone(), index };
+                            (format!("{} - {}", t.name, t.artist), item)
+                        })
+                        .collect()
+                }
+                SearchResultSection::Albums => self
+                    .albums
+                    .iter()
+                    .take(per_section)
+                    .map(|a| (format!("{} - {}", a.name, a.artist), SelectedItem::Album { id: a.id.clone() }))
+                    .collect(),
+                SearchResultSection::Artists => self
+                    .artists
+                    .iter()
+                    .take(per_section)
+                    .map(|a| (a.name.clone(), SelectedItem::Artist { id: a.id.clone() }))
+                    .collect(),
+                SearchResultSection::Playlists => self
+                    .playlists
+                    .iter()
+                    .take(per_section)
+                    .map(|p| (format!("{} - {}", p.name, p.owner), SelectedItem::Playlist { id: p.id.clone() }))
+                    .collect(),
+            };
+            suggestions.extend(items.into_iter().map(|(label, item)| SearchSuggestion { section, label, item }));
+        }
+        suggestions
+    }
+
+    /// Add a further page of results
+    pub fn append(&mut self, mut more: SearchResults) {
+        self.tracks.append(&mut more.tracks);
+        self.albums.append(&mut more.albums);
+        self.artists.append(&mut more.artists);
+        self.playlists.append(&mut more.playlists);
+    }
+
     /// Determine the best matching category based on exact/close name matches with the query
     pub fn determine_best_match(&mut self, query: &str) {
         let query_lower = query.to_lowercase();
@@ -124,6 +253,54 @@ pub struct AlbumDetail {
     pub artist: String,
     pub year: String,
     pub tracks: Vec<SearchTrack>,
+    pub saved: bool,
+}
+
+/// A podcast show
+#[derive(Clone, Debug)]
+pub struct Show {
+    pub id: String,
+    pub uri: String,
+    pub name: String,
+    pub publisher: String,
+}
+
+/// A podcast episode
+#[derive(Clone, Debug)]
+pub struct Episode {
+    pub id: String,
+    pub uri: String,
+    pub name: String,
+    /// `YYYY-MM-DD`, or just the year or month for older episodes
+    pub release_date: String,
+    pub duration_ms: u32,
+    /// Where listening stopped, 0 if it hasn't started
+    pub resume_position_ms: u32,
+    /// Finished according to Spotify
+    pub fully_played: bool,
+    /// Marked as played in spotify-rs
+    pub marked_played: bool,
+}
+
+impl Episode {
+    pub fn played(&self) -> bool {
+        self.fully_played || self.marked_played
+    }
+
+    /// Where playback should start: the resume point, or the beginning of
+    /// a played episode
+    pub fn start_position_ms(&self) -> u32 {
+        if self.played() { 0 } else { self.resume_position_ms }
+    }
+}
+
+/// Show detail view data
+#[derive(Clone, Debug)]
+pub struct ShowDetail {
+    pub show: Show,
+    pub description: String,
+    /// Newest first
+    pub episodes: Vec<Episode>,
 }
 
 /// Playlist detail view data
@@ -133,19 +310,118 @@ pub struct PlaylistDetail {
     pub uri: String,
     pub name: String,
     pub owner: String,
+    pub description: String,
+    /// Playlist version the loaded tracks belong to; edits are made against it
+    pub snapshot_id: String,
+    /// Owned by the user or collaborative
+    pub editable: bool,
+    pub public: bool,
+    pub collaborative: bool,
     pub tracks: Vec<SearchTrack>,
     pub total_tracks: u32,
     pub has_more: bool,
     pub loading_more: bool,
 }
 
-/// Artist detail view data
+/// Paging state of a list that loads more as it is scrolled
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListPaging {
+    /// Size of the whole list in the library
+    pub total: u32,
+    pub has_more: bool,
+    pub loading_more: bool,
+}
+
+impl ListPaging {
+    pub fn new(total: u32, has_more: bool) -> Self {
+        Self { total, has_more, loading_more: false }
+    }
+}
+
+/// Next page of the open library list
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LibraryPageRequest {
+    LikedSongs { offset: usize },
+    SavedAlbums { offset: usize },
+    /// Followed artists are paged by cursor: the ID of the last one loaded
+    FollowedArtists { after: String },
+    SavedShows { offset: usize },
+    ShowEpisodes { show_id: String, offset: usize },
+    CategoryPlaylists { category_id: String, offset: usize },
+}
+
+/// Selected track of the open playlist, as needed to edit it
 #[derive(Clone, Debug)]
+pub struct PlaylistTrackRef {
+    pub playlist_id: String,
+    pub snapshot_id: String,
+    pub editable: bool,
+    /// Position in the playlist
+    pub index: usize,
+    pub uri: String,
+    /// Number of tracks loaded so far
+    pub loaded: usize,
+}
+
+/// Artist detail view data. Releases are grouped like on Spotify's artist
+/// page, newest first within each group.
+#[derive(Clone, Debug, Default)]
 pub struct ArtistDetail {
+    pub id: String,
     pub name: String,
     pub genres: Vec<String>,
+    pub followers: u32,
+    /// 0-100, based on how much the artist's tracks are played lately
+    pub popularity: u32,
     pub top_tracks: Vec<SearchTrack>,
     pub albums: Vec<SearchAlbum>,
+    pub singles: Vec<SearchAlbum>,
+    pub compilations: Vec<SearchAlbum>,
+    /// Other artists' releases featuring this one
+    pub appears_on: Vec<SearchAlbum>,
+    pub related: Vec<SearchArtist>,
+    pub followed: bool,
+}
+
+impl ArtistDetail {
+    /// Number of items in `section`
+    pub fn len(&self, section: ArtistDetailSection) -> usize {
+        match section {
+            ArtistDetailSection::TopTracks => self.top_tracks.len(),
+            ArtistDetailSection::Related => self.related.len(),
+            _ => self.releases(section).map_or(0, <[SearchAlbum]>::len),
+        }
+    }
+
+    /// Releases of `section`, if it lists releases
+    pub fn releases(&self, section: ArtistDetailSection) -> Option<&[SearchAlbum]> {
+        match section {
+            ArtistDetailSection::Albums => Some(&self.albums),
+            ArtistDetailSection::Singles => Some(&self.singles),
+            ArtistDetailSection::Compilations => Some(&self.compilations),
+            ArtistDetailSection::AppearsOn => Some(&self.appears_on),
+            ArtistDetailSection::TopTracks | ArtistDetailSection::Related => None,
+        }
+    }
+
+    /// Section listed beside the top tracks while they are selected: the
+    /// first one with anything in it
+    pub fn side_section(&self) -> ArtistDetailSection {
+        ArtistDetailSection::ALL[1..]
+            .iter()
+            .copied()
+            .find(|&section| self.len(section) > 0)
+            .unwrap_or(ArtistDetailSection::Albums)
+    }
+
+    /// Every release of every section
+    pub fn releases_mut(&mut self) -> impl Iterator<Item = &mut SearchAlbum> {
+        self.albums
+            .iter_mut()
+            .chain(self.singles.iter_mut())
+            .chain(self.compilations.iter_mut())
+            .chain(self.appears_on.iter_mut())
+    }
 }
 
 /// Represents the current view in the main content area
@@ -153,6 +429,27 @@ pub struct ArtistDetail {
 pub enum ContentView {
     #[default]
     Empty,
+    /// Home page shown at startup
+    Home {
+        home: HomePage,
+        section: HomeSection,
+        /// Selected row of each section, by `HomeSection::index`
+        selected: [usize; HomeSection::ALL.len()],
+    },
+    /// "Your Top" (library): top tracks and artists, ranked
+    Top {
+        top: TopItems,
+        tab: TopTab,
+        track_index: usize,
+        artist_index: usize,
+    },
+    /// Playlists of a browse category, loading more as they are scrolled
+    CategoryPlaylists {
+        category: Category,
+        playlists: Vec<SearchPlaylist>,
+        selected_index: usize,
+        paging: ListPaging,
+    },
     SearchResults {
         results: SearchResults,
         section: SearchResultSection,
@@ -161,6 +458,13 @@ pub enum ContentView {
         artist_index: usize,
         playlist_index: usize,
     },
+    /// Every result of one search category, loading more as it is scrolled
+    SearchCategory {
+        results: SearchResults,
+        section: SearchResultSection,
+        selected_index: usize,
+        paging: ListPaging,
+    },
     AlbumDetail {
         detail: AlbumDetail,
         selected_index: usize,
@@ -172,41 +476,196 @@ pub enum ContentView {
     ArtistDetail {
         detail: ArtistDetail,
         section: ArtistDetailSection,
-        track_index: usize,
-        album_index: usize,
+        /// Selected row of each section, by `ArtistDetailSection::index`
+        selected: [usize; ArtistDetailSection::ALL.len()],
     },
     /// Liked songs view (library)
     LikedSongs {
         tracks: Vec<SearchTrack>,
         selected_index: usize,
+        paging: ListPaging,
     },
     /// Saved albums view (library)
     SavedAlbums {
         albums: Vec<SearchAlbum>,
         selected_index: usize,
+        paging: ListPaging,
     },
     /// Followed artists view (library)
     FollowedArtists {
         artists: Vec<SearchArtist>,
         selected_index: usize,
+        paging: ListPaging,
+    },
+    /// Saved podcast shows view (library)
+    SavedShows {
+        shows: Vec<Show>,
+        selected_index: usize,
+        paging: ListPaging,
+    },
+    /// Episodes of a show, loading more as they are scrolled
+    ShowDetail {
+        detail: ShowDetail,
+        selected_index: usize,
+        paging: ListPaging,
     },
     /// Recently played tracks view (library)
     RecentlyPlayed {
         tracks: Vec<SearchTrack>,
         selected_index: usize,
     },
+    /// Track list import waiting for the user to check its matches
+    ImportReview {
+        review: ImportReview,
+        selected_index: usize,
+    },
+    /// Changes a library restore would make, waiting for confirmation
+    LibraryRestore {
+        plan: RestorePlan,
+        selected_index: usize,
+    },
     /// Queue view - shows currently playing and upcoming tracks
     Queue {
         currently_playing: Option<SearchTrack>,
         queue: Vec<SearchTrack>,
+        /// Leading entries owned by the local queue (editable)
+        local_len: usize,
         selected_index: usize,
     },
 }
 
+impl ContentView {
+    /// Fields the `/` filter matches in each row of the focused list, or
+    /// `None` if the view can't be filtered
+    pub fn filter_fields(&self) -> Option<Vec<Vec<&str>>> {
+        fn track_fields(tracks: &[SearchTrack]) -> Vec<Vec<&str>> {
+            tracks
+                .iter()
+                .map(|t| {
+                    let mut fields = vec![t.name.as_str(), t.artist.as_str(), t.album.as_str()];
+                    fields.extend(t.artists.iter().map(String::as_str));
+                    fields
+                })
+                .collect()
+        }
+        fn album_fields(albums: &[SearchAlbum]) -> Vec<Vec<&str>> {
+            albums.iter().map(|a| vec![a.name.as_str(), a.artist.as_str()]).collect()
+        }
+        fn artist_fields(artists: &[SearchArtist]) -> Vec<Vec<&str>> {
+            artists
+                .iter()
+                .map(|a| std::iter::once(a.name.as_str()).chain(a.genres.iter().map(String::as_str)).collect())
+                .collect()
+        }
+        fn playlist_fields(playlists: &[SearchPlaylist]) -> Vec<Vec<&str>> {
+            playlists.iter().map(|p| vec![p.name.as_str(), p.owner.as_str()]).collect()
+        }
+        fn search_fields(results: &SearchResults, section: SearchResultSection) -> Vec<Vec<&str>> {
+            match section {
+                SearchResultSection::Tracks => track_fields(&results.tracks),
+                SearchResultSection::Albums => album_fields(&results.albums),
+                SearchResultSection::Artists => artist_fields(&results.artists),
+                SearchResultSection::Playlists => playlist_fields(&results.playlists),
+            }
+        }
+
+        match self {
+            ContentView::Home { home, section, .. } => Some(match section {
+                HomeSection::JumpBackIn => track_fields(&home.jump_back_in),
+                HomeSection::NewReleases => album_fields(&home.new_releases),
+                HomeSection::TopTracks => track_fields(&home.top_tracks),
+                HomeSection::TopArtists => artist_fields(&home.top_artists),
+                HomeSection::Categories => home.categories.iter().map(|c| vec![c.name.as_str()]).collect(),
+            }),
+            ContentView::Top { top, tab, .. } => Some(match tab {
+                TopTab::Tracks => track_fields(&top.tracks),
+                TopTab::Artists => artist_fields(&top.artists),
+            }),
+            ContentView::CategoryPlaylists { playlists, .. } => Some(playlist_fields(playlists)),
+            ContentView::SearchResults { results, section, .. }
+            | ContentView::SearchCategory { results, section, .. } => Some(search_fields(results, *section)),
+            ContentView::AlbumDetail { detail, .. } => Some(track_fields(&detail.tracks)),
+            ContentView::PlaylistDetail { detail, .. } => Some(track_fields(&detail.tracks)),
+            ContentView::ArtistDetail { detail, section, .. } => Some(match section {
+                ArtistDetailSection::TopTracks => track_fields(&detail.top_tracks),
+                ArtistDetailSection::Related => artist_fields(&detail.related),
+                _ => album_fields(detail.releases(*section).unwrap_or_default()),
+            }),
+            ContentView::LikedSongs { tracks, .. }
+            | ContentView::RecentlyPlayed { tracks, .. }
+            | ContentView::Queue { queue: tracks, .. } => Some(track_fields(tracks)),
+            ContentView::SavedAlbums { albums, .. } => Some(album_fields(albums)),
+            ContentView::FollowedArtists { artists, .. } => Some(artist_fields(artists)),
+            ContentView::SavedShows { shows, .. } => {
+                Some(shows.iter().map(|s| vec![s.name.as_str(), s.publisher.as_str()]).collect())
+            }
+            ContentView::ShowDetail { detail, .. } => {
+                Some(detail.episodes.iter().map(|e| vec![e.name.as_str(), e.release_date.as_str()]).collect())
+            }
+            ContentView::ImportReview { .. } | ContentView::LibraryRestore { .. } | ContentView::Empty => None,
+        }
+    }
+
+    /// Selected row of the focused list
+    pub fn selected_index_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            ContentView::Home { section, selected, .. } => Some(&mut selected[section.index()]),
+            ContentView::SearchResults {
+                section,
+                track_index,
+                album_index,
+                artist_index,
+                playlist_index,
+                ..
+            } => Some(match section {
+                SearchResultSection::Tracks => track_index,
+                SearchResultSection::Albums => album_index,
+                SearchResultSection::Artists => artist_index,
+                SearchResultSection::Playlists => playlist_index,
+            }),
+            ContentView::Top { tab, track_index, artist_index, .. } => Some(match tab {
+                TopTab::Tracks => track_index,
+                TopTab::Artists => artist_index,
+            }),
+            ContentView::ArtistDetail { section, selected, .. } => Some(&mut selected[section.index()]),
+            ContentView::SearchCategory { selected_index, .. }
+            | ContentView::AlbumDetail { selected_index, .. }
+            | ContentView::PlaylistDetail { selected_index, .. }
+            | ContentView::LikedSongs { selected_index, .. }
+            | ContentView::SavedAlbums { selected_index, .. }
+            | ContentView::FollowedArtists { selected_index, .. }
+            | ContentView::SavedShows { selected_index, .. }
+            | ContentView::ShowDetail { selected_index, .. }
+            | ContentView::CategoryPlaylists { selected_index, .. }
+            | ContentView::RecentlyPlayed { selected_index, .. }
+            | ContentView::ImportReview { selected_index, .. }
+            | ContentView::LibraryRestore { selected_index, .. }
+            | ContentView::Queue { selected_index, .. } => Some(selected_index),
+            ContentView::Empty => None,
+        }
+    }
+}
+
 /// State for the main content area
 #[derive(Clone, Debug, Default)]
 pub struct ContentState {
     pub view: ContentView,
     pub navigation_stack: Vec<ContentView>,
     pub is_loading: bool,
+    /// `/` filter of the focused list; dropped when the view changes
+    pub filter: Option<ListFilter>,
+}
+
+impl ContentState {
+    /// Rows of the focused list left by the filter, or `None` when the list
+    /// isn't filtered
+    pub fn filtered_rows(&self) -> Option<Vec<usize>> {
+        let filter = self.filter.as_ref().filter(|filter| !filter.query.trim().is_empty())?;
+        Some(filter.matching_rows(self.view.filter_fields()?))
+    }
+
+    /// Whether the focused list is narrowed by a filter query
+    pub fn is_filtered(&self) -> bool {
+        self.filter.as_ref().is_some_and(|filter| !filter.query.trim().is_empty())
+    }
 }
@@ -0,0 +1,1148 @@
+//! In-memory Spotify backend for controller tests and offline development
+//!
+//! `FakeSpotifyClient` implements `SpotifyApi` on top of a `FakeLibrary`
+//! catalog and a tiny simulated player, so the controller can be exercised
+//! without network access or a Spotify account.
+
+use std::sync::Arc;
+use std::collections::{HashMap, HashSet, VecDeque};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use rspotify::model::CurrentPlaybackContext;
+
+use super::search_query::{SearchField, SearchQuery};
+use super::spotify_api::{SpotifyApi, PLAYLIST_PAGE_SIZE};
+use super::types::{
+    ArtistDetailSection, DeviceInfo, PlaylistItem, PlaylistVisibility, RepeatState, SearchResultSection, TopRange,
+};
+use super::content::{
+    SearchTrack, SearchAlbum, SearchArtist, SearchPlaylist, SearchResults,
+    AlbumDetail, PlaylistDetail, ArtistDetail, SavedTrack, Show, Episode, ShowDetail, Category,
+};
+
+/// Seed data for the fake backend
+#[derive(Clone, Debug, Default)]
+pub struct FakeLibrary {
+    /// Standalone tracks (album and playlist tracks are found automatically)
+    pub tracks: Vec<SearchTrack>,
+    pub albums: Vec<AlbumDetail>,
+    pub artists: Vec<SearchArtist>,
+    pub playlists: Vec<PlaylistDetail>,
+    /// Playlists removed from the library, which can be followed again
+    pub unfollowed_playlists: Vec<PlaylistDetail>,
+    pub liked_track_ids: Vec<String>,
+    pub saved_album_ids: Vec<String>,
+    pub followed_artist_ids: Vec<String>,
+    /// Podcast shows with all of their episodes
+    pub shows: Vec<ShowDetail>,
+    pub saved_show_ids: Vec<String>,
+    pub played_episode_ids: Vec<String>,
+    pub recently_played_ids: Vec<String>,
+    pub new_release_ids: Vec<String>,
+    /// Browse categories with the IDs of their playlists
+    pub categories: Vec<(Category, Vec<String>)>,
+    /// Top track and artist IDs, most played first, for each range
+    pub top_track_ids: HashMap<TopRange, Vec<String>>,
+    pub top_artist_ids: HashMap<TopRange, Vec<String>>,
+    /// Section of its artist's page an album is listed in, when that isn't
+    /// Albums (singles, compilations)
+    pub release_groups: HashMap<String, ArtistDetailSection>,
+    /// Related artist IDs by artist ID
+    pub related_artist_ids: HashMap<String, Vec<String>>,
+    pub devices: Vec<DeviceInfo>,
+    /// Search queries that fail as if rate limited
+    pub failing_queries: Vec<String>,
+}
+
+impl FakeLibrary {
+    /// A small hand-made catalog used by `--offline` mode
+    pub fn demo(local_device_name: &str) -> Self {
+        let mut library = Self {
+            devices: vec![DeviceInfo {
+                id: "fake-local-device".to_string(),
+                name: local_device_name.to_string(),
+                is_active: true,
+            }],
+            ..Default::default()
+        };
+
+        let catalog = [
+            ("Northern Lights", "Aurora Fields", ["Polar Night", "Magnetic", "Solar Wind", "Green Veil"]),
+            ("Tidal", "Harbor Static", ["Low Tide", "Undertow", "Lighthouse", "Salt"]),
+            ("Paper Cities", "The Cartographers", ["Fold Lines", "Compass Rose", "Terra Incognita", "Legend"]),
+        ];
+
+        for (album_index, (album_name, artist_name, track_names)) in catalog.iter().enumerate() {
+            let album_id = format!("fakealbum{:02}", album_index + 1);
+            let artist_id = format!("fakeartist{:02}", album_index + 1);
+            let tracks: Vec<SearchTrack> = track_names
+                .iter()
+                .enumerate()
+                .map(|(track_index, name)| {
+                    let id = format!("faketrack{:02}{:02}", album_index + 1, track_index + 1);
+                    SearchTrack {
+                        uri: format!("spotify:track:{}", id),
+                        id,
+                        name: name.to_string(),
+                        artist: artist_name.to_string(),
+                        artists: vec![artist_name.to_string()],
+                        album: album_name.to_string(),
+                        duration_ms: 180_000 + (track_index as u32 * 17_000),
+                        liked: false,
+                    }
+                })
+                .collect();
+
+            library.artists.push(SearchArtist {
+                id: artist_id,
+                name: artist_name.to_string(),
+                genres: vec!["demo".to_string()],
+                followed: false,
+            });
+            library.albums.push(AlbumDetail {
+                id: album_id,
+                name: album_name.to_string(),
+                artist: artist_name.to_string(),
+                year: format!("{}", 2019 + album_index),
+                tracks,
+                saved: false,
+            });
+        }
+
+        let mix: Vec<SearchTrack> = library
+            .albums
+            .iter()
+            .flat_map(|album| album.tracks.iter().take(2).cloned())
+            .collect();
+        library.playlists.push(PlaylistDetail {
+            id: "fakeplaylist01".to_string(),
+            uri: "spotify:playlist:fakeplaylist01".to_string(),
+            name: "Offline Mix".to_string(),
+            owner: "offline".to_string(),
+            description: "Two tracks from every demo album".to_string(),
+            snapshot_id: "fake-snapshot-1".to_string(),
+            editable: true,
+            public: false,
+            collaborative: false,
+            total_tracks: mix.len() as u32,
+            tracks: mix,
+            has_more: false,
+            loading_more: false,
+        });
+
+        library.liked_track_ids = vec!["faketrack0101".to_string(), "faketrack0203".to_string()];
+        library.saved_album_ids = vec!["fakealbum01".to_string(), "fakealbum03".to_string()];
+        library.followed_artist_ids = vec!["fakeartist02".to_string()];
+        library.recently_played_ids = vec!["faketrack0302".to_string(), "faketrack0101".to_string()];
+        library.new_release_ids = vec!["fakealbum03".to_string(), "fakealbum02".to_string()];
+        library.categories = vec![
+            (
+                Category { id: "fakecategory01".to_string(), name: "Late Night".to_string() },
+                vec!["fakeplaylist01".to_string()],
+            ),
+            (Category { id: "fakecategory02".to_string(), name: "Focus".to_string() }, Vec::new()),
+        ];
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        library.related_artist_ids = HashMap::from([
+            ("fakeartist01".to_string(), ids(&["fakeartist03", "fakeartist02"])),
+            ("fakeartist03".to_string(), ids(&["fakeartist01"])),
+        ]);
+        library.top_track_ids = HashMap::from([
+            (TopRange::FourWeeks, ids(&["faketrack0101", "faketrack0302"])),
+            (TopRange::SixMonths, ids(&["faketrack0203", "faketrack0101", "faketrack0304"])),
+            (TopRange::Year, ids(&["faketrack0304", "faketrack0203", "faketrack0101", "faketrack0102"])),
+        ]);
+        library.top_artist_ids = HashMap::from([
+            (TopRange::FourWeeks, ids(&["fakeartist01"])),
+            (TopRange::SixMonths, ids(&["fakeartist02", "fakeartist01"])),
+            (TopRange::Year, ids(&["fakeartist03", "fakeartist02", "fakeartist01"])),
+        ]);
+
+        let episodes = ["Static on the Line", "Graveyard Shift", "Callers After Midnight", "Dead Air", "Sign-On"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let id = format!("fakeepisode{:02}", 5 - i);
+                Episode {
+                    uri: format!("spotify:episode:{}", id),
+                    id,
+                    name: name.to_string(),
+                    release_date: format!("2024-0{}-01", 5 - i),
+                    duration_ms: 1_800_000 + i as u32 * 60_000,
+                    // The newest episode was left halfway, the third finished
+                    resume_position_ms: if i == 0 { 900_000 } else { 0 },
+                    fully_played: i == 2,
+                    marked_played: false,
+                }
+            })
+            .collect();
+        library.shows.push(ShowDetail {
+            show: Show {
+                id: "fakeshow01".to_string(),
+                uri: "spotify:show:fakeshow01".to_string(),
+                name: "Night Shift Radio".to_string(),
+                publisher: "Offline FM".to_string(),
+            },
+            description: "Conversations for the small hours".to_string(),
+            episodes,
+        });
+        library.saved_show_ids = vec!["fakeshow01".to_string()];
+        library
+    }
+
+    fn find_show(&self, show_id: &str) -> Result<&ShowDetail> {
+        self.shows
+            .iter()
+            .find(|s| s.show.id == show_id)
+            .ok_or_else(|| anyhow!("Unknown show: {}", show_id))
+    }
+
+    fn all_tracks(&self) -> impl Iterator<Item = &SearchTrack> {
+        self.tracks
+            .iter()
+            .chain(self.albums.iter().flat_map(|a| a.tracks.iter()))
+            .chain(self.playlists.iter().flat_map(|p| p.tracks.iter()))
+    }
+
+    fn find_track(&self, uri: &str) -> Option<SearchTrack> {
+        self.all_tracks().find(|t| t.uri == uri).cloned()
+    }
+
+    fn find_track_by_id(&self, id: &str) -> Option<SearchTrack> {
+        self.all_tracks().find(|t| t.id == id).cloned()
+    }
+
+    fn liked_tracks(&self) -> Vec<SearchTrack> {
+        self.liked_track_ids
+            .iter()
+            .filter_map(|id| self.find_track_by_id(id))
+            .map(|track| SearchTrack { liked: true, ..track })
+            .collect()
+    }
+
+    fn artist_detail(&self, artist: &SearchArtist) -> ArtistDetail {
+        let mut seen = HashSet::new();
+        let top_tracks = self
+            .all_tracks()
+            .filter(|t| t.artists.contains(&artist.name) && seen.insert(t.uri.clone()))
+            .take(10)
+            .cloned()
+            .collect();
+
+        let mut detail = ArtistDetail {
+            id: artist.id.clone(),
+            name: artist.name.clone(),
+            genres: artist.genres.clone(),
+            top_tracks,
+            related: self
+                .related_artist_ids
+                .get(&artist.id)
+                .into_iter()
+                .flatten()
+                .filter_map(|id| self.artists.iter().find(|a| &a.id == id))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        };
+
+        // Newest first, then grouped like the real artist page
+        let mut releases: Vec<&AlbumDetail> = self
+            .albums
+            .iter()
+            .filter(|a| a.artist == artist.name || a.tracks.iter().any(|t| t.artists.contains(&artist.name)))
+            .collect();
+        releases.sort_by(|a, b| b.year.cmp(&a.year));
+        for album in releases {
+            let section = if album.artist != artist.name {
+                &mut detail.appears_on
+            } else {
+                match self.release_groups.get(&album.id) {
+                    Some(ArtistDetailSection::Singles) => &mut detail.singles,
+                    Some(ArtistDetailSection::Compilations) => &mut detail.compilations,
+                    _ => &mut detail.albums,
+                }
+            };
+            section.push(Self::album_summary(album));
+        }
+        detail
+    }
+
+    /// Playlist the user may edit, with its snapshot moved to the next version
+    fn edit_playlist(&mut self, playlist_id: &str) -> Result<&mut PlaylistDetail> {
+        let playlist = self
+            .playlists
+            .iter_mut()
+            .find(|p| p.id == playlist_id)
+            .ok_or_else(|| anyhow!("Unknown playlist: {}", playlist_id))?;
+        if !playlist.editable {
+            return Err(anyhow!("You cannot modify playlist {}", playlist_id));
+        }
+
+        let version = playlist
+            .snapshot_id
+            .rsplit('-')
+            .next()
+            .and_then(|n| n.parse::<u32>().ok())
+            .unwrap_or(0);
+        playlist.snapshot_id = format!("fake-snapshot-{}", version + 1);
+        Ok(playlist)
+    }
+
+    fn album_summary(album: &AlbumDetail) -> SearchAlbum {
+        SearchAlbum {
+            id: album.id.clone(),
+            name: album.name.clone(),
+            artist: album.artist.clone(),
+            year: album.year.clone(),
+            saved: false,
+        }
+    }
+}
+
+/// Simulated playback state
+#[derive(Clone, Debug, Default)]
+struct FakePlayer {
+    current: Option<SearchTrack>,
+    context: Vec<SearchTrack>,
+    context_index: usize,
+    queue: VecDeque<SearchTrack>,
+    is_playing: bool,
+    shuffle: bool,
+    repeat: Option<RepeatState>,
+    volume: u8,
+}
+
+/// In-memory implementation of `SpotifyApi`
+#[derive(Clone)]
+pub struct FakeSpotifyClient {
+    library: Arc<RwLock<FakeLibrary>>,
+    player: Arc<RwLock<FakePlayer>>,
+}
+
+impl FakeSpotifyClient {
+    pub fn new(library: FakeLibrary) -> Self {
+        Self {
+            library: Arc::new(RwLock::new(library)),
+            player: Arc::new(RwLock::new(FakePlayer {
+                volume: crate::audio::DEFAULT_VOLUME_PERCENT,
+                ..Default::default()
+            })),
+        }
+    }
+
+    async fn require_active_device(&self) -> Result<()> {
+        if self.has_active_device().await {
+            Ok(())
+        } else {
+            Err(anyhow!("Player command failed: No active device found"))
+        }
+    }
+
+    async fn start_context(&self, context: Vec<SearchTrack>, start_uri: &str) -> Result<()> {
+        self.require_active_device().await?;
+        let index = context
+            .iter()
+            .position(|t| t.uri == start_uri)
+            .ok_or_else(|| anyhow!("Track {} is not part of the context", start_uri))?;
+
+        let mut player = self.player.write().await;
+        player.current = context.get(index).cloned();
+        player.context = context;
+        player.context_index = index;
+        player.is_playing = true;
+        Ok(())
+    }
+}
+
+impl Default for FakeSpotifyClient {
+    fn default() -> Self {
+        Self::new(FakeLibrary::default())
+    }
+}
+
+#[async_trait]
+impl SpotifyApi for FakeSpotifyClient {
+    async fn refresh_token_if_needed(&self) -> Result<bool> {
+        Ok(false)
+    }
+
+    async fn current_user_id(&self) -> Result<String> {
+        Ok("offline".to_string())
+    }
+
+    async fn mark_tracks_liked(&self, tracks: &mut [SearchTrack]) {
+        let library = self.library.read().await;
+        for track in tracks.iter_mut() {
+            track.liked = library.liked_track_ids.contains(&track.id);
+        }
+    }
+
+    async fn toggle_liked_song(&self, track_id: &str) -> Result<bool> {
+        if track_id.is_empty() {
+            return Err(anyhow!("Track ID is empty"));
+        }
+
+        let mut library = self.library.write().await;
+        if let Some(pos) = library.liked_track_ids.iter().position(|id| id == track_id) {
+            library.liked_track_ids.remove(pos);
+            Ok(false)
+        } else {
+            library.liked_track_ids.insert(0, track_id.to_string());
+            Ok(true)
+        }
+    }
+
+    async fn is_album_saved(&self, album_id: &str) -> bool {
+        self.library.read().await.saved_album_ids.iter().any(|id| id == album_id)
+    }
+
+    async fn is_artist_followed(&self, artist_id: &str) -> bool {
+        self.library.read().await.followed_artist_ids.iter().any(|id| id == artist_id)
+    }
+
+    async fn toggle_saved_album(&self, album_id: &str) -> Result<bool> {
+        let mut library = self.library.write().await;
+        if !library.albums.iter().any(|a| a.id == album_id) {
+            return Err(anyhow!("Unknown album: {}", album_id));
+        }
+        if let Some(pos) = library.saved_album_ids.iter().position(|id| id == album_id) {
+            library.saved_album_ids.remove(pos);
+            Ok(false)
+        } else {
+            library.saved_album_ids.insert(0, album_id.to_string());
+            Ok(true)
+        }
+    }
+
+    async fn toggle_followed_artist(&self, artist_id: &str) -> Result<bool> {
+        let mut library = self.library.write().await;
+        if !library.artists.iter().any(|a| a.id == artist_id) {
+            return Err(anyhow!("Unknown artist: {}", artist_id));
+        }
+        if let Some(pos) = library.followed_artist_ids.iter().position(|id| id == artist_id) {
+            library.followed_artist_ids.remove(pos);
+            Ok(false)
+        } else {
+            library.followed_artist_ids.push(artist_id.to_string());
+            Ok(true)
+        }
+    }
+
+    /// The fake does not model `CurrentPlaybackContext`; playback state is
+    /// observable through `get_queue` and `get_available_devices` instead.
+    async fn get_current_playback(&self) -> Result<Option<CurrentPlaybackContext>> {
+        Ok(None)
+    }
+
+    async fn play(&self) -> Result<()> {
+        self.require_active_device().await?;
+        self.player.write().await.is_playing = true;
+        Ok(())
+    }
+
+    async fn pause(&self) -> Result<()> {
+        self.require_active_device().await?;
+        self.player.write().await.is_playing = false;
+        Ok(())
+    }
+
+    async fn next_track(&self) -> Result<()> {
+        self.require_active_device().await?;
+        let mut player = self.player.write().await;
+
+        if let Some(next) = player.queue.pop_front() {
+            player.current = Some(next);
+        } else if player.context_index + 1 < player.context.len() {
+            player.context_index += 1;
+            player.current = player.context.get(player.context_index).cloned();
+        } else if player.repeat == Some(RepeatState::All) && !player.context.is_empty() {
+            player.context_index = 0;
+            player.current = player.context.first().cloned();
+        } else {
+            player.current = None;
+            player.is_playing = false;
+        }
+        Ok(())
+    }
+
+    async fn previous_track(&self) -> Result<()> {
+        self.require_active_device().await?;
+        let mut player = self.player.write().await;
+        player.context_index = player.context_index.saturating_sub(1);
+        player.current = player.context.get(player.context_index).cloned();
+        Ok(())
+    }
+
+    async fn set_shuffle(&self, state: bool) -> Result<()> {
+        self.require_active_device().await?;
+        self.player.write().await.shuffle = state;
+        Ok(())
+    }
+
+    async fn set_repeat(&self, state: RepeatState) -> Result<()> {
+        self.require_active_device().await?;
+        self.player.write().await.repeat = Some(state);
+        Ok(())
+    }
+
+    async fn set_volume(&self, volume: u8) -> Result<()> {
+        self.require_active_device().await?;
+        self.player.write().await.volume = volume.min(100);
+        Ok(())
+    }
+
+    /// Position is not modelled (see `get_current_playback`); only the
+    /// preconditions of a real seek are checked
+    async fn seek(&self, position_ms: u32) -> Result<()> {
+        self.require_active_device().await?;
+        match &self.player.read().await.current {
+            Some(track) if position_ms <= track.duration_ms => Ok(()),
+            Some(_) => Err(anyhow!("Seek position {} ms is past the end of the track", position_ms)),
+            None => Err(anyhow!("Player command failed: Nothing is playing")),
+        }
+    }
+
+    async fn get_available_devices(&self) -> Result<Vec<DeviceInfo>> {
+        Ok(self.library.read().await.devices.clone())
+    }
+
+    async fn has_active_device(&self) -> bool {
+        self.library.read().await.devices.iter().any(|d| d.is_active)
+    }
+
+    async fn transfer_playback_to_device(&self, device_id: &str, start_playing: bool) -> Result<()> {
+        let mut library = self.library.write().await;
+        if !library.devices.iter().any(|d| d.id == device_id) {
+            return Err(anyhow!("Device not found: {}", device_id));
+        }
+        for device in library.devices.iter_mut() {
+            device.is_active = device.id == device_id;
+        }
+        drop(library);
+
+        if start_playing {
+            self.player.write().await.is_playing = true;
+        }
+        Ok(())
+    }
+
+    async fn play_tracks(&self, uris: &[String], offset: usize) -> Result<()> {
+        let context = {
+            let library = self.library.read().await;
+            uris.iter()
+                .map(|uri| library.find_track(uri).ok_or_else(|| anyhow!("Unknown track: {}", uri)))
+                .collect::<Result<Vec<_>>>()?
+        };
+        let start_uri = uris.get(offset).ok_or_else(|| anyhow!("Offset {} is out of range", offset))?;
+        self.start_context(context, start_uri).await
+    }
+
+    async fn play_context_from_track_uri(&self, context_uri: &str, track_uri: &str) -> Result<()> {
+        let id = context_uri.split(':').next_back().unwrap_or("");
+        let context = {
+            let library = self.library.read().await;
+            if context_uri.contains(":album:") {
+                library.albums.iter().find(|a| a.id == id).map(|a| a.tracks.clone())
+            } else if context_uri.contains(":playlist:") {
+                library.playlists.iter().find(|p| p.id == id).map(|p| p.tracks.clone())
+            } else if context_uri.contains(":artist:") {
+                library
+                    .artists
+                    .iter()
+                    .find(|a| a.id == id)
+                    .map(|a| library.artist_detail(a).top_tracks)
+            } else if context_uri.ends_with(":collection") {
+                Some(library.liked_tracks())
+            } else {
+                return Err(anyhow!("Unknown context type: {}", context_uri));
+            }
+        };
+
+        let context = context.ok_or_else(|| anyhow!("Unknown context: {}", context_uri))?;
+        self.start_context(context, track_uri).await
+    }
+
+    async fn get_queue(&self) -> Result<(Option<SearchTrack>, Vec<SearchTrack>)> {
+        let player = self.player.read().await.clone();
+        let mut upcoming: Vec<SearchTrack> = player.queue.into_iter().collect();
+        upcoming.extend(player.context.into_iter().skip(player.context_index + 1));
+
+        let mut current = player.current;
+        if let Some(track) = current.as_mut() {
+            self.mark_tracks_liked(std::slice::from_mut(track)).await;
+        }
+        self.mark_tracks_liked(&mut upcoming).await;
+        Ok((current, upcoming))
+    }
+
+    async fn add_to_queue(&self, track_uri: &str) -> Result<()> {
+        self.require_active_device().await?;
+        let track = self
+            .library
+            .read()
+            .await
+            .find_track(track_uri)
+            .ok_or_else(|| anyhow!("Unknown track: {}", track_uri))?;
+        self.player.write().await.queue.push_back(track);
+        Ok(())
+    }
+
+    async fn search(&self, query: &str, limit: u32) -> Result<SearchResults> {
+        let library = self.library.read().await;
+        if library.failing_queries.iter().any(|failing| failing == query) {
+            return Err(anyhow!("http status: 429"));
+        }
+        let parsed = SearchQuery::parse(query)?;
+        let needle = parsed.text.to_lowercase();
+        let matches = |s: &str| s.to_lowercase().contains(&needle);
+        // Field filters the fake library has data for
+        let filter = |field: SearchField, s: &str| {
+            parsed.value(field).is_none_or(|value| s.to_lowercase().contains(&value.to_lowercase()))
+        };
+        // Tracks match when every word is in the title, artist or album,
+        // so "artist title" queries work like they do on Spotify
+        let words: Vec<&str> = needle.split_whitespace().collect();
+        let track_matches = |t: &SearchTrack| {
+            let text = format!("{} {} {}", t.name, t.artist, t.album).to_lowercase();
+            words.iter().all(|word| text.contains(word))
+                && filter(SearchField::Track, &t.name)
+                && filter(SearchField::Artist, &t.artist)
+                && filter(SearchField::Album, &t.album)
+        };
+        let limit = limit as usize;
+
+        let mut seen = HashSet::new();
+        let mut results = SearchResults {
+            query: query.to_string(),
+            tracks: library
+                .all_tracks()
+                .filter(|t| track_matches(t))
+                .filter(|t| seen.insert(t.uri.clone()))
+                .take(limit)
+                .cloned()
+                .collect(),
+            albums: library
+                .albums
+                .iter()
+                .filter(|a| matches(&a.name) || matches(&a.artist))
+                .filter(|a| filter(SearchField::Album, &a.name) && filter(SearchField::Artist, &a.artist))
+                .take(limit)
+                .map(FakeLibrary::album_summary)
+                .collect(),
+            artists: library
+                .artists
+                .iter()
+                .filter(|a| matches(&a.name) && filter(SearchField::Artist, &a.name))
+                .take(limit)
+                .cloned()
+                .collect(),
+            playlists: library
+                .playlists
+                .iter()
+                .filter(|p| matches(&p.name))
+                .take(limit)
+                .map(|p| SearchPlaylist {
+                    id: p.id.clone(),
+                    name: p.name.clone(),
+                    owner: p.owner.clone(),
+                    total_tracks: p.total_tracks,
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        // Types the filters don't apply to are not searched
+        let sections = parsed.sections();
+        if !sections.contains(&SearchResultSection::Tracks) {
+            results.tracks.clear();
+        }
+        if !sections.contains(&SearchResultSection::Albums) {
+            results.albums.clear();
+        }
+        if !sections.contains(&SearchResultSection::Artists) {
+            results.artists.clear();
+        }
+        if !sections.contains(&SearchResultSection::Playlists) {
+            results.playlists.clear();
+        }
+
+        results.determine_best_match(&parsed.text);
+        Ok(results)
+    }
+
+    async fn search_category(
+        &self,
+        query: &str,
+        section: SearchResultSection,
+        offset: usize,
+        limit: u32,
+    ) -> Result<(SearchResults, u32, bool)> {
+        let all = self.search(query, u32::MAX).await?;
+        let page = |len: usize| offset.min(len)..(offset + limit as usize).min(len);
+        let mut results = SearchResults { query: all.query.clone(), best_match: section, ..Default::default() };
+        match section {
+            SearchResultSection::Tracks => results.tracks = all.tracks[page(all.tracks.len())].to_vec(),
+            SearchResultSection::Albums => results.albums = all.albums[page(all.albums.len())].to_vec(),
+            SearchResultSection::Artists => results.artists = all.artists[page(all.artists.len())].to_vec(),
+            SearchResultSection::Playlists => results.playlists = all.playlists[page(all.playlists.len())].to_vec(),
+        }
+        let total = all.len(section);
+        let has_more = offset + results.len(section) < total;
+        Ok((results, total as u32, has_more))
+    }
+
+    async fn get_album(&self, album_id: &str) -> Result<AlbumDetail> {
+        self.library
+            .read()
+            .await
+            .albums
+            .iter()
+            .find(|a| a.id == album_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown album: {}", album_id))
+    }
+
+    async fn get_playlist(&self, playlist_id: &str) -> Result<PlaylistDetail> {
+        let mut detail = self
+            .library
+            .read()
+            .await
+            .playlists
+            .iter()
+            .find(|p| p.id == playlist_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown playlist: {}", playlist_id))?;
+
+        detail.has_more = detail.tracks.len() > PLAYLIST_PAGE_SIZE;
+        detail.tracks.truncate(PLAYLIST_PAGE_SIZE);
+        detail.loading_more = false;
+        Ok(detail)
+    }
+
+    async fn get_more_playlist_tracks(&self, playlist_id: &str, offset: usize) -> Result<(Vec<SearchTrack>, u32, bool)> {
+        let library = self.library.read().await;
+        let playlist = library
+            .playlists
+            .iter()
+            .find(|p| p.id == playlist_id)
+            .ok_or_else(|| anyhow!("Unknown playlist: {}", playlist_id))?;
+
+        let tracks: Vec<SearchTrack> = playlist.tracks.iter().skip(offset).take(PLAYLIST_PAGE_SIZE).cloned().collect();
+        let has_more = offset + tracks.len() < playlist.tracks.len();
+        Ok((tracks, playlist.total_tracks, has_more))
+    }
+
+    async fn get_artist(&self, artist_id: &str) -> Result<ArtistDetail> {
+        let library = self.library.read().await;
+        library
+            .artists
+            .iter()
+            .find(|a| a.id == artist_id)
+            .map(|a| library.artist_detail(a))
+            .ok_or_else(|| anyhow!("Unknown artist: {}", artist_id))
+    }
+
+    async fn get_all_playlist_tracks(&self, playlist_id: &str) -> Result<Vec<SearchTrack>> {
+        let library = self.library.read().await;
+        library
+            .playlists
+            .iter()
+            .find(|p| p.id == playlist_id)
+            .map(|p| p.tracks.clone())
+            .ok_or_else(|| anyhow!("Unknown playlist: {}", playlist_id))
+    }
+
+    async fn get_tracks(&self, uris: &[String]) -> Result<Vec<Option<SearchTrack>>> {
+        let library = self.library.read().await;
+        Ok(uris.iter().map(|uri| library.find_track(uri)).collect())
+    }
+
+    async fn add_tracks_to_playlist(&self, playlist_id: &str, uris: &[String]) -> Result<String> {
+        let mut library = self.library.write().await;
+        let tracks = uris
+            .iter()
+            .map(|uri| library.find_track(uri).ok_or_else(|| anyhow!("Unknown track: {}", uri)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let playlist = library.edit_playlist(playlist_id)?;
+        playlist.tracks.extend(tracks);
+        playlist.total_tracks = playlist.tracks.len() as u32;
+        Ok(playlist.snapshot_id.clone())
+    }
+
+    async fn replace_playlist_tracks(&self, playlist_id: &str, uris: &[String]) -> Result<()> {
+        let mut library = self.library.write().await;
+        let tracks = uris
+            .iter()
+            .map(|uri| library.find_track(uri).ok_or_else(|| anyhow!("Unknown track: {}", uri)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let playlist = library.edit_playlist(playlist_id)?;
+        playlist.tracks = tracks;
+        playlist.total_tracks = playlist.tracks.len() as u32;
+        Ok(())
+    }
+
+    async fn remove_playlist_track(
+        &self,
+        playlist_id: &str,
+        track_uri: &str,
+        position: usize,
+        _snapshot_id: &str,
+    ) -> Result<String> {
+        let mut library = self.library.write().await;
+        let playlist = library.edit_playlist(playlist_id)?;
+        if playlist.tracks.get(position).map(|t| t.uri.as_str()) != Some(track_uri) {
+            return Err(anyhow!("Track {} is not at position {}", track_uri, position));
+        }
+
+        playlist.tracks.remove(position);
+        playlist.total_tracks = playlist.tracks.len() as u32;
+        Ok(playlist.snapshot_id.clone())
+    }
+
+    async fn move_playlist_track(
+        &self,
+        playlist_id: &str,
+        from: usize,
+        insert_before: usize,
+        _snapshot_id: &str,
+    ) -> Result<String> {
+        let mut library = self.library.write().await;
+        let playlist = library.edit_playlist(playlist_id)?;
+        if from >= playlist.tracks.len() || insert_before > playlist.tracks.len() {
+            return Err(anyhow!("Playlist position out of range"));
+        }
+
+        let track = playlist.tracks.remove(from);
+        let target = if insert_before > from { insert_before - 1 } else { insert_before };
+        playlist.tracks.insert(target, track);
+        Ok(playlist.snapshot_id.clone())
+    }
+
+    async fn create_playlist(&self, name: &str, visibility: PlaylistVisibility) -> Result<PlaylistItem> {
+        let mut library = self.library.write().await;
+        let id = (1..)
+            .map(|n| format!("fakeplaylist{:02}", n))
+            .find(|id| library.playlists.iter().all(|p| &p.id != id))
+            .unwrap_or_default();
+
+        // Like Spotify, list the newest playlist first
+        library.playlists.insert(0, PlaylistDetail {
+            uri: format!("spotify:playlist:{}", id),
+            id: id.clone(),
+            name: name.to_string(),
+            owner: "offline".to_string(),
+            description: String::new(),
+            snapshot_id: "fake-snapshot-1".to_string(),
+            editable: true,
+            public: visibility == PlaylistVisibility::Public,
+            collaborative: visibility == PlaylistVisibility::Collaborative,
+            tracks: Vec::new(),
+            total_tracks: 0,
+            has_more: false,
+            loading_more: false,
+        });
+        Ok(PlaylistItem { id, name: name.to_string(), editable: true })
+    }
+
+    async fn update_playlist_details(&self, playlist_id: &str, name: Option<&str>, description: Option<&str>) -> Result<()> {
+        let mut library = self.library.write().await;
+        let playlist = library.edit_playlist(playlist_id)?;
+        if let Some(name) = name {
+            playlist.name = name.to_string();
+        }
+        if let Some(description) = description {
+            playlist.description = description.to_string();
+        }
+        Ok(())
+    }
+
+    async fn unfollow_playlist(&self, playlist_id: &str) -> Result<()> {
+        let mut library = self.library.write().await;
+        let pos = library
+            .playlists
+            .iter()
+            .position(|p| p.id == playlist_id)
+            .ok_or_else(|| anyhow!("Unknown playlist: {}", playlist_id))?;
+        let playlist = library.playlists.remove(pos);
+        library.unfollowed_playlists.push(playlist);
+        Ok(())
+    }
+
+    async fn follow_playlist(&self, playlist_id: &str) -> Result<()> {
+        let mut library = self.library.write().await;
+        if library.playlists.iter().any(|p| p.id == playlist_id) {
+            return Ok(());
+        }
+        let pos = library
+            .unfollowed_playlists
+            .iter()
+            .position(|p| p.id == playlist_id)
+            .ok_or_else(|| anyhow!("Unknown playlist: {}", playlist_id))?;
+        let playlist = library.unfollowed_playlists.remove(pos);
+        library.playlists.insert(0, playlist);
+        Ok(())
+    }
+
+    async fn get_user_playlists(&self, limit: u32) -> Result<Vec<PlaylistItem>> {
+        Ok(self
+            .library
+            .read()
+            .await
+            .playlists
+            .iter()
+            .take(limit as usize)
+            .map(|p| PlaylistItem {
+                id: p.id.clone(),
+                name: p.name.clone(),
+                editable: p.editable,
+            })
+            .collect())
+    }
+
+    async fn get_liked_songs_page(&self, offset: usize, limit: u32) -> Result<(Vec<SearchTrack>, u32, bool)> {
+        Ok(page(self.library.read().await.liked_tracks(), offset, limit))
+    }
+
+    async fn get_saved_albums_page(&self, offset: usize, limit: u32) -> Result<(Vec<SearchAlbum>, u32, bool)> {
+        Ok(page(self.get_saved_albums(u32::MAX).await?, offset, limit))
+    }
+
+    async fn get_followed_artists_page(&self, after: Option<&str>, limit: u32) -> Result<(Vec<SearchArtist>, u32, bool)> {
+        let artists = self.get_followed_artists(u32::MAX).await?;
+        let offset = match after {
+            Some(after) => artists.iter().position(|a| a.id == after).map_or(artists.len(), |i| i + 1),
+            None => 0,
+        };
+        Ok(page(artists, offset, limit))
+    }
+
+    /// The fake does not record when songs were liked, so `added_at` is empty
+    async fn get_all_liked_songs(&self) -> Result<Vec<SavedTrack>> {
+        Ok(self
+            .library
+            .read()
+            .await
+            .liked_tracks()
+            .into_iter()
+            .map(|track| SavedTrack { track, added_at: String::new() })
+            .collect())
+    }
+
+    async fn save_liked_songs(&self, songs: &[(String, String)]) -> Result<()> {
+        let mut library = self.library.write().await;
+        for (uri, _) in songs {
+            let id = uri.split(':').next_back().unwrap_or(uri).to_string();
+            if !library.liked_track_ids.contains(&id) {
+                library.liked_track_ids.insert(0, id);
+            }
+        }
+        Ok(())
+    }
+
+    async fn save_albums(&self, album_ids: &[String]) -> Result<()> {
+        let mut library = self.library.write().await;
+        for id in album_ids {
+            if !library.albums.iter().any(|a| &a.id == id) {
+                return Err(anyhow!("Unknown album: {}", id));
+            }
+            if !library.saved_album_ids.contains(id) {
+                library.saved_album_ids.insert(0, id.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn follow_artists(&self, artist_ids: &[String]) -> Result<()> {
+        let mut library = self.library.write().await;
+        for id in artist_ids {
+            if !library.artists.iter().any(|a| &a.id == id) {
+                return Err(anyhow!("Unknown artist: {}", id));
+            }
+            if !library.followed_artist_ids.contains(id) {
+                library.followed_artist_ids.push(id.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_saved_albums(&self, limit: u32) -> Result<Vec<SearchAlbum>> {
+        let library = self.library.read().await;
+        Ok(library
+            .saved_album_ids
+            .iter()
+            .filter_map(|id| library.albums.iter().find(|a| &a.id == id))
+            .take(limit as usize)
+            .map(|album| SearchAlbum { saved: true, ..FakeLibrary::album_summary(album) })
+            .collect())
+    }
+
+    async fn get_followed_artists(&self, limit: u32) -> Result<Vec<SearchArtist>> {
+        let library = self.library.read().await;
+        Ok(library
+            .followed_artist_ids
+            .iter()
+            .filter_map(|id| library.artists.iter().find(|a| &a.id == id))
+            .take(limit as usize)
+            .map(|artist| SearchArtist { followed: true, ..artist.clone() })
+            .collect())
+    }
+
+    async fn get_recently_played(&self, limit: u32) -> Result<Vec<SearchTrack>> {
+        let library = self.library.read().await;
+        Ok(library
+            .recently_played_ids
+            .iter()
+            .filter_map(|id| library.find_track_by_id(id))
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn get_saved_shows_page(&self, offset: usize, limit: u32) -> Result<(Vec<Show>, u32, bool)> {
+        let library = self.library.read().await;
+        let shows = library
+            .saved_show_ids
+            .iter()
+            .filter_map(|id| library.find_show(id).ok())
+            .map(|detail| detail.show.clone())
+            .collect();
+        Ok(page(shows, offset, limit))
+    }
+
+    async fn get_show(&self, show_id: &str) -> Result<ShowDetail> {
+        let library = self.library.read().await;
+        let detail = library.find_show(show_id)?;
+        Ok(ShowDetail { episodes: Vec::new(), ..detail.clone() })
+    }
+
+    async fn get_show_episodes_page(&self, show_id: &str, offset: usize, limit: u32) -> Result<(Vec<Episode>, u32, bool)> {
+        let library = self.library.read().await;
+        let episodes = library
+            .find_show(show_id)?
+            .episodes
+            .iter()
+            .map(|episode| Episode {
+                marked_played: library.played_episode_ids.contains(&episode.id),
+                ..episode.clone()
+            })
+            .collect();
+        Ok(page(episodes, offset, limit))
+    }
+
+    /// The simulated player has no playback position, so `position_ms` is
+    /// ignored. Episodes play as tracks by the show's publisher.
+    async fn play_episode(&self, show_uri: &str, episode_uri: &str, _position_ms: u32) -> Result<()> {
+        let context = {
+            let library = self.library.read().await;
+            let detail = library.find_show(show_uri.split(':').next_back().unwrap_or(show_uri))?;
+            detail
+                .episodes
+                .iter()
+                .map(|episode| SearchTrack {
+                    id: episode.id.clone(),
+                    name: episode.name.clone(),
+                    artist: detail.show.publisher.clone(),
+                    artists: vec![detail.show.publisher.clone()],
+                    album: detail.show.name.clone(),
+                    duration_ms: episode.duration_ms,
+                    uri: episode.uri.clone(),
+                    liked: false,
+                })
+                .collect()
+        };
+        self.start_context(context, episode_uri).await
+    }
+
+    async fn toggle_episode_played(&self, episode_id: &str) -> Result<bool> {
+        let mut library = self.library.write().await;
+        if let Some(pos) = library.played_episode_ids.iter().position(|id| id == episode_id) {
+            library.played_episode_ids.remove(pos);
+            Ok(false)
+        } else {
+            library.played_episode_ids.push(episode_id.to_string());
+            Ok(true)
+        }
+    }
+
+    async fn get_new_releases(&self, limit: u32) -> Result<Vec<SearchAlbum>> {
+        let library = self.library.read().await;
+        Ok(library
+            .new_release_ids
+            .iter()
+            .filter_map(|id| library.albums.iter().find(|a| &a.id == id))
+            .take(limit as usize)
+            .map(FakeLibrary::album_summary)
+            .collect())
+    }
+
+    async fn get_categories(&self, limit: u32) -> Result<Vec<Category>> {
+        let library = self.library.read().await;
+        Ok(library
+            .categories
+            .iter()
+            .take(limit as usize)
+            .map(|(category, _)| category.clone())
+            .collect())
+    }
+
+    async fn get_category_playlists_page(
+        &self,
+        category_id: &str,
+        offset: usize,
+        limit: u32,
+    ) -> Result<(Vec<SearchPlaylist>, u32, bool)> {
+        let library = self.library.read().await;
+        let (_, playlist_ids) = library
+            .categories
+            .iter()
+            .find(|(category, _)| category.id == category_id)
+            .ok_or_else(|| anyhow!("Unknown category: {}", category_id))?;
+        let playlists = playlist_ids
+            .iter()
+            .filter_map(|id| library.playlists.iter().find(|p| &p.id == id))
+            .map(|p| SearchPlaylist {
+                id: p.id.clone(),
+                name: p.name.clone(),
+                owner: p.owner.clone(),
+                total_tracks: p.total_tracks,
+            })
+            .collect();
+        Ok(page(playlists, offset, limit))
+    }
+
+    async fn get_top_tracks(&self, range: TopRange, limit: u32) -> Result<Vec<SearchTrack>> {
+        let library = self.library.read().await;
+        Ok(library
+            .top_track_ids
+            .get(&range)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| library.find_track_by_id(id))
+            .take(limit as usize)
+            .collect())
+    }
+
+    async fn get_top_artists(&self, range: TopRange, limit: u32) -> Result<Vec<SearchArtist>> {
+        let library = self.library.read().await;
+        Ok(library
+            .top_artist_ids
+            .get(&range)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| library.artists.iter().find(|a| &a.id == id))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+}
+
+/// `limit` items from `offset`, in the `(items, total, has_more)` shape of
+/// the library pages
+fn page<T>(items: Vec<T>, offset: usize, limit: u32) -> (Vec<T>, u32, bool) {
+    let total = items.len();
+    let page: Vec<T> = items.into_iter().skip(offset).take(limit as usize).collect();
+    let has_more = offset + page.len() < total;
+    (page, total as u32, has_more)
+}
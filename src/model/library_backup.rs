@@ -0,0 +1,374 @@
+//! Whole-library backup archives and restoring them
+//!
+//! An archive holds liked songs (with when they were saved), saved albums,
+//! followed artists and every playlist in the library with all its tracks.
+//! Restoring never removes anything: the archive is compared with the
+//! account first and only what is missing there is added, as a
+//! `RestorePlan` the user confirms.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::content::{PlaylistDetail, SavedTrack, SearchAlbum, SearchArtist, SearchTrack};
+use super::playlist_export::ExportedTrack;
+use super::types::{PlaylistItem, PlaylistVisibility};
+
+/// Value of the `format` field, telling archives apart from playlist exports
+pub const BACKUP_FORMAT: &str = "spotify-rs-library";
+
+/// Current archive version. Newer archives are refused.
+pub const BACKUP_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LibraryBackup {
+    pub format: String,
+    pub version: u32,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub liked_songs: Vec<BackupLikedSong>,
+    #[serde(default)]
+    pub saved_albums: Vec<BackupItem>,
+    #[serde(default)]
+    pub followed_artists: Vec<BackupItem>,
+    #[serde(default)]
+    pub playlists: Vec<BackupPlaylist>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BackupLikedSong {
+    #[serde(flatten)]
+    pub track: ExportedTrack,
+    /// RFC 3339 time the song was liked, kept when restoring
+    #[serde(default)]
+    pub added_at: String,
+}
+
+/// A saved album or followed artist
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BackupItem {
+    pub uri: String,
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BackupPlaylist {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Owned or collaborative; other playlists are only followed
+    #[serde(default)]
+    pub editable: bool,
+    #[serde(default)]
+    pub public: bool,
+    #[serde(default)]
+    pub collaborative: bool,
+    #[serde(default)]
+    pub tracks: Vec<ExportedTrack>,
+}
+
+impl BackupPlaylist {
+    pub fn new(playlist: &PlaylistDetail, tracks: &[SearchTrack]) -> Self {
+        Self {
+            id: playlist.id.clone(),
+            name: playlist.name.clone(),
+            description: playlist.description.clone(),
+            editable: playlist.editable,
+            public: playlist.public,
+            collaborative: playlist.collaborative,
+            tracks: tracks.iter().map(ExportedTrack::from).collect(),
+        }
+    }
+}
+
+impl LibraryBackup {
+    pub fn new(
+        liked_songs: &[SavedTrack],
+        saved_albums: &[SearchAlbum],
+        followed_artists: &[SearchArtist],
+        playlists: Vec<BackupPlaylist>,
+    ) -> Self {
+        Self {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            liked_songs: liked_songs
+                .iter()
+                .map(|saved| BackupLikedSong {
+                    track: ExportedTrack::from(&saved.track),
+                    added_at: saved.added_at.clone(),
+                })
+                .collect(),
+            saved_albums: saved_albums
+                .iter()
+                .map(|album| BackupItem { uri: format!("spotify:album:{}", album.id), name: album.name.clone() })
+                .collect(),
+            followed_artists: followed_artists
+                .iter()
+                .map(|artist| BackupItem { uri: format!("spotify:artist:{}", artist.id), name: artist.name.clone() })
+                .collect(),
+            playlists,
+        }
+    }
+
+    /// Whether `text` is an archive rather than some other JSON file
+    pub fn is_backup(text: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(text)
+            .is_ok_and(|value| value["format"].as_str() == Some(BACKUP_FORMAT))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(text).context("Not a JSON file")?;
+        if value["format"].as_str() != Some(BACKUP_FORMAT) {
+            bail!("Not a spotify-rs library backup (missing \"format\": \"{}\")", BACKUP_FORMAT);
+        }
+        match value["version"].as_u64() {
+            Some(version) if version as u32 <= BACKUP_VERSION => {}
+            Some(version) => bail!(
+                "Library backup version {} is newer than this spotify-rs supports ({})",
+                version,
+                BACKUP_VERSION
+            ),
+            None => bail!("Library backup has no version"),
+        }
+
+        serde_json::from_value(value).context("Invalid library backup")
+    }
+
+    /// Write to `<dir>/library-<timestamp>.json`. Returns the file written.
+    pub fn write_to_dir(&self, dir: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(dir).with_context(|| format!("Could not create {}", dir.display()))?;
+        let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let path = dir.join(format!("library-{}.json", stamp));
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(&path, json).with_context(|| format!("Could not write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Compare with the account's library and list what is missing there
+    pub fn plan_restore(&self, source: String, current: &CurrentLibrary) -> RestorePlan {
+        let missing = |items: &[BackupItem], have: &HashSet<String>| -> Vec<BackupItem> {
+            items.iter().filter(|item| !have.contains(&item.uri)).cloned().collect()
+        };
+
+        let mut playlists = Vec::new();
+        for archived in &self.playlists {
+            let uris = || archived.tracks.iter().map(|track| track.uri.clone()).collect::<Vec<_>>();
+            match current.playlists.iter().find(|playlist| playlist.id == archived.id) {
+                Some(playlist) if playlist.editable => {
+                    let have: HashSet<&String> = current.playlist_tracks.get(&playlist.id).into_iter().flatten().collect();
+                    let uris: Vec<String> = uris().into_iter().filter(|uri| !have.contains(uri)).collect();
+                    if !uris.is_empty() {
+                        playlists.push(PlaylistRestore::AddTracks { id: playlist.id.clone(), name: playlist.name.clone(), uris });
+                    }
+                }
+                Some(_) => {}
+                // Gone from the library: own playlists are recreated, others followed again
+                None if archived.editable => playlists.push(PlaylistRestore::Create {
+                    name: archived.name.clone(),
+                    description: archived.description.clone(),
+                    visibility: PlaylistVisibility::of(archived.public, archived.collaborative),
+                    uris: uris(),
+                }),
+                None => playlists.push(PlaylistRestore::Follow { id: archived.id.clone(), name: archived.name.clone() }),
+            }
+        }
+
+        RestorePlan {
+            source,
+            liked_songs: self
+                .liked_songs
+                .iter()
+                .filter(|song| !current.liked_songs.contains(&song.track.uri))
+                .cloned()
+                .collect(),
+            saved_albums: missing(&self.saved_albums, &current.saved_albums),
+            followed_artists: missing(&self.followed_artists, &current.followed_artists),
+            playlists,
+        }
+    }
+}
+
+/// What the account has now, as URIs
+#[derive(Clone, Debug, Default)]
+pub struct CurrentLibrary {
+    pub liked_songs: HashSet<String>,
+    pub saved_albums: HashSet<String>,
+    pub followed_artists: HashSet<String>,
+    pub playlists: Vec<PlaylistItem>,
+    /// Track URIs of the editable playlists that are also in the archive
+    pub playlist_tracks: HashMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlaylistRestore {
+    Follow { id: String, name: String },
+    Create { name: String, description: String, visibility: PlaylistVisibility, uris: Vec<String> },
+    /// Tracks missing from a playlist that still exists; appended at the end
+    AddTracks { id: String, name: String, uris: Vec<String> },
+}
+
+/// Everything a restore would add, shown to the user before it is applied
+#[derive(Clone, Debug)]
+pub struct RestorePlan {
+    /// Archive file name, for the header
+    pub source: String,
+    pub liked_songs: Vec<BackupLikedSong>,
+    pub saved_albums: Vec<BackupItem>,
+    pub followed_artists: Vec<BackupItem>,
+    pub playlists: Vec<PlaylistRestore>,
+}
+
+impl RestorePlan {
+    pub fn is_empty(&self) -> bool {
+        self.liked_songs.is_empty()
+            && self.saved_albums.is_empty()
+            && self.followed_artists.is_empty()
+            && self.playlists.is_empty()
+    }
+
+    /// One line per change, for the restore screen
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for artist in &self.followed_artists {
+            lines.push(format!("Follow artist {}", artist.name));
+        }
+        for album in &self.saved_albums {
+            lines.push(format!("Save album {}", album.name));
+        }
+        for song in &self.liked_songs {
+            lines.push(format!("Like {} - {}", song.track.artists.join(", "), song.track.name));
+        }
+        for playlist in &self.playlists {
+            lines.push(match playlist {
+                PlaylistRestore::Follow { name, .. } => format!("Follow playlist \"{}\"", name),
+                PlaylistRestore::Create { name, uris, .. } => {
+                    format!("Recreate playlist \"{}\" with {} tracks", name, uris.len())
+                }
+                PlaylistRestore::AddTracks { name, uris, .. } => {
+                    format!("Add {} missing tracks to \"{}\"", uris.len(), name)
+                }
+            });
+        }
+        lines
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} liked songs · {} albums · {} artists · {} playlists",
+            self.liked_songs.len(),
+            self.saved_albums.len(),
+            self.followed_artists.len(),
+            self.playlists.len(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(n: u32) -> ExportedTrack {
+        ExportedTrack {
+            uri: format!("spotify:track:t{}", n),
+            name: format!("Track {}", n),
+            artists: vec!["Artist".to_string()],
+            album: "Album".to_string(),
+            duration_ms: 1000,
+        }
+    }
+
+    fn item(uri: &str) -> BackupItem {
+        BackupItem { uri: uri.to_string(), name: uri.to_string() }
+    }
+
+    fn playlist(id: &str, editable: bool, tracks: &[u32]) -> BackupPlaylist {
+        BackupPlaylist {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            editable,
+            public: false,
+            collaborative: false,
+            tracks: tracks.iter().map(|&n| track(n)).collect(),
+        }
+    }
+
+    fn archive() -> LibraryBackup {
+        LibraryBackup {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created_at: String::new(),
+            liked_songs: (1..=3)
+                .map(|n| BackupLikedSong { track: track(n), added_at: format!("2024-01-0{}T00:00:00Z", n) })
+                .collect(),
+            saved_albums: vec![item("spotify:album:a1"), item("spotify:album:a2")],
+            followed_artists: vec![item("spotify:artist:r1")],
+            playlists: vec![
+                playlist("kept", true, &[1, 2, 3]),
+                playlist("deleted", true, &[4]),
+                playlist("unfollowed", false, &[5]),
+                playlist("followed", false, &[6]),
+            ],
+        }
+    }
+
+    #[test]
+    fn archives_round_trip_and_are_told_apart_from_exports() {
+        let dir = std::env::temp_dir().join(format!("spotify-rs-test-backup-{}", std::process::id()));
+        let backup = archive();
+
+        let path = backup.write_to_dir(&dir).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(LibraryBackup::is_backup(&text));
+        assert_eq!(LibraryBackup::from_json(&text).unwrap(), backup);
+        // Liked songs keep their date next to the track fields
+        assert!(text.contains("\"added_at\": \"2024-01-01T00:00:00Z\""));
+
+        assert!(!LibraryBackup::is_backup(r#"{ "format": "spotify-rs-playlist", "version": 1 }"#));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn restore_adds_only_what_is_missing() {
+        let editable = |id: &str, editable| PlaylistItem { id: id.to_string(), name: id.to_string(), editable };
+        let current = CurrentLibrary {
+            liked_songs: HashSet::from(["spotify:track:t2".to_string()]),
+            saved_albums: HashSet::from(["spotify:album:a1".to_string()]),
+            followed_artists: HashSet::from(["spotify:artist:r1".to_string()]),
+            playlists: vec![editable("kept", true), editable("followed", false)],
+            playlist_tracks: HashMap::from([("kept".to_string(), vec!["spotify:track:t2".to_string()])]),
+        };
+
+        let plan = archive().plan_restore("backup.json".to_string(), &current);
+
+        let liked: Vec<&str> = plan.liked_songs.iter().map(|song| song.track.uri.as_str()).collect();
+        assert_eq!(liked, ["spotify:track:t1", "spotify:track:t3"]);
+        assert_eq!(plan.saved_albums, [item("spotify:album:a2")]);
+        assert!(plan.followed_artists.is_empty());
+        assert_eq!(
+            plan.playlists,
+            [
+                PlaylistRestore::AddTracks {
+                    id: "kept".to_string(),
+                    name: "kept".to_string(),
+                    uris: vec!["spotify:track:t1".to_string(), "spotify:track:t3".to_string()],
+                },
+                PlaylistRestore::Create {
+                    name: "deleted".to_string(),
+                    description: String::new(),
+                    visibility: PlaylistVisibility::Private,
+                    uris: vec!["spotify:track:t4".to_string()],
+                },
+                PlaylistRestore::Follow { id: "unfollowed".to_string(), name: "unfollowed".to_string() },
+            ]
+        );
+        assert_eq!(plan.lines().len(), 6);
+        assert_eq!(plan.summary(), "2 liked songs · 1 albums · 0 artists · 3 playlists");
+    }
+}
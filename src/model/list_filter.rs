@@ -0,0 +1,121 @@
+//! Incremental `/` filter over the list shown in the main content area
+//!
+//! The query is split into words, and a row is kept when every word fuzzily
+//! matches one of its fields (name, artists, album, ...): the word's
+//! characters appear in the field in order, ignoring case. Rows keep their
+//! place in the list and the selection keeps pointing into the whole list, so
+//! anything done to the selected row acts on the right item.
+
+/// The `/` filter of the content list
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListFilter {
+    pub query: String,
+    /// Keys go to the query until Enter or Esc
+    pub editing: bool,
+}
+
+impl ListFilter {
+    /// Indices of the rows whose fields match the query, in list order
+    pub fn matching_rows<'a>(&self, rows: impl IntoIterator<Item = Vec<&'a str>>) -> Vec<usize> {
+        let words: Vec<&str> = self.query.split_whitespace().collect();
+        rows.into_iter()
+            .enumerate()
+            .filter(|(_, fields)| {
+                words
+                    .iter()
+                    .all(|word| fields.iter().any(|field| fuzzy_match(word, field).is_some()))
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Character positions of a row as displayed that match the query, to
+    /// highlight them. Columns are told apart by runs of spaces, and like
+    /// fields each word is matched within one column.
+    pub fn highlights(&self, text: &str) -> Vec<usize> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut columns = Vec::new();
+        let mut start = 0;
+        for i in 0..=chars.len() {
+            let gap = i == chars.len() || (chars[i] == ' ' && chars.get(i + 1) == Some(&' '));
+            if gap {
+                if start < i {
+                    columns.push((start, chars[start..i].iter().collect::<String>()));
+                }
+                start = i + 1;
+            }
+        }
+
+        let mut positions: Vec<usize> = self
+            .query
+            .split_whitespace()
+            .filter_map(|word| {
+                columns.iter().find_map(|(start, column)| {
+                    fuzzy_match(word, column).map(|found| found.into_iter().map(|i| start + i).collect::<Vec<_>>())
+                })
+            })
+            .flatten()
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
+}
+
+/// Character positions of `text` matching `word`: the first place it
+/// appears as a whole, otherwise the earliest characters matching it in
+/// order. `None` if `text` doesn't contain the characters of `word` in order.
+pub fn fuzzy_match(word: &str, text: &str) -> Option<Vec<usize>> {
+    let same = |a: &char, b: &char| a.to_lowercase().eq(b.to_lowercase());
+    let word: Vec<char> = word.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    if word.is_empty() || word.len() > text.len() {
+        return None;
+    }
+
+    if let Some(start) = text.windows(word.len()).position(|window| window.iter().zip(&word).all(|(a, b)| same(a, b))) {
+        return Some((start..start + word.len()).collect());
+    }
+
+    let mut positions = Vec::with_capacity(word.len());
+    let mut wanted = word.iter().peekable();
+    for (i, c) in text.iter().enumerate() {
+        if wanted.next_if(|w| same(w, c)).is_some() {
+            positions.push(i);
+        }
+    }
+    wanted.peek().is_none().then_some(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_match_in_order_preferring_whole_words() {
+        assert_eq!(fuzzy_match("tide", "Low Tide"), Some(vec![4, 5, 6, 7]));
+        assert_eq!(fuzzy_match("lwt", "Low Tide"), Some(vec![0, 2, 4]));
+        assert_eq!(fuzzy_match("twl", "Low Tide"), None);
+        assert_eq!(fuzzy_match("ÉTÉ", "l'été"), Some(vec![2, 3, 4]));
+    }
+
+    #[test]
+    fn every_word_must_match_some_field() {
+        let rows = vec![
+            vec!["Low Tide", "Tidal", "Harbor Static"],
+            vec!["Polar Night", "Northern Lights", "Aurora Fields"],
+            vec!["Salt", "Tidal", "Harbor Static"],
+        ];
+        let filter = |query: &str| ListFilter { query: query.to_string(), editing: false };
+
+        assert_eq!(filter("harbor").matching_rows(rows.clone()), [0, 2]);
+        assert_eq!(filter("harbor salt").matching_rows(rows.clone()), [2]);
+        assert_eq!(filter("plnt").matching_rows(rows.clone()), [1]);
+        assert_eq!(filter("").matching_rows(rows.clone()).len(), 3);
+        // Words don't match across fields
+        assert!(filter("tidalharbor").matching_rows(rows).is_empty());
+        assert_eq!(filter("salt harb").highlights("Salt   Harbor Static"), [0, 1, 2, 3, 7, 8, 9, 10]);
+        // The letters of "ws" are in order across the columns, but not within one
+        assert!(filter("ws").highlights("Low Tide   Salt").is_empty());
+    }
+}
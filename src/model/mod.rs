@@ -6,33 +6,65 @@
 //! - `types`: Core type definitions (enums, UI state, etc.)
 //! - `playback`: Playback-related state (track metadata, timing, settings)
 //! - `content`: Content view data (search results, playlists, albums, etc.)
-//! - `cache`: Liked songs cache for fast lookup
+//! - `cache`: Liked songs, saved albums and followed artists caches for fast lookup
+//! - `queue`: Client-side queue fed to the local player
+//! - `playlist_export`: Versioned JSON format for playlist export/import
+//! - `track_list_import`: CSV/M3U/text track lists matched against search
+//! - `library_backup`: Whole-library backup archives and restore plans
+//! - `search_query`: Field filters (`artist:`, `year:`, ...) in search queries
+//! - `search_history`: Searches kept across sessions for recall
+//! - `list_filter`: Fuzzy `/` filter over the list in the main content area
+//! - `settings`: Settings editable from the settings overlay
+//! - `spotify_api`: Trait abstracting the Spotify backend
 //! - `spotify_client`: Spotify API client wrapper
+//! - `fake_client`: In-memory Spotify backend for tests and offline use
 //! - `app_model`: Main application model with state management methods
 
 mod types;
 mod playback;
 mod content;
 mod cache;
+mod queue;
+mod playlist_export;
+mod track_list_import;
+mod library_backup;
+mod search_query;
+mod search_history;
+mod list_filter;
+mod settings;
+mod spotify_api;
 mod spotify_client;
+mod fake_client;
 mod app_model;
 
 // Re-export all public types for convenient access
 pub use types::{
     ActiveSection, DeviceInfo, RepeatState,
-    SearchResultSection, ArtistDetailSection, SelectedItem, UiState,
+    SearchResultSection, ArtistDetailSection, HomeSection, TopTab, TopRange, SelectedItem, UiState,
+    PlaylistItem, PlaylistPrompt, PlaylistPromptKind,
 };
 
 pub use playback::{
-    TrackMetadata, PlaybackInfo,
+    TrackMetadata, PlaybackInfo, parse_timestamp,
 };
 
 pub use content::{
     SearchTrack, SearchAlbum, SearchArtist, SearchPlaylist, SearchResults,
-    AlbumDetail, PlaylistDetail, ArtistDetail, ContentView, ContentState,
+    AlbumDetail, PlaylistDetail, PlaylistTrackRef, ArtistDetail, ContentView, ContentState,
+    ListPaging, LibraryPageRequest, Show, Episode, ShowDetail, Category, HomePage, TopItems,
 };
 
 
+pub use playlist_export::PlaylistExport;
+pub use library_backup::{BackupPlaylist, CurrentLibrary, LibraryBackup, PlaylistRestore, RestorePlan};
+pub use track_list_import::{
+    ImportEntry, ImportReview, MatchStatus, TrackListFormat, TrackQuery, IMPORT_CANDIDATES, parse_track_list,
+};
+pub use search_query::SearchQuery;
+pub use search_history::SearchHistory;
+pub use list_filter::ListFilter;
+pub use spotify_api::SpotifyApi;
 pub use spotify_client::SpotifyClient;
+pub use fake_client::{FakeSpotifyClient, FakeLibrary};
 
 pub use app_model::AppModel;
@@ -1,6 +1,6 @@
 //! Playback-related types and state management
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use rspotify::model::{CurrentPlaybackContext, PlayableItem};
 use rspotify::prelude::Id;
 
@@ -76,6 +76,12 @@ impl TrackMetadata {
     }
 }
 
+/// How long position reports that disagree with a seek are treated as stale
+const SEEK_SETTLE_TIME: Duration = Duration::from_secs(3);
+
+/// Reports this close to the seek target confirm the seek
+const SEEK_TOLERANCE_MS: i64 = 2000;
+
 /// Internal timing state for smooth progress bar updates
 #[derive(Clone)]
 pub struct PlaybackTiming {
@@ -83,6 +89,8 @@ pub struct PlaybackTiming {
     pub last_update: Instant,
     pub is_playing: bool,
     pub duration_ms: u32,
+    /// Target and time of the last seek, until the player confirms it
+    seek_target: Option<(u32, Instant)>,
 }
 
 impl Default for PlaybackTiming {
@@ -92,6 +100,7 @@ impl Default for PlaybackTiming {
             last_update: Instant::now(),
             is_playing: false,
             duration_ms: 0,
+            seek_target: None,
         }
     }
 }
@@ -106,7 +115,46 @@ impl PlaybackTiming {
         }
     }
 
+    /// Jump to a position immediately after a successful seek. Reports from
+    /// before the seek are ignored until the player catches up.
+    pub fn seek(&mut self, position_ms: u32) {
+        let now = Instant::now();
+        self.position_ms = if self.duration_ms > 0 { position_ms.min(self.duration_ms) } else { position_ms };
+        self.last_update = now;
+        self.seek_target = Some((self.position_ms, now));
+    }
+
+    /// Whether a reported position predates the last seek
+    fn is_stale_after_seek(&mut self, reported_ms: u32) -> bool {
+        let Some((target_ms, seeked_at)) = self.seek_target else {
+            return false;
+        };
+
+        let elapsed = if self.is_playing { seeked_at.elapsed().as_millis() as i64 } else { 0 };
+        let expected = target_ms as i64 + elapsed;
+        if seeked_at.elapsed() < SEEK_SETTLE_TIME && (reported_ms as i64 - expected).abs() > SEEK_TOLERANCE_MS {
+            return true;
+        }
+
+        self.seek_target = None;
+        false
+    }
+
+    /// Take a position from a full playback state refresh
+    pub fn sync_position(&mut self, position_ms: u32, is_playing: bool) {
+        if !self.is_stale_after_seek(position_ms) {
+            self.position_ms = position_ms;
+            self.last_update = Instant::now();
+        }
+        self.is_playing = is_playing;
+    }
+
     pub fn update_position(&mut self, new_position_ms: u32, is_playing: bool) {
+        if self.is_stale_after_seek(new_position_ms) {
+            self.is_playing = is_playing;
+            return;
+        }
+
         let current_calculated = self.current_position_ms();
         let diff = new_position_ms as i64 - current_calculated as i64;
 
@@ -145,7 +193,7 @@ impl Default for PlaybackSettings {
 }
 
 /// Complete playback information for rendering the UI
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PlaybackInfo {
     pub track: TrackMetadata,
     pub progress_ms: u32,
@@ -154,14 +202,72 @@ pub struct PlaybackInfo {
     pub settings: PlaybackSettings,
 }
 
-impl Default for PlaybackInfo {
-    fn default() -> Self {
-        Self {
-            track: TrackMetadata::default(),
-            progress_ms: 0,
-            duration_ms: 0,
-            is_playing: false,
-            settings: PlaybackSettings::default(),
+/// Parse `mm:ss`, `h:mm:ss` or plain seconds into milliseconds
+pub fn parse_timestamp(input: &str) -> Option<u32> {
+    let parts: Vec<&str> = input.trim().split(':').collect();
+    if parts.len() > 3 || parts.iter().any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+
+    let mut seconds: u32 = 0;
+    for (i, part) in parts.iter().enumerate() {
+        let value: u32 = part.parse().ok()?;
+        // Only the leading component may exceed 59
+        if i > 0 && value > 59 {
+            return None;
+        }
+        seconds = seconds.checked_mul(60)?.checked_add(value)?;
+    }
+    seconds.checked_mul(1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing_at(position_ms: u32, is_playing: bool) -> PlaybackTiming {
+        PlaybackTiming {
+            position_ms,
+            is_playing,
+            duration_ms: 200_000,
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn seek_applies_immediately_and_ignores_stale_reports() {
+        let mut timing = timing_at(10_000, false);
+
+        timing.seek(120_000);
+        assert_eq!(timing.current_position_ms(), 120_000);
+
+        // The player has not caught up yet
+        timing.update_position(10_500, false);
+        timing.sync_position(10_500, false);
+        assert_eq!(timing.current_position_ms(), 120_000);
+
+        // A report near the target confirms the seek and is applied
+        timing.update_position(120_400, false);
+        assert_eq!(timing.current_position_ms(), 120_400);
+        timing.update_position(30_000, false);
+        assert_eq!(timing.current_position_ms(), 30_000);
+    }
+
+    #[test]
+    fn seek_is_clamped_to_track_length() {
+        let mut timing = timing_at(0, true);
+        timing.seek(500_000);
+        assert!(timing.current_position_ms() <= 200_000);
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        assert_eq!(parse_timestamp("1:23"), Some(83_000));
+        assert_eq!(parse_timestamp(" 90 "), Some(90_000));
+        assert_eq!(parse_timestamp("1:02:03"), Some(3_723_000));
+        assert_eq!(parse_timestamp("1:75"), None);
+        assert_eq!(parse_timestamp("1::2"), None);
+        assert_eq!(parse_timestamp("-5"), None);
+        assert_eq!(parse_timestamp(""), None);
+    }
 }
@@ -0,0 +1,218 @@
+//! Versioned JSON format for exported playlists
+//!
+//! An export holds everything needed to recreate the playlist (its track
+//! URIs) plus readable metadata, so files can be reviewed and edited outside
+//! the app. Only `uri` is required per track when importing.
+//!
+//! ```json
+//! {
+//!   "format": "spotify-rs-playlist",
+//!   "version": 1,
+//!   "exported_at": "2026-10-17T12:00:00+00:00",
+//!   "playlist": { "id": "…", "name": "Road Trip", "description": "", "owner": "…" },
+//!   "tracks": [
+//!     { "uri": "spotify:track:…", "name": "…", "artists": ["…"], "album": "…", "duration_ms": 215000 }
+//!   ]
+//! }
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::content::{PlaylistDetail, SearchTrack};
+
+/// Value of the `format` field, so other JSON files are rejected early
+pub const EXPORT_FORMAT: &str = "spotify-rs-playlist";
+
+/// Current schema version. Files with a newer version are refused.
+pub const EXPORT_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlaylistExport {
+    pub format: String,
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at: String,
+    pub playlist: ExportedPlaylist,
+    pub tracks: Vec<ExportedTrack>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportedPlaylist {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub owner: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportedTrack {
+    pub uri: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub artists: Vec<String>,
+    #[serde(default)]
+    pub album: String,
+    #[serde(default)]
+    pub duration_ms: u32,
+}
+
+impl From<&SearchTrack> for ExportedTrack {
+    fn from(track: &SearchTrack) -> Self {
+        Self {
+            uri: track.uri.clone(),
+            name: track.name.clone(),
+            artists: track.artists.clone(),
+            album: track.album.clone(),
+            duration_ms: track.duration_ms,
+        }
+    }
+}
+
+impl PlaylistExport {
+    /// Export `detail` with `tracks`, which should be all of its tracks
+    /// rather than just the loaded pages
+    pub fn new(detail: &PlaylistDetail, tracks: &[SearchTrack]) -> Self {
+        Self {
+            format: EXPORT_FORMAT.to_string(),
+            version: EXPORT_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            playlist: ExportedPlaylist {
+                id: detail.id.clone(),
+                name: detail.name.clone(),
+                description: detail.description.clone(),
+                owner: detail.owner.clone(),
+            },
+            tracks: tracks.iter().map(ExportedTrack::from).collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        // Check the header first so the error says what is wrong with the file
+        let value: serde_json::Value = serde_json::from_str(text).context("Not a JSON file")?;
+        if value["format"].as_str() != Some(EXPORT_FORMAT) {
+            bail!("Not a spotify-rs playlist export (missing \"format\": \"{}\")", EXPORT_FORMAT);
+        }
+        match value["version"].as_u64() {
+            Some(version) if version as u32 <= EXPORT_VERSION => {}
+            Some(version) => bail!(
+                "Playlist export version {} is newer than this spotify-rs supports ({})",
+                version,
+                EXPORT_VERSION
+            ),
+            None => bail!("Playlist export has no version"),
+        }
+
+        serde_json::from_value(value).context("Invalid playlist export")
+    }
+
+    /// Write to `<dir>/<playlist name>.json`, replacing an earlier export of
+    /// the same name. Returns the file written.
+    pub fn write_to_dir(&self, dir: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(dir).with_context(|| format!("Could not create {}", dir.display()))?;
+        let path = dir.join(format!("{}.json", file_stem(&self.playlist.name)));
+        fs::write(&path, self.to_json()?).with_context(|| format!("Could not write {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn uris(&self) -> Vec<String> {
+        self.tracks.iter().map(|track| track.uri.clone()).collect()
+    }
+}
+
+/// Playlist name made safe to use as a file name
+fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.') { c } else { '_' })
+        .collect();
+    let stem = stem.trim().trim_start_matches('.');
+    if stem.is_empty() { "playlist".to_string() } else { stem.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detail() -> PlaylistDetail {
+        PlaylistDetail {
+            id: "pl1".to_string(),
+            uri: "spotify:playlist:pl1".to_string(),
+            name: "Road / Trip".to_string(),
+            owner: "me".to_string(),
+            description: "For the car".to_string(),
+            snapshot_id: "s1".to_string(),
+            editable: true,
+            public: false,
+            collaborative: false,
+            tracks: Vec::new(),
+            total_tracks: 0,
+            has_more: false,
+            loading_more: false,
+        }
+    }
+
+    fn track(n: u32) -> SearchTrack {
+        SearchTrack {
+            id: format!("t{}", n),
+            name: format!("Track {}", n),
+            artist: "Artist".to_string(),
+            artists: vec!["Artist".to_string(), "Guest".to_string()],
+            album: "Album".to_string(),
+            duration_ms: 1000 * n,
+            uri: format!("spotify:track:t{}", n),
+            liked: false,
+        }
+    }
+
+    #[test]
+    fn export_round_trips_through_a_file() {
+        let dir = std::env::temp_dir().join(format!("spotify-rs-test-export-{}", std::process::id()));
+        let export = PlaylistExport::new(&detail(), &[track(1), track(2)]);
+
+        let path = export.write_to_dir(&dir).unwrap();
+        let imported = PlaylistExport::from_json(&fs::read_to_string(&path).unwrap()).unwrap();
+
+        assert_eq!(path.file_name().unwrap(), "Road _ Trip.json");
+        assert_eq!(imported, export);
+        assert_eq!(imported.uris(), ["spotify:track:t1", "spotify:track:t2"]);
+        assert_eq!(imported.tracks[1].artists, ["Artist", "Guest"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn hand_written_files_only_need_uris() {
+        let text = r#"{
+            "format": "spotify-rs-playlist",
+            "version": 1,
+            "playlist": { "name": "Minimal" },
+            "tracks": [{ "uri": "spotify:track:abc" }]
+        }"#;
+
+        let imported = PlaylistExport::from_json(text).unwrap();
+        assert_eq!(imported.playlist.name, "Minimal");
+        assert_eq!(imported.uris(), ["spotify:track:abc"]);
+    }
+
+    #[test]
+    fn unknown_formats_and_newer_versions_are_refused() {
+        let other = PlaylistExport::from_json(r#"{ "tracks": [] }"#).unwrap_err();
+        assert!(other.to_string().contains("Not a spotify-rs playlist export"), "{}", other);
+
+        let newer = PlaylistExport::from_json(
+            r#"{ "format": "spotify-rs-playlist", "version": 99, "playlist": { "name": "x" }, "tracks": [] }"#,
+        )
+        .unwrap_err();
+        assert!(newer.to_string().contains("newer"), "{}", newer);
+    }
+}
@@ -0,0 +1,199 @@
+//! Client-side playback queue
+//!
+//! While the local librespot device is playing, tracks queued from spotify-rs
+//! are kept here rather than in Spotify's queue, so they can be removed,
+//! reordered and inserted anywhere. The controller hands them to the player
+//! one at a time: shortly before the current track ends the head is added to
+//! Spotify's queue, after which it is "submitted" and stays first.
+
+use super::content::SearchTrack;
+
+#[derive(Clone, Debug, Default)]
+pub struct LocalQueue {
+    /// Track already handed to the player as the next one to play
+    submitted: Option<SearchTrack>,
+    /// Tracks still waiting, in play order
+    pending: Vec<SearchTrack>,
+}
+
+impl LocalQueue {
+    pub fn len(&self) -> usize {
+        usize::from(self.submitted.is_some()) + self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All entries in play order, the submitted one first
+    pub fn tracks(&self) -> Vec<SearchTrack> {
+        self.submitted.iter().chain(&self.pending).cloned().collect()
+    }
+
+    pub fn is_submitted(&self, index: usize) -> bool {
+        index == 0 && self.submitted.is_some()
+    }
+
+    pub fn push(&mut self, track: SearchTrack) {
+        self.pending.push(track);
+    }
+
+    /// Insert so the track ends up at `index`, clamped to the entries that can
+    /// still move (never before the submitted one)
+    pub fn insert(&mut self, index: usize, track: SearchTrack) {
+        let offset = usize::from(self.submitted.is_some());
+        let index = index.saturating_sub(offset).min(self.pending.len());
+        self.pending.insert(index, track);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<SearchTrack> {
+        if self.is_submitted(index) {
+            return self.submitted.take();
+        }
+        let index = index - usize::from(self.submitted.is_some());
+        (index < self.pending.len()).then(|| self.pending.remove(index))
+    }
+
+    /// Swap an entry with its neighbour. Returns false if either of them is
+    /// submitted or out of range.
+    pub fn move_entry(&mut self, index: usize, up: bool) -> bool {
+        let Some(index) = index.checked_sub(usize::from(self.submitted.is_some())) else {
+            return false;
+        };
+        let other = if up { index.checked_sub(1) } else { Some(index + 1) };
+        match other {
+            Some(other) if index < self.pending.len() && other < self.pending.len() => {
+                self.pending.swap(index, other);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Drop every entry, returning the submitted one (it is already in
+    /// Spotify's queue, so it has to be skipped instead)
+    pub fn clear(&mut self) -> Option<SearchTrack> {
+        self.pending.clear();
+        self.submitted.take()
+    }
+
+    /// Take the head to hand to the player, unless one is already submitted
+    pub fn submit_next(&mut self) -> Option<SearchTrack> {
+        if self.submitted.is_some() || self.pending.is_empty() {
+            return None;
+        }
+        let track = self.pending.remove(0);
+        self.submitted = Some(track.clone());
+        Some(track)
+    }
+
+    /// Undo `submit_next` after the player could not be reached
+    pub fn cancel_submit(&mut self) {
+        if let Some(track) = self.submitted.take() {
+            self.pending.insert(0, track);
+        }
+    }
+
+    /// The player started `uri`; the submitted entry is done once it plays
+    pub fn track_started(&mut self, uri: &str) {
+        if self.submitted.as_ref().is_some_and(|t| t.uri == uri) {
+            self.submitted = None;
+        }
+    }
+
+    /// Take the entries not yet handed to the player
+    pub fn take_pending(&mut self) -> Vec<SearchTrack> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Put back taken entries the player could not be given, ahead of any
+    /// queued since
+    pub fn restore_pending(&mut self, tracks: Vec<SearchTrack>) {
+        self.pending.splice(0..0, tracks);
+    }
+
+    /// Combine with Spotify's upcoming tracks for the queue view. Returns the
+    /// entries and how many of them are local; the submitted track is taken
+    /// out of Spotify's list so it is not shown twice.
+    pub fn merge(&self, mut upcoming: Vec<SearchTrack>) -> (Vec<SearchTrack>, usize) {
+        if let Some(submitted) = &self.submitted
+            && let Some(pos) = upcoming.iter().position(|t| t.uri == submitted.uri)
+        {
+            upcoming.remove(pos);
+        }
+        let mut tracks = self.tracks();
+        let local_len = tracks.len();
+        tracks.extend(upcoming);
+        (tracks, local_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(uri: &str) -> SearchTrack {
+        SearchTrack {
+            id: uri.to_string(),
+            name: uri.to_string(),
+            artist: String::new(),
+            artists: Vec::new(),
+            album: String::new(),
+            duration_ms: 0,
+            uri: uri.to_string(),
+            liked: false,
+        }
+    }
+
+    fn uris(queue: &LocalQueue) -> Vec<String> {
+        queue.tracks().into_iter().map(|t| t.uri).collect()
+    }
+
+    #[test]
+    fn edits_keep_the_submitted_track_first() {
+        let mut queue = LocalQueue::default();
+        for uri in ["a", "b", "c"] {
+            queue.push(track(uri));
+        }
+        assert_eq!(queue.submit_next().map(|t| t.uri), Some("a".to_string()));
+        assert!(queue.submit_next().is_none());
+
+        queue.insert(0, track("d"));
+        assert_eq!(uris(&queue), ["a", "d", "b", "c"]);
+        assert!(!queue.move_entry(1, true));
+        assert!(queue.move_entry(1, false));
+        assert!(!queue.move_entry(3, false));
+        assert_eq!(uris(&queue), ["a", "b", "d", "c"]);
+
+        assert_eq!(queue.remove(2).map(|t| t.uri), Some("d".to_string()));
+        queue.track_started("a");
+        assert_eq!(uris(&queue), ["b", "c"]);
+        assert!(!queue.is_submitted(0));
+    }
+
+    #[test]
+    fn duplicates_are_separate_entries() {
+        let mut queue = LocalQueue::default();
+        queue.push(track("a"));
+        queue.push(track("a"));
+        queue.remove(1);
+        assert_eq!(uris(&queue), ["a"]);
+    }
+
+    #[test]
+    fn merge_hides_submitted_track_from_spotify_queue() {
+        let mut queue = LocalQueue::default();
+        queue.push(track("a"));
+        queue.push(track("b"));
+        queue.submit_next();
+
+        let (tracks, local_len) = queue.merge(vec![track("a"), track("x")]);
+        let merged: Vec<_> = tracks.into_iter().map(|t| t.uri).collect();
+        assert_eq!(merged, ["a", "b", "x"]);
+        assert_eq!(local_len, 2);
+
+        queue.cancel_submit();
+        assert_eq!(queue.clear().map(|t| t.uri), None);
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+//! Searches made from the search bar, kept across sessions
+//!
+//! Entries are stored newest first in the cache directory. Up/Down in the
+//! search bar walk through them; the text typed before recalling is kept so
+//! walking back down past the newest entry restores it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+
+/// Search history file, relative to the cache directory
+const SEARCH_HISTORY_FILE: &str = "search_history.json";
+
+/// Most searches remembered
+pub const SEARCH_HISTORY_LIMIT: usize = 100;
+
+#[derive(Clone, Debug, Default)]
+pub struct SearchHistory {
+    /// Newest first
+    entries: Vec<String>,
+    /// Backing file, or `None` for a history that never touches the disk
+    path: Option<PathBuf>,
+    /// Entry shown while recalling, and the text typed before recall started
+    recall: Option<(usize, String)>,
+}
+
+impl SearchHistory {
+    /// Load the history saved in `cache_dir`. A missing or unreadable file
+    /// starts an empty history.
+    pub fn load(cache_dir: &Path) -> Self {
+        let path = cache_dir.join(SEARCH_HISTORY_FILE);
+        let entries = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                tracing::warn!(path = %path.display(), error = %e, "Ignoring unreadable search history");
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self { entries, path: Some(path), recall: None }
+    }
+
+    #[cfg(test)]
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Put `query` at the top, dropping an earlier copy, and save
+    pub fn record(&mut self, query: &str) -> Result<()> {
+        self.recall = None;
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(());
+        }
+        self.entries.retain(|entry| entry != query);
+        self.entries.insert(0, query.to_string());
+        self.entries.truncate(SEARCH_HISTORY_LIMIT);
+        self.save()
+    }
+
+    /// The search before the one shown, remembering `typed` when recall
+    /// starts. `None` at the oldest entry.
+    pub fn older(&mut self, typed: &str) -> Option<String> {
+        let next = match &self.recall {
+            Some((index, _)) => index + 1,
+            None => 0,
+        };
+        let entry = self.entries.get(next)?.clone();
+        let typed = self.recall.take().map_or_else(|| typed.to_string(), |(_, typed)| typed);
+        self.recall = Some((next, typed));
+        Some(entry)
+    }
+
+    /// The search after the one shown, or the text typed before recall
+    /// started once past the newest. `None` when not recalling.
+    pub fn newer(&mut self) -> Option<String> {
+        let (index, typed) = self.recall.take()?;
+        if index == 0 {
+            return Some(typed);
+        }
+        let entry = self.entries[index - 1].clone();
+        self.recall = Some((index - 1, typed));
+        Some(entry)
+    }
+
+    /// Stop recalling, e.g. because the text was edited
+    pub fn reset_recall(&mut self) {
+        self.recall = None;
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Could not create {}", dir.display()))?;
+        }
+        fs::write(path, serde_json::to_string(&self.entries)?)
+            .with_context(|| format!("Could not write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_is_saved_newest_first_without_duplicates() {
+        let dir = std::env::temp_dir().join(format!("spotify-rs-test-history-{}", std::process::id()));
+        let mut history = SearchHistory::load(&dir);
+        for query in ["daft punk", "coldplay", " daft punk ", ""] {
+            history.record(query).unwrap();
+        }
+
+        assert_eq!(SearchHistory::load(&dir).entries(), ["daft punk", "coldplay"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn recall_walks_back_and_restores_the_typed_text() {
+        let mut history = SearchHistory::default();
+        history.record("first").unwrap();
+        history.record("second").unwrap();
+
+        assert_eq!(history.newer(), None);
+        assert_eq!(history.older("sec").as_deref(), Some("second"));
+        assert_eq!(history.older("second").as_deref(), Some("first"));
+        assert_eq!(history.older("first"), None);
+        assert_eq!(history.newer().as_deref(), Some("second"));
+        assert_eq!(history.newer().as_deref(), Some("sec"));
+        assert_eq!(history.newer(), None);
+    }
+}
@@ -0,0 +1,236 @@
+//! Field filters in search queries
+//!
+//! Spotify narrows a search with `field:value` filters (`artist:Coldplay`,
+//! `year:1990-1999`, `tag:new`, ...); the rest of the query is free text.
+//! Values with spaces are quoted: `artist:"Daft Punk"`. Spotify reads the
+//! filters itself, so queries are sent as typed. Parsing them here catches
+//! values Spotify would silently ignore and works out which result types the
+//! filters apply to, so only those are searched.
+
+use std::ops::Range;
+use anyhow::{bail, Result};
+
+use super::types::SearchResultSection;
+
+const ALL_SECTIONS: [SearchResultSection; 4] = [
+    SearchResultSection::Tracks,
+    SearchResultSection::Albums,
+    SearchResultSection::Artists,
+    SearchResultSection::Playlists,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchField {
+    Artist,
+    Album,
+    Track,
+    Year,
+    Genre,
+    Tag,
+    Isrc,
+}
+
+impl SearchField {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "artist" => Some(Self::Artist),
+            "album" => Some(Self::Album),
+            "track" => Some(Self::Track),
+            "year" => Some(Self::Year),
+            "genre" => Some(Self::Genre),
+            "tag" => Some(Self::Tag),
+            "isrc" => Some(Self::Isrc),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Artist => "artist",
+            Self::Album => "album",
+            Self::Track => "track",
+            Self::Year => "year",
+            Self::Genre => "genre",
+            Self::Tag => "tag",
+            Self::Isrc => "isrc",
+        }
+    }
+
+    /// Result types Spotify applies the filter to
+    pub fn sections(self) -> &'static [SearchResultSection] {
+        use SearchResultSection::{Albums, Artists, Tracks};
+        match self {
+            Self::Artist | Self::Year => &[Tracks, Albums, Artists],
+            Self::Album => &[Tracks, Albums],
+            Self::Genre => &[Tracks, Artists],
+            Self::Track | Self::Isrc => &[Tracks],
+            Self::Tag => &[Albums],
+        }
+    }
+}
+
+/// A `field:value` filter in a search query
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchFilter {
+    pub field: SearchField,
+    /// Value without surrounding quotes
+    pub value: String,
+    /// Byte range of the whole filter in the query
+    pub range: Range<usize>,
+}
+
+impl SearchFilter {
+    pub fn validate(&self) -> Result<()> {
+        let value = self.value.as_str();
+        match self.field {
+            SearchField::Year => {
+                let years: Vec<&str> = value.split('-').collect();
+                let valid = years.len() <= 2
+                    && years.iter().all(|year| year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()))
+                    && years.first() <= years.last();
+                if !valid {
+                    bail!("year: takes a year or a range like 1990-1999, not \"{}\"", value);
+                }
+            }
+            SearchField::Tag => {
+                if !matches!(value.to_lowercase().as_str(), "new" | "hipster") {
+                    bail!("tag: is either tag:new or tag:hipster, not \"{}\"", value);
+                }
+            }
+            SearchField::Isrc => {
+                let valid = value.len() == 12
+                    && value.chars().all(|c| c.is_ascii_alphanumeric())
+                    && value.chars().take(2).all(|c| c.is_ascii_alphabetic());
+                if !valid {
+                    bail!("isrc: takes a 12-character code like USUM71703861, not \"{}\"", value);
+                }
+            }
+            SearchField::Artist | SearchField::Album | SearchField::Track | SearchField::Genre => {}
+        }
+        Ok(())
+    }
+}
+
+/// A search query split into free text and filters
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// The query without its filters
+    pub text: String,
+    pub filters: Vec<SearchFilter>,
+}
+
+impl SearchQuery {
+    /// Split `input` into free text and filters without checking the filter
+    /// values. Words like `Re:` or `foo:bar` that aren't filters stay text.
+    pub fn split(input: &str) -> Self {
+        let mut query = Self::default();
+        let mut words = Vec::new();
+        for range in words_of(input) {
+            let word = &input[range.clone()];
+            let filter = word.split_once(':').and_then(|(name, value)| {
+                let field = SearchField::from_name(name)?;
+                let value = value.trim_matches('"');
+                (!value.is_empty()).then(|| SearchFilter { field, value: value.to_string(), range })
+            });
+            match filter {
+                Some(filter) => query.filters.push(filter),
+                None => words.push(word),
+            }
+        }
+        query.text = words.join(" ");
+        query
+    }
+
+    /// Split `input` and check its filters
+    pub fn parse(input: &str) -> Result<Self> {
+        let query = Self::split(input);
+        for filter in &query.filters {
+            filter.validate()?;
+        }
+        if query.sections().is_empty() {
+            let names: Vec<String> = query.filters.iter().map(|f| format!("{}:", f.field.name())).collect();
+            bail!("{} can't be used together", names.join(", "));
+        }
+        Ok(query)
+    }
+
+    /// Result types every filter applies to (all four without filters)
+    pub fn sections(&self) -> Vec<SearchResultSection> {
+        ALL_SECTIONS
+            .into_iter()
+            .filter(|section| self.filters.iter().all(|filter| filter.field.sections().contains(section)))
+            .collect()
+    }
+
+    /// Value of the first filter on `field`
+    pub fn value(&self, field: SearchField) -> Option<&str> {
+        self.filters.iter().find(|filter| filter.field == field).map(|filter| filter.value.as_str())
+    }
+}
+
+/// Byte ranges of the whitespace-separated words of `input`, keeping quoted
+/// parts together
+fn words_of(input: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut quoted = false;
+    for (i, c) in input.char_indices() {
+        if c.is_whitespace() && !quoted {
+            if let Some(start) = start.take() {
+                words.push(start..i);
+            }
+            continue;
+        }
+        if c == '"' {
+            quoted = !quoted;
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(start) = start {
+        words.push(start..input.len());
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_are_split_from_the_text() {
+        let query = SearchQuery::parse(r#"live artist:"Daft Punk" year:1990-1999 Re:Stacks"#).unwrap();
+
+        assert_eq!(query.text, "live Re:Stacks");
+        assert_eq!(query.value(SearchField::Artist), Some("Daft Punk"));
+        assert_eq!(query.value(SearchField::Year), Some("1990-1999"));
+        assert_eq!(query.filters[0].range, 5..23);
+        assert_eq!(
+            query.sections(),
+            [SearchResultSection::Tracks, SearchResultSection::Albums, SearchResultSection::Artists]
+        );
+        assert_eq!(SearchQuery::parse("coldplay").unwrap().sections().len(), 4);
+    }
+
+    #[test]
+    fn filters_narrow_the_searched_types() {
+        let sections = |input: &str| SearchQuery::parse(input).unwrap().sections();
+
+        assert_eq!(sections("genre:jazz"), [SearchResultSection::Tracks, SearchResultSection::Artists]);
+        assert_eq!(sections("tag:new"), [SearchResultSection::Albums]);
+        assert_eq!(sections("album:x isrc:USUM71703861"), [SearchResultSection::Tracks]);
+
+        let err = SearchQuery::parse("tag:new track:x").unwrap_err();
+        assert_eq!(err.to_string(), "tag:, track: can't be used together");
+    }
+
+    #[test]
+    fn invalid_values_are_refused() {
+        for input in ["year:199", "year:2000-1990", "year:1990-1995-1999", "tag:old", "isrc:12345"] {
+            assert!(SearchQuery::parse(input).is_err(), "{}", input);
+        }
+        assert!(SearchQuery::parse("year:2001 tag:hipster isrc:GBAYE0601498").is_err());
+        assert!(SearchQuery::parse("year:2001 tag:Hipster").is_ok());
+        // A field name without a value is just text
+        assert_eq!(SearchQuery::parse("year: 3000").unwrap().text, "year: 3000");
+    }
+}
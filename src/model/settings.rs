@@ -0,0 +1,251 @@
+//! Settings editable from the settings overlay
+//!
+//! Each `Setting` maps to one config value. Some take effect immediately,
+//! the rest are read only when the audio session starts and are flagged
+//! "applies on restart" once changed.
+
+use crate::config::{Config, ThemeName};
+
+const BITRATES: [u16; 3] = [96, 160, 320];
+
+/// Audio cache limit presets in MB (0 = unlimited)
+const CACHE_LIMITS_MB: [u64; 7] = [0, 256, 512, 1024, 2048, 4096, 8192];
+
+const MAX_VOLUME_STEP: u8 = 50;
+
+const SEEK_STEPS_SECS: [u32; 6] = [5, 10, 15, 30, 60, 120];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Setting {
+    Bitrate,
+    VolumeStep,
+    SeekStep,
+    Normalisation,
+    DeviceName,
+    Theme,
+    LiveSearch,
+    AudioCacheLimit,
+    PlaylistsLimit,
+    LikedSongsLimit,
+    SavedAlbumsLimit,
+    FollowedArtistsLimit,
+    SavedShowsLimit,
+    EpisodesLimit,
+    RecentlyPlayedLimit,
+    SearchLimit,
+    HomeLimit,
+    TopLimit,
+}
+
+/// One row of the settings overlay
+#[derive(Clone, Debug)]
+pub struct SettingRow {
+    pub label: &'static str,
+    pub value: String,
+    /// Changed, but only takes effect after a restart
+    pub pending_restart: bool,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 18] = [
+        Setting::Bitrate,
+        Setting::VolumeStep,
+        Setting::SeekStep,
+        Setting::Normalisation,
+        Setting::DeviceName,
+        Setting::Theme,
+        Setting::LiveSearch,
+        Setting::AudioCacheLimit,
+        Setting::PlaylistsLimit,
+        Setting::LikedSongsLimit,
+        Setting::SavedAlbumsLimit,
+        Setting::FollowedArtistsLimit,
+        Setting::SavedShowsLimit,
+        Setting::EpisodesLimit,
+        Setting::RecentlyPlayedLimit,
+        Setting::SearchLimit,
+        Setting::HomeLimit,
+        Setting::TopLimit,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Setting::Bitrate => "Bitrate",
+            Setting::VolumeStep => "Volume step",
+            Setting::SeekStep => "Seek step",
+            Setting::Normalisation => "Normalisation",
+            Setting::DeviceName => "Device name",
+            Setting::Theme => "Theme",
+            Setting::LiveSearch => "Live search",
+            Setting::AudioCacheLimit => "Audio cache limit",
+            Setting::PlaylistsLimit => "Playlists limit",
+            Setting::LikedSongsLimit => "Liked songs limit",
+            Setting::SavedAlbumsLimit => "Saved albums limit",
+            Setting::FollowedArtistsLimit => "Followed artists limit",
+            Setting::SavedShowsLimit => "Saved shows limit",
+            Setting::EpisodesLimit => "Episodes limit",
+            Setting::RecentlyPlayedLimit => "Recently played limit",
+            Setting::SearchLimit => "Search results limit",
+            Setting::HomeLimit => "Home section limit",
+            Setting::TopLimit => "Your Top limit",
+        }
+    }
+
+    pub fn value(&self, config: &Config) -> String {
+        let library = &config.library;
+        match self {
+            Setting::Bitrate => format!("{} kbps", config.audio.bitrate),
+            Setting::VolumeStep => format!("{}%", config.audio.volume_step),
+            Setting::SeekStep => format!("{}s", config.audio.seek_step_secs),
+            Setting::Normalisation => if config.audio.normalisation { "on" } else { "off" }.to_string(),
+            Setting::DeviceName => config.device.name.clone(),
+            Setting::Theme => config.ui.theme.as_str().to_string(),
+            Setting::LiveSearch => if config.ui.live_search { "on" } else { "off" }.to_string(),
+            Setting::AudioCacheLimit => match config.audio.cache_limit_mb {
+                0 => "unlimited".to_string(),
+                mb => format!("{} MB", mb),
+            },
+            Setting::PlaylistsLimit => library.playlists_limit.to_string(),
+            Setting::LikedSongsLimit => library.liked_songs_limit.to_string(),
+            Setting::SavedAlbumsLimit => library.saved_albums_limit.to_string(),
+            Setting::FollowedArtistsLimit => library.followed_artists_limit.to_string(),
+            Setting::SavedShowsLimit => library.saved_shows_limit.to_string(),
+            Setting::EpisodesLimit => library.episodes_limit.to_string(),
+            Setting::RecentlyPlayedLimit => library.recently_played_limit.to_string(),
+            Setting::SearchLimit => library.search_limit.to_string(),
+            Setting::HomeLimit => library.home_limit.to_string(),
+            Setting::TopLimit => library.top_limit.to_string(),
+        }
+    }
+
+    /// Whether a change takes effect without restarting. Bitrate,
+    /// normalisation, device name and cache size are fixed when librespot
+    /// creates the session and player.
+    pub fn applies_live(&self) -> bool {
+        !matches!(
+            self,
+            Setting::Bitrate | Setting::Normalisation | Setting::DeviceName | Setting::AudioCacheLimit
+        )
+    }
+
+    /// Whether the value is edited as free text rather than cycled
+    pub fn is_text(&self) -> bool {
+        matches!(self, Setting::DeviceName)
+    }
+
+    /// Step the value forward or backward
+    pub fn adjust(&self, config: &mut Config, forward: bool) {
+        let library = &mut config.library;
+        match self {
+            Setting::Bitrate => config.audio.bitrate = cycle(&BITRATES, config.audio.bitrate, forward),
+            Setting::VolumeStep => {
+                let step = config.audio.volume_step;
+                config.audio.volume_step = if forward {
+                    (step + 1).min(MAX_VOLUME_STEP)
+                } else {
+                    step.saturating_sub(1).max(1)
+                };
+            }
+            Setting::SeekStep => {
+                let step = &mut config.audio.seek_step_secs;
+                *step = cycle(&SEEK_STEPS_SECS, *step, forward);
+            }
+            Setting::Normalisation => config.audio.normalisation = !config.audio.normalisation,
+            Setting::DeviceName => {}
+            Setting::Theme => config.ui.theme = cycle(&ThemeName::ALL, config.ui.theme, forward),
+            Setting::LiveSearch => config.ui.live_search = !config.ui.live_search,
+            Setting::AudioCacheLimit => {
+                config.audio.cache_limit_mb = cycle(&CACHE_LIMITS_MB, config.audio.cache_limit_mb, forward)
+            }
+            Setting::PlaylistsLimit => step_limit(&mut library.playlists_limit, 50, 1000, forward),
+            Setting::LikedSongsLimit => step_limit(&mut library.liked_songs_limit, 50, 5000, forward),
+            Setting::SavedAlbumsLimit => step_limit(&mut library.saved_albums_limit, 50, 1000, forward),
+            Setting::FollowedArtistsLimit => step_limit(&mut library.followed_artists_limit, 5, 50, forward),
+            Setting::SavedShowsLimit => step_limit(&mut library.saved_shows_limit, 50, 1000, forward),
+            Setting::EpisodesLimit => step_limit(&mut library.episodes_limit, 50, 1000, forward),
+            Setting::RecentlyPlayedLimit => step_limit(&mut library.recently_played_limit, 5, 50, forward),
+            Setting::SearchLimit => step_limit(&mut library.search_limit, 5, 50, forward),
+            Setting::HomeLimit => step_limit(&mut library.home_limit, 5, 50, forward),
+            Setting::TopLimit => step_limit(&mut library.top_limit, 5, 50, forward),
+        }
+    }
+
+    /// Current value as editable text, for text settings
+    pub fn text(&self, config: &Config) -> Option<String> {
+        match self {
+            Setting::DeviceName => Some(config.device.name.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn set_text(&self, config: &mut Config, text: &str) {
+        if let Setting::DeviceName = self {
+            config.device.name = text.trim().to_string();
+        }
+    }
+
+    pub fn row(&self, config: &Config, running: &Config) -> SettingRow {
+        SettingRow {
+            label: self.label(),
+            value: self.value(config),
+            pending_restart: !self.applies_live() && self.value(config) != self.value(running),
+        }
+    }
+}
+
+/// Next/previous entry of `values`; unknown values restart from the first
+fn cycle<T: Copy + PartialEq>(values: &[T], current: T, forward: bool) -> T {
+    let len = values.len();
+    match values.iter().position(|v| *v == current) {
+        Some(i) if forward => values[(i + 1) % len],
+        Some(i) => values[(i + len - 1) % len],
+        None => values[0],
+    }
+}
+
+fn step_limit(value: &mut u32, step: u32, max: u32, forward: bool) {
+    *value = if forward {
+        (*value + step).min(max)
+    } else {
+        value.saturating_sub(step).max(1)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjusting_cycles_and_clamps_values() {
+        let mut config = Config::default();
+
+        Setting::Bitrate.adjust(&mut config, true);
+        assert_eq!(config.audio.bitrate, 96);
+        Setting::Bitrate.adjust(&mut config, false);
+        assert_eq!(config.audio.bitrate, 320);
+
+        Setting::SearchLimit.adjust(&mut config, true);
+        Setting::SearchLimit.adjust(&mut config, true);
+        assert_eq!(config.library.search_limit, 50);
+
+        config.audio.volume_step = 1;
+        Setting::VolumeStep.adjust(&mut config, false);
+        assert_eq!(config.audio.volume_step, 1);
+
+        Setting::AudioCacheLimit.adjust(&mut config, false);
+        assert_eq!(config.audio.cache_limit_mb, 8192);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn only_restart_settings_are_flagged() {
+        let running = Config::default();
+        let mut config = running.clone();
+        Setting::Normalisation.adjust(&mut config, true);
+        Setting::Theme.adjust(&mut config, true);
+
+        assert!(Setting::Normalisation.row(&config, &running).pending_restart);
+        assert!(!Setting::Theme.row(&config, &running).pending_restart);
+        assert!(!Setting::Bitrate.row(&config, &running).pending_restart);
+    }
+}
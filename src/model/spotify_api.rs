@@ -0,0 +1,257 @@
+//! Backend-agnostic interface to the Spotify Web API
+//!
+//! The controller only talks to Spotify through this trait, so it can run
+//! against the real `SpotifyClient` or the in-memory `FakeSpotifyClient`.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rspotify::model::CurrentPlaybackContext;
+
+use super::types::{DeviceInfo, PlaylistItem, PlaylistVisibility, RepeatState, SearchResultSection, TopRange};
+use super::content::{
+    SearchTrack, SearchAlbum, SearchArtist, SearchPlaylist, SearchResults,
+    AlbumDetail, PlaylistDetail, ArtistDetail, SavedTrack, Show, Episode, ShowDetail, Category,
+};
+
+/// Number of tracks fetched per playlist page
+pub const PLAYLIST_PAGE_SIZE: usize = 100;
+
+#[async_trait]
+pub trait SpotifyApi: Send + Sync {
+    // ========================================================================
+    // Session
+    // ========================================================================
+
+    /// Refresh the access token if it is about to expire.
+    /// Returns `true` if a refresh actually happened.
+    async fn refresh_token_if_needed(&self) -> Result<bool>;
+
+    /// Spotify user ID of the logged-in account
+    async fn current_user_id(&self) -> Result<String>;
+
+    // ========================================================================
+    // Liked Songs
+    // ========================================================================
+
+    /// Set the `liked` flag on each track from the liked songs cache
+    async fn mark_tracks_liked(&self, tracks: &mut [SearchTrack]);
+
+    /// Like or unlike a track, returning the new liked status
+    async fn toggle_liked_song(&self, track_id: &str) -> Result<bool>;
+
+    // ========================================================================
+    // Saved Albums & Followed Artists
+    // ========================================================================
+
+    /// Whether the album is saved, from the saved albums cache
+    async fn is_album_saved(&self, album_id: &str) -> bool;
+
+    /// Whether the artist is followed, from the followed artists cache
+    async fn is_artist_followed(&self, artist_id: &str) -> bool;
+
+    /// Set the `saved` flag on each album from the saved albums cache
+    async fn mark_albums_saved(&self, albums: &mut [SearchAlbum]) {
+        for album in albums.iter_mut() {
+            album.saved = self.is_album_saved(&album.id).await;
+        }
+    }
+
+    /// Set the `followed` flag on each artist from the followed artists cache
+    async fn mark_artists_followed(&self, artists: &mut [SearchArtist]) {
+        for artist in artists.iter_mut() {
+            artist.followed = self.is_artist_followed(&artist.id).await;
+        }
+    }
+
+    /// Save or unsave an album, returning the new saved status
+    async fn toggle_saved_album(&self, album_id: &str) -> Result<bool>;
+
+    /// Follow or unfollow an artist, returning the new followed status
+    async fn toggle_followed_artist(&self, artist_id: &str) -> Result<bool>;
+
+    // ========================================================================
+    // Playback & Devices
+    // ========================================================================
+
+    async fn get_current_playback(&self) -> Result<Option<CurrentPlaybackContext>>;
+    async fn play(&self) -> Result<()>;
+    async fn pause(&self) -> Result<()>;
+    async fn next_track(&self) -> Result<()>;
+    async fn previous_track(&self) -> Result<()>;
+    async fn set_shuffle(&self, state: bool) -> Result<()>;
+    async fn set_repeat(&self, state: RepeatState) -> Result<()>;
+    async fn set_volume(&self, volume: u8) -> Result<()>;
+
+    /// Seek within the current track
+    async fn seek(&self, position_ms: u32) -> Result<()>;
+
+    async fn get_available_devices(&self) -> Result<Vec<DeviceInfo>>;
+    async fn has_active_device(&self) -> bool;
+    async fn transfer_playback_to_device(&self, device_id: &str, start_playing: bool) -> Result<()>;
+
+    /// Play a list of track URIs as an ad-hoc context, starting at `offset`
+    async fn play_tracks(&self, uris: &[String], offset: usize) -> Result<()>;
+
+    /// Play an album/playlist/artist context (or the liked songs collection,
+    /// `spotify:user:<id>:collection`) starting from the given track
+    async fn play_context_from_track_uri(&self, context_uri: &str, track_uri: &str) -> Result<()>;
+
+    // ========================================================================
+    // Queue
+    // ========================================================================
+
+    /// Returns the currently playing track and the upcoming queue
+    async fn get_queue(&self) -> Result<(Option<SearchTrack>, Vec<SearchTrack>)>;
+    async fn add_to_queue(&self, track_uri: &str) -> Result<()>;
+
+    // ========================================================================
+    // Catalog
+    // ========================================================================
+
+    async fn search(&self, query: &str, limit: u32) -> Result<SearchResults>;
+
+    /// Fetch a page of one search category, for browsing past the first
+    /// results. Only `section` is filled in.
+    /// Returns `(results, total, has_more)`.
+    async fn search_category(
+        &self,
+        query: &str,
+        section: SearchResultSection,
+        offset: usize,
+        limit: u32,
+    ) -> Result<(SearchResults, u32, bool)>;
+    async fn get_album(&self, album_id: &str) -> Result<AlbumDetail>;
+
+    /// Fetch playlist metadata and the first page of tracks
+    async fn get_playlist(&self, playlist_id: &str) -> Result<PlaylistDetail>;
+
+    /// Fetch the next page of playlist tracks.
+    /// Returns `(tracks, total_tracks, has_more)`.
+    async fn get_more_playlist_tracks(&self, playlist_id: &str, offset: usize) -> Result<(Vec<SearchTrack>, u32, bool)>;
+
+    async fn get_artist(&self, artist_id: &str) -> Result<ArtistDetail>;
+
+    /// Every track of a playlist, across all pages
+    async fn get_all_playlist_tracks(&self, playlist_id: &str) -> Result<Vec<SearchTrack>>;
+
+    /// Look tracks up by URI, in order. Malformed and unknown URIs give `None`.
+    async fn get_tracks(&self, uris: &[String]) -> Result<Vec<Option<SearchTrack>>>;
+
+    // ========================================================================
+    // Playlist Editing
+    // ========================================================================
+    //
+    // Each edit returns the playlist's new snapshot ID. Removing and moving
+    // take the snapshot the positions refer to, so Spotify can apply the
+    // change on top of edits made elsewhere in the meantime.
+
+    /// Append tracks to the end of a playlist
+    async fn add_tracks_to_playlist(&self, playlist_id: &str, uris: &[String]) -> Result<String>;
+
+    /// Replace all tracks of a playlist
+    async fn replace_playlist_tracks(&self, playlist_id: &str, uris: &[String]) -> Result<()>;
+
+    /// Remove the track at `position`
+    async fn remove_playlist_track(
+        &self,
+        playlist_id: &str,
+        track_uri: &str,
+        position: usize,
+        snapshot_id: &str,
+    ) -> Result<String>;
+
+    /// Move the track at `from` so it ends up before the track at `insert_before`
+    async fn move_playlist_track(
+        &self,
+        playlist_id: &str,
+        from: usize,
+        insert_before: usize,
+        snapshot_id: &str,
+    ) -> Result<String>;
+
+    /// Create an empty playlist owned by the user
+    async fn create_playlist(&self, name: &str, visibility: PlaylistVisibility) -> Result<PlaylistItem>;
+
+    /// Change a playlist's name and/or description
+    async fn update_playlist_details(&self, playlist_id: &str, name: Option<&str>, description: Option<&str>) -> Result<()>;
+
+    /// Remove a playlist from the user's library (deleting it if they own it)
+    async fn unfollow_playlist(&self, playlist_id: &str) -> Result<()>;
+
+    // ========================================================================
+    // Library
+    // ========================================================================
+
+    // These page through the library until `limit` items (`u32::MAX` for all)
+
+    async fn get_user_playlists(&self, limit: u32) -> Result<Vec<PlaylistItem>>;
+    async fn get_saved_albums(&self, limit: u32) -> Result<Vec<SearchAlbum>>;
+    async fn get_followed_artists(&self, limit: u32) -> Result<Vec<SearchArtist>>;
+    async fn get_recently_played(&self, limit: u32) -> Result<Vec<SearchTrack>>;
+
+    // Single pages for the library views, which load more as they are
+    // scrolled. They return `(items, total, has_more)`.
+
+    /// Liked songs from `offset`, marked as liked and added to the cache
+    async fn get_liked_songs_page(&self, offset: usize, limit: u32) -> Result<(Vec<SearchTrack>, u32, bool)>;
+    async fn get_saved_albums_page(&self, offset: usize, limit: u32) -> Result<(Vec<SearchAlbum>, u32, bool)>;
+    /// Followed artists after the one with ID `after`, or from the start
+    async fn get_followed_artists_page(&self, after: Option<&str>, limit: u32) -> Result<(Vec<SearchArtist>, u32, bool)>;
+
+    /// Every liked song with the time it was saved, most recent first
+    async fn get_all_liked_songs(&self) -> Result<Vec<SavedTrack>>;
+
+    /// Like songs given as `(uri, added_at)`, keeping the original save time
+    /// where `added_at` is set
+    async fn save_liked_songs(&self, songs: &[(String, String)]) -> Result<()>;
+
+    async fn save_albums(&self, album_ids: &[String]) -> Result<()>;
+    async fn follow_artists(&self, artist_ids: &[String]) -> Result<()>;
+    async fn follow_playlist(&self, playlist_id: &str) -> Result<()>;
+
+    // ========================================================================
+    // Podcasts
+    // ========================================================================
+
+    /// Saved shows from `offset`, as `(shows, total, has_more)`
+    async fn get_saved_shows_page(&self, offset: usize, limit: u32) -> Result<(Vec<Show>, u32, bool)>;
+
+    /// A show's details, without its episodes
+    async fn get_show(&self, show_id: &str) -> Result<ShowDetail>;
+
+    /// A show's episodes from `offset`, newest first, with their resume
+    /// points and played marks
+    async fn get_show_episodes_page(&self, show_id: &str, offset: usize, limit: u32) -> Result<(Vec<Episode>, u32, bool)>;
+
+    /// Play a show's episodes starting with `episode_uri`, `position_ms` in
+    async fn play_episode(&self, show_uri: &str, episode_uri: &str, position_ms: u32) -> Result<()>;
+
+    /// Mark or unmark an episode as played, returning the new mark. The Web
+    /// API can't change what Spotify counts as played, so marks are kept by
+    /// spotify-rs.
+    async fn toggle_episode_played(&self, episode_id: &str) -> Result<bool>;
+
+    // ========================================================================
+    // Home & Browse
+    // ========================================================================
+
+    /// Newest albums released on Spotify
+    async fn get_new_releases(&self, limit: u32) -> Result<Vec<SearchAlbum>>;
+
+    /// Browse categories, as on Spotify's search page
+    async fn get_categories(&self, limit: u32) -> Result<Vec<Category>>;
+
+    /// A category's playlists from `offset`, as `(playlists, total, has_more)`
+    async fn get_category_playlists_page(
+        &self,
+        category_id: &str,
+        offset: usize,
+        limit: u32,
+    ) -> Result<(Vec<SearchPlaylist>, u32, bool)>;
+
+    /// The user's most played tracks over `range`, most played first
+    async fn get_top_tracks(&self, range: TopRange, limit: u32) -> Result<Vec<SearchTrack>>;
+
+    /// The user's most played artists over `range`, most played first
+    async fn get_top_artists(&self, range: TopRange, limit: u32) -> Result<Vec<SearchArtist>>;
+}
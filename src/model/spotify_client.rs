@@ -1,29 +1,205 @@
 //! Spotify API client wrapper with all API methods
 
 use std::sync::Arc;
+use std::path::PathBuf;
 use anyhow::Result;
-use tokio::sync::RwLock;
+use async_trait::async_trait;
+use tokio::sync::{OnceCell, RwLock};
 use rspotify::{
-    model::{CurrentPlaybackContext, PlayableItem, SearchType, Market, AlbumId, PlaylistId, ArtistId, TrackId, PlayContextId, PlayableId},
+    model::{CurrentPlaybackContext, PlayableItem, SearchType, Market, AlbumId, PlaylistId, ArtistId, TrackId, ShowId, PlayContextId, PlayableId, ItemPositions, UserId, FullTrack, FullAlbum, FullArtist, SimplifiedAlbum, SimplifiedPlaylist, Page, SearchResult, SimplifiedShow, SimplifiedEpisode, Offset, TimeRange},
     prelude::*,
     AuthCodeSpotify,
 };
 
-use super::cache::LikedSongsCache;
-use super::types::{DeviceInfo, PlaylistItem, RepeatState};
+use crate::endpoints::Endpoints;
+use super::cache::{
+    LibraryCache, FOLLOWED_ARTISTS_CACHE_FILE, LIKED_SONGS_CACHE_FILE, PLAYED_EPISODES_CACHE_FILE, SAVED_ALBUMS_CACHE_FILE,
+};
+use super::search_query::SearchQuery;
+use super::spotify_api::{SpotifyApi, PLAYLIST_PAGE_SIZE};
+use super::types::{DeviceInfo, PlaylistItem, PlaylistVisibility, RepeatState, SearchResultSection, TopRange};
 use super::content::{
     SearchTrack, SearchAlbum, SearchArtist, SearchPlaylist,
-    SearchResults, AlbumDetail, PlaylistDetail, ArtistDetail,
+    SearchResults, AlbumDetail, PlaylistDetail, ArtistDetail, SavedTrack, Show, Episode, ShowDetail, Category,
 };
 
+/// Most items a single playlist add or replace request accepts
+const PLAYLIST_EDIT_LIMIT: usize = 100;
+
+/// Most tracks the several-tracks endpoint returns at once
+const TRACKS_LOOKUP_LIMIT: usize = 50;
+
+/// Most tracks or artists saved/followed per request
+const SAVE_LIMIT: usize = 50;
+
+/// Most albums saved per request
+const SAVE_ALBUMS_LIMIT: usize = 20;
+
+/// Largest page the library endpoints hand out
+const LIBRARY_PAGE_LIMIT: usize = 50;
+
+/// Search results past this offset are not handed out
+const SEARCH_OFFSET_LIMIT: usize = 1000;
+
+/// Track IDs for playlist requests, from `spotify:track:` URIs or bare IDs
+fn track_ids(uris: &[String]) -> Result<Vec<PlayableId<'_>>> {
+    uris.iter()
+        .map(|uri| Ok(PlayableId::Track(TrackId::from_id(uri.split(':').next_back().unwrap_or(uri))?)))
+        .collect()
+}
+
+fn search_track(track: &FullTrack) -> SearchTrack {
+    let track_id = track.id.as_ref().map(|id| id.id().to_string()).unwrap_or_default();
+    SearchTrack {
+        uri: format!("spotify:track:{}", track_id),
+        id: track_id,
+        name: track.name.clone(),
+        artist: track.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
+        artists: track.artists.iter().map(|a| a.name.clone()).collect(),
+        album: track.album.name.clone(),
+        duration_ms: track.duration.num_milliseconds() as u32,
+        liked: false, // Set by mark_tracks_liked() in controller
+    }
+}
+
+fn search_type(section: SearchResultSection) -> SearchType {
+    match section {
+        SearchResultSection::Tracks => SearchType::Track,
+        SearchResultSection::Albums => SearchType::Album,
+        SearchResultSection::Artists => SearchType::Artist,
+        SearchResultSection::Playlists => SearchType::Playlist,
+    }
+}
+
+/// Add a page of one search category to `results`. Returns the category's
+/// total number of results.
+fn add_search_page(results: &mut SearchResults, result: SearchResult) -> u32 {
+    match result {
+        SearchResult::Tracks(page) => {
+            results.tracks.extend(page.items.iter().map(search_track));
+            page.total
+        }
+        SearchResult::Albums(page) => {
+            results.albums.extend(page.items.iter().map(simplified_album));
+            page.total
+        }
+        SearchResult::Artists(page) => {
+            results.artists.extend(page.items.iter().map(search_artist));
+            page.total
+        }
+        SearchResult::Playlists(page) => {
+            results.playlists.extend(page.items.iter().map(search_playlist));
+            page.total
+        }
+        _ => 0,
+    }
+}
+
+fn search_album(album: &FullAlbum) -> SearchAlbum {
+    SearchAlbum {
+        id: album.id.id().to_string(),
+        name: album.name.clone(),
+        artist: album.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
+        year: album.release_date.chars().take(4).collect(),
+        saved: false, // Set by mark_albums_saved() in controller
+    }
+}
+
+fn simplified_album(album: &SimplifiedAlbum) -> SearchAlbum {
+    SearchAlbum {
+        id: album.id.as_ref().map(|id| id.id().to_string()).unwrap_or_default(),
+        name: album.name.clone(),
+        artist: album.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
+        year: album.release_date.as_deref().unwrap_or_default().chars().take(4).collect(),
+        saved: false, // Set by mark_albums_saved() in controller
+    }
+}
+
+fn search_artist(artist: &FullArtist) -> SearchArtist {
+    SearchArtist {
+        id: artist.id.id().to_string(),
+        name: artist.name.clone(),
+        genres: artist.genres.clone(),
+        followed: false, // Set by mark_artists_followed() in controller
+    }
+}
+
+fn search_playlist(playlist: &SimplifiedPlaylist) -> SearchPlaylist {
+    SearchPlaylist {
+        id: playlist.id.id().to_string(),
+        name: playlist.name.clone(),
+        owner: playlist.owner.display_name.clone().unwrap_or_else(|| playlist.owner.id.id().to_string()),
+        total_tracks: playlist.tracks.total,
+    }
+}
+
+fn time_range(range: TopRange) -> TimeRange {
+    match range {
+        TopRange::FourWeeks => TimeRange::ShortTerm,
+        TopRange::SixMonths => TimeRange::MediumTerm,
+        TopRange::Year => TimeRange::LongTerm,
+    }
+}
+
+fn show(show: &SimplifiedShow) -> Show {
+    Show {
+        id: show.id.id().to_string(),
+        uri: show.id.uri(),
+        name: show.name.clone(),
+        publisher: show.publisher.clone(),
+    }
+}
+
+fn episode(episode: &SimplifiedEpisode) -> Episode {
+    let resume_point = episode.resume_point.as_ref();
+    Episode {
+        id: episode.id.id().to_string(),
+        uri: episode.id.uri(),
+        name: episode.name.clone(),
+        release_date: episode.release_date.clone(),
+        duration_ms: episode.duration.num_milliseconds() as u32,
+        resume_position_ms: resume_point.map_or(0, |point| point.resume_position.num_milliseconds() as u32),
+        fully_played: resume_point.is_some_and(|point| point.fully_played),
+        marked_played: false, // Set from the played episodes cache
+    }
+}
+
+/// Up to `limit` items from `offset` of an offset-paged endpoint, fetched
+/// `LIBRARY_PAGE_LIMIT` at a time. Returns the items and the list's total.
+async fn collect_offset_pages<T, F, Fut>(offset: usize, limit: usize, fetch: F) -> Result<(Vec<T>, u32)>
+where
+    T: serde::de::DeserializeOwned,
+    F: Fn(u32, u32) -> Fut,
+    Fut: std::future::Future<Output = rspotify::ClientResult<Page<T>>>,
+{
+    let mut items = Vec::new();
+    let mut total = 0;
+    while items.len() < limit {
+        let page_size = (limit - items.len()).min(LIBRARY_PAGE_LIMIT) as u32;
+        let page = fetch(page_size, (offset + items.len()) as u32).await?;
+        total = page.total;
+        let received = page.items.len();
+        items.extend(page.items);
+        if received == 0 || page.next.is_none() {
+            break;
+        }
+    }
+    Ok((items, total))
+}
+
 /// Spotify API client with caching and token refresh support
 #[derive(Clone)]
 pub struct SpotifyClient {
     client: Arc<AuthCodeSpotify>,
     local_device_name: Option<String>,
-    liked_songs_cache: LikedSongsCache,
+    liked_songs_cache: LibraryCache,
+    saved_albums_cache: LibraryCache,
+    followed_artists_cache: LibraryCache,
+    played_episodes_cache: LibraryCache,
     refresh_token: Arc<RwLock<String>>,
     token_expires_at: Arc<RwLock<Option<chrono::DateTime<chrono::Utc>>>>,
+    cache_dir: PathBuf,
+    user_id: Arc<OnceCell<String>>,
 }
 
 impl SpotifyClient {
@@ -32,16 +208,26 @@ impl SpotifyClient {
         local_device_name: Option<String>,
         refresh_token: String,
         expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        cache_dir: PathBuf,
     ) -> Self {
         Self {
             client: Arc::new(client),
             local_device_name,
-            liked_songs_cache: LikedSongsCache::new(),
+            liked_songs_cache: LibraryCache::new(&cache_dir, LIKED_SONGS_CACHE_FILE),
+            saved_albums_cache: LibraryCache::new(&cache_dir, SAVED_ALBUMS_CACHE_FILE),
+            followed_artists_cache: LibraryCache::new(&cache_dir, FOLLOWED_ARTISTS_CACHE_FILE),
+            played_episodes_cache: LibraryCache::new(&cache_dir, PLAYED_EPISODES_CACHE_FILE),
             refresh_token: Arc::new(RwLock::new(refresh_token)),
             token_expires_at: Arc::new(RwLock::new(expires_at)),
+            cache_dir,
+            user_id: Arc::new(OnceCell::new()),
         }
     }
 
+    pub fn liked_songs_cache_exists(&self) -> bool {
+        self.liked_songs_cache.exists_on_disk()
+    }
+
     pub async fn token_needs_refresh(&self) -> bool {
         let expires_at = self.token_expires_at.read().await;
         if let Some(exp) = *expires_at {
@@ -54,48 +240,6 @@ impl SpotifyClient {
         }
     }
 
-    pub async fn refresh_token_if_needed(&self) -> Result<bool> {
-        if !self.token_needs_refresh().await {
-            return Ok(false);
-        }
-
-        let refresh_token = self.refresh_token.read().await.clone();
-
-        tracing::info!("Token expiring soon, refreshing...");
-
-        match crate::auth::refresh_access_token(&refresh_token).await {
-            Ok((new_access_token, new_refresh_token, new_expires_at)) => {
-                // Update the client's token
-                use rspotify::Token;
-                use std::collections::HashSet;
-
-                let new_token = Token {
-                    access_token: new_access_token,
-                    expires_in: chrono::Duration::seconds(3600),
-                    expires_at: Some(new_expires_at),
-                    scopes: crate::auth::SCOPES
-                        .split_whitespace()
-                        .map(|s| s.to_string())
-                        .collect::<HashSet<String>>(),
-                    refresh_token: None,
-                };
-
-                *self.client.token.lock().await.unwrap() = Some(new_token);
-
-                // Update stored refresh token and expiry
-                *self.refresh_token.write().await = new_refresh_token;
-                *self.token_expires_at.write().await = Some(new_expires_at);
-
-                tracing::info!("Token refreshed successfully");
-                Ok(true)
-            }
-            Err(e) => {
-                tracing::error!(error = %e, "Failed to refresh token");
-                Err(e)
-            }
-        }
-    }
-
     pub async fn init_liked_songs_cache(&self) -> Result<()> {
         // First try to load from disk
         if let Err(_e) = self.liked_songs_cache.load_from_disk().await {
@@ -132,10 +276,30 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn mark_tracks_liked(&self, tracks: &mut [SearchTrack]) {
-        for track in tracks.iter_mut() {
-            track.liked = self.liked_songs_cache.is_liked(&track.id).await;
-        }
+    /// Load the saved albums, followed artists and played episodes caches
+    /// from disk
+    pub async fn init_library_caches(&self) {
+        // Missing files are fine - the refresh creates them
+        let _ = self.saved_albums_cache.load_from_disk().await;
+        let _ = self.followed_artists_cache.load_from_disk().await;
+        let _ = self.played_episodes_cache.load_from_disk().await;
+    }
+
+    /// Replace the saved albums and followed artists caches with the
+    /// library's current contents
+    pub async fn refresh_library_caches(&self) -> Result<()> {
+        tracing::debug!("Refreshing saved albums and followed artists caches from API");
+
+        let albums = self.get_saved_albums(u32::MAX).await?;
+        self.saved_albums_cache.update(albums.into_iter().map(|a| a.id).collect()).await;
+        let _ = self.saved_albums_cache.save_to_disk().await;
+
+        let artists = self.get_followed_artists(u32::MAX).await?;
+        self.followed_artists_cache.update(artists.into_iter().map(|a| a.id).collect()).await;
+        let _ = self.followed_artists_cache.save_to_disk().await;
+
+        tracing::info!("Saved albums and followed artists caches refreshed");
+        Ok(())
     }
 
     pub async fn add_to_liked_songs(&self, track_id: &str) -> Result<()> {
@@ -172,31 +336,6 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn toggle_liked_song(&self, track_id: &str) -> Result<bool> {
-        let is_liked = self.liked_songs_cache.is_liked(track_id).await;
-
-        if is_liked {
-            self.remove_from_liked_songs(track_id).await?;
-            Ok(false)
-        } else {
-            self.add_to_liked_songs(track_id).await?;
-            Ok(true)
-        }
-    }
-    
-    pub async fn get_current_playback(&self) -> Result<Option<CurrentPlaybackContext>> {
-        tracing::trace!("Fetching current playback state");
-        let result = self.client.current_playback(None, None::<Vec<_>>).await?;
-        if let Some(ref playback) = result {
-            tracing::trace!(
-                is_playing = playback.is_playing,
-                device = ?playback.device.name,
-                "Got playback state"
-            );
-        }
-        Ok(result)
-    }
-
     async fn get_device_id(&self) -> Option<String> {
         if let Ok(devices) = self.client.device().await {
             // First, try to find the active device
@@ -223,169 +362,380 @@ impl SpotifyClient {
         }
     }
 
-    pub async fn play(&self) -> Result<()> {
-        let device_id = self.get_device_id().await;
-        tracing::debug!(device_id = ?device_id, "API: resume_playback");
-        self.client
-            .resume_playback(device_id.as_deref(), None)
+    /// Liked songs with their save times, up to `limit`. Refreshes the liked
+    /// songs cache with what was fetched.
+    async fn saved_tracks(&self, limit: usize) -> Result<Vec<SavedTrack>> {
+        use futures::TryStreamExt;
+        use futures::StreamExt;
+
+        let tracks_stream = self.client.current_user_saved_tracks(None);
+        let saved_tracks: Vec<_> = tracks_stream
+            .take(limit)
+            .try_collect()
             .await?;
-        Ok(())
-    }
 
-    pub async fn pause(&self) -> Result<()> {
-        let device_id = self.get_device_id().await;
-        tracing::debug!(device_id = ?device_id, "API: pause_playback");
-        self.client.pause_playback(device_id.as_deref()).await?;
-        Ok(())
-    }
+        let tracks: Vec<SavedTrack> = saved_tracks
+            .into_iter()
+            .map(|saved| SavedTrack {
+                track: SearchTrack {
+                    liked: true, // These are liked songs by definition
+                    ..search_track(&saved.track)
+                },
+                added_at: saved.added_at.to_rfc3339(),
+            })
+            .collect();
 
-    pub async fn next_track(&self) -> Result<()> {
-        let device_id = self.get_device_id().await;
-        tracing::debug!(device_id = ?device_id, "API: next_track");
-        self.client.next_track(device_id.as_deref()).await?;
-        Ok(())
-    }
+        // Update the cache with all liked song IDs
+        let track_ids: Vec<String> = tracks.iter().map(|t| t.track.id.clone()).collect();
+        self.liked_songs_cache.update(track_ids).await;
 
-    pub async fn previous_track(&self) -> Result<()> {
-        let device_id = self.get_device_id().await;
-        tracing::debug!(device_id = ?device_id, "API: previous_track");
-        self.client.previous_track(device_id.as_deref()).await?;
-        Ok(())
-    }
+        // Save cache to disk (async, don't block on errors)
+        let cache = self.liked_songs_cache.clone();
+        tokio::spawn(async move {
+            let _ = cache.save_to_disk().await;
+        });
 
-    pub async fn set_shuffle(&self, state: bool) -> Result<()> {
-        let device_id = self.get_device_id().await;
-        tracing::debug!(state, device_id = ?device_id, "API: set_shuffle");
-        self.client.shuffle(state, device_id.as_deref()).await?;
-        Ok(())
+        Ok(tracks)
     }
 
-    pub async fn set_repeat(&self, state: RepeatState) -> Result<()> {
-        let device_id = self.get_device_id().await;
-        tracing::debug!(state = ?state, device_id = ?device_id, "API: set_repeat");
-        let repeat_state = match state {
-            RepeatState::Off => rspotify::model::RepeatState::Off,
-            RepeatState::All => rspotify::model::RepeatState::Context,
-            RepeatState::One => rspotify::model::RepeatState::Track,
-        };
-        self.client.repeat(repeat_state, device_id.as_deref()).await?;
-        Ok(())
-    }
+    pub async fn get_playlist_with_offset(&self, playlist_id: &str, offset: usize) -> Result<PlaylistDetail> {
+        use futures::TryStreamExt;
+        use futures::StreamExt;
 
-    pub async fn set_volume(&self, volume: u8) -> Result<()> {
-        let device_id = self.get_device_id().await;
-        tracing::debug!(volume, device_id = ?device_id, "API: set_volume");
-        self.client.volume(volume, device_id.as_deref()).await?;
-        Ok(())
-    }
-    
-    pub async fn get_available_devices(&self) -> Result<Vec<DeviceInfo>> {
-        tracing::debug!("API: get_available_devices");
-        let devices = self.client.device().await?;
-        let device_infos: Vec<DeviceInfo> = devices
-            .into_iter()
-            .map(|d| DeviceInfo {
-                id: d.id.unwrap_or_default(),
-                name: d.name,
-                is_active: d.is_active,
-            })
-            .collect();
-        tracing::debug!(count = device_infos.len(), "Found devices");
-        Ok(device_infos)
-    }
+        let id = PlaylistId::from_id(playlist_id)?;
 
-    pub async fn has_active_device(&self) -> bool {
-        if let Ok(devices) = self.client.device().await {
-            devices.iter().any(|d| d.is_active)
-        } else {
-            false
-        }
-    }
+        // First get playlist metadata
+        let playlist = self.client.playlist(id.clone(), None, None).await?;
+        let total_tracks = playlist.tracks.total;
 
-    pub async fn transfer_playback_to_device(&self, device_id: &str, start_playing: bool) -> Result<()> {
-        tracing::debug!(device_id, start_playing, "API: transfer_playback");
-        self.client
-            .transfer_playback(device_id, Some(start_playing))
+        // Fetch tracks with pagination using playlist_items stream
+        let items_stream = self.client.playlist_items(id.clone(), None, None);
+        let items: Vec<_> = items_stream
+            .skip(offset)
+            .take(PLAYLIST_PAGE_SIZE)
+            .try_collect()
             .await?;
-        Ok(())
-    }
-
-    pub async fn search(&self, query: &str, limit: u32) -> Result<SearchResults> {
-        // Use None for market to let Spotify use the user's account country
-        let market: Option<Market> = None;
-        let mut results = SearchResults::default();
-
-        // Search all types in parallel using futures::join!
-        let (track_result, album_result, artist_result, playlist_result) = futures::join!(
-            self.client.search(query, SearchType::Track, market, None, Some(limit), None),
-            self.client.search(query, SearchType::Album, market, None, Some(limit), None),
-            self.client.search(query, SearchType::Artist, market, None, Some(limit), None),
-            self.client.search(query, SearchType::Playlist, market, None, Some(limit), None)
-        );
 
-        // Process track results
-        if let Ok(rspotify::model::SearchResult::Tracks(page)) = track_result {
-            for track in page.items {
+        let mut tracks = Vec::new();
+        for item in items.iter() {
+            if let Some(PlayableItem::Track(track)) = &item.track {
                 let track_id = track.id.as_ref().map(|id| id.id().to_string()).unwrap_or_default();
                 let all_artists: Vec<String> = track.artists.iter().map(|a| a.name.clone()).collect();
-                results.tracks.push(SearchTrack {
+                tracks.push(SearchTrack {
                     uri: format!("spotify:track:{}", track_id),
                     id: track_id,
-                    name: track.name,
+                    name: track.name.clone(),
                     artist: track.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
                     artists: all_artists,
-                    album: track.album.name,
+                    album: track.album.name.clone(),
                     duration_ms: track.duration.num_milliseconds() as u32,
                     liked: false, // Set by mark_tracks_liked() in controller
                 });
             }
         }
 
-        // Process album results
-        if let Ok(rspotify::model::SearchResult::Albums(page)) = album_result {
-            for album in page.items {
-                results.albums.push(SearchAlbum {
-                    id: album.id.as_ref().map(|id| id.id().to_string()).unwrap_or_default(),
-                    name: album.name,
-                    artist: album.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
-                    year: album.release_date.unwrap_or_default().chars().take(4).collect(),
-                });
-            }
-        }
+        let loaded_count = offset + tracks.len();
+        let has_more = loaded_count < total_tracks as usize;
+        let user_id = self.current_user_id().await?;
 
-        // Process artist results
-        if let Ok(rspotify::model::SearchResult::Artists(page)) = artist_result {
-            for artist in page.items {
-                results.artists.push(SearchArtist {
-                    id: artist.id.id().to_string(),
-                    name: artist.name,
-                    genres: artist.genres,
-                });
-            }
-        }
+        Ok(PlaylistDetail {
+            id: playlist_id.to_string(),
+            uri: format!("spotify:playlist:{}", playlist_id),
+            name: playlist.name,
+            owner: playlist.owner.display_name.clone().unwrap_or_else(|| playlist.owner.id.to_string()),
+            description: playlist.description.unwrap_or_default(),
+            snapshot_id: playlist.snapshot_id,
+            editable: playlist.collaborative || playlist.owner.id.id() == user_id,
+            public: playlist.public.unwrap_or(false),
+            collaborative: playlist.collaborative,
+            tracks,
+            total_tracks,
+            has_more,
+            loading_more: false,
+        })
+    }
+}
 
-        // Process playlist results
-        if let Ok(rspotify::model::SearchResult::Playlists(page)) = playlist_result {
-            for playlist in page.items {
-                let playlist_id = playlist.id.id().to_string();
-                results.playlists.push(SearchPlaylist {
-                    id: playlist_id.clone(),
-                    name: playlist.name,
-                    owner: playlist.owner.display_name.unwrap_or_else(|| playlist.owner.id.id().to_string()),
-                    total_tracks: playlist.tracks.total,
-                });
-            }
+#[async_trait]
+impl SpotifyApi for SpotifyClient {
+    async fn refresh_token_if_needed(&self) -> Result<bool> {
+        if !self.token_needs_refresh().await {
+            return Ok(false);
         }
 
-        // Determine which category best matches the query
-        results.determine_best_match(query);
+        let refresh_token = self.refresh_token.read().await.clone();
 
-        Ok(results)
-    }
+        tracing::info!("Token expiring soon, refreshing...");
 
-    pub async fn get_album(&self, album_id: &str) -> Result<AlbumDetail> {
-        let id = AlbumId::from_id(album_id)?;
-        let album = self.client.album(id.clone(), None).await?;
+        // Refresh against the same accounts service the client was configured with
+        let endpoints = Endpoints {
+            api_base_url: self.client.config.api_base_url.clone(),
+            accounts_base_url: self.client.config.auth_base_url.clone(),
+        };
+
+        match crate::auth::refresh_access_token(&refresh_token, &endpoints, &self.cache_dir).await {
+            Ok((new_access_token, new_refresh_token, new_expires_at)) => {
+                // Update the client's token
+                use rspotify::Token;
+                use std::collections::HashSet;
+
+                let new_token = Token {
+                    access_token: new_access_token,
+                    expires_in: chrono::Duration::seconds(3600),
+                    expires_at: Some(new_expires_at),
+                    scopes: crate::auth::SCOPES
+                        .split_whitespace()
+                        .map(|s| s.to_string())
+                        .collect::<HashSet<String>>(),
+                    refresh_token: None,
+                };
+
+                *self
+                    .client
+                    .token
+                    .lock()
+                    .await
+                    .map_err(|_| anyhow::anyhow!("Token lock is poisoned"))? = Some(new_token);
+
+                // Update stored refresh token and expiry
+                *self.refresh_token.write().await = new_refresh_token;
+                *self.token_expires_at.write().await = Some(new_expires_at);
+
+                tracing::info!("Token refreshed successfully");
+                Ok(true)
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to refresh token");
+                Err(e)
+            }
+        }
+    }
+
+    async fn current_user_id(&self) -> Result<String> {
+        let id = self
+            .user_id
+            .get_or_try_init(|| async { Ok::<_, anyhow::Error>(self.client.me().await?.id.id().to_string()) })
+            .await?;
+        Ok(id.clone())
+    }
+
+    async fn mark_tracks_liked(&self, tracks: &mut [SearchTrack]) {
+        for track in tracks.iter_mut() {
+            track.liked = self.liked_songs_cache.contains(&track.id).await;
+        }
+    }
+
+    async fn toggle_liked_song(&self, track_id: &str) -> Result<bool> {
+        let is_liked = self.liked_songs_cache.contains(track_id).await;
+
+        if is_liked {
+            self.remove_from_liked_songs(track_id).await?;
+            Ok(false)
+        } else {
+            self.add_to_liked_songs(track_id).await?;
+            Ok(true)
+        }
+    }
+
+    async fn is_album_saved(&self, album_id: &str) -> bool {
+        self.saved_albums_cache.contains(album_id).await
+    }
+
+    async fn is_artist_followed(&self, artist_id: &str) -> bool {
+        self.followed_artists_cache.contains(artist_id).await
+    }
+
+    async fn toggle_saved_album(&self, album_id: &str) -> Result<bool> {
+        let id = AlbumId::from_id(album_id)?;
+        let saved = !self.saved_albums_cache.contains(album_id).await;
+        if saved {
+            self.client.current_user_saved_albums_add([id]).await?;
+            self.saved_albums_cache.add(album_id.to_string()).await;
+        } else {
+            self.client.current_user_saved_albums_delete([id]).await?;
+            self.saved_albums_cache.remove(album_id).await;
+        }
+        let _ = self.saved_albums_cache.save_to_disk().await;
+
+        tracing::info!(album_id, saved, "Toggled saved album");
+        Ok(saved)
+    }
+
+    async fn toggle_followed_artist(&self, artist_id: &str) -> Result<bool> {
+        let id = ArtistId::from_id(artist_id)?;
+        let followed = !self.followed_artists_cache.contains(artist_id).await;
+        if followed {
+            self.client.user_follow_artists([id]).await?;
+            self.followed_artists_cache.add(artist_id.to_string()).await;
+        } else {
+            self.client.user_unfollow_artists([id]).await?;
+            self.followed_artists_cache.remove(artist_id).await;
+        }
+        let _ = self.followed_artists_cache.save_to_disk().await;
+
+        tracing::info!(artist_id, followed, "Toggled followed artist");
+        Ok(followed)
+    }
+
+    async fn get_current_playback(&self) -> Result<Option<CurrentPlaybackContext>> {
+        tracing::trace!("Fetching current playback state");
+        let result = self.client.current_playback(None, None::<Vec<_>>).await?;
+        if let Some(ref playback) = result {
+            tracing::trace!(
+                is_playing = playback.is_playing,
+                device = ?playback.device.name,
+                "Got playback state"
+            );
+        }
+        Ok(result)
+    }
+
+    async fn play(&self) -> Result<()> {
+        let device_id = self.get_device_id().await;
+        tracing::debug!(device_id = ?device_id, "API: resume_playback");
+        self.client
+            .resume_playback(device_id.as_deref(), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn pause(&self) -> Result<()> {
+        let device_id = self.get_device_id().await;
+        tracing::debug!(device_id = ?device_id, "API: pause_playback");
+        self.client.pause_playback(device_id.as_deref()).await?;
+        Ok(())
+    }
+
+    async fn next_track(&self) -> Result<()> {
+        let device_id = self.get_device_id().await;
+        tracing::debug!(device_id = ?device_id, "API: next_track");
+        self.client.next_track(device_id.as_deref()).await?;
+        Ok(())
+    }
+
+    async fn previous_track(&self) -> Result<()> {
+        let device_id = self.get_device_id().await;
+        tracing::debug!(device_id = ?device_id, "API: previous_track");
+        self.client.previous_track(device_id.as_deref()).await?;
+        Ok(())
+    }
+
+    async fn set_shuffle(&self, state: bool) -> Result<()> {
+        let device_id = self.get_device_id().await;
+        tracing::debug!(state, device_id = ?device_id, "API: set_shuffle");
+        self.client.shuffle(state, device_id.as_deref()).await?;
+        Ok(())
+    }
+
+    async fn set_repeat(&self, state: RepeatState) -> Result<()> {
+        let device_id = self.get_device_id().await;
+        tracing::debug!(state = ?state, device_id = ?device_id, "API: set_repeat");
+        let repeat_state = match state {
+            RepeatState::Off => rspotify::model::RepeatState::Off,
+            RepeatState::All => rspotify::model::RepeatState::Context,
+            RepeatState::One => rspotify::model::RepeatState::Track,
+        };
+        self.client.repeat(repeat_state, device_id.as_deref()).await?;
+        Ok(())
+    }
+
+    async fn set_volume(&self, volume: u8) -> Result<()> {
+        let device_id = self.get_device_id().await;
+        tracing::debug!(volume, device_id = ?device_id, "API: set_volume");
+        self.client.volume(volume, device_id.as_deref()).await?;
+        Ok(())
+    }
+
+    async fn seek(&self, position_ms: u32) -> Result<()> {
+        let device_id = self.get_device_id().await;
+        tracing::debug!(position_ms, device_id = ?device_id, "API: seek");
+        self.client
+            .seek_track(chrono::Duration::milliseconds(position_ms as i64), device_id.as_deref())
+            .await?;
+        Ok(())
+    }
+
+    async fn get_available_devices(&self) -> Result<Vec<DeviceInfo>> {
+        tracing::debug!("API: get_available_devices");
+        let devices = self.client.device().await?;
+        let device_infos: Vec<DeviceInfo> = devices
+            .into_iter()
+            .map(|d| DeviceInfo {
+                id: d.id.unwrap_or_default(),
+                name: d.name,
+                is_active: d.is_active,
+            })
+            .collect();
+        tracing::debug!(count = device_infos.len(), "Found devices");
+        Ok(device_infos)
+    }
+
+    async fn has_active_device(&self) -> bool {
+        if let Ok(devices) = self.client.device().await {
+            devices.iter().any(|d| d.is_active)
+        } else {
+            false
+        }
+    }
+
+    async fn transfer_playback_to_device(&self, device_id: &str, start_playing: bool) -> Result<()> {
+        tracing::debug!(device_id, start_playing, "API: transfer_playback");
+        self.client
+            .transfer_playback(device_id, Some(start_playing))
+            .await?;
+        Ok(())
+    }
+
+    async fn search(&self, query: &str, limit: u32) -> Result<SearchResults> {
+        // Use None for market to let Spotify use the user's account country
+        let market: Option<Market> = None;
+        let mut results = SearchResults { query: query.to_string(), ..Default::default() };
+        let parsed = SearchQuery::parse(query)?;
+
+        // Search the types the filters apply to in parallel
+        let searches = parsed
+            .sections()
+            .into_iter()
+            .map(|section| self.client.search(query, search_type(section), market, None, Some(limit), None));
+
+        // A category that fails is left empty rather than failing the search
+        for result in futures::future::join_all(searches).await.into_iter().flatten() {
+            add_search_page(&mut results, result);
+        }
+
+        // Determine which category best matches the query
+        results.determine_best_match(&parsed.text);
+
+        Ok(results)
+    }
+
+    async fn search_category(
+        &self,
+        query: &str,
+        section: SearchResultSection,
+        offset: usize,
+        limit: u32,
+    ) -> Result<(SearchResults, u32, bool)> {
+        // Spotify refuses offsets past the end of its result window
+        let limit = limit.min(SEARCH_OFFSET_LIMIT.saturating_sub(offset) as u32);
+        let mut results = SearchResults { query: query.to_string(), best_match: section, ..Default::default() };
+        if limit == 0 {
+            return Ok((results, SEARCH_OFFSET_LIMIT as u32, false));
+        }
+        // Nothing of this type matches the query's filters
+        if !SearchQuery::parse(query)?.sections().contains(&section) {
+            return Ok((results, 0, false));
+        }
+
+        let result = self.client.search(query, search_type(section), None, None, Some(limit), Some(offset as u32)).await?;
+        let total = add_search_page(&mut results, result).min(SEARCH_OFFSET_LIMIT as u32);
+        let has_more = results.len(section) > 0 && offset + results.len(section) < total as usize;
+        Ok((results, total, has_more))
+    }
+
+    async fn get_album(&self, album_id: &str) -> Result<AlbumDetail> {
+        let id = AlbumId::from_id(album_id)?;
+        let album = self.client.album(id.clone(), None).await?;
 
         let mut tracks = Vec::new();
 
@@ -411,67 +761,15 @@ impl SpotifyClient {
             artist: album.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
             year: album.release_date.chars().take(4).collect(),
             tracks,
+            saved: false, // Set by the controller from is_album_saved()
         })
     }
 
-    pub const PLAYLIST_PAGE_SIZE: usize = 100;
-
-    pub async fn get_playlist(&self, playlist_id: &str) -> Result<PlaylistDetail> {
+    async fn get_playlist(&self, playlist_id: &str) -> Result<PlaylistDetail> {
         self.get_playlist_with_offset(playlist_id, 0).await
     }
 
-    pub async fn get_playlist_with_offset(&self, playlist_id: &str, offset: usize) -> Result<PlaylistDetail> {
-        use futures::TryStreamExt;
-        use futures::StreamExt;
-
-        let id = PlaylistId::from_id(playlist_id)?;
-
-        // First get playlist metadata
-        let playlist = self.client.playlist(id.clone(), None, None).await?;
-        let total_tracks = playlist.tracks.total;
-
-        // Fetch tracks with pagination using playlist_items stream
-        let items_stream = self.client.playlist_items(id.clone(), None, None);
-        let items: Vec<_> = items_stream
-            .skip(offset)
-            .take(Self::PLAYLIST_PAGE_SIZE)
-            .try_collect()
-            .await?;
-
-        let mut tracks = Vec::new();
-        for item in items.iter() {
-            if let Some(PlayableItem::Track(track)) = &item.track {
-                let track_id = track.id.as_ref().map(|id| id.id().to_string()).unwrap_or_default();
-                let all_artists: Vec<String> = track.artists.iter().map(|a| a.name.clone()).collect();
-                tracks.push(SearchTrack {
-                    uri: format!("spotify:track:{}", track_id),
-                    id: track_id,
-                    name: track.name.clone(),
-                    artist: track.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
-                    artists: all_artists,
-                    album: track.album.name.clone(),
-                    duration_ms: track.duration.num_milliseconds() as u32,
-                    liked: false, // Set by mark_tracks_liked() in controller
-                });
-            }
-        }
-
-        let loaded_count = offset + tracks.len();
-        let has_more = loaded_count < total_tracks as usize;
-
-        Ok(PlaylistDetail {
-            id: playlist_id.to_string(),
-            uri: format!("spotify:playlist:{}", playlist_id),
-            name: playlist.name,
-            owner: playlist.owner.display_name.clone().unwrap_or_else(|| playlist.owner.id.to_string()),
-            tracks,
-            total_tracks,
-            has_more,
-            loading_more: false,
-        })
-    }
-
-    pub async fn get_more_playlist_tracks(&self, playlist_id: &str, offset: usize) -> Result<(Vec<SearchTrack>, u32, bool)> {
+    async fn get_more_playlist_tracks(&self, playlist_id: &str, offset: usize) -> Result<(Vec<SearchTrack>, u32, bool)> {
         use futures::TryStreamExt;
         use futures::StreamExt;
 
@@ -485,7 +783,7 @@ impl SpotifyClient {
         let items_stream = self.client.playlist_items(id.clone(), None, None);
         let items: Vec<_> = items_stream
             .skip(offset)
-            .take(Self::PLAYLIST_PAGE_SIZE)
+            .take(PLAYLIST_PAGE_SIZE)
             .try_collect()
             .await?;
 
@@ -514,7 +812,7 @@ impl SpotifyClient {
         Ok((tracks, total_tracks, has_more))
     }
 
-    pub async fn get_artist(&self, artist_id: &str) -> Result<ArtistDetail> {
+    async fn get_artist(&self, artist_id: &str) -> Result<ArtistDetail> {
         use futures::TryStreamExt;
 
         let id = ArtistId::from_id(artist_id)?;
@@ -543,58 +841,142 @@ impl SpotifyClient {
             .collect();
 
         // Get albums - artist_albums returns a stream
-        let album_stream = self.client.artist_albums(id, None, None);
-        let album_pages: Vec<_> = album_stream.try_collect().await?;
-
-        let albums: Vec<SearchAlbum> = album_pages
-            .into_iter()
-            .map(|album| SearchAlbum {
-                id: album.id.as_ref().map(|i| i.id().to_string()).unwrap_or_default(),
-                name: album.name,
-                artist: album.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
-                year: album.release_date.unwrap_or_default().chars().take(4).collect(),
-            })
-            .collect();
-
-        Ok(ArtistDetail {
+        let album_stream = self.client.artist_albums(id.clone(), None, None);
+        let mut releases: Vec<SimplifiedAlbum> = album_stream.try_collect().await?;
+        // Newest first. Dates are "YYYY-MM-DD", or shorter when Spotify only
+        // knows the month or year, so they sort as text.
+        releases.sort_by(|a, b| b.release_date.cmp(&a.release_date));
+
+        let mut detail = ArtistDetail {
+            id: artist_id.to_string(),
             name: artist.name,
             genres: artist.genres,
+            followers: artist.followers.total,
+            popularity: artist.popularity,
             top_tracks,
-            albums,
-        })
+            followed: false, // Set by the controller from is_artist_followed()
+            ..Default::default()
+        };
+        for release in &releases {
+            let group = release.album_group.as_deref().or(release.album_type.as_deref());
+            let section = match group {
+                Some("single") => &mut detail.singles,
+                Some("compilation") => &mut detail.compilations,
+                Some("appears_on") => &mut detail.appears_on,
+                _ => &mut detail.albums,
+            };
+            section.push(simplified_album(release));
+        }
+
+        // Spotify no longer serves related artists to newer API clients; the
+        // section is left empty then rather than failing the whole page
+        #[allow(deprecated)]
+        let related = self.client.artist_related_artists(id).await;
+        detail.related = match related {
+            Ok(artists) => artists.iter().map(search_artist).collect(),
+            Err(e) => {
+                tracing::warn!(artist_id, error = %e, "Could not load related artists");
+                Vec::new()
+            }
+        };
+        Ok(detail)
     }
 
-    pub async fn play_track(&self, uri: &str) -> Result<()> {
-        let device_id = self.get_device_id().await;
-        tracing::debug!(uri, device_id = ?device_id, "API: play_track");
+    async fn get_all_playlist_tracks(&self, playlist_id: &str) -> Result<Vec<SearchTrack>> {
+        use futures::TryStreamExt;
 
-        // Extract track ID from URI (format: spotify:track:ID)
-        let track_id = uri.split(':').last().unwrap_or(uri);
+        let id = PlaylistId::from_id(playlist_id)?;
+        let items: Vec<_> = self.client.playlist_items(id, None, None).try_collect().await?;
 
-        self.client
-            .start_uris_playback(
-                [PlayableId::Track(TrackId::from_id(track_id)?)],
-                device_id.as_deref(),
-                None,
-                None,
-            )
-            .await?;
-        Ok(())
+        // Episodes, unavailable tracks and local files have no track URI to export
+        let tracks = items
+            .iter()
+            .filter_map(|item| match &item.track {
+                Some(PlayableItem::Track(track)) if track.id.is_some() => Some(search_track(track)),
+                _ => None,
+            })
+            .collect();
+        Ok(tracks)
     }
 
-    pub async fn play_context_from_track_uri(&self, context_uri: &str, track_uri: &str) -> Result<()> {
-        let device_id = self.get_device_id().await;
-
+    async fn get_tracks(&self, uris: &[String]) -> Result<Vec<Option<SearchTrack>>> {
+        let mut found = vec![None; uris.len()];
+
+        // Only well-formed track URIs are looked up; the rest stay `None`
+        let valid: Vec<(usize, &str)> = uris
+            .iter()
+            .enumerate()
+            .filter_map(|(index, uri)| {
+                // `from_id` accepts an empty ID, which the API rejects
+                let id = uri.strip_prefix("spotify:track:").filter(|id| !id.is_empty())?;
+                TrackId::from_id(id).ok().map(|_| (index, id))
+            })
+            .collect();
+
+        // The several-tracks endpoint answers unknown IDs with `null`, which
+        // rspotify's `tracks()` can't deserialize, so the response is parsed here
+        for chunk in valid.chunks(TRACKS_LOOKUP_LIMIT) {
+            let ids = chunk.iter().map(|(_, id)| *id).collect::<Vec<_>>().join(",");
+            let query = std::collections::HashMap::from([("ids", ids.as_str())]);
+            let response = self.client.api_get("tracks", &query).await?;
+
+            #[derive(serde::Deserialize)]
+            struct Tracks {
+                tracks: Vec<Option<FullTrack>>,
+            }
+            let tracks: Tracks = serde_json::from_str(&response)?;
+            for ((index, _), track) in chunk.iter().zip(tracks.tracks) {
+                found[*index] = track.as_ref().map(search_track);
+            }
+        }
+        Ok(found)
+    }
+
+    async fn play_tracks(&self, uris: &[String], offset: usize) -> Result<()> {
+        let device_id = self.get_device_id().await;
+        tracing::debug!(count = uris.len(), offset, device_id = ?device_id, "API: play_tracks");
+
+        // Extract track IDs from URIs (format: spotify:track:ID)
+        let ids = uris
+            .iter()
+            .map(|uri| Ok(PlayableId::Track(TrackId::from_id(uri.split(':').next_back().unwrap_or(uri))?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // rspotify sends `Offset::Position` as the duration's millisecond count,
+        // which the API reads as a track index
+        let offset = rspotify::model::Offset::Position(chrono::Duration::milliseconds(offset as i64));
+
+        self.client
+            .start_uris_playback(ids, device_id.as_deref(), Some(offset), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn play_context_from_track_uri(&self, context_uri: &str, track_uri: &str) -> Result<()> {
+        let device_id = self.get_device_id().await;
+
         // Parse the URI to determine type
         let play_context = if context_uri.contains(":album:") {
-            let id = context_uri.split(':').last().unwrap_or("");
+            let id = context_uri.split(':').next_back().unwrap_or("");
             PlayContextId::Album(AlbumId::from_id(id)?)
         } else if context_uri.contains(":playlist:") {
-            let id = context_uri.split(':').last().unwrap_or("");
+            let id = context_uri.split(':').next_back().unwrap_or("");
             PlayContextId::Playlist(PlaylistId::from_id(id)?)
         } else if context_uri.contains(":artist:") {
-            let id = context_uri.split(':').last().unwrap_or("");
+            let id = context_uri.split(':').next_back().unwrap_or("");
             PlayContextId::Artist(ArtistId::from_id(id)?)
+        } else if context_uri.ends_with(":collection") {
+            // rspotify has no PlayContextId for the liked songs collection
+            let url = match &device_id {
+                Some(id) => format!("me/player/play?device_id={}", id),
+                None => "me/player/play".to_string(),
+            };
+            let body = serde_json::json!({
+                "context_uri": context_uri,
+                "offset": { "uri": track_uri },
+            });
+            self.client.api_put(&url, &body).await?;
+            return Ok(());
         } else {
             return Err(anyhow::anyhow!("Unknown context type: {}", context_uri));
         };
@@ -608,7 +990,7 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn get_queue(&self) -> Result<(Option<SearchTrack>, Vec<SearchTrack>)> {
+    async fn get_queue(&self) -> Result<(Option<SearchTrack>, Vec<SearchTrack>)> {
         let queue_result = self.client.current_user_queue().await?;
 
         // Convert currently playing track
@@ -626,7 +1008,7 @@ impl SpotifyClient {
                             artists: all_artists,
                             album: track.album.name.clone(),
                             duration_ms: track.duration.num_milliseconds() as u32,
-                            liked: self.liked_songs_cache.is_liked(&track_id).await,
+                            liked: self.liked_songs_cache.contains(&track_id).await,
                         })
                     } else {
                         None
@@ -653,7 +1035,7 @@ impl SpotifyClient {
                         artists: all_artists,
                         album: track.album.name.clone(),
                         duration_ms: track.duration.num_milliseconds() as u32,
-                        liked: self.liked_songs_cache.is_liked(&track_id).await,
+                        liked: self.liked_songs_cache.contains(&track_id).await,
                     });
                 }
             }
@@ -662,8 +1044,8 @@ impl SpotifyClient {
         Ok((currently_playing, queue_tracks))
     }
 
-    pub async fn add_to_queue(&self, track_uri: &str) -> Result<()> {
-        let track_id = track_uri.split(':').last().unwrap_or(track_uri);
+    async fn add_to_queue(&self, track_uri: &str) -> Result<()> {
+        let track_id = track_uri.split(':').next_back().unwrap_or(track_uri);
         let id = TrackId::from_id(track_id)?;
         let device_id = self.get_device_id().await;
 
@@ -673,20 +1055,118 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn get_user_playlists(&self, limit: u32) -> Result<Vec<PlaylistItem>> {
+    async fn add_tracks_to_playlist(&self, playlist_id: &str, uris: &[String]) -> Result<String> {
+        let id = PlaylistId::from_id(playlist_id)?;
+
+        // The API takes at most 100 items per request
+        let mut snapshot_id = String::new();
+        for chunk in uris.chunks(PLAYLIST_EDIT_LIMIT) {
+            let result = self.client.playlist_add_items(id.clone(), track_ids(chunk)?, None).await?;
+            snapshot_id = result.snapshot_id;
+        }
+        tracing::info!(playlist_id, count = uris.len(), "Added tracks to playlist");
+        Ok(snapshot_id)
+    }
+
+    async fn replace_playlist_tracks(&self, playlist_id: &str, uris: &[String]) -> Result<()> {
+        let id = PlaylistId::from_id(playlist_id)?;
+
+        // Replace with the first 100 (an empty list clears the playlist), then append the rest
+        let first = uris.len().min(PLAYLIST_EDIT_LIMIT);
+        self.client.playlist_replace_items(id.clone(), track_ids(&uris[..first])?).await?;
+        for chunk in uris[first..].chunks(PLAYLIST_EDIT_LIMIT) {
+            self.client.playlist_add_items(id.clone(), track_ids(chunk)?, None).await?;
+        }
+        tracing::info!(playlist_id, count = uris.len(), "Replaced playlist tracks");
+        Ok(())
+    }
+
+    async fn remove_playlist_track(
+        &self,
+        playlist_id: &str,
+        track_uri: &str,
+        position: usize,
+        snapshot_id: &str,
+    ) -> Result<String> {
+        let id = PlaylistId::from_id(playlist_id)?;
+        let track_id = TrackId::from_id(track_uri.split(':').next_back().unwrap_or(track_uri))?;
+        let positions = [position as u32];
+        let item = ItemPositions { id: PlayableId::Track(track_id), positions: &positions };
+
+        let result = self
+            .client
+            .playlist_remove_specific_occurrences_of_items(id, [item], Some(snapshot_id))
+            .await?;
+        tracing::info!(playlist_id, track_uri, position, "Removed track from playlist");
+        Ok(result.snapshot_id)
+    }
+
+    async fn move_playlist_track(
+        &self,
+        playlist_id: &str,
+        from: usize,
+        insert_before: usize,
+        snapshot_id: &str,
+    ) -> Result<String> {
+        let id = PlaylistId::from_id(playlist_id)?;
+        let result = self
+            .client
+            .playlist_reorder_items(id, Some(from as i32), Some(insert_before as i32), Some(1), Some(snapshot_id))
+            .await?;
+        tracing::info!(playlist_id, from, insert_before, "Moved playlist track");
+        Ok(result.snapshot_id)
+    }
+
+    async fn create_playlist(&self, name: &str, visibility: PlaylistVisibility) -> Result<PlaylistItem> {
+        let user_id = self.current_user_id().await?;
+        let (public, collaborative) = match visibility {
+            PlaylistVisibility::Private => (false, false),
+            PlaylistVisibility::Public => (true, false),
+            PlaylistVisibility::Collaborative => (false, true),
+        };
+
+        let playlist = self
+            .client
+            .user_playlist_create(UserId::from_id(&user_id)?, name, Some(public), Some(collaborative), None)
+            .await?;
+        tracing::info!(name, visibility = visibility.label(), "Created playlist");
+        Ok(PlaylistItem {
+            id: playlist.id.id().to_string(),
+            name: playlist.name,
+            editable: true,
+        })
+    }
+
+    async fn update_playlist_details(&self, playlist_id: &str, name: Option<&str>, description: Option<&str>) -> Result<()> {
+        let id = PlaylistId::from_id(playlist_id)?;
+        self.client.playlist_change_detail(id, name, None, description, None).await?;
+        tracing::info!(playlist_id, ?name, ?description, "Updated playlist details");
+        Ok(())
+    }
+
+    async fn unfollow_playlist(&self, playlist_id: &str) -> Result<()> {
+        let id = PlaylistId::from_id(playlist_id)?;
+        self.client.playlist_unfollow(id).await?;
+        tracing::info!(playlist_id, "Unfollowed playlist");
+        Ok(())
+    }
+
+    async fn get_user_playlists(&self, limit: u32) -> Result<Vec<PlaylistItem>> {
         use futures::TryStreamExt;
+        use futures::StreamExt;
 
+        let user_id = self.current_user_id().await?;
         let playlist_stream = self.client.current_user_playlists();
-        let all_playlists: Vec<_> = playlist_stream.try_collect().await?;
+        let all_playlists: Vec<_> = playlist_stream.take(limit as usize).try_collect().await?;
 
         let playlists: Vec<PlaylistItem> = all_playlists
             .into_iter()
-            .take(limit as usize)
             .map(|playlist| {
                 let id = playlist.id.id().to_string();
                 PlaylistItem {
                     id: id.clone(),
                     name: playlist.name,
+                    editable: playlist.collaborative || playlist.owner.id.id() == user_id,
                 }
             })
             .collect();
@@ -694,20 +1174,30 @@ impl SpotifyClient {
         Ok(playlists)
     }
 
-    pub async fn get_liked_songs(&self, limit: u32) -> Result<Vec<SearchTrack>> {
+    async fn get_saved_albums(&self, limit: u32) -> Result<Vec<SearchAlbum>> {
         use futures::TryStreamExt;
         use futures::StreamExt;
 
-        let tracks_stream = self.client.current_user_saved_tracks(None);
-        let saved_tracks: Vec<_> = tracks_stream
-            .take(limit as usize)
-            .try_collect()
-            .await?;
+        let albums_stream = self.client.current_user_saved_albums(None);
+        let saved_albums: Vec<_> = albums_stream.take(limit as usize).try_collect().await?;
+
+        Ok(saved_albums
+            .iter()
+            .map(|saved| SearchAlbum { saved: true, ..search_album(&saved.album) })
+            .collect())
+    }
 
-        let tracks: Vec<SearchTrack> = saved_tracks
+    async fn get_followed_artists(&self, limit: u32) -> Result<Vec<SearchArtist>> {
+        Ok(self.get_followed_artists_page(None, limit).await?.0)
+    }
+
+    async fn get_recently_played(&self, limit: u32) -> Result<Vec<SearchTrack>> {
+        let history = self.client.current_user_recently_played(Some(limit), None).await?;
+
+        let tracks: Vec<SearchTrack> = history.items
             .into_iter()
-            .map(|saved| {
-                let track = saved.track;
+            .map(|item| {
+                let track = item.track;
                 let track_id = track.id.as_ref().map(|id| id.id().to_string()).unwrap_or_default();
                 let all_artists: Vec<String> = track.artists.iter().map(|a| a.name.clone()).collect();
                 SearchTrack {
@@ -718,84 +1208,892 @@ impl SpotifyClient {
                     artists: all_artists,
                     album: track.album.name,
                     duration_ms: track.duration.num_milliseconds() as u32,
-                    liked: true, // These are liked songs by definition
+                    liked: false, // Set by mark_tracks_liked() in controller
                 }
             })
             .collect();
 
-        // Update the cache with all liked song IDs
-        let track_ids: Vec<String> = tracks.iter().map(|t| t.id.clone()).collect();
-        self.liked_songs_cache.update(track_ids).await;
-
-        // Save cache to disk (async, don't block on errors)
-        let cache = self.liked_songs_cache.clone();
-        tokio::spawn(async move {
-            let _ = cache.save_to_disk().await;
-        });
-
         Ok(tracks)
     }
 
-    pub async fn get_saved_albums(&self, limit: u32) -> Result<Vec<SearchAlbum>> {
-        use futures::TryStreamExt;
+    async fn get_liked_songs_page(&self, offset: usize, limit: u32) -> Result<(Vec<SearchTrack>, u32, bool)> {
+        let (saved, total) = collect_offset_pages(offset, limit as usize, |limit, offset| {
+            self.client.current_user_saved_tracks_manual(None, Some(limit), Some(offset))
+        })
+        .await?;
 
-        let albums_stream = self.client.current_user_saved_albums(None);
-        let saved_albums: Vec<_> = albums_stream.try_collect().await?;
+        let mut tracks = Vec::with_capacity(saved.len());
+        for saved in saved {
+            let track = SearchTrack { liked: true, ..search_track(&saved.track) };
+            self.liked_songs_cache.add(track.id.clone()).await;
+            tracks.push(track);
+        }
+        let has_more = offset + tracks.len() < total as usize;
+        Ok((tracks, total, has_more))
+    }
 
-        let albums: Vec<SearchAlbum> = saved_albums
-            .into_iter()
-            .take(limit as usize)
-            .map(|saved| {
-                let album = saved.album;
-                SearchAlbum {
-                    id: album.id.id().to_string(),
-                    name: album.name,
-                    artist: album.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
-                    year: album.release_date.chars().take(4).collect(),
-                }
+    async fn get_saved_albums_page(&self, offset: usize, limit: u32) -> Result<(Vec<SearchAlbum>, u32, bool)> {
+        let (saved, total) = collect_offset_pages(offset, limit as usize, |limit, offset| {
+            self.client.current_user_saved_albums_manual(None, Some(limit), Some(offset))
+        })
+        .await?;
+
+        let albums: Vec<SearchAlbum> = saved
+            .iter()
+            .map(|saved| SearchAlbum {
+                saved: true, // These are saved albums by definition
+                ..search_album(&saved.album)
             })
             .collect();
-
-        Ok(albums)
+        for album in &albums {
+            self.saved_albums_cache.add(album.id.clone()).await;
+        }
+        let has_more = offset + albums.len() < total as usize;
+        Ok((albums, total, has_more))
     }
 
-    pub async fn get_followed_artists(&self, limit: u32) -> Result<Vec<SearchArtist>> {
-        let result = self.client.current_user_followed_artists(None, Some(limit)).await?;
-
-        let artists: Vec<SearchArtist> = result.items
-            .into_iter()
-            .map(|artist| SearchArtist {
+    async fn get_followed_artists_page(&self, after: Option<&str>, limit: u32) -> Result<(Vec<SearchArtist>, u32, bool)> {
+        // Followed artists are paged by cursor, at most 50 per request
+        let limit = limit as usize;
+        let mut artists = Vec::new();
+        let mut after = after.map(str::to_string);
+        let mut total = 0;
+        let mut has_more = true;
+        while has_more && artists.len() < limit {
+            let page_size = (limit - artists.len()).min(LIBRARY_PAGE_LIMIT) as u32;
+            let page = self.client.current_user_followed_artists(after.as_deref(), Some(page_size)).await?;
+            let received = page.items.len();
+            total = page.total.unwrap_or(total);
+            for artist in &page.items {
+                self.followed_artists_cache.add(artist.id.id().to_string()).await;
+            }
+            artists.extend(page.items.into_iter().map(|artist| SearchArtist {
                 id: artist.id.id().to_string(),
                 name: artist.name,
                 genres: artist.genres,
-            })
-            .collect();
+                followed: true, // These are followed artists by definition
+            }));
+
+            after = page.cursors.and_then(|cursors| cursors.after);
+            has_more = received > 0 && page.next.is_some() && after.is_some();
+        }
 
-        Ok(artists)
+        Ok((artists, total, has_more))
     }
 
-    pub async fn get_recently_played(&self, limit: u32) -> Result<Vec<SearchTrack>> {
-        let history = self.client.current_user_recently_played(Some(limit), None).await?;
+    async fn get_all_liked_songs(&self) -> Result<Vec<SavedTrack>> {
+        self.saved_tracks(usize::MAX).await
+    }
 
-        let tracks: Vec<SearchTrack> = history.items
+    async fn save_liked_songs(&self, songs: &[(String, String)]) -> Result<()> {
+        // rspotify can't send save times, so the timestamped form is built here
+        let now = chrono::Utc::now().to_rfc3339();
+        for chunk in songs.chunks(SAVE_LIMIT) {
+            let ids = chunk
+                .iter()
+                .map(|(uri, added_at)| {
+                    let id = TrackId::from_id(uri.split(':').next_back().unwrap_or(uri))?;
+                    let added_at = if added_at.is_empty() { &now } else { added_at };
+                    Ok(serde_json::json!({ "id": id.id(), "added_at": added_at }))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            self.client.api_put("me/tracks", &serde_json::json!({ "timestamped_ids": ids })).await?;
+
+            for (uri, _) in chunk {
+                self.liked_songs_cache.add(uri.split(':').next_back().unwrap_or(uri).to_string()).await;
+            }
+        }
+        tracing::info!(count = songs.len(), "Saved liked songs");
+        Ok(())
+    }
+
+    async fn save_albums(&self, album_ids: &[String]) -> Result<()> {
+        for chunk in album_ids.chunks(SAVE_ALBUMS_LIMIT) {
+            let ids = chunk.iter().map(|id| AlbumId::from_id(id.as_str())).collect::<Result<Vec<_>, _>>()?;
+            self.client.current_user_saved_albums_add(ids).await?;
+            for id in chunk {
+                self.saved_albums_cache.add(id.clone()).await;
+            }
+        }
+        tracing::info!(count = album_ids.len(), "Saved albums");
+        Ok(())
+    }
+
+    async fn follow_artists(&self, artist_ids: &[String]) -> Result<()> {
+        for chunk in artist_ids.chunks(SAVE_LIMIT) {
+            let ids = chunk.iter().map(|id| ArtistId::from_id(id.as_str())).collect::<Result<Vec<_>, _>>()?;
+            self.client.user_follow_artists(ids).await?;
+            for id in chunk {
+                self.followed_artists_cache.add(id.clone()).await;
+            }
+        }
+        tracing::info!(count = artist_ids.len(), "Followed artists");
+        Ok(())
+    }
+
+    async fn follow_playlist(&self, playlist_id: &str) -> Result<()> {
+        self.client.playlist_follow(PlaylistId::from_id(playlist_id)?, None).await?;
+        tracing::info!(playlist_id, "Followed playlist");
+        Ok(())
+    }
+
+    async fn get_saved_shows_page(&self, offset: usize, limit: u32) -> Result<(Vec<Show>, u32, bool)> {
+        let (saved, total) = collect_offset_pages(offset, limit as usize, |limit, offset| {
+            self.client.get_saved_show_manual(Some(limit), Some(offset))
+        })
+        .await?;
+
+        let shows: Vec<Show> = saved.iter().map(|saved| show(&saved.show)).collect();
+        let has_more = offset + shows.len() < total as usize;
+        Ok((shows, total, has_more))
+    }
+
+    async fn get_show(&self, show_id: &str) -> Result<ShowDetail> {
+        let full = self.client.get_a_show(ShowId::from_id(show_id)?, Some(Market::FromToken)).await?;
+        Ok(ShowDetail {
+            show: Show {
+                id: full.id.id().to_string(),
+                uri: full.id.uri(),
+                name: full.name,
+                publisher: full.publisher,
+            },
+            description: full.description,
+            episodes: Vec::new(),
+        })
+    }
+
+    async fn get_show_episodes_page(&self, show_id: &str, offset: usize, limit: u32) -> Result<(Vec<Episode>, u32, bool)> {
+        let id = ShowId::from_id(show_id)?;
+        let (items, total) = collect_offset_pages(offset, limit as usize, |limit, offset| {
+            self.client.get_shows_episodes_manual(id.as_ref(), Some(Market::FromToken), Some(limit), Some(offset))
+        })
+        .await?;
+
+        let mut episodes: Vec<Episode> = items.iter().map(episode).collect();
+        for episode in episodes.iter_mut() {
+            episode.marked_played = self.played_episodes_cache.contains(&episode.id).await;
+        }
+        let has_more = offset + episodes.len() < total as usize;
+        Ok((episodes, total, has_more))
+    }
+
+    async fn play_episode(&self, show_uri: &str, episode_uri: &str, position_ms: u32) -> Result<()> {
+        let device_id = self.get_device_id().await;
+        tracing::debug!(show_uri, episode_uri, position_ms, device_id = ?device_id, "API: play_episode");
+
+        let show_id = ShowId::from_id(show_uri.split(':').next_back().unwrap_or(show_uri))?;
+        self.client
+            .start_context_playback(
+                PlayContextId::Show(show_id),
+                device_id.as_deref(),
+                Some(Offset::Uri(episode_uri.to_string())),
+                Some(chrono::Duration::milliseconds(position_ms as i64)),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn toggle_episode_played(&self, episode_id: &str) -> Result<bool> {
+        let played = !self.played_episodes_cache.contains(episode_id).await;
+        if played {
+            self.played_episodes_cache.add(episode_id.to_string()).await;
+        } else {
+            self.played_episodes_cache.remove(episode_id).await;
+        }
+        self.played_episodes_cache.save_to_disk().await?;
+        Ok(played)
+    }
+
+    async fn get_new_releases(&self, limit: u32) -> Result<Vec<SearchAlbum>> {
+        let page = self.client.new_releases_manual(None, Some(limit), None).await?;
+        Ok(page.items.iter().map(simplified_album).collect())
+    }
+
+    async fn get_categories(&self, limit: u32) -> Result<Vec<Category>> {
+        let page = self.client.categories_manual(None, None, Some(limit), None).await?;
+        Ok(page
+            .items
             .into_iter()
-            .map(|item| {
-                let track = item.track;
-                let track_id = track.id.as_ref().map(|id| id.id().to_string()).unwrap_or_default();
-                let all_artists: Vec<String> = track.artists.iter().map(|a| a.name.clone()).collect();
-                SearchTrack {
-                    uri: format!("spotify:track:{}", track_id),
-                    id: track_id,
-                    name: track.name,
-                    artist: track.artists.first().map(|a| a.name.clone()).unwrap_or_default(),
-                    artists: all_artists,
-                    album: track.album.name,
-                    duration_ms: track.duration.num_milliseconds() as u32,
-                    liked: false, // Set by mark_tracks_liked() in controller
-                }
+            .map(|category| Category { id: category.id, name: category.name })
+            .collect())
+    }
+
+    async fn get_category_playlists_page(
+        &self,
+        category_id: &str,
+        offset: usize,
+        limit: u32,
+    ) -> Result<(Vec<SearchPlaylist>, u32, bool)> {
+        let (items, total) = collect_offset_pages(offset, limit as usize, |limit, offset| {
+            self.client.category_playlists_manual(category_id, None, Some(limit), Some(offset))
+        })
+        .await?;
+
+        let playlists: Vec<SearchPlaylist> = items.iter().map(search_playlist).collect();
+        let has_more = offset + playlists.len() < total as usize;
+        Ok((playlists, total, has_more))
+    }
+
+    async fn get_top_tracks(&self, range: TopRange, limit: u32) -> Result<Vec<SearchTrack>> {
+        let page = self
+            .client
+            .current_user_top_tracks_manual(Some(time_range(range)), Some(limit), None)
+            .await?;
+        Ok(page.items.iter().map(search_track).collect())
+    }
+
+    async fn get_top_artists(&self, range: TopRange, limit: u32) -> Result<Vec<SearchArtist>> {
+        let page = self
+            .client
+            .current_user_top_artists_manual(Some(time_range(range)), Some(limit), None)
+            .await?;
+        Ok(page.items.iter().map(search_artist).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::{fixtures, MockData, MockPlaylist, MockSpotifyServer};
+
+    async fn client_for(server: &MockSpotifyServer) -> SpotifyClient {
+        let rspotify = AuthCodeSpotify::with_config(
+            Default::default(),
+            Default::default(),
+            server.endpoints().rspotify_config(),
+        );
+        *rspotify.token.lock().await.unwrap() = Some(rspotify::Token {
+            access_token: "test-token".to_string(),
+            ..Default::default()
+        });
+
+        SpotifyClient {
+            client: Arc::new(rspotify),
+            local_device_name: None,
+            liked_songs_cache: LibraryCache::in_memory(),
+            saved_albums_cache: LibraryCache::in_memory(),
+            followed_artists_cache: LibraryCache::in_memory(),
+            played_episodes_cache: LibraryCache::in_memory(),
+            refresh_token: Arc::new(RwLock::new("test-refresh".to_string())),
+            token_expires_at: Arc::new(RwLock::new(None)),
+            cache_dir: PathBuf::new(),
+            user_id: Arc::new(OnceCell::new()),
+        }
+    }
+
+    fn playlist_with_tracks(count: usize) -> MockData {
+        MockData {
+            playlists: vec![MockPlaylist {
+                id: fixtures::id("playlist", 1),
+                name: "Big Playlist".to_string(),
+                owner: "tester".to_string(),
+                tracks: fixtures::tracks(count),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn offsets(server: &MockSpotifyServer, path: &str) -> Vec<usize> {
+        server
+            .requests_to(path)
+            .iter()
+            .map(|r| r.query_usize("offset").unwrap_or(0))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn get_playlist_loads_first_page_across_api_pages() {
+        let server = MockSpotifyServer::start(playlist_with_tracks(230)).await;
+        let client = client_for(&server).await;
+        let playlist_id = fixtures::id("playlist", 1);
+
+        let detail = client.get_playlist(&playlist_id).await.unwrap();
+
+        assert_eq!(detail.name, "Big Playlist");
+        assert_eq!(detail.owner, "tester");
+        assert_eq!(detail.total_tracks, 230);
+        assert_eq!(detail.tracks.len(), PLAYLIST_PAGE_SIZE);
+        assert!(detail.has_more);
+        assert_eq!(detail.tracks.first().unwrap().name, "Track 1");
+        assert_eq!(detail.tracks.last().unwrap().name, "Track 100");
+
+        let path = format!("/v1/playlists/{}/tracks", playlist_id);
+        assert_eq!(offsets(&server, &path), vec![0, 50]);
+    }
+
+    #[tokio::test]
+    async fn get_playlist_with_offset_returns_final_partial_page() {
+        let server = MockSpotifyServer::start(playlist_with_tracks(230)).await;
+        let client = client_for(&server).await;
+        let playlist_id = fixtures::id("playlist", 1);
+
+        let detail = client.get_playlist_with_offset(&playlist_id, 200).await.unwrap();
+
+        assert_eq!(detail.tracks.len(), 30);
+        assert_eq!(detail.tracks.first().unwrap().name, "Track 201");
+        assert_eq!(detail.tracks.last().unwrap().name, "Track 230");
+        assert!(!detail.has_more);
+    }
+
+    #[tokio::test]
+    async fn get_more_playlist_tracks_reports_has_more() {
+        let server = MockSpotifyServer::start(playlist_with_tracks(150)).await;
+        let client = client_for(&server).await;
+        let playlist_id = fixtures::id("playlist", 1);
+
+        let (tracks, total, has_more) = client.get_more_playlist_tracks(&playlist_id, 100).await.unwrap();
+        assert_eq!(tracks.len(), 50);
+        assert_eq!(total, 150);
+        assert!(!has_more);
+
+        let (tracks, _, has_more) = client.get_more_playlist_tracks(&playlist_id, 0).await.unwrap();
+        assert_eq!(tracks.len(), 100);
+        assert!(has_more);
+    }
+
+    #[tokio::test]
+    async fn liked_songs_page_stops_at_limit_and_fills_cache() {
+        let server = MockSpotifyServer::start(MockData {
+            liked_tracks: fixtures::tracks(130),
+            ..Default::default()
+        })
+        .await;
+        let client = client_for(&server).await;
+
+        let (tracks, total, has_more) = client.get_liked_songs_page(0, 120).await.unwrap();
+
+        assert_eq!(tracks.len(), 120);
+        assert_eq!(total, 130);
+        assert!(has_more);
+        assert!(tracks.iter().all(|t| t.liked));
+        assert_eq!(tracks[119].name, "Track 120");
+        assert_eq!(offsets(&server, "/v1/me/tracks"), vec![0, 50, 100]);
+
+        let mut check = vec![
+            SearchTrack { id: fixtures::id("track", 5), ..tracks[0].clone() },
+            SearchTrack { id: fixtures::id("track", 125), ..tracks[0].clone() },
+        ];
+        client.mark_tracks_liked(&mut check).await;
+        assert!(check[0].liked);
+        assert!(!check[1].liked, "tracks past the limit are not cached");
+
+        // The next page picks up where the first stopped
+        let (tracks, _, has_more) = client.get_liked_songs_page(120, 120).await.unwrap();
+        assert_eq!(tracks.len(), 10);
+        assert_eq!(tracks[0].name, "Track 121");
+        assert!(!has_more);
+        assert_eq!(offsets(&server, "/v1/me/tracks").last(), Some(&120));
+    }
+
+    #[tokio::test]
+    async fn liked_songs_page_handles_library_smaller_than_limit() {
+        let server = MockSpotifyServer::start(MockData {
+            liked_tracks: fixtures::tracks(30),
+            ..Default::default()
+        })
+        .await;
+        let client = client_for(&server).await;
+
+        let (tracks, total, has_more) = client.get_liked_songs_page(0, 100).await.unwrap();
+
+        assert_eq!(tracks.len(), 30);
+        assert_eq!(total, 30);
+        assert!(!has_more);
+        assert_eq!(offsets(&server, "/v1/me/tracks"), vec![0]);
+    }
+
+    #[tokio::test]
+    async fn get_queue_converts_tracks_and_liked_state() {
+        let server = MockSpotifyServer::start(MockData {
+            currently_playing: Some(fixtures::track(1)),
+            queue: vec![fixtures::track(2), fixtures::track(3)],
+            ..Default::default()
+        })
+        .await;
+        let client = client_for(&server).await;
+        client.liked_songs_cache.add(fixtures::id("track", 3)).await;
+
+        let (current, queue) = client.get_queue().await.unwrap();
+
+        let current = current.unwrap();
+        assert_eq!(current.name, "Track 1");
+        assert_eq!(current.uri, format!("spotify:track:{}", fixtures::id("track", 1)));
+        assert!(!current.liked);
+        assert_eq!(queue.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), vec!["Track 2", "Track 3"]);
+        assert!(queue[1].liked);
+    }
+
+    #[tokio::test]
+    async fn get_queue_with_nothing_playing() {
+        let server = MockSpotifyServer::start(MockData::default()).await;
+        let client = client_for(&server).await;
+
+        let (current, queue) = client.get_queue().await.unwrap();
+
+        assert!(current.is_none());
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_current_playback_parses_player_state() {
+        let server = MockSpotifyServer::start(MockData {
+            currently_playing: Some(fixtures::track(7)),
+            is_playing: true,
+            ..Default::default()
+        })
+        .await;
+        let client = client_for(&server).await;
+
+        let playback = client.get_current_playback().await.unwrap().unwrap();
+
+        assert!(playback.is_playing);
+        assert_eq!(playback.device.name, "Mock Device");
+        match playback.item {
+            Some(PlayableItem::Track(track)) => assert_eq!(track.name, "Track 7"),
+            other => panic!("unexpected item: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_current_playback_is_none_when_idle() {
+        let server = MockSpotifyServer::start(MockData::default()).await;
+        let client = client_for(&server).await;
+
+        assert!(client.get_current_playback().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn seek_sends_position() {
+        let server = MockSpotifyServer::start(MockData::default()).await;
+        let client = client_for(&server).await;
+
+        client.seek(83_000).await.unwrap();
+
+        let seeks = server.requests_to("/v1/me/player/seek");
+        assert_eq!(seeks.len(), 1);
+        assert_eq!(seeks[0].method, "PUT");
+        assert_eq!(seeks[0].query_usize("position_ms"), Some(83_000));
+    }
+
+    #[tokio::test]
+    async fn show_episodes_carry_resume_points_and_played_marks() {
+        let server = MockSpotifyServer::start(MockData {
+            episodes: vec![
+                fixtures::episode(3, None, false),
+                fixtures::episode(2, Some(600_000), false),
+                fixtures::episode(1, Some(1_801_000), true),
+            ],
+            ..Default::default()
+        })
+        .await;
+        let client = client_for(&server).await;
+        let show_id = fixtures::id("show", 1);
+        assert!(client.toggle_episode_played(&fixtures::id("episode", 3)).await.unwrap());
+
+        let (episodes, total, has_more) = client.get_show_episodes_page(&show_id, 0, 2).await.unwrap();
+        assert_eq!((total, has_more), (3, true));
+        assert!(episodes[0].played() && episodes[0].marked_played);
+        assert_eq!(episodes[1].start_position_ms(), 600_000);
+        let (episodes, _, has_more) = client.get_show_episodes_page(&show_id, 2, 2).await.unwrap();
+        assert!(!has_more);
+        assert!(episodes[0].fully_played && episodes[0].start_position_ms() == 0);
+
+        let show_uri = format!("spotify:show:{}", show_id);
+        client.play_episode(&show_uri, &episodes[0].uri, 600_000).await.unwrap();
+        let plays = server.requests_to("/v1/me/player/play");
+        let body: serde_json::Value = serde_json::from_str(&plays[0].body).unwrap();
+        assert_eq!(body["context_uri"], show_uri.as_str());
+        assert_eq!(body["offset"]["uri"], episodes[0].uri.as_str());
+        assert_eq!(body["position_ms"], 600_000);
+    }
+
+    #[tokio::test]
+    async fn home_sections_read_top_items_and_page_category_playlists() {
+        let playlists = (1..=60)
+            .map(|n| MockPlaylist {
+                id: fixtures::id("playlist", n),
+                name: format!("Mood {}", n),
+                owner: "spotify".to_string(),
+                tracks: fixtures::tracks(n % 3),
             })
             .collect();
+        let server = MockSpotifyServer::start(MockData {
+            playlists,
+            top_tracks: fixtures::tracks(3),
+            top_artists: vec![fixtures::artist(2), fixtures::artist(4)],
+            ..Default::default()
+        })
+        .await;
+        let client = client_for(&server).await;
+
+        let tracks = client.get_top_tracks(TopRange::SixMonths, 3).await.unwrap();
+        let artists = client.get_top_artists(TopRange::Year, 10).await.unwrap();
+        assert_eq!(tracks.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), ["Track 1", "Track 2", "Track 3"]);
+        assert_eq!(artists[1].name, "Artist 4");
+        let top = server.requests_to("/v1/me/top/tracks");
+        assert_eq!(top[0].query.get("time_range").map(String::as_str), Some("medium_term"));
+        let top = server.requests_to("/v1/me/top/artists");
+        assert_eq!(top[0].query.get("time_range").map(String::as_str), Some("long_term"));
+
+        let (page, total, has_more) = client.get_category_playlists_page("chill", 40, 15).await.unwrap();
+        assert_eq!((page.len(), total, has_more), (15, 60, true));
+        assert_eq!(page[0].name, "Mood 41");
+        assert_eq!(page[1].total_tracks, 0);
+        let (page, _, has_more) = client.get_category_playlists_page("chill", 55, 15).await.unwrap();
+        assert_eq!((page.len(), has_more), (5, false));
+    }
 
-        Ok(tracks)
+    #[tokio::test]
+    async fn artist_releases_are_grouped_newest_first() {
+        let mut artist = fixtures::artist(1);
+        artist["followers"]["total"] = 123_456.into();
+        artist["popularity"] = 71.into();
+        let server = MockSpotifyServer::start(MockData {
+            artists: vec![artist],
+            top_tracks: fixtures::tracks(2),
+            artist_albums: vec![
+                fixtures::album(1, "album", "2015-03-01"),
+                fixtures::album(2, "single", "2021-06-11"),
+                fixtures::album(3, "album", "2019"),
+                fixtures::album(4, "appears_on", "2018-01-01"),
+                fixtures::album(5, "compilation", "2020-12-01"),
+                fixtures::album(6, "single", "2022-02-02"),
+            ],
+            // Related artists answer 404
+            ..Default::default()
+        })
+        .await;
+        let client = client_for(&server).await;
+
+        let detail = client.get_artist(&fixtures::id("artist", 1)).await.unwrap();
+        let names = |albums: &[SearchAlbum]| albums.iter().map(|a| a.name.clone()).collect::<Vec<_>>();
+        assert_eq!((detail.followers, detail.popularity), (123_456, 71));
+        assert_eq!(detail.top_tracks.len(), 2);
+        assert_eq!(names(&detail.albums), ["Album 3", "Album 1"]);
+        assert_eq!(names(&detail.singles), ["Album 6", "Album 2"]);
+        assert_eq!(names(&detail.compilations), ["Album 5"]);
+        assert_eq!(names(&detail.appears_on), ["Album 4"]);
+        assert_eq!(detail.appears_on[0].artist, "Artist 4");
+        assert!(detail.related.is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_forwards_query_and_limit() {
+        let server = MockSpotifyServer::start(playlist_with_tracks(60)).await;
+        let client = client_for(&server).await;
+
+        let results = client.search("track 1", 5).await.unwrap();
+
+        assert_eq!(results.tracks.len(), 5);
+        assert!(results.tracks.iter().all(|t| t.name.starts_with("Track 1")));
+        assert!(results.albums.is_empty());
+        assert!(results.playlists.is_empty());
+
+        let search = server.requests_to("/v1/search");
+        assert_eq!(search.len(), 4);
+        assert!(search.iter().all(|r| r.query.get("q").map(String::as_str) == Some("track 1")));
+    }
+
+    #[tokio::test]
+    async fn search_filters_narrow_the_requested_types() {
+        let server = MockSpotifyServer::start(playlist_with_tracks(5)).await;
+        let client = client_for(&server).await;
+        let types = |server: &MockSpotifyServer| -> Vec<String> {
+            let mut types: Vec<String> = server
+                .requests_to("/v1/search")
+                .iter()
+                .filter_map(|r| r.query.get("type").cloned())
+                .collect();
+            types.sort();
+            types
+        };
+
+        client.search("genre:jazz blue", 5).await.unwrap();
+        assert_eq!(types(&server), ["artist", "track"]);
+        let search = server.requests_to("/v1/search");
+        assert!(search.iter().all(|r| r.query.get("q").map(String::as_str) == Some("genre:jazz blue")));
+
+        let server = MockSpotifyServer::start(playlist_with_tracks(5)).await;
+        let client = client_for(&server).await;
+        client.search("tag:new", 5).await.unwrap();
+        assert!(client.search("year:19", 5).await.is_err());
+        assert_eq!(types(&server), ["album"]);
+    }
+
+    #[tokio::test]
+    async fn search_category_pages_one_type() {
+        let server = MockSpotifyServer::start(playlist_with_tracks(60)).await;
+        let client = client_for(&server).await;
+
+        let (results, total, has_more) = client.search_category("track", SearchResultSection::Tracks, 45, 10).await.unwrap();
+        assert_eq!(results.query, "track");
+        assert_eq!(results.tracks.len(), 10);
+        assert_eq!(results.tracks[0].name, "Track 46");
+        assert_eq!((total, has_more), (60, true));
+
+        let search = server.requests_to("/v1/search");
+        assert_eq!(search.len(), 1);
+        assert_eq!(search[0].query.get("type").map(String::as_str), Some("track"));
+        assert_eq!(search[0].query_usize("offset"), Some(45));
+        assert_eq!(search[0].query_usize("limit"), Some(10));
+
+        // Nothing is asked for past Spotify's last offset
+        let (results, _, has_more) = client.search_category("track", SearchResultSection::Tracks, 1000, 10).await.unwrap();
+        assert!(results.tracks.is_empty() && !has_more);
+        assert_eq!(server.requests_to("/v1/search").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn play_tracks_starts_list_at_offset() {
+        let server = MockSpotifyServer::start(MockData::default()).await;
+        let client = client_for(&server).await;
+        let uris = vec!["spotify:track:a".to_string(), "spotify:track:b".to_string(), "spotify:track:c".to_string()];
+
+        client.play_tracks(&uris, 2).await.unwrap();
+
+        let plays = server.requests_to("/v1/me/player/play");
+        assert_eq!(plays.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&plays[0].body).unwrap();
+        assert_eq!(body["uris"], serde_json::json!(uris));
+        assert_eq!(body["offset"]["position"], 2);
+    }
+
+    #[tokio::test]
+    async fn liked_songs_play_in_collection_context() {
+        let server = MockSpotifyServer::start(MockData::default()).await;
+        let client = client_for(&server).await;
+
+        let user_id = client.current_user_id().await.unwrap();
+        let context = format!("spotify:user:{}:collection", user_id);
+        client.play_context_from_track_uri(&context, "spotify:track:a").await.unwrap();
+
+        let plays = server.requests_to("/v1/me/player/play");
+        assert_eq!(plays.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&plays[0].body).unwrap();
+        assert_eq!(body["context_uri"], "spotify:user:mock-user:collection");
+        assert_eq!(body["offset"]["uri"], "spotify:track:a");
+    }
+
+    #[tokio::test]
+    async fn get_playlist_reports_snapshot_and_ownership() {
+        let mut data = playlist_with_tracks(3);
+        data.playlists.push(MockPlaylist {
+            id: fixtures::id("playlist", 2),
+            name: "Mine".to_string(),
+            owner: "mock-user".to_string(),
+            tracks: fixtures::tracks(3),
+        });
+        let server = MockSpotifyServer::start(data).await;
+        let client = client_for(&server).await;
+
+        let theirs = client.get_playlist(&fixtures::id("playlist", 1)).await.unwrap();
+        let mine = client.get_playlist(&fixtures::id("playlist", 2)).await.unwrap();
+
+        assert_eq!(theirs.snapshot_id, "mock-snapshot");
+        assert!(!theirs.editable);
+        assert!(mine.editable);
+    }
+
+    #[tokio::test]
+    async fn playlist_edits_send_snapshot_and_positions() {
+        let server = MockSpotifyServer::start(playlist_with_tracks(5)).await;
+        let client = client_for(&server).await;
+        let playlist_id = fixtures::id("playlist", 1);
+        let track_uri = format!("spotify:track:{}", fixtures::id("track", 3));
+
+        let snapshot = client.remove_playlist_track(&playlist_id, &track_uri, 2, "mock-snapshot").await.unwrap();
+        assert_eq!(snapshot, "mock-snapshot-2");
+        client.move_playlist_track(&playlist_id, 0, 2, &snapshot).await.unwrap();
+        client.add_tracks_to_playlist(&playlist_id, std::slice::from_ref(&track_uri)).await.unwrap();
+
+        let requests = server.requests_to(&format!("/v1/playlists/{}/tracks", playlist_id));
+        let bodies: Vec<(String, serde_json::Value)> = requests
+            .iter()
+            .map(|r| (r.method.clone(), serde_json::from_str(&r.body).unwrap()))
+            .collect();
+
+        assert_eq!(bodies[0].0, "DELETE");
+        assert_eq!(bodies[0].1["snapshot_id"], "mock-snapshot");
+        assert_eq!(bodies[0].1["tracks"][0]["uri"], track_uri.as_str());
+        assert_eq!(bodies[0].1["tracks"][0]["positions"], serde_json::json!([2]));
+
+        assert_eq!(bodies[1].0, "PUT");
+        assert_eq!(bodies[1].1["snapshot_id"], "mock-snapshot-2");
+        assert_eq!(bodies[1].1["range_start"], 0);
+        assert_eq!(bodies[1].1["insert_before"], 2);
+
+        assert_eq!(bodies[2].0, "POST");
+        assert_eq!(bodies[2].1["uris"], serde_json::json!([track_uri]));
+    }
+
+    #[tokio::test]
+    async fn playlist_management_requests() {
+        let server = MockSpotifyServer::start(playlist_with_tracks(1)).await;
+        let client = client_for(&server).await;
+        let playlist_id = fixtures::id("playlist", 1);
+
+        let created = client.create_playlist("Road Trip", PlaylistVisibility::Collaborative).await.unwrap();
+        assert_eq!(created.name, "Road Trip");
+        assert!(created.editable);
+        let create = server.requests_to("/v1/users/mock-user/playlists");
+        let body: serde_json::Value = serde_json::from_str(&create[0].body).unwrap();
+        assert_eq!(body["public"], false);
+        assert_eq!(body["collaborative"], true);
+
+        client.update_playlist_details(&playlist_id, None, Some("Songs for the car")).await.unwrap();
+        let update = server.requests_to(&format!("/v1/playlists/{}", playlist_id));
+        assert_eq!(update[0].method, "PUT");
+        let body: serde_json::Value = serde_json::from_str(&update[0].body).unwrap();
+        assert_eq!(body, serde_json::json!({ "description": "Songs for the car" }));
+
+        client.unfollow_playlist(&playlist_id).await.unwrap();
+        let unfollow = server.requests_to(&format!("/v1/playlists/{}/followers", playlist_id));
+        assert_eq!(unfollow[0].method, "DELETE");
+    }
+
+    #[tokio::test]
+    async fn track_lookup_keeps_order_and_reports_unknown_uris() {
+        let server = MockSpotifyServer::start(playlist_with_tracks(3)).await;
+        let client = client_for(&server).await;
+        let uris = vec![
+            format!("spotify:track:{}", fixtures::id("track", 2)),
+            "not a uri".to_string(),
+            format!("spotify:track:{}", fixtures::id("track", 99)),
+            format!("spotify:track:{}", fixtures::id("track", 1)),
+        ];
+
+        let found = client.get_tracks(&uris).await.unwrap();
+        let names: Vec<Option<&str>> = found.iter().map(|t| t.as_ref().map(|t| t.name.as_str())).collect();
+        assert_eq!(names, [Some("Track 2"), None, None, Some("Track 1")]);
+
+        // The malformed URI is never sent
+        let lookups = server.requests_to("/v1/tracks");
+        assert_eq!(lookups.len(), 1);
+        assert_eq!(lookups[0].query["ids"].split(',').count(), 3);
+    }
+
+    #[tokio::test]
+    async fn local_files_are_left_out_of_exported_tracks() {
+        let playlist_id = fixtures::id("playlist", 1);
+        let server = MockSpotifyServer::start(MockData {
+            playlists: vec![MockPlaylist {
+                id: playlist_id.clone(),
+                name: "Mixed".to_string(),
+                owner: "tester".to_string(),
+                tracks: vec![fixtures::track(1), fixtures::local_track("Home Recording"), fixtures::track(2)],
+            }],
+            ..Default::default()
+        })
+        .await;
+        let client = client_for(&server).await;
+
+        let tracks = client.get_all_playlist_tracks(&playlist_id).await.unwrap();
+        let uris: Vec<&str> = tracks.iter().map(|t| t.uri.as_str()).collect();
+        let expected = [1, 2].map(|n| format!("spotify:track:{}", fixtures::id("track", n)));
+        assert_eq!(uris, expected);
+
+        // Exports written before local files were skipped hold an empty track URI
+        let found = client.get_tracks(&["spotify:track:".to_string(), expected[0].clone()]).await.unwrap();
+        assert!(found[0].is_none());
+        assert_eq!(server.requests_to("/v1/tracks")[0].query["ids"], fixtures::id("track", 1));
+    }
+
+    #[tokio::test]
+    async fn replacing_playlist_tracks_splits_into_batches_of_100() {
+        let server = MockSpotifyServer::start(playlist_with_tracks(1)).await;
+        let client = client_for(&server).await;
+        let playlist_id = fixtures::id("playlist", 1);
+        let uris: Vec<String> = (0..250).map(|n| format!("spotify:track:{}", fixtures::id("track", n))).collect();
+
+        client.replace_playlist_tracks(&playlist_id, &uris).await.unwrap();
+
+        let requests = server.requests_to(&format!("/v1/playlists/{}/tracks", playlist_id));
+        let methods: Vec<&str> = requests.iter().map(|r| r.method.as_str()).collect();
+        assert_eq!(methods, ["PUT", "POST", "POST"]);
+        let counts: Vec<usize> = requests
+            .iter()
+            .map(|r| serde_json::from_str::<serde_json::Value>(&r.body).unwrap()["uris"].as_array().unwrap().len())
+            .collect();
+        assert_eq!(counts, [100, 100, 50]);
+    }
+
+    #[tokio::test]
+    async fn followed_artists_follow_cursors_past_one_page() {
+        let server = MockSpotifyServer::start(MockData {
+            followed_artists: (1..=120).map(fixtures::artist).collect(),
+            ..Default::default()
+        })
+        .await;
+        let client = client_for(&server).await;
+
+        let artists = client.get_followed_artists(u32::MAX).await.unwrap();
+        assert_eq!(artists.len(), 120);
+        assert_eq!(artists[119].name, "Artist 120");
+
+        let afters: Vec<Option<String>> = server
+            .requests_to("/v1/me/following")
+            .iter()
+            .map(|r| r.query.get("after").cloned())
+            .collect();
+        assert_eq!(afters, [None, Some(fixtures::id("artist", 50)), Some(fixtures::id("artist", 100))]);
+
+        // A limit inside the first page makes a single, smaller request
+        let first = client.get_followed_artists(10).await.unwrap();
+        assert_eq!(first.len(), 10);
+        assert_eq!(server.requests_to("/v1/me/following").last().unwrap().query_usize("limit"), Some(10));
+
+        // Later pages start after the last artist shown
+        let after = fixtures::id("artist", 100);
+        let (rest, total, has_more) = client.get_followed_artists_page(Some(&after), 50).await.unwrap();
+        assert_eq!(rest.len(), 20);
+        assert_eq!(rest[0].name, "Artist 101");
+        assert_eq!(total, 120);
+        assert!(!has_more);
+    }
+
+    #[tokio::test]
+    async fn restored_liked_songs_keep_their_save_time() {
+        let server = MockSpotifyServer::start(MockData::default()).await;
+        let client = client_for(&server).await;
+        let songs: Vec<(String, String)> = (1..=60)
+            .map(|n| (format!("spotify:track:{}", fixtures::id("track", n)), "2020-05-01T10:00:00+00:00".to_string()))
+            .collect();
+
+        client.save_liked_songs(&songs).await.unwrap();
+
+        let requests = server.requests_to("/v1/me/tracks");
+        assert_eq!(requests.len(), 2);
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        let ids = body["timestamped_ids"].as_array().unwrap();
+        assert_eq!(ids.len(), 50);
+        assert_eq!(ids[0]["id"], fixtures::id("track", 1));
+        assert_eq!(ids[0]["added_at"], "2020-05-01T10:00:00+00:00");
+        assert!(client.liked_songs_cache.contains(&fixtures::id("track", 60)).await);
+    }
+
+    #[tokio::test]
+    async fn saving_albums_and_following_artists_toggle_the_caches() {
+        let server = MockSpotifyServer::start(MockData::default()).await;
+        let client = client_for(&server).await;
+        let album = fixtures::id("album", 1);
+        let artist = fixtures::id("artist", 1);
+
+        assert!(client.toggle_saved_album(&album).await.unwrap());
+        assert!(client.is_album_saved(&album).await);
+        assert!(!client.toggle_saved_album(&album).await.unwrap());
+        assert!(!client.is_album_saved(&album).await);
+        let methods: Vec<String> = server.requests_to("/v1/me/albums/").into_iter().map(|r| r.method).collect();
+        assert_eq!(methods, ["PUT", "DELETE"]);
+
+        assert!(client.toggle_followed_artist(&artist).await.unwrap());
+        let mut artists = vec![fixtures::id("artist", 2), artist.clone()]
+            .into_iter()
+            .map(|id| SearchArtist { id, name: String::new(), genres: Vec::new(), followed: false })
+            .collect::<Vec<_>>();
+        client.mark_artists_followed(&mut artists).await;
+        assert_eq!(artists.iter().map(|a| a.followed).collect::<Vec<_>>(), [false, true]);
+        assert!(!client.toggle_followed_artist(&artist).await.unwrap());
+        let methods: Vec<String> = server.requests_to("/v1/me/following").into_iter().map(|r| r.method).collect();
+        assert_eq!(methods, ["PUT", "DELETE"]);
     }
 }
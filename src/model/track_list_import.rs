@@ -0,0 +1,414 @@
+//! Track lists from outside Spotify (CSV, M3U, plain text) and matching
+//! their lines against search results
+//!
+//! Each line becomes a `TrackQuery`, which is searched for and scored
+//! against the returned tracks. Clear matches are taken as they are; lines
+//! with several equally good or only partial matches are left for the user
+//! to review before anything is added.
+
+use std::path::Path;
+
+use super::content::SearchTrack;
+use super::types::PlaylistItem;
+
+/// Search results considered per line
+pub const IMPORT_CANDIDATES: u32 = 5;
+
+/// Lowest score taken without review: title and artist must both match
+const CONFIDENT_SCORE: u32 = 160;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackListFormat {
+    /// `artist,title,album` rows, with an optional header naming the columns
+    Csv,
+    /// `#EXTINF:<seconds>,Artist - Title` entries, or file names
+    M3u,
+    /// `Artist - Title` lines
+    Text,
+}
+
+impl TrackListFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "m3u" | "m3u8" => Some(Self::M3u),
+            "txt" => Some(Self::Text),
+            _ => None,
+        }
+    }
+}
+
+/// One line of a track list
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackQuery {
+    /// 1-based line in the file
+    pub line: usize,
+    pub artist: String,
+    pub title: String,
+    pub album: Option<String>,
+}
+
+impl TrackQuery {
+    fn new(line: usize, artist: &str, title: &str, album: Option<&str>) -> Option<Self> {
+        let title = title.trim();
+        if title.is_empty() {
+            return None;
+        }
+        Some(Self {
+            line,
+            artist: artist.trim().to_string(),
+            title: title.to_string(),
+            album: album.map(str::trim).filter(|album| !album.is_empty()).map(str::to_string),
+        })
+    }
+
+    /// `Artist - Title`, or `Title` alone
+    fn from_label(line: usize, label: &str) -> Option<Self> {
+        let label = label.trim();
+        let separator = [" - ", " – "]
+            .iter()
+            .filter_map(|separator| label.find(separator).map(|at| (at, separator.len())))
+            .min();
+        match separator {
+            Some((at, len)) => Self::new(line, &label[..at], &label[at + len..], None),
+            None => Self::new(line, "", label, None),
+        }
+    }
+
+    pub fn search_query(&self) -> String {
+        format!("{} {}", self.artist, self.title).trim().to_string()
+    }
+
+    /// How the line reads in the review screen
+    pub fn label(&self) -> String {
+        if self.artist.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} - {}", self.artist, self.title)
+        }
+    }
+
+    /// Title and artist are scored like `SearchResults::determine_best_match`
+    /// (exact, prefix, substring); a matching album breaks ties
+    pub fn score(&self, track: &SearchTrack) -> u32 {
+        let tier = |candidate: &str, wanted: &str| {
+            let (candidate, wanted) = (normalize(candidate), normalize(wanted));
+            if candidate == wanted { 100 }
+            else if candidate.starts_with(&wanted) { 80 }
+            else if candidate.contains(&wanted) { 60 }
+            else { 0 }
+        };
+
+        let title_score = tier(&track.name, &self.title);
+        if title_score == 0 {
+            return 0;
+        }
+        // Without an artist any artist will do, but the line needs a look
+        let artist_score = if self.artist.is_empty() {
+            0
+        } else {
+            track.artists.iter().map(|artist| tier(artist, &self.artist)).max().unwrap_or(0)
+        };
+        let album_score = match &self.album {
+            Some(album) if normalize(album) == normalize(&track.album) => 20,
+            _ => 0,
+        };
+        title_score + artist_score + album_score
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+pub fn parse_track_list(text: &str, format: TrackListFormat) -> Vec<TrackQuery> {
+    let lines = text.lines().enumerate().map(|(i, line)| (i + 1, line.trim_start_matches('\u{feff}').trim()));
+    match format {
+        TrackListFormat::Csv => parse_csv(lines),
+        TrackListFormat::M3u => parse_m3u(lines),
+        TrackListFormat::Text => lines
+            .filter(|(_, line)| !line.starts_with('#'))
+            .filter_map(|(number, line)| TrackQuery::from_label(number, line))
+            .collect(),
+    }
+}
+
+fn parse_csv<'a>(lines: impl Iterator<Item = (usize, &'a str)>) -> Vec<TrackQuery> {
+    let mut columns = (0, 1, Some(2));
+    let mut queries = Vec::new();
+
+    let mut first = true;
+    for (number, line) in lines {
+        if line.is_empty() {
+            continue;
+        }
+        let fields = split_csv_row(line);
+        let field = |index: usize| fields.get(index).map(String::as_str).unwrap_or_default();
+
+        // A header row names the columns, in any order
+        if first {
+            first = false;
+            let lower: Vec<String> = fields.iter().map(|f| f.to_lowercase()).collect();
+            let find = |names: &[&str]| lower.iter().position(|f| names.contains(&f.as_str()));
+            if let Some(title) = find(&["title", "track", "name", "track name"]) {
+                let artist = find(&["artist", "artists", "artist name"]).unwrap_or(usize::MAX);
+                columns = (artist, title, find(&["album", "album name"]));
+                continue;
+            }
+        }
+
+        let (artist, title, album) = columns;
+        queries.extend(TrackQuery::new(number, field(artist), field(title), album.map(field)));
+    }
+    queries
+}
+
+/// Fields of one CSV row; quoted fields may contain commas and `""`
+fn split_csv_row(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn parse_m3u<'a>(lines: impl Iterator<Item = (usize, &'a str)>) -> Vec<TrackQuery> {
+    let mut queries = Vec::new();
+    let mut pending: Option<TrackQuery> = None;
+
+    for (number, line) in lines {
+        if let Some(info) = line.strip_prefix("#EXTINF:") {
+            // `#EXTINF:<seconds>,<label>`; the label describes the next entry
+            pending = info.split_once(',').and_then(|(_, label)| TrackQuery::from_label(number, label));
+        } else if line.is_empty() || line.starts_with('#') {
+            continue;
+        } else if let Some(query) = pending.take() {
+            queries.push(query);
+        } else {
+            // Plain entry: guess from the file name
+            let name = line.rsplit(['/', '\\']).next().unwrap_or(line);
+            let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+            queries.extend(TrackQuery::from_label(number, stem));
+        }
+    }
+    queries
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchStatus {
+    Matched,
+    /// Several or only partial matches; a candidate is preselected
+    NeedsReview,
+    NotFound,
+    /// The search itself failed, e.g. when rate limited
+    LookupFailed,
+}
+
+/// A track list line with its search results, best first
+#[derive(Clone, Debug)]
+pub struct ImportEntry {
+    pub query: TrackQuery,
+    pub candidates: Vec<SearchTrack>,
+    /// Index into `candidates`, `None` to skip the line
+    pub choice: Option<usize>,
+    pub status: MatchStatus,
+}
+
+impl ImportEntry {
+    pub fn resolve(query: TrackQuery, results: Vec<SearchTrack>) -> Self {
+        let mut scored: Vec<(u32, SearchTrack)> = results
+            .into_iter()
+            .map(|track| (query.score(&track), track))
+            .filter(|(score, _)| *score > 0)
+            .collect();
+        // Stable, so equal scores keep Spotify's relevance order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+        let status = match scored.as_slice() {
+            [] => MatchStatus::NotFound,
+            [(best, _), ..] if *best < CONFIDENT_SCORE => MatchStatus::NeedsReview,
+            // A tie only matters between different songs, not releases of one
+            [(best, first), (second, other), ..]
+                if best == second
+                    && (normalize(&first.name) != normalize(&other.name) || first.artists != other.artists) =>
+            {
+                MatchStatus::NeedsReview
+            }
+            _ => MatchStatus::Matched,
+        };
+
+        let candidates: Vec<SearchTrack> = scored.into_iter().map(|(_, track)| track).collect();
+        Self {
+            query,
+            choice: (!candidates.is_empty()).then_some(0),
+            candidates,
+            status,
+        }
+    }
+
+    /// A line whose search failed, so nothing is known about it
+    pub fn failed(query: TrackQuery) -> Self {
+        Self { query, candidates: Vec::new(), choice: None, status: MatchStatus::LookupFailed }
+    }
+
+    pub fn chosen(&self) -> Option<&SearchTrack> {
+        self.candidates.get(self.choice?)
+    }
+
+    /// Step through the candidates, with "skip" after the last one
+    pub fn cycle_choice(&mut self, forward: bool) {
+        let options = self.candidates.len() + 1;
+        let current = self.choice.unwrap_or(self.candidates.len());
+        let next = if forward { (current + 1) % options } else { (current + options - 1) % options };
+        self.choice = (next < self.candidates.len()).then_some(next);
+    }
+}
+
+/// Track list waiting in the review screen
+#[derive(Clone, Debug)]
+pub struct ImportReview {
+    /// File name, for the header
+    pub source: String,
+    /// Name of the playlist to create
+    pub playlist_name: String,
+    /// Playlist whose tracks are replaced instead of creating one
+    pub replace: Option<PlaylistItem>,
+    pub entries: Vec<ImportEntry>,
+}
+
+impl ImportReview {
+    pub fn count(&self, status: MatchStatus) -> usize {
+        self.entries.iter().filter(|entry| entry.status == status).count()
+    }
+
+    pub fn chosen_tracks(&self) -> Vec<SearchTrack> {
+        self.entries.iter().filter_map(ImportEntry::chosen).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(id: &str, name: &str, artist: &str, album: &str) -> SearchTrack {
+        SearchTrack {
+            id: id.to_string(),
+            name: name.to_string(),
+            artist: artist.to_string(),
+            artists: vec![artist.to_string()],
+            album: album.to_string(),
+            duration_ms: 200_000,
+            uri: format!("spotify:track:{}", id),
+            liked: false,
+        }
+    }
+
+    fn labels(queries: &[TrackQuery]) -> Vec<(usize, String)> {
+        queries.iter().map(|q| (q.line, q.label())).collect()
+    }
+
+    #[test]
+    fn csv_rows_follow_the_header_or_default_columns() {
+        let with_header = "Title,Artist,Album\n\"Hello, World\",Someone,\"The \"\"Best\"\" Of\"\n\nLow Tide,Harbor Static,\n";
+        let queries = parse_track_list(with_header, TrackListFormat::Csv);
+        assert_eq!(labels(&queries), [(2, "Someone - Hello, World".to_string()), (4, "Harbor Static - Low Tide".to_string())]);
+        assert_eq!(queries[0].album.as_deref(), Some("The \"Best\" Of"));
+        assert_eq!(queries[1].album, None);
+
+        let without_header = "Harbor Static,Low Tide,Tidal";
+        let queries = parse_track_list(without_header, TrackListFormat::Csv);
+        assert_eq!(labels(&queries), [(1, "Harbor Static - Low Tide".to_string())]);
+        assert_eq!(queries[0].album.as_deref(), Some("Tidal"));
+    }
+
+    #[test]
+    fn m3u_uses_extinf_labels_or_file_names() {
+        let playlist = "#EXTM3U\n#EXTINF:215,Harbor Static - Low Tide\n/music/01.mp3\n\n/music/Aurora Fields - Polar Night.flac\nC:\\Music\\Salt.mp3\n";
+        let queries = parse_track_list(playlist, TrackListFormat::M3u);
+        assert_eq!(
+            labels(&queries),
+            [
+                (2, "Harbor Static - Low Tide".to_string()),
+                (5, "Aurora Fields - Polar Night".to_string()),
+                (6, "Salt".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn text_lines_split_on_the_first_dash() {
+        let text = "# my list\nHarbor Static - Low Tide\nJay-Z – Song - Remix\n\nJust A Title\n";
+        let queries = parse_track_list(text, TrackListFormat::Text);
+        assert_eq!(
+            labels(&queries),
+            [
+                (2, "Harbor Static - Low Tide".to_string()),
+                (3, "Jay-Z - Song - Remix".to_string()),
+                (5, "Just A Title".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn clear_matches_are_taken_and_the_rest_reviewed() {
+        let query = TrackQuery::from_label(1, "Harbor Static - Low Tide").unwrap();
+
+        // Same song on two releases: the first is taken
+        let entry = ImportEntry::resolve(
+            query.clone(),
+            vec![
+                track("other", "Low Tide Blues", "Someone Else", "X"),
+                track("single", "Low Tide", "Harbor Static", "Low Tide (Single)"),
+                track("album", "Low Tide", "Harbor Static", "Tidal"),
+            ],
+        );
+        assert_eq!(entry.status, MatchStatus::Matched);
+        assert_eq!(entry.chosen().unwrap().id, "single");
+        assert_eq!(entry.candidates.len(), 3);
+
+        // Only the title matches
+        let entry = ImportEntry::resolve(query.clone(), vec![track("cover", "Low Tide", "Cover Band", "Covers")]);
+        assert_eq!(entry.status, MatchStatus::NeedsReview);
+        assert_eq!(entry.chosen().unwrap().id, "cover");
+
+        let entry = ImportEntry::resolve(query, vec![track("x", "Unrelated", "Harbor Static", "Tidal")]);
+        assert_eq!(entry.status, MatchStatus::NotFound);
+        assert!(entry.chosen().is_none());
+    }
+
+    #[test]
+    fn album_breaks_ties_and_choices_cycle_through_skip() {
+        let query = TrackQuery::new(1, "Harbor Static", "Low Tide", Some("Tidal")).unwrap();
+        let mut entry = ImportEntry::resolve(
+            query,
+            vec![
+                track("single", "Low Tide", "Harbor Static", "Low Tide (Single)"),
+                track("album", "Low Tide", "Harbor Static", "Tidal"),
+            ],
+        );
+        assert_eq!(entry.chosen().unwrap().id, "album");
+
+        entry.cycle_choice(true);
+        assert_eq!(entry.chosen().unwrap().id, "single");
+        entry.cycle_choice(true);
+        assert!(entry.chosen().is_none());
+        entry.cycle_choice(true);
+        assert_eq!(entry.chosen().unwrap().id, "album");
+        entry.cycle_choice(false);
+        assert!(entry.chosen().is_none());
+    }
+}
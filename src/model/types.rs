@@ -2,6 +2,9 @@
 
 use std::time::Instant;
 
+use crate::config::ThemeName;
+use super::settings::SettingRow;
+
 /// Which section of the UI is currently active/focused
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ActiveSection {
@@ -46,10 +49,73 @@ pub struct DeviceInfo {
 }
 
 /// A user's playlist (for sidebar display)
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PlaylistItem {
     pub id: String,
     pub name: String,
+    /// Owned by the user or collaborative, so tracks can be added
+    pub editable: bool,
+}
+
+/// Who can see and edit a new playlist
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PlaylistVisibility {
+    #[default]
+    Private,
+    Public,
+    /// Private, but followers can edit it
+    Collaborative,
+}
+
+impl PlaylistVisibility {
+    /// Visibility of an existing playlist. Spotify only lets private
+    /// playlists be collaborative.
+    pub fn of(public: bool, collaborative: bool) -> Self {
+        match (public, collaborative) {
+            (_, true) => Self::Collaborative,
+            (true, false) => Self::Public,
+            (false, false) => Self::Private,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Private => Self::Public,
+            Self::Public => Self::Collaborative,
+            Self::Collaborative => Self::Private,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Private => "private",
+            Self::Public => "public",
+            Self::Collaborative => "collaborative",
+        }
+    }
+}
+
+/// What the playlist prompt is asking for
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlaylistPromptKind {
+    Create { visibility: PlaylistVisibility },
+    /// A new playlist of your top tracks, `uris` in ranking order
+    ExportTop { visibility: PlaylistVisibility, uris: Vec<String> },
+    Rename { playlist_id: String },
+    Describe { playlist_id: String },
+    /// Confirmation only, the input is unused
+    Unfollow { playlist_id: String, name: String },
+    /// Input is the file to import. `target` is the editable playlist the
+    /// command was given on, whose tracks are replaced when `replace` is set;
+    /// otherwise a new playlist is created.
+    Import { target: Option<PlaylistItem>, replace: bool },
+}
+
+/// Prompt for creating or changing a playlist
+#[derive(Clone, Debug)]
+pub struct PlaylistPrompt {
+    pub kind: PlaylistPromptKind,
+    pub input: String,
 }
 
 /// Repeat mode state
@@ -71,6 +137,15 @@ pub enum SearchResultSection {
 }
 
 impl SearchResultSection {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Tracks => "Songs",
+            Self::Albums => "Albums",
+            Self::Artists => "Artists",
+            Self::Playlists => "Playlists",
+        }
+    }
+
     pub fn next(self) -> Self {
         match self {
             Self::Tracks => Self::Albums,
@@ -90,49 +165,217 @@ impl SearchResultSection {
     }
 }
 
-/// Which section within artist detail is selected
+/// Which section of the home page is selected
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum HomeSection {
+    #[default]
+    JumpBackIn,
+    NewReleases,
+    TopTracks,
+    TopArtists,
+    Categories,
+}
+
+impl HomeSection {
+    /// Sections in the order they are shown
+    pub const ALL: [HomeSection; 5] = [
+        Self::JumpBackIn,
+        Self::NewReleases,
+        Self::TopTracks,
+        Self::TopArtists,
+        Self::Categories,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::JumpBackIn => "Jump back in",
+            Self::NewReleases => "New releases",
+            Self::TopTracks => "Your top tracks",
+            Self::TopArtists => "Your top artists",
+            Self::Categories => "Browse",
+        }
+    }
+
+    /// Position among `ALL`
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    pub fn prev(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// Which list of the "Your Top" view is shown
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TopTab {
+    #[default]
+    Tracks,
+    Artists,
+}
+
+impl TopTab {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Tracks => "Tracks",
+            Self::Artists => "Artists",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Tracks => Self::Artists,
+            Self::Artists => Self::Tracks,
+        }
+    }
+}
+
+/// Period Spotify computes the user's top tracks and artists over
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum TopRange {
+    FourWeeks,
+    #[default]
+    SixMonths,
+    Year,
+}
+
+impl TopRange {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FourWeeks => "Last 4 weeks",
+            Self::SixMonths => "Last 6 months",
+            Self::Year => "Last year",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::FourWeeks => Self::SixMonths,
+            Self::SixMonths => Self::Year,
+            Self::Year => Self::FourWeeks,
+        }
+    }
+}
+
+/// Which section within artist detail is selected. Top tracks are listed
+/// on their own; the other sections share the list beside them.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum ArtistDetailSection {
     #[default]
     TopTracks,
     Albums,
+    Singles,
+    Compilations,
+    AppearsOn,
+    Related,
 }
 
 impl ArtistDetailSection {
-    pub fn next(self) -> Self {
+    /// Sections in the order they are shown
+    pub const ALL: [ArtistDetailSection; 6] = [
+        Self::TopTracks,
+        Self::Albums,
+        Self::Singles,
+        Self::Compilations,
+        Self::AppearsOn,
+        Self::Related,
+    ];
+
+    pub fn label(self) -> &'static str {
         match self {
-            Self::TopTracks => Self::Albums,
-            Self::Albums => Self::TopTracks,
+            Self::TopTracks => "Top Tracks",
+            Self::Albums => "Albums",
+            Self::Singles => "Singles & EPs",
+            Self::Compilations => "Compilations",
+            Self::AppearsOn => "Appears On",
+            Self::Related => "Related",
         }
     }
+
+    /// Position among `ALL`
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    pub fn prev(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
 }
 
 /// Represents a selected item for action handling
 #[derive(Clone, Debug)]
 pub enum SelectedItem {
-    Track { uri: String },
+    /// Track in a list without a Spotify context (search results, top tracks,
+    /// recently played, the queue); the whole list is played with this track
+    /// first
+    TrackList { uris: Vec<String>, index: usize },
+    /// Track in Liked Songs, played in the user's collection context
+    LikedTrack { track_uri: String },
     Album { id: String },
     Artist { id: String },
     Playlist { id: String },
+    /// Browse category, opened as a list of its playlists
+    Category { id: String, name: String },
+    Show { id: String },
+    /// Episode of a show, played in the show's context from `position_ms`
+    Episode { show_uri: String, episode_uri: String, position_ms: u32 },
     PlaylistTrack { playlist_uri: String, track_uri: String },
     AlbumTrack { album_uri: String, track_uri: String },
 }
 
+/// A result offered under the search bar while typing (live search)
+#[derive(Clone, Debug)]
+pub struct SearchSuggestion {
+    pub section: SearchResultSection,
+    pub label: String,
+    /// What Enter opens or plays
+    pub item: SelectedItem,
+}
+
 /// UI state for the application
 #[derive(Clone)]
 pub struct UiState {
     pub active_section: ActiveSection,
     pub search_query: String,
+    /// Live search results for `search_query`, empty when closed
+    pub search_suggestions: Vec<SearchSuggestion>,
+    pub suggestion_selected: Option<usize>,
     pub library_items: Vec<LibraryItem>,
     pub library_selected: usize,
     pub playlists: Vec<PlaylistItem>,
     pub playlist_selected: usize,
     pub error_message: Option<String>,
     pub error_timestamp: Option<Instant>,
+    /// `error_message` is a notice (e.g. an export report), not an error
+    pub message_is_info: bool,
     pub show_device_picker: bool,
     pub available_devices: Vec<DeviceInfo>,
     pub device_selected: usize,
+    pub show_playlist_picker: bool,
+    /// Playlists offered by the "add to playlist" picker
+    pub playlist_picker_items: Vec<PlaylistItem>,
+    pub playlist_picker_selected: usize,
     pub show_help_popup: bool,
+    pub show_settings: bool,
+    pub settings: Vec<SettingRow>,
+    pub settings_selected: usize,
+    /// Text being typed for a text setting (e.g. device name)
+    pub settings_edit: Option<String>,
+    pub theme: ThemeName,
+    /// Text typed into the "go to position" prompt, `Some` while it is open
+    pub seek_prompt: Option<String>,
+    /// Position typed into the "insert into queue" prompt, `Some` while it is open
+    pub queue_insert_prompt: Option<String>,
+    /// Create/rename/describe/unfollow prompt, `Some` while it is open
+    pub playlist_prompt: Option<PlaylistPrompt>,
 }
 
 impl Default for UiState {
@@ -140,21 +383,37 @@ impl Default for UiState {
         Self {
             active_section: ActiveSection::Search,
             search_query: String::new(),
+            search_suggestions: vec![],
+            suggestion_selected: None,
             library_items: vec![
                 LibraryItem { name: "Recently played".to_string() },
                 LibraryItem { name: "Liked songs".to_string() },
                 LibraryItem { name: "Albums".to_string() },
                 LibraryItem { name: "Artists".to_string() },
+                LibraryItem { name: "Podcasts".to_string() },
+                LibraryItem { name: "Your Top".to_string() },
             ],
             library_selected: 0,
             playlists: vec![], // Will be loaded from Spotify API
             playlist_selected: 0,
             error_message: None,
             error_timestamp: None,
+            message_is_info: false,
             show_device_picker: false,
             available_devices: vec![],
             device_selected: 0,
+            show_playlist_picker: false,
+            playlist_picker_items: vec![],
+            playlist_picker_selected: 0,
             show_help_popup: false,
+            show_settings: false,
+            settings: vec![],
+            settings_selected: 0,
+            settings_edit: None,
+            theme: ThemeName::Default,
+            seek_prompt: None,
+            queue_insert_prompt: None,
+            playlist_prompt: None,
         }
     }
 }
@@ -0,0 +1,371 @@
+//! Locations of config, cache and log files
+//!
+//! Directories follow the XDG base directory spec so the app behaves the same
+//! no matter which directory it is launched from:
+//!
+//! - config: `$XDG_CONFIG_HOME/spotify-rs` (default `~/.config/spotify-rs`)
+//! - cache: `$XDG_CACHE_HOME/spotify-rs` (default `~/.cache/spotify-rs`)
+//! - state: `$XDG_STATE_HOME/spotify-rs` (default `~/.local/state/spotify-rs`)
+//! - data: `$XDG_DATA_HOME/spotify-rs` (default `~/.local/share/spotify-rs`)
+//!
+//! Each can be overridden with `SPOTIFY_RS_CONFIG_DIR`, `SPOTIFY_RS_CACHE_DIR`,
+//! `SPOTIFY_RS_STATE_DIR` and `SPOTIFY_RS_DATA_DIR`. The cache, log and data
+//! locations can also be set in the config file's `[paths]` section;
+//! environment overrides win over it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+
+use crate::config::PathsConfig;
+
+const APP_DIR: &str = "spotify-rs";
+const CONFIG_FILE: &str = "config.toml";
+const LOG_SUBDIR: &str = "logs";
+
+/// Cache directory used by versions that stored everything in the working directory
+const LEGACY_CACHE_DIR: &str = ".cache";
+
+/// Entries older versions wrote to `./.cache`: the refresh token, librespot's
+/// credentials, volume and audio files, and the liked songs cache
+const LEGACY_CACHE_ENTRIES: [&str; 5] = ["refresh_token", "credentials.json", "liked_songs.json", "volume", "files"];
+
+/// Files only spotify-rs writes, whose presence marks `./.cache` as ours
+const LEGACY_CACHE_MARKERS: [&str; 2] = ["refresh_token", "liked_songs.json"];
+
+const CONFIG_DIR_ENV: &str = "SPOTIFY_RS_CONFIG_DIR";
+const CACHE_DIR_ENV: &str = "SPOTIFY_RS_CACHE_DIR";
+const STATE_DIR_ENV: &str = "SPOTIFY_RS_STATE_DIR";
+const DATA_DIR_ENV: &str = "SPOTIFY_RS_DATA_DIR";
+
+/// Resolved on-disk locations
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppPaths {
+    /// Credentials, refresh token, audio files, the liked songs cache and search history
+    pub cache_dir: PathBuf,
+    pub log_dir: PathBuf,
+    /// Files written for the user, e.g. playlist exports
+    pub data_dir: PathBuf,
+}
+
+impl AppPaths {
+    /// Default config file (`<config dir>/config.toml`)
+    pub fn default_config_file() -> PathBuf {
+        config_dir_with(&env_var).join(CONFIG_FILE)
+    }
+
+    /// Resolve all directories from the environment and the config file
+    pub fn resolve(config: &PathsConfig) -> Self {
+        Self::resolve_with(&env_var, config)
+    }
+
+    fn resolve_with(env: &dyn Fn(&str) -> Option<PathBuf>, config: &PathsConfig) -> Self {
+        let cache_dir = env(CACHE_DIR_ENV)
+            .or_else(|| config.cache_dir.as_deref().map(|dir| expand_home(env, dir)))
+            .unwrap_or_else(|| base_dir(env, "XDG_CACHE_HOME", ".cache").join(APP_DIR));
+
+        let log_dir = match (env(STATE_DIR_ENV), &config.log_dir) {
+            (Some(state_dir), _) => state_dir.join(LOG_SUBDIR),
+            (None, Some(log_dir)) => expand_home(env, log_dir),
+            (None, None) => base_dir(env, "XDG_STATE_HOME", ".local/state")
+                .join(APP_DIR)
+                .join(LOG_SUBDIR),
+        };
+
+        let data_dir = env(DATA_DIR_ENV)
+            .or_else(|| config.data_dir.as_deref().map(|dir| expand_home(env, dir)))
+            .unwrap_or_else(|| base_dir(env, "XDG_DATA_HOME", ".local/share").join(APP_DIR));
+
+        Self { cache_dir, log_dir, data_dir }
+    }
+
+    /// Move files left in `./.cache` by older versions into the cache directory.
+    /// Runs only when `./.cache` holds spotify-rs's own files and the new cache
+    /// directory has no credentials yet, so an existing login is never
+    /// overwritten. Only the files older versions wrote are moved, since
+    /// `./.cache` is `~/.cache` itself when started from the home directory.
+    /// Returns the number of entries moved.
+    pub fn migrate_legacy_cache(&self) -> Result<usize> {
+        migrate_dir(Path::new(LEGACY_CACHE_DIR), &self.cache_dir)
+    }
+}
+
+fn migrate_dir(legacy: &Path, target: &Path) -> Result<usize> {
+    if !LEGACY_CACHE_MARKERS.iter().any(|name| legacy.join(name).is_file()) {
+        return Ok(0);
+    }
+    if resolve_existing(target)?.starts_with(legacy.canonicalize()?) {
+        tracing::warn!(
+            legacy = %legacy.display(),
+            target = %target.display(),
+            "Cache directory is inside the legacy cache directory, skipping migration"
+        );
+        return Ok(0);
+    }
+    if target.join("credentials.json").exists() || target.join("refresh_token").exists() {
+        tracing::debug!(legacy = %legacy.display(), "Cache directory already populated, skipping migration");
+        return Ok(0);
+    }
+
+    fs::create_dir_all(target)?;
+    let mut moved = 0;
+    for name in LEGACY_CACHE_ENTRIES {
+        let source = legacy.join(name);
+        let destination = target.join(name);
+        if !source.exists() || destination.exists() {
+            continue;
+        }
+        move_path(&source, &destination)?;
+        moved += 1;
+    }
+
+    // Only remove the old directory once it is empty
+    let _ = fs::remove_dir(legacy);
+
+    tracing::info!(
+        from = %legacy.display(),
+        to = %target.display(),
+        entries = moved,
+        "Migrated legacy cache directory"
+    );
+    Ok(moved)
+}
+
+/// `path` made absolute with symlinks resolved as far as it exists, so a
+/// directory that is yet to be created compares with canonical paths
+fn resolve_existing(path: &Path) -> Result<PathBuf> {
+    let absolute = std::path::absolute(path)?;
+    for ancestor in absolute.ancestors() {
+        if let Ok(canonical) = ancestor.canonicalize() {
+            return Ok(canonical.join(absolute.strip_prefix(ancestor)?));
+        }
+    }
+    Ok(absolute)
+}
+
+/// Rename, falling back to copy + delete across filesystems
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            move_path(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        fs::remove_dir(from)?;
+    } else {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn config_dir_with(env: &dyn Fn(&str) -> Option<PathBuf>) -> PathBuf {
+    env(CONFIG_DIR_ENV).unwrap_or_else(|| base_dir(env, "XDG_CONFIG_HOME", ".config").join(APP_DIR))
+}
+
+/// `$xdg_var` if it is an absolute path, otherwise `$HOME/<home_fallback>`.
+/// On Windows, `%APPDATA%`/`%LOCALAPPDATA%` stand in for the XDG defaults.
+fn base_dir(env: &dyn Fn(&str) -> Option<PathBuf>, xdg_var: &str, home_fallback: &str) -> PathBuf {
+    if let Some(dir) = env(xdg_var).filter(|dir| dir.is_absolute()) {
+        return dir;
+    }
+
+    if cfg!(windows) {
+        let windows_var = if xdg_var == "XDG_CONFIG_HOME" { "APPDATA" } else { "LOCALAPPDATA" };
+        if let Some(dir) = env(windows_var) {
+            return dir;
+        }
+    }
+
+    match home_dir(env) {
+        Some(home) => home.join(home_fallback),
+        None => PathBuf::from(home_fallback),
+    }
+}
+
+fn home_dir(env: &dyn Fn(&str) -> Option<PathBuf>) -> Option<PathBuf> {
+    env("HOME").or_else(|| env("USERPROFILE"))
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(env: &dyn Fn(&str) -> Option<PathBuf>, path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => home_dir(env).map(|home| home.join(rest)).unwrap_or_else(|| path.to_path_buf()),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Expand a leading `~` in a path typed by the user
+pub fn expand_user_path(path: &Path) -> PathBuf {
+    expand_home(&env_var, path)
+}
+
+fn env_var(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env_from(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<PathBuf> {
+        let vars: HashMap<String, PathBuf> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), PathBuf::from(v)))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn defaults_follow_home_directory() {
+        let env = env_from(&[("HOME", "/home/user")]);
+        let paths = AppPaths::resolve_with(&env, &PathsConfig::default());
+
+        assert_eq!(config_dir_with(&env), PathBuf::from("/home/user/.config/spotify-rs"));
+        assert_eq!(paths.cache_dir, PathBuf::from("/home/user/.cache/spotify-rs"));
+        assert_eq!(paths.log_dir, PathBuf::from("/home/user/.local/state/spotify-rs/logs"));
+        assert_eq!(paths.data_dir, PathBuf::from("/home/user/.local/share/spotify-rs"));
+    }
+
+    #[test]
+    fn xdg_variables_take_precedence_over_home() {
+        let env = env_from(&[
+            ("HOME", "/home/user"),
+            ("XDG_CONFIG_HOME", "/xdg/config"),
+            ("XDG_CACHE_HOME", "/xdg/cache"),
+            ("XDG_STATE_HOME", "relative/is/ignored"),
+        ]);
+        let paths = AppPaths::resolve_with(&env, &PathsConfig::default());
+
+        assert_eq!(config_dir_with(&env), PathBuf::from("/xdg/config/spotify-rs"));
+        assert_eq!(paths.cache_dir, PathBuf::from("/xdg/cache/spotify-rs"));
+        assert_eq!(paths.log_dir, PathBuf::from("/home/user/.local/state/spotify-rs/logs"));
+    }
+
+    #[test]
+    fn env_overrides_win_over_config_file() {
+        let env = env_from(&[
+            ("HOME", "/home/user"),
+            ("SPOTIFY_RS_CACHE_DIR", "/override/cache"),
+            ("SPOTIFY_RS_STATE_DIR", "/override/state"),
+            ("SPOTIFY_RS_DATA_DIR", "/override/data"),
+        ]);
+        let config = PathsConfig {
+            cache_dir: Some(PathBuf::from("/from/config")),
+            log_dir: Some(PathBuf::from("/from/config/logs")),
+            data_dir: Some(PathBuf::from("/from/config/data")),
+        };
+        let paths = AppPaths::resolve_with(&env, &config);
+
+        assert_eq!(paths.cache_dir, PathBuf::from("/override/cache"));
+        assert_eq!(paths.log_dir, PathBuf::from("/override/state/logs"));
+        assert_eq!(paths.data_dir, PathBuf::from("/override/data"));
+    }
+
+    #[test]
+    fn config_file_paths_are_used_without_env_overrides() {
+        let env = env_from(&[("HOME", "/home/user")]);
+        let config = PathsConfig {
+            cache_dir: Some(PathBuf::from("~/music-cache")),
+            log_dir: None,
+            data_dir: None,
+        };
+        let paths = AppPaths::resolve_with(&env, &config);
+
+        assert_eq!(paths.cache_dir, PathBuf::from("/home/user/music-cache"));
+        assert_eq!(paths.log_dir, PathBuf::from("/home/user/.local/state/spotify-rs/logs"));
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("spotify-rs-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn legacy_cache_is_moved_into_empty_target() {
+        let root = scratch_dir("migrate");
+        let legacy = root.join(".cache");
+        fs::create_dir_all(legacy.join("files")).unwrap();
+        fs::write(legacy.join("refresh_token"), "token").unwrap();
+        fs::write(legacy.join("files").join("audio"), "bytes").unwrap();
+        let target = root.join("xdg-cache");
+
+        let moved = migrate_dir(&legacy, &target).unwrap();
+
+        assert_eq!(moved, 2);
+        assert_eq!(fs::read_to_string(target.join("refresh_token")).unwrap(), "token");
+        assert!(target.join("files").join("audio").exists());
+        assert!(!legacy.exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn only_spotify_rs_files_are_moved() {
+        let root = scratch_dir("only-ours");
+        let legacy = root.join(".cache");
+        fs::create_dir_all(legacy.join("other-app")).unwrap();
+        fs::write(legacy.join("liked_songs.json"), "[]").unwrap();
+        let target = root.join("xdg-cache");
+
+        assert_eq!(migrate_dir(&legacy, &target).unwrap(), 1);
+        assert!(target.join("liked_songs.json").exists());
+        assert!(!target.join("other-app").exists());
+        assert!(legacy.join("other-app").is_dir());
+
+        // A cache directory without spotify-rs's files is left alone
+        let other = root.join("other-cache");
+        fs::create_dir_all(other.join("files")).unwrap();
+        assert_eq!(migrate_dir(&other, &root.join("other-target")).unwrap(), 0);
+        assert!(other.join("files").is_dir());
+        assert!(!root.join("other-target").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn home_cache_is_not_moved_into_itself() {
+        // Started from $HOME, ./.cache is ~/.cache and the target lives inside it
+        let home = scratch_dir("home-cache");
+        let legacy = home.join(".cache");
+        fs::create_dir_all(legacy.join("some-browser")).unwrap();
+        fs::write(legacy.join("refresh_token"), "token").unwrap();
+        let home_var = [("HOME", home.to_str().unwrap())];
+        let target = AppPaths::resolve_with(&env_from(&home_var), &PathsConfig::default()).cache_dir;
+        assert_eq!(target, legacy.join("spotify-rs"));
+
+        assert_eq!(migrate_dir(&legacy, &target).unwrap(), 0);
+        assert!(!target.exists());
+        assert!(legacy.join("some-browser").is_dir());
+        assert_eq!(fs::read_to_string(legacy.join("refresh_token")).unwrap(), "token");
+
+        // Also when the cache directory already exists
+        fs::create_dir_all(&target).unwrap();
+        assert_eq!(migrate_dir(&legacy, &target).unwrap(), 0);
+        assert!(!target.join("refresh_token").exists());
+        fs::remove_dir_all(home).unwrap();
+    }
+
+    #[test]
+    fn migration_never_overwrites_an_existing_login() {
+        let root = scratch_dir("no-overwrite");
+        let legacy = root.join(".cache");
+        let target = root.join("xdg-cache");
+        fs::create_dir_all(&legacy).unwrap();
+        fs::create_dir_all(&target).unwrap();
+        fs::write(legacy.join("refresh_token"), "old").unwrap();
+        fs::write(target.join("refresh_token"), "current").unwrap();
+
+        assert_eq!(migrate_dir(&legacy, &target).unwrap(), 0);
+        assert_eq!(fs::read_to_string(target.join("refresh_token")).unwrap(), "current");
+        assert!(legacy.join("refresh_token").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
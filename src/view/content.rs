@@ -2,19 +2,22 @@
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::Span,
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, ListItem, Paragraph},
     Frame,
 };
 use ratatui::widgets::Padding;
 
 use crate::model::{
     ActiveSection, ArtistDetailSection, ContentState, ContentView,
-    SearchResultSection, UiState, AlbumDetail, PlaylistDetail, ArtistDetail,
-    SearchResults, SearchTrack, SearchAlbum, SearchArtist,
+    SearchResultSection, HomeSection, TopTab, TopItems, UiState, AlbumDetail, PlaylistDetail, ArtistDetail,
+    SearchResults, SearchTrack, SearchAlbum, SearchArtist, ImportReview, MatchStatus,
+    RestorePlan, ListPaging, ListFilter, Show, ShowDetail, HomePage, Category, SearchPlaylist,
 };
-use super::utils::{calculate_num_width, format_duration, render_scrollable_list, truncate_string};
+use super::theme::palette;
+use super::ListRegion;
+use super::utils::{calculate_num_width, format_count, format_duration, render_scrollable_list, truncate_string};
 
 pub fn render_main_content(
     frame: &mut Frame,
@@ -22,17 +25,17 @@ pub fn render_main_content(
     ui_state: &UiState,
     content_state: &ContentState,
     current_playing_uri: Option<&str>,
-) {
+) -> Vec<ListRegion> {
     let is_focused = ui_state.active_section == ActiveSection::MainContent;
     let border_style = if is_focused {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
 
     if content_state.is_loading {
         let loading = Paragraph::new("Loading...")
-            .style(Style::default().fg(Color::Yellow))
+            .style(Style::default().fg(palette().warning))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -40,13 +43,29 @@ pub fn render_main_content(
                     .border_style(border_style),
             );
         frame.render_widget(loading, area);
-        return;
+        return Vec::new();
     }
 
+    // The filter bar takes the last row of the content area
+    let filtered_rows = content_state.filtered_rows();
+    let narrowing = content_state
+        .filter
+        .as_ref()
+        .zip(filtered_rows.as_deref())
+        .map(|(filter, rows)| Narrowing { filter, rows });
+    let area = match &content_state.filter {
+        Some(filter) => {
+            let [list_area, bar_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+            render_filter_bar(frame, bar_area, filter, filtered_rows.as_ref().map(Vec::len));
+            list_area
+        }
+        None => area,
+    };
+
     match &content_state.view {
         ContentView::Empty => {
             let content = Paragraph::new("Type in search and press Enter to find music\n\nUse Tab to navigate between sections\nUse ↑/↓ to select items\nPress Enter to open")
-                .style(Style::default().fg(Color::DarkGray))
+                .style(Style::default().fg(palette().muted))
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
@@ -54,6 +73,24 @@ pub fn render_main_content(
                         .border_style(border_style),
                 );
             frame.render_widget(content, area);
+            Vec::new()
+        }
+        ContentView::Home { home, section, selected } => {
+            render_home(frame, area, home, *section, selected[section.index()], is_focused, current_playing_uri, narrowing)
+                .into_iter()
+                .collect()
+        }
+        ContentView::Top { top, tab, track_index, artist_index } => {
+            let selected_index = match tab {
+                TopTab::Tracks => *track_index,
+                TopTab::Artists => *artist_index,
+            };
+            render_top(frame, area, top, *tab, selected_index, is_focused, current_playing_uri, narrowing)
+                .into_iter()
+                .collect()
+        }
+        ContentView::CategoryPlaylists { category, playlists, selected_index, paging } => {
+            vec![render_category_playlists(frame, area, category, playlists, *selected_index, is_focused, paging, narrowing)]
         }
         ContentView::SearchResults {
             results,
@@ -74,44 +111,47 @@ pub fn render_main_content(
                 *playlist_index,
                 is_focused,
                 current_playing_uri,
-            );
+                narrowing,
+            )
+            .into_iter()
+            .collect()
+        }
+        ContentView::SearchCategory { results, section, selected_index, paging } => {
+            vec![render_search_category(frame, area, results, *section, *selected_index, is_focused, current_playing_uri, paging, narrowing)]
         }
         ContentView::AlbumDetail { detail, selected_index } => {
-            render_album_detail(frame, area, detail, *selected_index, is_focused, current_playing_uri);
+            vec![render_album_detail(frame, area, detail, *selected_index, is_focused, current_playing_uri, narrowing)]
         }
         ContentView::PlaylistDetail { detail, selected_index } => {
-            render_playlist_detail(frame, area, detail, *selected_index, is_focused, current_playing_uri);
+            vec![render_playlist_detail(frame, area, detail, *selected_index, is_focused, current_playing_uri, narrowing)]
         }
-        ContentView::ArtistDetail {
-            detail,
-            section,
-            track_index,
-            album_index,
-        } => {
+        ContentView::ArtistDetail { detail, section, selected } => {
             render_artist_detail(
                 frame,
                 area,
                 detail,
                 *section,
-                *track_index,
-                *album_index,
+                selected,
                 is_focused,
                 current_playing_uri,
-            );
+                narrowing,
+            )
         }
-        ContentView::LikedSongs { tracks, selected_index } => {
-            render_track_list(
+        ContentView::LikedSongs { tracks, selected_index, paging } => {
+            vec![render_track_list(
                 frame,
                 area,
-                " Liked Songs ",
+                &library_title("Liked Songs", tracks.len(), paging),
                 tracks,
                 *selected_index,
                 is_focused,
                 current_playing_uri,
-            );
+                Some(paging),
+                narrowing,
+            )]
         }
         ContentView::RecentlyPlayed { tracks, selected_index } => {
-            render_track_list(
+            vec![render_track_list(
                 frame,
                 area,
                 " Recently Played ",
@@ -119,42 +159,72 @@ pub fn render_main_content(
                 *selected_index,
                 is_focused,
                 current_playing_uri,
-            );
+                None,
+                narrowing,
+            )]
         }
-        ContentView::SavedAlbums { albums, selected_index } => {
-            render_album_list(
+        ContentView::SavedAlbums { albums, selected_index, paging } => {
+            vec![render_album_list(
                 frame,
                 area,
-                " Your Albums ",
+                &library_title("Your Albums", albums.len(), paging),
                 albums,
                 *selected_index,
                 is_focused,
-            );
+                paging,
+                narrowing,
+            )]
         }
-        ContentView::FollowedArtists { artists, selected_index } => {
-            render_artist_list(
+        ContentView::FollowedArtists { artists, selected_index, paging } => {
+            vec![render_artist_list(
                 frame,
                 area,
-                " Followed Artists ",
+                &library_title("Followed Artists", artists.len(), paging),
                 artists,
                 *selected_index,
                 is_focused,
-            );
+                paging,
+                narrowing,
+            )]
+        }
+        ContentView::SavedShows { shows, selected_index, paging } => {
+            vec![render_show_list(
+                frame,
+                area,
+                &library_title("Your Podcasts", shows.len(), paging),
+                shows,
+                *selected_index,
+                is_focused,
+                paging,
+                narrowing,
+            )]
+        }
+        ContentView::ShowDetail { detail, selected_index, paging } => {
+            vec![render_show_detail(frame, area, detail, *selected_index, is_focused, current_playing_uri, paging, narrowing)]
         }
-        ContentView::Queue { currently_playing, queue, selected_index } => {
-            render_queue(
+        ContentView::ImportReview { review, selected_index } => {
+            vec![render_import_review(frame, area, review, *selected_index, is_focused)]
+        }
+        ContentView::LibraryRestore { plan, selected_index } => {
+            vec![render_restore_plan(frame, area, plan, *selected_index, is_focused)]
+        }
+        ContentView::Queue { currently_playing, queue, local_len, selected_index } => {
+            vec![render_queue(
                 frame,
                 area,
                 currently_playing.as_ref(),
                 queue,
+                *local_len,
                 *selected_index,
                 is_focused,
                 current_playing_uri,
-            );
+                narrowing,
+            )]
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_search_results(
     frame: &mut Frame,
     area: Rect,
@@ -166,9 +236,10 @@ fn render_search_results(
     playlist_index: usize,
     is_focused: bool,
     current_playing_uri: Option<&str>,
-) {
+    narrowing: Option<Narrowing>,
+) -> Option<ListRegion> {
     let border_style = if is_focused {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
@@ -182,7 +253,7 @@ fn render_search_results(
         ])
         .split(area);
 
-    let tab_titles = vec![
+    let tab_titles = [
         format!(" Songs ({}) ", results.tracks.len()),
         format!(" Albums ({}) ", results.albums.len()),
         format!(" Artists ({}) ", results.artists.len()),
@@ -200,9 +271,9 @@ fn render_search_results(
                 _ => SearchResultSection::Playlists,
             };
             let style = if tab_section == section {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(palette().muted)
             };
             vec![
                 Span::styled(title.clone(), style),
@@ -216,7 +287,7 @@ fn render_search_results(
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Results (←/→ to switch) ")
+                .title(format!(" Results for \"{}\" (←/→ to switch, M for all) ", results.query))
                 .border_style(border_style),
         );
     frame.render_widget(tabs, chunks[0]);
@@ -225,16 +296,16 @@ fn render_search_results(
 
     let list_items: Vec<ListItem> = match section {
         SearchResultSection::Tracks => {
-            render_track_items(results, track_index, is_focused, current_playing_uri, content_width)
+            render_track_items(results, track_index, is_focused, current_playing_uri, content_width, narrowing)
         }
         SearchResultSection::Albums => {
-            render_album_items(&results.albums, album_index, is_focused, content_width)
+            render_album_items(&results.albums, album_index, is_focused, content_width, narrowing)
         }
         SearchResultSection::Artists => {
-            render_artist_items(&results.artists, artist_index, is_focused, content_width)
+            render_artist_items(&results.artists, artist_index, is_focused, content_width, narrowing)
         }
         SearchResultSection::Playlists => {
-            render_playlist_items(&results.playlists, playlist_index, is_focused, content_width)
+            render_playlist_items(&results.playlists, playlist_index, is_focused, content_width, narrowing)
         }
     };
 
@@ -247,7 +318,7 @@ fn render_search_results(
 
     if list_items.is_empty() {
         let empty = Paragraph::new(format!("  {}", empty_msg))
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(palette().muted))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -255,12 +326,13 @@ fn render_search_results(
                     .border_style(border_style),
             );
         frame.render_widget(empty, chunks[1]);
+        None
     } else {
         let selected_index = match section {
-            SearchResultSection::Tracks => track_index + 1, // +1 for header row
-            SearchResultSection::Albums => album_index,
-            SearchResultSection::Artists => artist_index,
-            SearchResultSection::Playlists => playlist_index,
+            SearchResultSection::Tracks => shown_position(track_index, narrowing) + 1, // +1 for header row
+            SearchResultSection::Albums => shown_position(album_index, narrowing),
+            SearchResultSection::Artists => shown_position(artist_index, narrowing),
+            SearchResultSection::Playlists => shown_position(playlist_index, narrowing),
         };
 
         let list_block = Block::default()
@@ -268,8 +340,278 @@ fn render_search_results(
             .padding(Padding::horizontal(1))
             .border_style(border_style);
 
-        render_scrollable_list(frame, chunks[1], list_items, selected_index, list_block);
+        let region = render_scrollable_list(frame, chunks[1], list_items, selected_index, list_block);
+        let header_rows = usize::from(section == SearchResultSection::Tracks);
+        Some(ListRegion { header_rows, ..region })
+    }
+}
+
+/// Home page sections as tabs, with the selected section's list below
+#[allow(clippy::too_many_arguments)]
+fn render_home(
+    frame: &mut Frame,
+    area: Rect,
+    home: &HomePage,
+    section: HomeSection,
+    selected_index: usize,
+    is_focused: bool,
+    current_playing_uri: Option<&str>,
+    narrowing: Option<Narrowing>,
+) -> Option<ListRegion> {
+    let border_style = if is_focused {
+        Style::default().fg(palette().accent)
+    } else {
+        Style::default()
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Section tabs
+            Constraint::Min(0),    // Section list
+        ])
+        .split(area);
+
+    let tabs_content: Vec<Span> = HomeSection::ALL
+        .iter()
+        .flat_map(|&tab_section| {
+            let style = if tab_section == section {
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else {
+                Style::default().fg(palette().muted)
+            };
+            vec![
+                Span::styled(format!(" {} ({}) ", tab_section.label(), home.len(tab_section)), style),
+                Span::raw("  "),
+            ]
+        })
+        .collect();
+    let tabs = Paragraph::new(Line::from(tabs_content)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Home (←/→ to switch) ")
+            .border_style(border_style),
+    );
+    frame.render_widget(tabs, chunks[0]);
+
+    if home.len(section) == 0 {
+        let empty = Paragraph::new(format!("  Nothing in {} yet", section.label()))
+            .style(Style::default().fg(palette().muted))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .padding(Padding::horizontal(1))
+                    .border_style(border_style),
+            );
+        frame.render_widget(empty, chunks[1]);
+        return None;
+    }
+
+    let content_width = chunks[1].width.saturating_sub(4) as usize;
+    let list_items = match section {
+        HomeSection::JumpBackIn | HomeSection::TopTracks => {
+            let tracks = home.tracks(section).unwrap_or_default();
+            render_detail_track_items(tracks, selected_index, is_focused, current_playing_uri, content_width, tracks.len(), narrowing)
+        }
+        HomeSection::NewReleases => {
+            render_album_items(&home.new_releases, selected_index, is_focused, content_width, narrowing)
+        }
+        HomeSection::TopArtists => {
+            render_artist_items(&home.top_artists, selected_index, is_focused, content_width, narrowing)
+        }
+        HomeSection::Categories => render_category_items(&home.categories, selected_index, is_focused, narrowing),
+    };
+
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
+
+    let region = render_scrollable_list(frame, chunks[1], list_items, shown_position(selected_index, narrowing) + 1, list_block);
+    Some(ListRegion { header_rows: 1, ..region })
+}
+
+/// "Your Top" with its tracks and artists tabs; rows are numbered by rank
+#[allow(clippy::too_many_arguments)]
+fn render_top(
+    frame: &mut Frame,
+    area: Rect,
+    top: &TopItems,
+    tab: TopTab,
+    selected_index: usize,
+    is_focused: bool,
+    current_playing_uri: Option<&str>,
+    narrowing: Option<Narrowing>,
+) -> Option<ListRegion> {
+    let border_style = if is_focused {
+        Style::default().fg(palette().accent)
+    } else {
+        Style::default()
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Tabs
+            Constraint::Min(0),    // Ranked list
+        ])
+        .split(area);
+
+    let tabs_content: Vec<Span> = [(TopTab::Tracks, top.tracks.len()), (TopTab::Artists, top.artists.len())]
+        .into_iter()
+        .flat_map(|(tab_kind, count)| {
+            let style = if tab_kind == tab {
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else {
+                Style::default().fg(palette().muted)
+            };
+            vec![Span::styled(format!(" {} ({}) ", tab_kind.label(), count), style), Span::raw("  ")]
+        })
+        .collect();
+    let tabs = Paragraph::new(Line::from(tabs_content)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Your Top · {} (←/→ tab, W range) ", top.range.label()))
+            .border_style(border_style),
+    );
+    frame.render_widget(tabs, chunks[0]);
+
+    let len = match tab {
+        TopTab::Tracks => top.tracks.len(),
+        TopTab::Artists => top.artists.len(),
+    };
+    if len == 0 {
+        let empty = Paragraph::new(format!("  No top {} for {} yet", tab.label().to_lowercase(), top.range.label().to_lowercase()))
+            .style(Style::default().fg(palette().muted))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .padding(Padding::horizontal(1))
+                    .border_style(border_style),
+            );
+        frame.render_widget(empty, chunks[1]);
+        return None;
     }
+
+    let content_width = chunks[1].width.saturating_sub(4) as usize;
+    let list_items = match tab {
+        TopTab::Tracks => {
+            render_detail_track_items(&top.tracks, selected_index, is_focused, current_playing_uri, content_width, len, narrowing)
+        }
+        TopTab::Artists => render_artist_items(&top.artists, selected_index, is_focused, content_width, narrowing),
+    };
+
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
+
+    let region = render_scrollable_list(frame, chunks[1], list_items, shown_position(selected_index, narrowing) + 1, list_block);
+    Some(ListRegion { header_rows: 1, ..region })
+}
+
+fn render_category_items(
+    categories: &[Category],
+    selected_index: usize,
+    is_focused: bool,
+    narrowing: Option<Narrowing>,
+) -> Vec<ListItem<'static>> {
+    let num_width = calculate_num_width(categories.len());
+    let mut items = vec![
+        ListItem::new(format!(" {:<num_width$}   {}", "#", "Category"))
+            .style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
+    ];
+
+    items.extend(visible_rows(categories.len(), narrowing).into_iter().map(|i| {
+        let style = if i == selected_index && is_focused {
+            Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
+        } else if i == selected_index {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        ListItem::new(row_line(format!(" {:<num_width$}   {}", i + 1, categories[i].name), narrowing)).style(style)
+    }));
+    items
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_category_playlists(
+    frame: &mut Frame,
+    area: Rect,
+    category: &Category,
+    playlists: &[SearchPlaylist],
+    selected_index: usize,
+    is_focused: bool,
+    paging: &ListPaging,
+    narrowing: Option<Narrowing>,
+) -> ListRegion {
+    let border_style = if is_focused {
+        Style::default().fg(palette().accent)
+    } else {
+        Style::default()
+    };
+
+    let content_width = area.width.saturating_sub(4) as usize;
+    let mut list_items = render_playlist_items(playlists, selected_index, is_focused, content_width, narrowing);
+    list_items.extend(more_items_row(paging, "playlists", 1));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(library_title(&category.name, playlists.len(), paging))
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
+
+    let region = render_scrollable_list(frame, area, list_items, shown_position(selected_index, narrowing) + 1, block);
+    ListRegion { header_rows: 1, ..region }
+}
+
+/// Every loaded result of one search category, with the table layout of
+/// the search results
+#[allow(clippy::too_many_arguments)]
+fn render_search_category(
+    frame: &mut Frame,
+    area: Rect,
+    results: &SearchResults,
+    section: SearchResultSection,
+    selected_index: usize,
+    is_focused: bool,
+    current_playing_uri: Option<&str>,
+    paging: &ListPaging,
+    narrowing: Option<Narrowing>,
+) -> ListRegion {
+    let border_style = if is_focused {
+        Style::default().fg(palette().accent)
+    } else {
+        Style::default()
+    };
+
+    let content_width = area.width.saturating_sub(4) as usize;
+    let mut list_items = match section {
+        SearchResultSection::Tracks => {
+            render_track_items(results, selected_index, is_focused, current_playing_uri, content_width, narrowing)
+        }
+        SearchResultSection::Albums => {
+            render_album_items(&results.albums, selected_index, is_focused, content_width, narrowing)
+        }
+        SearchResultSection::Artists => {
+            render_artist_items(&results.artists, selected_index, is_focused, content_width, narrowing)
+        }
+        SearchResultSection::Playlists => {
+            render_playlist_items(&results.playlists, selected_index, is_focused, content_width, narrowing)
+        }
+    };
+    list_items.extend(more_items_row(paging, &section.label().to_lowercase(), 1));
+
+    let name = format!("{} for \"{}\"", section.label(), results.query);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(library_title(&name, results.len(section), paging))
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
+
+    let region = render_scrollable_list(frame, area, list_items, shown_position(selected_index, narrowing) + 1, block); // +1 for header
+    ListRegion { header_rows: 1, ..region }
 }
 
 fn render_track_items(
@@ -278,6 +620,7 @@ fn render_track_items(
     is_focused: bool,
     current_playing_uri: Option<&str>,
     content_width: usize,
+    narrowing: Option<Narrowing>,
 ) -> Vec<ListItem<'static>> {
     let num_width = calculate_num_width(results.tracks.len());
     let liked_width = 2;
@@ -296,16 +639,17 @@ fn render_track_items(
             title_width = title_width,
             artist_width = artist_width
         ))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
     ];
 
-    let track_items: Vec<ListItem> = results.tracks.iter().enumerate().map(|(i, track)| {
+    let track_items: Vec<ListItem> = visible_rows(results.tracks.len(), narrowing).into_iter().map(|i| {
+        let track = &results.tracks[i];
         let duration = format_duration(track.duration_ms);
-        let is_playing = current_playing_uri.map_or(false, |uri| uri == track.uri);
+        let is_playing = current_playing_uri.is_some_and(|uri| uri == track.uri);
         let style = if i == track_index && is_focused {
-            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
         } else if is_playing {
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+            Style::default().fg(palette().info).add_modifier(Modifier::BOLD)
         } else if i == track_index {
             Style::default().add_modifier(Modifier::BOLD)
         } else {
@@ -324,7 +668,7 @@ fn render_track_items(
         };
         let artist_str = truncate_string(&artists_display, artist_width);
 
-        ListItem::new(format!("{}   {}   {}   {}   {}", track_num, liked_indicator, title_str, artist_str, duration)).style(style)
+        ListItem::new(row_line(format!("{}   {}   {}   {}   {}", track_num, liked_indicator, title_str, artist_str, duration), narrowing)).style(style)
     }).collect();
 
     items.extend(track_items);
@@ -336,44 +680,48 @@ fn render_album_items(
     album_index: usize,
     is_focused: bool,
     content_width: usize,
+    narrowing: Option<Narrowing>,
 ) -> Vec<ListItem<'static>> {
     let album_num_width = calculate_num_width(albums.len());
+    let saved_width = 2;
     let year_width = 4;
-    let album_fixed_width = 1 + album_num_width + 3 + 3 + 3 + year_width;
+    let album_fixed_width = 1 + album_num_width + 3 + saved_width + 3 + 3 + 3 + year_width;
     let album_remaining = content_width.saturating_sub(album_fixed_width);
     let album_name_width = (album_remaining * 50) / 100;
     let album_artist_width = album_remaining.saturating_sub(album_name_width);
 
     let mut items = vec![
         ListItem::new(format!(
-            " {:<num_w$}   {:<album_w$}   {:<artist_w$}   {:>year_w$}",
-            "#", "Album", "Artist", "Year",
+            " {:<num_w$}   {}   {:<album_w$}   {:<artist_w$}   {:>year_w$}",
+            "#", "  ", "Album", "Artist", "Year",
             num_w = album_num_width,
             album_w = album_name_width,
             artist_w = album_artist_width,
             year_w = year_width
         ))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
     ];
 
-    let album_items: Vec<ListItem> = albums.iter().enumerate().map(|(i, album)| {
+    let album_items: Vec<ListItem> = visible_rows(albums.len(), narrowing).into_iter().map(|i| {
+        let album = &albums[i];
         let style = if i == album_index && is_focused {
-            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
         } else if i == album_index {
             Style::default().add_modifier(Modifier::BOLD)
         } else {
             Style::default()
         };
 
+        let saved_indicator = if album.saved { "💚" } else { "  " };
         let album_str = truncate_string(&album.name, album_name_width);
         let artist_str = truncate_string(&album.artist, album_artist_width);
 
-        ListItem::new(format!(
-            " {:<num_w$}   {}   {}   {:>year_w$}",
-            i + 1, album_str, artist_str, album.year,
+        ListItem::new(row_line(format!(
+            " {:<num_w$}   {}   {}   {}   {:>year_w$}",
+            i + 1, saved_indicator, album_str, artist_str, album.year,
             num_w = album_num_width,
             year_w = year_width
-        )).style(style)
+        ), narrowing)).style(style)
     }).collect();
 
     items.extend(album_items);
@@ -385,33 +733,37 @@ fn render_artist_items(
     artist_index: usize,
     is_focused: bool,
     content_width: usize,
+    narrowing: Option<Narrowing>,
 ) -> Vec<ListItem<'static>> {
     let artist_num_width = calculate_num_width(artists.len());
-    let artist_fixed_width = 1 + artist_num_width + 3 + 3;
+    let followed_width = 2;
+    let artist_fixed_width = 1 + artist_num_width + 3 + followed_width + 3 + 3;
     let artist_remaining = content_width.saturating_sub(artist_fixed_width);
     let artist_name_width = (artist_remaining * 35) / 100;
     let genres_width = artist_remaining.saturating_sub(artist_name_width);
 
     let mut items = vec![
         ListItem::new(format!(
-            " {:<num_w$}   {:<name_w$}   {:<genres_w$}",
-            "#", "Artist", "Genres",
+            " {:<num_w$}   {}   {:<name_w$}   {:<genres_w$}",
+            "#", "  ", "Artist", "Genres",
             num_w = artist_num_width,
             name_w = artist_name_width,
             genres_w = genres_width
         ))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
     ];
 
-    let artist_items: Vec<ListItem> = artists.iter().enumerate().map(|(i, artist)| {
+    let artist_items: Vec<ListItem> = visible_rows(artists.len(), narrowing).into_iter().map(|i| {
+        let artist = &artists[i];
         let style = if i == artist_index && is_focused {
-            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
         } else if i == artist_index {
             Style::default().add_modifier(Modifier::BOLD)
         } else {
             Style::default()
         };
 
+        let followed_indicator = if artist.followed { "💚" } else { "  " };
         let name_str = truncate_string(&artist.name, artist_name_width);
 
         let genres_str = if artist.genres.is_empty() {
@@ -421,11 +773,11 @@ fn render_artist_items(
             truncate_string(&genres_text, genres_width)
         };
 
-        ListItem::new(format!(
-            " {:<num_w$}   {}   {}",
-            i + 1, name_str, genres_str,
+        ListItem::new(row_line(format!(
+            " {:<num_w$}   {}   {}   {}",
+            i + 1, followed_indicator, name_str, genres_str,
             num_w = artist_num_width
-        )).style(style)
+        ), narrowing)).style(style)
     }).collect();
 
     items.extend(artist_items);
@@ -437,6 +789,7 @@ fn render_playlist_items(
     playlist_index: usize,
     is_focused: bool,
     content_width: usize,
+    narrowing: Option<Narrowing>,
 ) -> Vec<ListItem<'static>> {
     let pl_num_width = calculate_num_width(playlists.len());
     let tracks_width = 8;
@@ -453,12 +806,13 @@ fn render_playlist_items(
             owner_w = owner_width,
             tracks_w = tracks_width
         ))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
     ];
 
-    let playlist_items: Vec<ListItem> = playlists.iter().enumerate().map(|(i, playlist)| {
+    let playlist_items: Vec<ListItem> = visible_rows(playlists.len(), narrowing).into_iter().map(|i| {
+        let playlist = &playlists[i];
         let style = if i == playlist_index && is_focused {
-            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+            Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
         } else if i == playlist_index {
             Style::default().add_modifier(Modifier::BOLD)
         } else {
@@ -468,12 +822,12 @@ fn render_playlist_items(
         let name_str = truncate_string(&playlist.name, pl_name_width);
         let owner_str = truncate_string(&playlist.owner, owner_width);
 
-        ListItem::new(format!(
+        ListItem::new(row_line(format!(
             " {:<num_w$}   {}   {}   {:>tracks_w$}",
             i + 1, name_str, owner_str, playlist.total_tracks,
             num_w = pl_num_width,
             tracks_w = tracks_width
-        )).style(style)
+        ), narrowing)).style(style)
     }).collect();
 
     items.extend(playlist_items);
@@ -487,9 +841,10 @@ fn render_album_detail(
     selected_index: usize,
     is_focused: bool,
     current_playing_uri: Option<&str>,
-) {
+    narrowing: Option<Narrowing>,
+) -> ListRegion {
     let border_style = if is_focused {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
@@ -503,14 +858,15 @@ fn render_album_detail(
         .split(area);
 
     let header_text = format!(
-        "💿 {} by {} ({})\n {} tracks | Enter: Play from selected | Backspace: Go back",
+        "💿 {} by {} ({}){}\n {} tracks | Enter: Play from selected | Backspace: Go back",
         detail.name,
         detail.artist,
         detail.year,
+        if detail.saved { "  💚 Saved" } else { "" },
         detail.tracks.len()
     );
     let header = Paragraph::new(header_text)
-        .style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(palette().info))
         .block(Block::default()
         .padding(Padding::horizontal(1))
         .borders(Borders::ALL)
@@ -518,7 +874,7 @@ fn render_album_detail(
     frame.render_widget(header, chunks[0]);
 
     let content_width = chunks[1].width.saturating_sub(4) as usize;
-    let track_items = render_detail_track_items(&detail.tracks, selected_index, is_focused, current_playing_uri, content_width, detail.tracks.len());
+    let track_items = render_detail_track_items(&detail.tracks, selected_index, is_focused, current_playing_uri, content_width, detail.tracks.len(), narrowing);
 
     let tracks_block = Block::default()
         .borders(Borders::ALL)
@@ -526,7 +882,8 @@ fn render_album_detail(
         .padding(Padding::horizontal(1))
         .border_style(border_style);
 
-    render_scrollable_list(frame, chunks[1], track_items, selected_index + 1, tracks_block);
+    let region = render_scrollable_list(frame, chunks[1], track_items, shown_position(selected_index, narrowing) + 1, tracks_block);
+    ListRegion { header_rows: 1, ..region }
 }
 
 fn render_playlist_detail(
@@ -536,9 +893,10 @@ fn render_playlist_detail(
     selected_index: usize,
     is_focused: bool,
     current_playing_uri: Option<&str>,
-) {
+    narrowing: Option<Narrowing>,
+) -> ListRegion {
     let border_style = if is_focused {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
@@ -552,15 +910,17 @@ fn render_playlist_detail(
         .split(area);
 
     let loading_indicator = if detail.loading_more { " (loading...)" } else { "" };
+    let read_only = if detail.editable { "" } else { " (read-only)" };
     let header_text = format!(
-        "📻 {} by {}\n {} tracks{} | Enter: Play from selected | Backspace: Go back",
+        "📻 {} by {}{}\n {} tracks{} | Enter: Play from selected | Backspace: Go back",
         detail.name,
         detail.owner,
+        read_only,
         detail.total_tracks,
         loading_indicator
     );
     let header = Paragraph::new(header_text)
-        .style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(palette().info))
         .block(Block::default()
         .padding(Padding::horizontal(1))
         .borders(Borders::ALL)
@@ -568,7 +928,7 @@ fn render_playlist_detail(
     frame.render_widget(header, chunks[0]);
 
     let content_width = chunks[1].width.saturating_sub(4) as usize;
-    let mut track_items = render_detail_track_items(&detail.tracks, selected_index, is_focused, current_playing_uri, content_width, detail.total_tracks as usize);
+    let mut track_items = render_detail_track_items(&detail.tracks, selected_index, is_focused, current_playing_uri, content_width, detail.total_tracks as usize, narrowing);
 
     // Add loading indicator at the bottom if more tracks are available or loading
     if detail.has_more || detail.loading_more {
@@ -579,7 +939,7 @@ fn render_playlist_detail(
         };
         track_items.push(
             ListItem::new(format!("       {}", loading_text))
-                .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::ITALIC))
+                .style(Style::default().fg(palette().warning).add_modifier(Modifier::ITALIC))
         );
     }
 
@@ -589,21 +949,23 @@ fn render_playlist_detail(
         .padding(Padding::horizontal(1))
         .border_style(border_style);
 
-    render_scrollable_list(frame, chunks[1], track_items, selected_index + 1, tracks_block);
+    let region = render_scrollable_list(frame, chunks[1], track_items, shown_position(selected_index, narrowing) + 1, tracks_block);
+    ListRegion { header_rows: 1, ..region }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_artist_detail(
     frame: &mut Frame,
     area: Rect,
     detail: &ArtistDetail,
     section: ArtistDetailSection,
-    track_index: usize,
-    album_index: usize,
+    selected: &[usize; ArtistDetailSection::ALL.len()],
     is_focused: bool,
     current_playing_uri: Option<&str>,
-) {
+    narrowing: Option<Narrowing>,
+) -> Vec<ListRegion> {
     let border_style = if is_focused {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
@@ -612,7 +974,7 @@ fn render_artist_detail(
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Header
-            Constraint::Min(0),    // Content (top tracks + albums)
+            Constraint::Min(0),    // Content (top tracks + discography)
         ])
         .split(area);
 
@@ -621,12 +983,13 @@ fn render_artist_detail(
     } else {
         format!(" | {}", detail.genres.join(", "))
     };
+    let followed = if detail.followed { " 💚 Following" } else { "" };
     let header_text = format!(
-        " {}{} | Press ←/→ to switch sections, Backspace to go back",
-        detail.name, genres
+        " {}{} | {} followers | Popularity {}/100{} | Press ←/→ to switch sections, Backspace to go back",
+        detail.name, followed, format_count(detail.followers), detail.popularity, genres
     );
     let header = Paragraph::new(header_text)
-        .style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(palette().info))
         .block(Block::default().borders(Borders::ALL).border_style(border_style));
     frame.render_widget(header, chunks[0]);
 
@@ -638,11 +1001,17 @@ fn render_artist_detail(
         ])
         .split(chunks[1]);
 
+    // The section beside the top tracks, and only the focused list is filtered
+    let tracks_focused = section == ArtistDetailSection::TopTracks;
+    let side = if tracks_focused { detail.side_section() } else { section };
+    let (track_narrowing, side_narrowing) = if tracks_focused { (narrowing, None) } else { (None, narrowing) };
+
+    let track_index = selected[ArtistDetailSection::TopTracks.index()];
     let track_content_width = content_chunks[0].width.saturating_sub(4) as usize;
-    let track_items = render_artist_track_items(&detail.top_tracks, track_index, section == ArtistDetailSection::TopTracks && is_focused, current_playing_uri, track_content_width);
+    let track_items = render_artist_track_items(&detail.top_tracks, track_index, tracks_focused && is_focused, current_playing_uri, track_content_width, track_narrowing);
 
-    let tracks_border = if section == ArtistDetailSection::TopTracks && is_focused {
-        Style::default().fg(Color::Green)
+    let tracks_border = if tracks_focused && is_focused {
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
@@ -653,39 +1022,167 @@ fn render_artist_detail(
         .padding(Padding::horizontal(1))
         .border_style(tracks_border);
 
-    render_scrollable_list(frame, content_chunks[0], track_items, track_index + 1, tracks_block);
+    let tracks_region = render_scrollable_list(frame, content_chunks[0], track_items, shown_position(track_index, track_narrowing) + 1, tracks_block);
+
+    let side_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Section tabs
+            Constraint::Min(0),    // Section list
+        ])
+        .split(content_chunks[1]);
+
+    let side_focused = !tracks_focused && is_focused;
+    let side_border = if side_focused {
+        Style::default().fg(palette().accent)
+    } else {
+        Style::default()
+    };
 
-    let album_items: Vec<ListItem> = detail
-        .albums
+    let tabs_content: Vec<Span> = ArtistDetailSection::ALL[1..]
         .iter()
-        .enumerate()
-        .map(|(i, album)| {
-            let style = if i == album_index && section == ArtistDetailSection::Albums && is_focused {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-            } else if i == album_index {
-                Style::default().add_modifier(Modifier::BOLD)
+        .flat_map(|&tab_section| {
+            let style = if tab_section == side {
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
             } else {
-                Style::default()
+                Style::default().fg(palette().muted)
             };
-            ListItem::new(format!("{} ({})", album.name, album.year)).style(style)
+            vec![Span::styled(format!("{} ({})", tab_section.label(), detail.len(tab_section)), style), Span::raw("  ")]
         })
         .collect();
+    let tabs = Paragraph::new(Line::from(tabs_content)).block(
+        Block::default().borders(Borders::ALL).padding(Padding::horizontal(1)).border_style(side_border),
+    );
+    frame.render_widget(tabs, side_chunks[0]);
 
-    let albums_border = if section == ArtistDetailSection::Albums && is_focused {
-        Style::default().fg(Color::Green)
-    } else {
-        Style::default()
+    let side_index = selected[side.index()];
+    let row_style = |i: usize| {
+        if i == side_index && side_focused {
+            Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
+        } else if i == side_index {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    };
+    let side_items: Vec<ListItem> = match detail.releases(side) {
+        Some(releases) => visible_rows(releases.len(), side_narrowing)
+            .into_iter()
+            .map(|i| {
+                let album = &releases[i];
+                let saved = if album.saved { "💚 " } else { "   " };
+                // Releases of other artists say whose they are
+                let by = if side == ArtistDetailSection::AppearsOn { format!(" - {}", album.artist) } else { String::new() };
+                ListItem::new(row_line(format!("{}{} ({}){}", saved, album.name, album.year, by), side_narrowing)).style(row_style(i))
+            })
+            .collect(),
+        None => visible_rows(detail.related.len(), side_narrowing)
+            .into_iter()
+            .map(|i| {
+                let artist = &detail.related[i];
+                let followed = if artist.followed { "💚 " } else { "   " };
+                ListItem::new(row_line(format!("{}{}", followed, artist.name), side_narrowing)).style(row_style(i))
+            })
+            .collect(),
     };
 
-    let albums_block = Block::default()
+    let side_block = Block::default()
         .borders(Borders::ALL)
-        .title(" Albums ")
+        .title(format!(" {} ", side.label()))
         .padding(Padding::horizontal(1))
-        .border_style(albums_border);
+        .border_style(side_border);
+
+    let side_region = render_scrollable_list(frame, side_chunks[1], side_items, shown_position(side_index, side_narrowing), side_block);
 
-    render_scrollable_list(frame, content_chunks[1], album_items, album_index, albums_block);
+    // Order matches `content_focus_list`: top tracks, then the section beside them
+    vec![ListRegion { header_rows: 1, ..tracks_region }, side_region]
 }
 
+/// Block title of a library list, with its size in the library
+fn library_title(name: &str, loaded: usize, paging: &ListPaging) -> String {
+    let total = (paging.total as usize).max(loaded);
+    let loading = if paging.loading_more { ", loading..." } else { "" };
+    if loaded < total {
+        format!(" {} ({} of {}{}) ", name, loaded, total, loading)
+    } else {
+        format!(" {} ({}{}) ", name, total, loading)
+    }
+}
+
+/// Last row of a library list that loads more as it is scrolled, indented
+/// by `indent` columns
+fn more_items_row(paging: &ListPaging, what: &str, indent: usize) -> Option<ListItem<'static>> {
+    let text = if paging.loading_more {
+        format!("{:indent$}⏳ Loading more {}...", "", what)
+    } else if paging.has_more {
+        format!("{:indent$}↓ Scroll down for more {}...", "", what)
+    } else {
+        return None;
+    };
+    Some(ListItem::new(text).style(Style::default().fg(palette().warning).add_modifier(Modifier::ITALIC)))
+}
+
+/// Rows of a list kept by the `/` filter, and the filter to highlight them with
+#[derive(Clone, Copy)]
+struct Narrowing<'a> {
+    filter: &'a ListFilter,
+    rows: &'a [usize],
+}
+
+/// Indices of the rows to show: all of them, or those kept by the filter
+fn visible_rows(len: usize, narrowing: Option<Narrowing>) -> Vec<usize> {
+    narrowing.map_or_else(|| (0..len).collect(), |narrowing| narrowing.rows.to_vec())
+}
+
+/// Where the selected row is among the rows shown
+fn shown_position(selected: usize, narrowing: Option<Narrowing>) -> usize {
+    narrowing.map_or(selected, |narrowing| {
+        narrowing.rows.iter().position(|&row| row == selected).unwrap_or(0)
+    })
+}
+
+/// A row's text with the characters matching the filter highlighted
+fn row_line(text: String, narrowing: Option<Narrowing>) -> Line<'static> {
+    let Some(narrowing) = narrowing else {
+        return Line::from(text);
+    };
+    let highlights = narrowing.filter.highlights(&text);
+    let highlight = Style::default().fg(palette().warning).add_modifier(Modifier::UNDERLINED);
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_highlighted = false;
+    for (i, c) in text.chars().enumerate() {
+        let highlighted = highlights.binary_search(&i).is_ok();
+        if highlighted != run_highlighted && !run.is_empty() {
+            let style = if run_highlighted { highlight } else { Style::default() };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_highlighted = highlighted;
+        run.push(c);
+    }
+    let style = if run_highlighted { highlight } else { Style::default() };
+    spans.push(Span::styled(run, style));
+    Line::from(spans)
+}
+
+/// The `/` prompt under a filtered list
+fn render_filter_bar(frame: &mut Frame, area: Rect, filter: &ListFilter, matches: Option<usize>) {
+    let cursor = if filter.editing { "▏" } else { "" };
+    let hint = match (matches, filter.editing) {
+        (None, _) => "type to filter · Esc: cancel".to_string(),
+        (Some(0), _) => "no matches · Esc: clear".to_string(),
+        (Some(n), true) => format!("{} matches · Enter: done · Esc: clear", n),
+        (Some(n), false) => format!("{} matches · n/N: next/previous · Esc: clear", n),
+    };
+    let line = Line::from(vec![
+        Span::styled(format!(" /{}{}", filter.query, cursor), Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("   {}", hint), Style::default().fg(palette().muted)),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+#[allow(clippy::too_many_arguments)]
 fn render_track_list(
     frame: &mut Frame,
     area: Rect,
@@ -694,32 +1191,31 @@ fn render_track_list(
     selected_index: usize,
     is_focused: bool,
     current_playing_uri: Option<&str>,
-) {
+    paging: Option<&ListPaging>,
+    narrowing: Option<Narrowing>,
+) -> ListRegion {
     let border_style = if is_focused {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
 
     let content_width = area.width.saturating_sub(4) as usize;
-    let track_items = render_detail_track_items(tracks, selected_index, is_focused, current_playing_uri, content_width, tracks.len());
-
-    let list = List::new(track_items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .padding(Padding::horizontal(1))
-                .border_style(border_style),
-        )
-        .highlight_style(Style::default());
+    let total_count = paging.map_or(tracks.len(), |paging| (paging.total as usize).max(tracks.len()));
+    let mut track_items = render_detail_track_items(tracks, selected_index, is_focused, current_playing_uri, content_width, total_count, narrowing);
+    track_items.extend(paging.and_then(|paging| more_items_row(paging, "tracks", 7)));
 
-    let mut list_state = ListState::default();
-    list_state.select(Some(selected_index + 1)); // +1 for header
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
 
-    frame.render_stateful_widget(list, area, &mut list_state);
+    let region = render_scrollable_list(frame, area, track_items, shown_position(selected_index, narrowing) + 1, block); // +1 for header
+    ListRegion { header_rows: 1, ..region }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_album_list(
     frame: &mut Frame,
     area: Rect,
@@ -727,44 +1223,42 @@ fn render_album_list(
     albums: &[SearchAlbum],
     selected_index: usize,
     is_focused: bool,
-) {
+    paging: &ListPaging,
+    narrowing: Option<Narrowing>,
+) -> ListRegion {
     let border_style = if is_focused {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
 
-    let list_items: Vec<ListItem> = albums
-        .iter()
-        .enumerate()
-        .map(|(i, album)| {
+    let mut list_items: Vec<ListItem> = visible_rows(albums.len(), narrowing)
+        .into_iter()
+        .map(|i| {
+            let album = &albums[i];
             let style = if i == selected_index && is_focused {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
             } else if i == selected_index {
                 Style::default().add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
-            ListItem::new(format!("{} - {} ({})", album.name, album.artist, album.year)).style(style)
+            let saved = if album.saved { "💚 " } else { "   " };
+            ListItem::new(row_line(format!("{}{} - {} ({})", saved, album.name, album.artist, album.year), narrowing)).style(style)
         })
         .collect();
+    list_items.extend(more_items_row(paging, "albums", 0));
 
-    let list = List::new(list_items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .padding(Padding::horizontal(1))
-                .border_style(border_style),
-        )
-        .highlight_style(Style::default());
-
-    let mut list_state = ListState::default();
-    list_state.select(Some(selected_index));
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
 
-    frame.render_stateful_widget(list, area, &mut list_state);
+    render_scrollable_list(frame, area, list_items, shown_position(selected_index, narrowing), block)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_artist_list(
     frame: &mut Frame,
     area: Rect,
@@ -772,19 +1266,21 @@ fn render_artist_list(
     artists: &[SearchArtist],
     selected_index: usize,
     is_focused: bool,
-) {
+    paging: &ListPaging,
+    narrowing: Option<Narrowing>,
+) -> ListRegion {
     let border_style = if is_focused {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
 
-    let list_items: Vec<ListItem> = artists
-        .iter()
-        .enumerate()
-        .map(|(i, artist)| {
+    let mut list_items: Vec<ListItem> = visible_rows(artists.len(), narrowing)
+        .into_iter()
+        .map(|i| {
+            let artist = &artists[i];
             let style = if i == selected_index && is_focused {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
             } else if i == selected_index {
                 Style::default().add_modifier(Modifier::BOLD)
             } else {
@@ -795,37 +1291,180 @@ fn render_artist_list(
             } else {
                 format!(" ({})", artist.genres.iter().take(2).cloned().collect::<Vec<_>>().join(", "))
             };
-            ListItem::new(format!("{}{}", artist.name, genres)).style(style)
+            let followed = if artist.followed { "💚 " } else { "   " };
+            ListItem::new(row_line(format!("{}{}{}", followed, artist.name, genres), narrowing)).style(style)
         })
         .collect();
+    list_items.extend(more_items_row(paging, "artists", 0));
 
-    let list = List::new(list_items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .padding(Padding::horizontal(1))
-                .border_style(border_style),
-        )
-        .highlight_style(Style::default());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
+
+    render_scrollable_list(frame, area, list_items, shown_position(selected_index, narrowing), block)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_show_list(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    shows: &[Show],
+    selected_index: usize,
+    is_focused: bool,
+    paging: &ListPaging,
+    narrowing: Option<Narrowing>,
+) -> ListRegion {
+    let border_style = if is_focused {
+        Style::default().fg(palette().accent)
+    } else {
+        Style::default()
+    };
+
+    let mut list_items: Vec<ListItem> = visible_rows(shows.len(), narrowing)
+        .into_iter()
+        .map(|i| {
+            let show = &shows[i];
+            let style = if i == selected_index && is_focused {
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
+            } else if i == selected_index {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(row_line(format!("🎙 {}  -  {}", show.name, show.publisher), narrowing)).style(style)
+        })
+        .collect();
+    list_items.extend(more_items_row(paging, "podcasts", 0));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
+
+    render_scrollable_list(frame, area, list_items, shown_position(selected_index, narrowing), block)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_show_detail(
+    frame: &mut Frame,
+    area: Rect,
+    detail: &ShowDetail,
+    selected_index: usize,
+    is_focused: bool,
+    current_playing_uri: Option<&str>,
+    paging: &ListPaging,
+    narrowing: Option<Narrowing>,
+) -> ListRegion {
+    let border_style = if is_focused {
+        Style::default().fg(palette().accent)
+    } else {
+        Style::default()
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5), // Header
+            Constraint::Min(0),    // Episodes
+        ])
+        .split(area);
+
+    let header_width = chunks[0].width.saturating_sub(4) as usize;
+    let header_text = format!(
+        "🎙 {} by {}\n {}\n {} episodes | Enter: Play from resume point | e: Mark played | Backspace: Go back",
+        detail.show.name,
+        detail.show.publisher,
+        truncate_string(&detail.description, header_width.saturating_sub(1)),
+        (paging.total as usize).max(detail.episodes.len()),
+    );
+    let header = Paragraph::new(header_text)
+        .style(Style::default().fg(palette().info))
+        .block(Block::default()
+        .padding(Padding::horizontal(1))
+        .borders(Borders::ALL)
+        .border_style(border_style));
+    frame.render_widget(header, chunks[0]);
 
-    let mut list_state = ListState::default();
-    list_state.select(Some(selected_index));
+    let content_width = chunks[1].width.saturating_sub(4) as usize;
+    let num_width = calculate_num_width((paging.total as usize).max(detail.episodes.len()));
+    let date_width = 10;
+    let duration_width = 8;
+    let progress_width = 10;
+    let fixed_width = 1 + num_width + 3 + 3 + date_width + 3 + duration_width + 3 + progress_width;
+    let title_width = content_width.saturating_sub(fixed_width);
+
+    let mut items: Vec<ListItem<'static>> = vec![
+        ListItem::new(format!(
+            " {:<num_width$}   {:<title_width$}   {:<date_width$}   {:<duration_width$}   {}",
+            "#", "Title", "Released", "Duration", "Progress",
+        ))
+        .style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
+    ];
+
+    items.extend(visible_rows(detail.episodes.len(), narrowing).into_iter().map(|i| {
+        let episode = &detail.episodes[i];
+        let is_playing = current_playing_uri.is_some_and(|uri| uri == episode.uri);
+        let style = if i == selected_index && is_focused {
+            Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
+        } else if is_playing {
+            Style::default().fg(palette().info).add_modifier(Modifier::BOLD)
+        } else if i == selected_index {
+            Style::default().add_modifier(Modifier::BOLD)
+        } else if episode.played() {
+            Style::default().fg(palette().muted)
+        } else {
+            Style::default()
+        };
 
-    frame.render_stateful_widget(list, area, &mut list_state);
+        let playing_indicator = if is_playing { "▶" } else { " " };
+        let progress = if episode.played() {
+            "✓ played".to_string()
+        } else if episode.resume_position_ms > 0 {
+            format!("▸ {}", format_duration(episode.resume_position_ms))
+        } else {
+            String::new()
+        };
+        let text = format!(
+            "{}{:<num_width$}   {:<title_width$}   {:<date_width$}   {:<duration_width$}   {}",
+            playing_indicator,
+            i + 1,
+            truncate_string(&episode.name, title_width),
+            episode.release_date,
+            format_duration(episode.duration_ms),
+            progress,
+        );
+        ListItem::new(row_line(text, narrowing)).style(style)
+    }));
+    items.extend(more_items_row(paging, "episodes", 7));
+
+    let episodes_block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Episodes ")
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
+
+    let region = render_scrollable_list(frame, chunks[1], items, shown_position(selected_index, narrowing) + 1, episodes_block);
+    ListRegion { header_rows: 1, ..region }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_queue(
     frame: &mut Frame,
     area: Rect,
     currently_playing: Option<&SearchTrack>,
     queue: &[SearchTrack],
+    local_len: usize,
     selected_index: usize,
     is_focused: bool,
     current_playing_uri: Option<&str>,
-) {
+    narrowing: Option<Narrowing>,
+) -> ListRegion {
     let border_style = if is_focused {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
@@ -856,29 +1495,187 @@ fn render_queue(
         "No track playing".to_string()
     };
     let cp_widget = Paragraph::new(cp_text)
-        .style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(palette().info))
         .block(Block::default()
         .padding(Padding::horizontal(1))
         .borders(Borders::ALL).title(" 🎵 Now Playing ").border_style(border_style));
     frame.render_widget(cp_widget, chunks[0]);
 
     let content_width = chunks[1].width.saturating_sub(4) as usize;
-    let mut list_items = render_detail_track_items(queue, selected_index, is_focused, current_playing_uri, content_width, queue.len());
+    let mut list_items = render_detail_track_items(queue, selected_index, is_focused, current_playing_uri, content_width, queue.len(), narrowing);
 
     if queue.is_empty() {
         list_items.push(
             ListItem::new("       Queue is empty")
-                .style(Style::default().fg(Color::DarkGray))
+                .style(Style::default().fg(palette().muted))
         );
     }
 
+    // Entries queued on this device come first and can be edited
+    let title = if local_len > 0 {
+        format!(" Up Next · {} queued here ", local_len)
+    } else {
+        " Up Next ".to_string()
+    };
     let queue_block = Block::default()
         .borders(Borders::ALL)
-        .title(" Up Next ")
+        .title(title)
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
+
+    let region = render_scrollable_list(frame, chunks[1], list_items, shown_position(selected_index, narrowing) + 1, queue_block);
+    ListRegion { header_rows: 1, ..region }
+}
+
+fn render_import_review(
+    frame: &mut Frame,
+    area: Rect,
+    review: &ImportReview,
+    selected_index: usize,
+    is_focused: bool,
+) -> ListRegion {
+    let border_style = if is_focused {
+        Style::default().fg(palette().accent)
+    } else {
+        Style::default()
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // Summary and keys
+            Constraint::Min(0),    // Lines
+        ])
+        .split(area);
+
+    let destination = match &review.replace {
+        Some(playlist) => format!("replace the tracks of \"{}\"", playlist.name),
+        None => format!("create \"{}\"", review.playlist_name),
+    };
+    let failed = match review.count(MatchStatus::LookupFailed) {
+        0 => String::new(),
+        count => format!(" · {} lookups failed", count),
+    };
+    let summary = format!(
+        "{} matched · {} to review · {} not found{}\n←/→ choose a match or skip · Enter: {} · k: add to queue · Esc: cancel",
+        review.count(MatchStatus::Matched),
+        review.count(MatchStatus::NeedsReview),
+        review.count(MatchStatus::NotFound),
+        failed,
+        destination,
+    );
+    let summary_widget = Paragraph::new(summary)
+        .style(Style::default().fg(palette().info))
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(Block::default()
+        .padding(Padding::horizontal(1))
+        .borders(Borders::ALL).title(format!(" Import {} ", review.source)).border_style(border_style));
+    frame.render_widget(summary_widget, chunks[0]);
+
+    let content_width = chunks[1].width.saturating_sub(4) as usize;
+    let label_width = (content_width / 2).saturating_sub(4);
+    let list_items: Vec<ListItem> = review
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let (mark, color) = match (entry.status, entry.chosen()) {
+                (_, None) if !entry.candidates.is_empty() => ("–", palette().muted),
+                (MatchStatus::NotFound, _) => ("✗", palette().error),
+                (MatchStatus::LookupFailed, _) => ("!", palette().error),
+                (MatchStatus::NeedsReview, _) => ("?", palette().warning),
+                (MatchStatus::Matched, _) => ("✓", palette().accent),
+            };
+            let choice = match entry.chosen() {
+                Some(track) => format!(
+                    "{} - {} ({}) [{}/{}]",
+                    track.artists.join(", "),
+                    track.name,
+                    track.album,
+                    entry.choice.unwrap_or_default() + 1,
+                    entry.candidates.len(),
+                ),
+                None if entry.status == MatchStatus::LookupFailed => "lookup failed".to_string(),
+                None if entry.candidates.is_empty() => "not found".to_string(),
+                None => "skipped".to_string(),
+            };
+            let text = format!("{} {}  →  {}", mark, truncate_string(&entry.query.label(), label_width), choice);
+
+            let style = if i == selected_index && is_focused {
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
+            } else if i == selected_index {
+                Style::default().fg(color).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(color)
+            };
+            ListItem::new(truncate_string(&text, content_width)).style(style)
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} lines ", review.entries.len()))
+        .padding(Padding::horizontal(1))
+        .border_style(border_style);
+
+    render_scrollable_list(frame, chunks[1], list_items, selected_index, block)
+}
+
+fn render_restore_plan(
+    frame: &mut Frame,
+    area: Rect,
+    plan: &RestorePlan,
+    selected_index: usize,
+    is_focused: bool,
+) -> ListRegion {
+    let border_style = if is_focused {
+        Style::default().fg(palette().accent)
+    } else {
+        Style::default()
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // Summary and keys
+            Constraint::Min(0),    // Changes
+        ])
+        .split(area);
+
+    let summary = format!("Missing from this account: {}
+Enter: apply · Esc: cancel", plan.summary());
+    let summary_widget = Paragraph::new(summary)
+        .style(Style::default().fg(palette().info))
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(Block::default()
+        .padding(Padding::horizontal(1))
+        .borders(Borders::ALL).title(format!(" Restore {} ", plan.source)).border_style(border_style));
+    frame.render_widget(summary_widget, chunks[0]);
+
+    let content_width = chunks[1].width.saturating_sub(4) as usize;
+    let lines = plan.lines();
+    let list_items: Vec<ListItem> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let style = if i == selected_index && is_focused {
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
+            } else if i == selected_index {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(truncate_string(&format!("+ {}", line), content_width)).style(style)
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} changes ", lines.len()))
         .padding(Padding::horizontal(1))
         .border_style(border_style);
 
-    render_scrollable_list(frame, chunks[1], list_items, selected_index + 1, queue_block);
+    render_scrollable_list(frame, chunks[1], list_items, selected_index, block)
 }
 
 fn render_detail_track_items(
@@ -888,6 +1685,7 @@ fn render_detail_track_items(
     current_playing_uri: Option<&str>,
     content_width: usize,
     total_count: usize,
+    narrowing: Option<Narrowing>,
 ) -> Vec<ListItem<'static>> {
     let num_width = calculate_num_width(total_count);
     let liked_width = 2;
@@ -906,19 +1704,19 @@ fn render_detail_track_items(
             title_width = title_width,
             artist_width = artist_width
         ))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
     ];
 
-    let track_items: Vec<ListItem> = tracks
-        .iter()
-        .enumerate()
-        .map(|(i, track)| {
+    let track_items: Vec<ListItem> = visible_rows(tracks.len(), narrowing)
+        .into_iter()
+        .map(|i| {
+            let track = &tracks[i];
             let duration = format_duration(track.duration_ms);
-            let is_playing = current_playing_uri.map_or(false, |uri| uri == track.uri);
+            let is_playing = current_playing_uri.is_some_and(|uri| uri == track.uri);
             let style = if i == selected_index && is_focused {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
             } else if is_playing {
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                Style::default().fg(palette().info).add_modifier(Modifier::BOLD)
             } else if i == selected_index {
                 Style::default().add_modifier(Modifier::BOLD)
             } else {
@@ -937,7 +1735,7 @@ fn render_detail_track_items(
             };
             let artist_str = truncate_string(&artists_display, artist_width);
 
-            ListItem::new(format!("{}   {}   {}   {}   {}", track_num, liked_indicator, title_str, artist_str, duration)).style(style)
+            ListItem::new(row_line(format!("{}   {}   {}   {}   {}", track_num, liked_indicator, title_str, artist_str, duration), narrowing)).style(style)
         })
         .collect();
 
@@ -951,6 +1749,7 @@ fn render_artist_track_items(
     is_focused: bool,
     current_playing_uri: Option<&str>,
     content_width: usize,
+    narrowing: Option<Narrowing>,
 ) -> Vec<ListItem<'static>> {
     let num_width = calculate_num_width(tracks.len());
     let liked_width = 2;
@@ -965,19 +1764,19 @@ fn render_artist_track_items(
             num_width = num_width,
             title_width_artist = title_width_artist
         ))
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
     ];
 
-    let track_items: Vec<ListItem> = tracks
-        .iter()
-        .enumerate()
-        .map(|(i, track)| {
+    let track_items: Vec<ListItem> = visible_rows(tracks.len(), narrowing)
+        .into_iter()
+        .map(|i| {
+            let track = &tracks[i];
             let duration = format_duration(track.duration_ms);
-            let is_playing = current_playing_uri.map_or(false, |uri| uri == track.uri);
+            let is_playing = current_playing_uri.is_some_and(|uri| uri == track.uri);
             let style = if i == track_index && is_focused {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
             } else if is_playing {
-                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+                Style::default().fg(palette().info).add_modifier(Modifier::BOLD)
             } else if i == track_index {
                 Style::default().add_modifier(Modifier::BOLD)
             } else {
@@ -990,7 +1789,7 @@ fn render_artist_track_items(
 
             let title_str = truncate_string(&track.name, title_width_artist);
 
-            ListItem::new(format!("{}   {}   {}   {}", track_num, liked_indicator, title_str, duration)).style(style)
+            ListItem::new(row_line(format!("{}   {}   {}   {}", track_num, liked_indicator, title_str, duration), narrowing)).style(style)
         })
         .collect();
 
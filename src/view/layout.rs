@@ -2,15 +2,20 @@
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, ListItem, Paragraph},
     Frame,
 };
 use ratatui::widgets::Padding;
 
-use crate::model::{ActiveSection, UiState};
+use crate::model::{ActiveSection, SearchQuery, UiState};
+use super::ListRegion;
+use super::theme::palette;
+use super::utils::render_scrollable_list;
 
-pub fn render_top_bar(frame: &mut Frame, area: Rect, ui_state: &UiState, device_name: &str) {
+/// Returns the search field and device name areas
+pub fn render_top_bar(frame: &mut Frame, area: Rect, ui_state: &UiState, device_name: &str) -> (Rect, Rect) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -20,15 +25,25 @@ pub fn render_top_bar(frame: &mut Frame, area: Rect, ui_state: &UiState, device_
         .split(area);
 
     let search_style = if ui_state.active_section == ActiveSection::Search {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
-        Style::default().fg(Color::White)
+        Style::default().fg(palette().text)
     };
 
+    let query = SearchQuery::split(&ui_state.search_query);
     let search_text = if ui_state.search_query.is_empty() {
-        "Type to search..."
+        Line::from("Type to search...")
     } else {
-        &ui_state.search_query
+        highlight_filters(&ui_state.search_query, &query)
+    };
+    // Valid filters narrow what is searched for
+    let sections: Vec<&str> = query.sections().into_iter().map(|section| section.label()).collect();
+    let title = if query.filters.is_empty() || query.filters.iter().any(|filter| filter.validate().is_err()) {
+        " Search ".to_string()
+    } else if sections.is_empty() {
+        " Search (filters can't be combined) ".to_string()
+    } else {
+        format!(" Search ({}) ", sections.join(", "))
     };
 
     let search = Paragraph::new(search_text)
@@ -36,10 +51,10 @@ pub fn render_top_bar(frame: &mut Frame, area: Rect, ui_state: &UiState, device_
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Search ")
+                .title(title)
                 .padding(Padding::horizontal(1))
                 .border_style(if ui_state.active_section == ActiveSection::Search {
-                    Style::default().fg(Color::Green)
+                    Style::default().fg(palette().accent)
                 } else {
                     Style::default()
                 }),
@@ -48,16 +63,38 @@ pub fn render_top_bar(frame: &mut Frame, area: Rect, ui_state: &UiState, device_
 
     // Device name
     let device = Paragraph::new(format!("🎵 {}", device_name))
-        .style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(palette().info))
         .block(Block::default().borders(Borders::ALL).title(" Device "));
     frame.render_widget(device, chunks[1]);
+
+    (chunks[0], chunks[1])
+}
+
+/// The search input with its field filters picked out, in red when the
+/// value isn't valid
+fn highlight_filters<'a>(input: &'a str, query: &SearchQuery) -> Line<'a> {
+    let mut spans = Vec::new();
+    let mut end = 0;
+    for filter in &query.filters {
+        spans.push(Span::raw(&input[end..filter.range.start]));
+        let style = if filter.validate().is_ok() {
+            Style::default().fg(palette().info).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(palette().error)
+        };
+        spans.push(Span::styled(&input[filter.range.clone()], style));
+        end = filter.range.end;
+    }
+    spans.push(Span::raw(&input[end..]));
+    Line::from(spans)
 }
 
-pub fn render_sidebar(frame: &mut Frame, area: Rect, ui_state: &UiState) {
+/// Returns the library and playlists list regions
+pub fn render_sidebar(frame: &mut Frame, area: Rect, ui_state: &UiState) -> (ListRegion, ListRegion) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(6), // Library (4 items + 2 borderlines)
+            Constraint::Length(ui_state.library_items.len() as u16 + 2), // Library (items + 2 borderlines)
             Constraint::Min(0),    // Playlists (fills remaining space)
         ])
         .split(area);
@@ -72,31 +109,29 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, ui_state: &UiState) {
                 && ui_state.active_section == ActiveSection::Library
             {
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(palette().accent)
                     .add_modifier(Modifier::BOLD)
             } else if i == ui_state.library_selected {
-                Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+                Style::default().fg(palette().text).add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(palette().text)
             };
-            ListItem::new(format!("{}", item.name)).style(style)
+            ListItem::new(item.name.to_string()).style(style)
         })
         .collect();
 
     let library_border_style = if ui_state.active_section == ActiveSection::Library {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
 
-    let library = List::new(library_items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(" Library ")
-            .padding(Padding::horizontal(1))
-            .border_style(library_border_style),
-    );
-    frame.render_widget(library, chunks[0]);
+    let library_block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Library ")
+        .padding(Padding::horizontal(1))
+        .border_style(library_border_style);
+    let library = render_scrollable_list(frame, chunks[0], library_items, ui_state.library_selected, library_block);
 
     let playlist_items: Vec<ListItem> = ui_state
         .playlists
@@ -107,35 +142,29 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, ui_state: &UiState) {
                 && ui_state.active_section == ActiveSection::Playlists
             {
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(palette().accent)
                     .add_modifier(Modifier::BOLD)
             } else if i == ui_state.playlist_selected {
-                Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+                Style::default().fg(palette().text).add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(palette().text)
             };
-            ListItem::new(format!("{}", item.name)).style(style)
+            ListItem::new(item.name.to_string()).style(style)
         })
         .collect();
 
     let playlists_border_style = if ui_state.active_section == ActiveSection::Playlists {
-        Style::default().fg(Color::Green)
+        Style::default().fg(palette().accent)
     } else {
         Style::default()
     };
 
-    let playlists = List::new(playlist_items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Playlists ")
-                .padding(Padding::horizontal(1))
-                .border_style(playlists_border_style),
-        )
-        .highlight_style(Style::default()); // Highlight handled by item styles
-
-    let mut list_state = ListState::default();
-    list_state.select(Some(ui_state.playlist_selected));
+    let playlists_block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Playlists ")
+        .padding(Padding::horizontal(1))
+        .border_style(playlists_border_style);
+    let playlists = render_scrollable_list(frame, chunks[1], playlist_items, ui_state.playlist_selected, playlists_block);
 
-    frame.render_stateful_widget(playlists, chunks[1], &mut list_state);
+    (library, playlists)
 }
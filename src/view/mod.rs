@@ -7,25 +7,82 @@
 //! - `layout`: Main layout structure (top bar, sidebar)
 //! - `content`: Main content area rendering
 //! - `progress`: Progress bar rendering
-//! - `overlays`: Modal overlays (error, device picker, help)
+//! - `overlays`: Modal overlays (error, device picker, help, settings)
+//! - `theme`: Colour palettes
 
 mod utils;
 mod layout;
 mod content;
 mod progress;
 mod overlays;
+mod theme;
 
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Position, Rect},
     Frame,
 };
 
-use crate::model::{ContentState, PlaybackInfo, UiState};
+use crate::keymap::Keymap;
+use crate::model::{ActiveSection, ContentState, PlaybackInfo, UiState};
 
 pub struct AppView;
 
+/// Screen areas from the last frame, used by the controller to hit-test mouse events
+#[derive(Clone, Debug, Default)]
+pub struct ViewLayout {
+    pub search_bar: Rect,
+    pub device_name: Rect,
+    pub library: ListRegion,
+    pub playlists: ListRegion,
+    pub content: Rect,
+    /// Lists in the main content area (artist detail: top tracks, then albums)
+    pub content_lists: Vec<ListRegion>,
+    pub device_picker: Option<ListRegion>,
+    pub playlist_picker: Option<ListRegion>,
+    pub progress_bar: Rect,
+}
+
+/// Where a list was drawn, so a screen position can be mapped back to an item
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ListRegion {
+    /// Inside of the list's block
+    pub area: Rect,
+    /// First visible row after scrolling
+    pub offset: usize,
+    /// Leading rows that are not items (e.g. a column header)
+    pub header_rows: usize,
+    /// Total rows, including header rows
+    pub len: usize,
+}
+
+impl ListRegion {
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        self.area.contains(Position::new(column, row))
+    }
+
+    /// Item index under the given screen position, if any
+    pub fn item_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.contains(column, row) {
+            return None;
+        }
+        let list_row = self.offset + (row - self.area.y) as usize;
+        if list_row >= self.len {
+            return None;
+        }
+        list_row.checked_sub(self.header_rows)
+    }
+}
+
 impl AppView {
-    pub fn render(frame: &mut Frame, playback: &PlaybackInfo, ui_state: &UiState, content_state: &ContentState) {
+    pub fn render(
+        frame: &mut Frame,
+        playback: &PlaybackInfo,
+        ui_state: &UiState,
+        content_state: &ContentState,
+        keymap: &Keymap,
+    ) -> ViewLayout {
+        theme::set(ui_state.theme);
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -36,7 +93,8 @@ impl AppView {
             .split(frame.area());
 
         // Top bar: Search + Device
-        layout::render_top_bar(frame, chunks[0], ui_state, &playback.settings.device_name);
+        let (search_bar, device_name) =
+            layout::render_top_bar(frame, chunks[0], ui_state, &playback.settings.device_name);
 
         // Middle: Sidebar (Library + Playlists) and Main Content
         let main_chunks = Layout::default()
@@ -48,7 +106,7 @@ impl AppView {
             .split(chunks[1]);
 
         // Sidebar: Library and Playlists stacked vertically
-        layout::render_sidebar(frame, main_chunks[0], ui_state);
+        let (library, playlists) = layout::render_sidebar(frame, main_chunks[0], ui_state);
 
         // Main content area
         let current_playing_uri = if !playback.track.uri.is_empty() {
@@ -56,24 +114,92 @@ impl AppView {
         } else {
             None
         };
-        content::render_main_content(frame, main_chunks[1], ui_state, content_state, current_playing_uri);
+        let content_lists =
+            content::render_main_content(frame, main_chunks[1], ui_state, content_state, current_playing_uri);
 
         // Bottom: Progress bar with track info and controls
         progress::render_progress_bar(frame, chunks[2], playback);
 
+        // Live search dropdown (while typing a search)
+        if ui_state.active_section == ActiveSection::Search && !ui_state.search_suggestions.is_empty() {
+            overlays::render_search_suggestions(frame, search_bar, ui_state);
+        }
+
         // Error notification overlay (if there's an error)
         if ui_state.error_message.is_some() {
             overlays::render_error_notification(frame, ui_state);
         }
 
         // Device picker overlay (if open)
-        if ui_state.show_device_picker {
-            overlays::render_device_picker(frame, ui_state);
+        let device_picker = ui_state
+            .show_device_picker
+            .then(|| overlays::render_device_picker(frame, ui_state));
+
+        // "Add to playlist" picker overlay (if open)
+        let playlist_picker = ui_state
+            .show_playlist_picker
+            .then(|| overlays::render_playlist_picker(frame, ui_state));
+
+        // Settings overlay (if open)
+        if ui_state.show_settings {
+            overlays::render_settings(frame, ui_state);
+        }
+
+        // "Go to position" prompt (if open)
+        if let Some(input) = &ui_state.seek_prompt {
+            overlays::render_prompt(frame, "Go to", "mm:ss", Some(input));
+        }
+
+        // "Insert into queue" prompt (if open)
+        if let Some(input) = &ui_state.queue_insert_prompt {
+            overlays::render_prompt(frame, "Insert at", "queue position", Some(input));
+        }
+
+        // Playlist create/rename/describe/unfollow prompt (if open)
+        if let Some(prompt) = &ui_state.playlist_prompt {
+            overlays::render_playlist_prompt(frame, prompt);
         }
 
         // Help popup overlay (if open)
         if ui_state.show_help_popup {
-            overlays::render_help_popup(frame);
+            overlays::render_help_popup(frame, keymap);
+        }
+
+        ViewLayout {
+            search_bar,
+            device_name,
+            library,
+            playlists,
+            content: main_chunks[1],
+            content_lists,
+            device_picker,
+            playlist_picker,
+            progress_bar: chunks[2],
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_region_maps_rows_to_items() {
+        let region = ListRegion {
+            area: Rect::new(10, 5, 20, 4),
+            offset: 3,
+            header_rows: 1,
+            len: 6,
+        };
+
+        // Rows 5..9 show list rows 3..7, of which 6 and 7 are past the end
+        assert_eq!(region.item_at(12, 5), Some(2));
+        assert_eq!(region.item_at(12, 7), Some(4));
+        assert_eq!(region.item_at(12, 8), None);
+        assert_eq!(region.item_at(9, 5), None);
+
+        let header = ListRegion { offset: 0, ..region };
+        assert_eq!(header.item_at(12, 5), None);
+        assert_eq!(header.item_at(12, 6), Some(0));
+    }
+}
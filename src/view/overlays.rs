@@ -1,14 +1,18 @@
-//! Overlay rendering (error notification, device picker, help popup)
+//! Overlay rendering (error notification, device and playlist pickers, settings, prompts, help popup)
 
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, ListItem, Paragraph},
     Frame,
 };
 
-use crate::model::UiState;
+use crate::keymap::{Action, Keymap};
+use crate::model::{PlaylistPrompt, PlaylistPromptKind, SearchResultSection, UiState};
+use super::ListRegion;
+use super::theme::palette;
+use super::utils::render_scrollable_list;
 
 pub fn render_error_notification(frame: &mut Frame, ui_state: &UiState) {
     if let Some(ref error_msg) = ui_state.error_message {
@@ -37,24 +41,76 @@ pub fn render_error_notification(frame: &mut Frame, ui_state: &UiState) {
         // Clear the area behind the popup first
         frame.render_widget(Clear, popup_area);
 
+        let (color, title) = if ui_state.message_is_info {
+            (palette().info, " Info (Esc to dismiss) ")
+        } else {
+            (palette().error, " Error (Esc to dismiss) ")
+        };
+
         // Create text with error message and dismiss hint
         let error_widget = Paragraph::new(error_msg.to_string())
-            .style(Style::default().fg(Color::Red))
+            .style(Style::default().fg(color))
             .wrap(ratatui::widgets::Wrap { trim: false })
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Red))
-                    .title(" Error (Esc to dismiss) ")
-                    .title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
-                    .style(Style::default().bg(Color::Black)),
+                    .border_style(Style::default().fg(color))
+                    .title(title)
+                    .title_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+                    .style(Style::default().bg(palette().background)),
             );
 
         frame.render_widget(error_widget, popup_area);
     }
 }
 
-pub fn render_device_picker(frame: &mut Frame, ui_state: &UiState) {
+/// Live search results in a dropdown under the search bar
+pub fn render_search_suggestions(frame: &mut Frame, search_bar: Rect, ui_state: &UiState) {
+    let area = frame.area();
+    let below = search_bar.y + search_bar.height;
+    let popup_height = (ui_state.search_suggestions.len() as u16 + 2).min(area.height.saturating_sub(below));
+    let popup_area = Rect {
+        x: search_bar.x,
+        y: below,
+        width: search_bar.width,
+        height: popup_height,
+    };
+    frame.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = ui_state
+        .search_suggestions
+        .iter()
+        .enumerate()
+        .map(|(i, suggestion)| {
+            let icon = match suggestion.section {
+                SearchResultSection::Tracks => "🎵",
+                SearchResultSection::Albums => "💿",
+                SearchResultSection::Artists => "👤",
+                SearchResultSection::Playlists => "📃",
+            };
+            let style = if ui_state.suggestion_selected == Some(i) {
+                Style::default()
+                    .fg(palette().background)
+                    .bg(palette().accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(palette().text)
+            };
+            ListItem::new(format!("{} {}", icon, suggestion.label)).style(style)
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(palette().info))
+        .title(" Suggestions (↓ to pick, Enter for all results) ")
+        .title_style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(palette().background));
+
+    render_scrollable_list(frame, popup_area, items, ui_state.suggestion_selected.unwrap_or(0), block);
+}
+
+pub fn render_device_picker(frame: &mut Frame, ui_state: &UiState) -> ListRegion {
     let area = frame.area();
 
     // Calculate popup size based on number of devices
@@ -66,7 +122,7 @@ pub fn render_device_picker(frame: &mut Frame, ui_state: &UiState) {
         .max()
         .unwrap_or(30);
 
-    let popup_width = (max_name_len as u16 + 6).min(60).max(35);
+    let popup_width = (max_name_len as u16 + 6).clamp(35, 60);
     let popup_height = (device_count as u16 + 4).min(area.height - 4).max(6);
 
     let popup_x = area.width.saturating_sub(popup_width) / 2;
@@ -98,67 +154,211 @@ pub fn render_device_picker(frame: &mut Frame, ui_state: &UiState) {
 
             let style = if is_selected {
                 Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Green)
+                    .fg(palette().background)
+                    .bg(palette().accent)
                     .add_modifier(Modifier::BOLD)
             } else if is_active {
-                Style::default().fg(Color::Green)
+                Style::default().fg(palette().accent)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(palette().text)
             };
 
             ListItem::new(text).style(style)
         })
         .collect();
 
-    let list = List::new(items).block(
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(palette().info))
+        .title(" Select Device (↑↓ Enter Esc) ")
+        .title_style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(palette().background));
+
+    render_scrollable_list(frame, popup_area, items, ui_state.device_selected, block)
+}
+
+pub fn render_playlist_picker(frame: &mut Frame, ui_state: &UiState) -> ListRegion {
+    let area = frame.area();
+
+    let playlists = &ui_state.playlist_picker_items;
+    let max_name_len = playlists.iter().map(|p| p.name.chars().count() + 2).max().unwrap_or(30);
+
+    let popup_width = (max_name_len as u16 + 6).clamp(35, 60);
+    let popup_height = (playlists.len() as u16 + 4).min(area.height - 4).max(6);
+
+    let popup_area = Rect {
+        x: area.width.saturating_sub(popup_width) / 2,
+        y: area.height.saturating_sub(popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = playlists
+        .iter()
+        .enumerate()
+        .map(|(i, playlist)| {
+            let style = if i == ui_state.playlist_picker_selected {
+                Style::default()
+                    .fg(palette().background)
+                    .bg(palette().accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(palette().text)
+            };
+            ListItem::new(format!("  {}", playlist.name)).style(style)
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(palette().info))
+        .title(" Add to Playlist (↑↓ Enter Esc) ")
+        .title_style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
+        .style(Style::default().bg(palette().background));
+
+    render_scrollable_list(frame, popup_area, items, ui_state.playlist_picker_selected, block)
+}
+
+pub fn render_settings(frame: &mut Frame, ui_state: &UiState) {
+    let area = frame.area();
+
+    let popup_width = 64.min(area.width.saturating_sub(4));
+    let popup_height = (ui_state.settings.len() as u16 + 5).min(area.height.saturating_sub(4));
+
+    let popup_area = Rect {
+        x: area.width.saturating_sub(popup_width) / 2,
+        y: area.height.saturating_sub(popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let mut lines: Vec<Line> = ui_state
+        .settings
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let is_selected = i == ui_state.settings_selected;
+            let value = match (&ui_state.settings_edit, is_selected) {
+                (Some(text), true) => format!("{}▏", text),
+                _ => format!("◀ {} ▶", row.value),
+            };
+
+            let label_style = if is_selected {
+                Style::default().fg(palette().accent).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(palette().text)
+            };
+
+            let mut spans = vec![
+                Span::styled(format!(" {:<24}", row.label), label_style),
+                Span::styled(value, Style::default().fg(palette().info)),
+            ];
+            if row.pending_restart {
+                spans.push(Span::styled(
+                    "  applies on restart",
+                    Style::default().fg(palette().warning),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    let hint = if ui_state.settings_edit.is_some() {
+        " Enter save · Esc cancel"
+    } else {
+        " ←/→ change · Enter edit · Esc close"
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(hint, Style::default().fg(palette().muted))));
+
+    let settings = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(palette().info))
+                .title(" Settings ")
+                .title_style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
+                .style(Style::default().bg(palette().background)),
+        )
+        .style(Style::default().bg(palette().background));
+
+    frame.render_widget(settings, popup_area);
+}
+
+/// Small single-line text prompt centred on screen
+/// One-line prompt; without `input` it only asks for confirmation
+pub fn render_prompt(frame: &mut Frame, label: &str, hint: &str, input: Option<&str>) {
+    let area = frame.area();
+
+    let content_width = label.chars().count() + input.map_or(0, |i| i.chars().count()) + 8;
+    let popup_width = (content_width as u16).max(36).min(area.width.saturating_sub(4));
+    let popup_area = Rect {
+        x: area.width.saturating_sub(popup_width) / 2,
+        y: area.height.saturating_sub(3) / 2,
+        width: popup_width,
+        height: 3.min(area.height),
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let mut spans = vec![Span::styled(format!(" {}", label), Style::default().fg(palette().info))];
+    if let Some(input) = input {
+        spans.push(Span::styled(format!(": {}▏", input), Style::default().fg(palette().text)));
+    }
+
+    let prompt = Paragraph::new(Line::from(spans))
+    .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
-            .title(" Select Device (↑↓ Enter Esc) ")
-            .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
-            .style(Style::default().bg(Color::Black)),
+            .border_style(Style::default().fg(palette().info))
+            .title(format!(" {} (Enter Esc) ", hint))
+            .title_style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
+            .style(Style::default().bg(palette().background)),
     );
 
-    let mut list_state = ListState::default();
-    list_state.select(Some(ui_state.device_selected));
+    frame.render_widget(prompt, popup_area);
+}
 
-    frame.render_stateful_widget(list, popup_area, &mut list_state);
+pub fn render_playlist_prompt(frame: &mut Frame, prompt: &PlaylistPrompt) {
+    match &prompt.kind {
+        PlaylistPromptKind::Create { visibility } => {
+            let hint = format!("New {} playlist, Tab to change", visibility.label());
+            render_prompt(frame, "Name", &hint, Some(&prompt.input));
+        }
+        PlaylistPromptKind::ExportTop { visibility, uris } => {
+            let hint = format!("New {} playlist of {} top tracks, Tab to change", visibility.label(), uris.len());
+            render_prompt(frame, "Name", &hint, Some(&prompt.input));
+        }
+        PlaylistPromptKind::Rename { .. } => {
+            render_prompt(frame, "Name", "Rename playlist", Some(&prompt.input));
+        }
+        PlaylistPromptKind::Describe { .. } => {
+            render_prompt(frame, "Description", "Playlist description", Some(&prompt.input));
+        }
+        PlaylistPromptKind::Unfollow { name, .. } => {
+            let label = format!("Remove \"{}\" from your library?", name);
+            render_prompt(frame, &label, "Confirm", None);
+        }
+        PlaylistPromptKind::Import { target, replace } => {
+            let hint = match target {
+                Some(target) if *replace => format!("Replace tracks of \"{}\", Tab to change", target.name),
+                Some(_) => "Import as a new playlist, Tab to change".to_string(),
+                None => "Import as a new playlist".to_string(),
+            };
+            render_prompt(frame, "Import file (.json, .csv, .m3u, .txt)", &hint, Some(&prompt.input));
+        }
+    }
 }
 
-pub fn render_help_popup(frame: &mut Frame) {
+pub fn render_help_popup(frame: &mut Frame, keymap: &Keymap) {
     let area = frame.area();
 
-    // Define keybindings organized by category
-    let keybindings = vec![
-        ("", "── Navigation ──"),
-        ("Tab / Shift+Tab", "Cycle sections"),
-        ("↑ / ↓", "Move selection"),
-        ("← / →", "Switch search category"),
-        ("Enter", "Select / Play"),
-        ("Backspace / Esc", "Go back"),
-        ("G", "Focus search"),
-        ("L", "Focus playlists"),
-        ("", ""),
-        ("", "── Playback ──"),
-        ("Space", "Play / Pause"),
-        ("N", "Next track"),
-        ("P", "Previous track"),
-        ("S", "Toggle shuffle"),
-        ("R", "Cycle repeat (off → all → one)"),
-        ("+ / -", "Volume up / down"),
-        ("", ""),
-        ("", "── Actions ──"),
-        ("X", "Like / Unlike track"),
-        ("K", "Add to queue"),
-        ("Delete", "Remove from queue"),
-        ("U", "Show queue"),
-        ("D", "Device picker"),
-        ("", ""),
-        ("", "── General ──"),
-        ("H", "Toggle this help"),
-        ("Q", "Quit"),
-    ];
+    // Keybindings organized by category, generated from the active keymap
+    let keybindings = keymap.help_entries();
 
     let popup_width = 62;
     let popup_height = (keybindings.len() as u16 + 2).min(area.height - 4);
@@ -184,34 +384,39 @@ pub fn render_help_popup(frame: &mut Frame) {
                 // Section header or empty line
                 Line::from(Span::styled(
                     format!("{:^38}", desc),
-                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    Style::default().fg(palette().warning).add_modifier(Modifier::BOLD),
                 ))
             } else {
                 Line::from(vec![
                     Span::styled(
                         format!("{:>18}", key),
-                        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+                        Style::default().fg(palette().accent).add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("  "),
                     Span::styled(
                         desc.to_string(),
-                        Style::default().fg(Color::White),
+                        Style::default().fg(palette().text),
                     ),
                 ])
             }
         })
         .collect();
 
+    let title = match keymap.bindings_for(Action::ToggleHelp).first() {
+        Some(binding) => format!(" Help ({} or Esc to close) ", binding),
+        None => " Help (Esc to close) ".to_string(),
+    };
+
     let help_text = Paragraph::new(lines)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title(" Help (H or Esc to close) ")
-                .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
-                .style(Style::default().bg(Color::Black)),
+                .border_style(Style::default().fg(palette().info))
+                .title(title)
+                .title_style(Style::default().fg(palette().info).add_modifier(Modifier::BOLD))
+                .style(Style::default().bg(palette().background)),
         )
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(palette().background));
 
     frame.render_widget(help_text, popup_area);
 }
@@ -2,13 +2,14 @@
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     text::Line,
     widgets::{Block, Borders, Gauge},
     Frame,
 };
 
 use crate::model::{PlaybackInfo, RepeatState};
+use super::theme::palette;
 use super::utils::format_duration;
 
 pub fn render_progress_bar(
@@ -74,7 +75,7 @@ pub fn render_progress_bar(
                 .title(title)
                 .title_bottom(Line::from(controls_info).right_aligned()),
         )
-        .gauge_style(Style::default().fg(Color::Green))
+        .gauge_style(Style::default().fg(palette().accent))
         .ratio(progress_ratio)
         .label(time_str);
 
@@ -0,0 +1,67 @@
+//! Colour palettes selectable with `ui.theme`
+//!
+//! `AppView::render` selects the palette from `UiState::theme` at the start of
+//! each frame, so render functions read colours through `palette()` instead
+//! of hardcoding them.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use ratatui::style::Color;
+
+use crate::config::ThemeName;
+
+pub struct Palette {
+    /// Focused borders, selections, highlights
+    pub accent: Color,
+    /// Titles, headers and secondary information
+    pub info: Color,
+    pub text: Color,
+    /// Hints and placeholders
+    pub muted: Color,
+    pub warning: Color,
+    pub error: Color,
+    pub background: Color,
+}
+
+const DEFAULT: Palette = Palette {
+    accent: Color::Green,
+    info: Color::Cyan,
+    text: Color::White,
+    muted: Color::DarkGray,
+    warning: Color::Yellow,
+    error: Color::Red,
+    background: Color::Black,
+};
+
+const OCEAN: Palette = Palette {
+    accent: Color::LightBlue,
+    info: Color::LightCyan,
+    text: Color::White,
+    muted: Color::DarkGray,
+    warning: Color::LightYellow,
+    error: Color::LightRed,
+    background: Color::Black,
+};
+
+const MONO: Palette = Palette {
+    accent: Color::White,
+    info: Color::Gray,
+    text: Color::Gray,
+    muted: Color::DarkGray,
+    warning: Color::White,
+    error: Color::LightRed,
+    background: Color::Black,
+};
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set(theme: ThemeName) {
+    CURRENT.store(theme as u8, Ordering::Relaxed);
+}
+
+pub fn palette() -> &'static Palette {
+    match CURRENT.load(Ordering::Relaxed) {
+        x if x == ThemeName::Ocean as u8 => &OCEAN,
+        x if x == ThemeName::Mono as u8 => &MONO,
+        _ => &DEFAULT,
+    }
+}
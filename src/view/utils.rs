@@ -1,5 +1,6 @@
 //! Utility functions for rendering UI components
 
+use super::ListRegion;
 use ratatui::{
     layout::Rect,
     style::Style,
@@ -13,7 +14,9 @@ pub fn render_scrollable_list(
     items: Vec<ListItem>,
     selected_index: usize,
     block: Block,
-) {
+) -> ListRegion {
+    let inner = block.inner(area);
+    let len = items.len();
     let list = List::new(items)
         .block(block)
         .highlight_style(Style::default()); // Highlight handled by item styles
@@ -22,6 +25,13 @@ pub fn render_scrollable_list(
     list_state.select(Some(selected_index));
 
     frame.render_stateful_widget(list, area, &mut list_state);
+
+    ListRegion {
+        area: inner,
+        offset: list_state.offset(),
+        header_rows: 0,
+        len,
+    }
 }
 
 pub fn format_duration(ms: u32) -> String {
@@ -31,6 +41,19 @@ pub fn format_duration(ms: u32) -> String {
     format!("{}:{:02}", minutes, seconds)
 }
 
+/// Count with thousands separators, e.g. `1,234,567`
+pub fn format_count(count: u32) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
 /// Calculate width needed for index column (log10(n) + padding)
 pub fn calculate_num_width(item_count: usize) -> usize {
     if item_count == 0 {